@@ -0,0 +1,50 @@
+// batch.rs
+
+//! Benchmarks of batch pricing with the tree engines in double and single precision.
+//!
+//!     cargo bench -p numerical-options-core --bench batch
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use numerical_options_core::batch_pricing::{price_batch, BatchEngine, OptionSpec};
+use numerical_options_core::option_type::{ExerciseStyle, OptionType};
+use numerical_options_core::precision::Precision;
+
+/// The number of options of the batch.
+const BATCH_OPTIONS: usize = 1_000;
+/// The number of steps of each tree.
+const BATCH_STEPS: usize = 201;
+
+/// Creates a batch of American options across strikes, expiries, and volatilities.
+fn batch(engine: BatchEngine, precision: Precision) -> Vec<OptionSpec> {
+    (0..BATCH_OPTIONS)
+        .map(|i| OptionSpec {
+            s0: 100.0,
+            k: 60.0 + 80.0 * i as f64 / BATCH_OPTIONS as f64,
+            r: 0.05,
+            t: 0.25 + (i % 8) as f64 * 0.25,
+            div: 0.02,
+            sigma: 0.15 + (i % 5) as f64 * 0.05,
+            option_type: if i % 2 == 1 { OptionType::Call } else { OptionType::Put },
+            exercise: ExerciseStyle::American,
+            n: BATCH_STEPS,
+            engine,
+            precision,
+        })
+        .collect()
+}
+
+fn batch_pricing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch");
+    group.sample_size(10);
+    for (name, engine) in [("crr", BatchEngine::Crr), ("lr", BatchEngine::Lr)] {
+        for (precision_name, precision) in [("double", Precision::Double), ("single", Precision::Single)] {
+            let specs = batch(engine, precision);
+            let id = BenchmarkId::new(format!("{name}/{precision_name}"), BATCH_OPTIONS);
+            group.bench_function(id, |b| b.iter(|| price_batch(&specs)));
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, batch_pricing);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+// trees.rs
+
+//! Benchmarks of the tree engines from 100 to 100,000 steps, and of a strike chain.
+//!
+//!     cargo bench -p numerical-options-core --bench trees
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use numerical_options_core::binomial_crr_option::BinomialCRROption;
+use numerical_options_core::binomial_lr_option::BinomialLROption;
+use numerical_options_core::binomial_lr_with_greeks::BinomialLRWithGreeks;
+use numerical_options_core::binomial_tree_option::BinomialTreeOption;
+use numerical_options_core::option_type::{ExerciseStyle, OptionType};
+use numerical_options_core::stock_option::StockOption;
+
+/// The numbers of steps of the trees.
+const TREE_SIZES: [usize; 4] = [100, 1_000, 10_000, 100_000];
+/// Early exercise reads the stock prices of every level, so the whole tree of about n² / 2
+/// nodes is stored: 40 GB at 100,000 steps. Larger American cases are skipped.
+const AMERICAN_MAX_STEPS: usize = 20_000;
+/// The number of strikes of the chain.
+const CHAIN_STRIKES: usize = 101;
+/// The number of steps of the chain.
+const CHAIN_STEPS: usize = 1_001;
+
+/// Creates the put priced by every case.
+fn put(n: usize, exercise: ExerciseStyle) -> StockOption {
+    StockOption::new(100.0, 95.0, 0.05, 1.0, n, 0.02, 0.25, OptionType::Put, exercise).unwrap()
+}
+
+/// Yields the exercise styles timed at a number of steps, with their names.
+fn styles(n: usize) -> impl Iterator<Item = (&'static str, ExerciseStyle)> {
+    let american = (n <= AMERICAN_MAX_STEPS).then_some(("american", ExerciseStyle::American));
+    [("european", ExerciseStyle::European)].into_iter().chain(american)
+}
+
+fn crr(c: &mut Criterion) {
+    let mut group = c.benchmark_group("crr");
+    group.sample_size(10);
+    for n in TREE_SIZES {
+        for (style, exercise) in styles(n) {
+            let engine = BinomialCRROption::new(BinomialTreeOption::new(put(n, exercise)));
+            group.bench_function(BenchmarkId::new(style, n), |b| b.iter(|| engine.price()));
+        }
+    }
+    group.finish();
+}
+
+fn lr_greeks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lr_greeks");
+    group.sample_size(10);
+    for n in TREE_SIZES {
+        for (style, exercise) in styles(n) {
+            // The Peizer-Pratt inversion of the Leisen-Reimer tree wants an odd number of steps.
+            let lr = BinomialLROption::new(BinomialTreeOption::new(put(n | 1, exercise)));
+            let engine = BinomialLRWithGreeks::new(lr);
+            group.bench_function(BenchmarkId::new(style, n), |b| b.iter(|| engine.price()));
+        }
+    }
+    group.finish();
+}
+
+fn chain(c: &mut Criterion) {
+    let option = StockOption { k: 100.0, ..put(CHAIN_STEPS, ExerciseStyle::American) };
+    let engine = BinomialCRROption::new(BinomialTreeOption::new(option));
+    let strikes = (0..CHAIN_STRIKES).map(|i| 60.0 + 80.0 * i as f64 / CHAIN_STRIKES as f64).collect::<Vec<_>>();
+    c.bench_function(&format!("chain/american/{CHAIN_STRIKES}"), |b| b.iter(|| engine.price_strikes(&strikes)));
+}
+
+criterion_group!(benches, crr, lr_greeks, chain);
+criterion_main!(benches);
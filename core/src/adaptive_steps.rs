@@ -0,0 +1,54 @@
+// adaptive_steps.rs
+
+use crate::pricing_engine::PricingEngine;
+use crate::workspace::Workspace;
+
+/// Prices an option on a lattice with enough time steps to reach a target accuracy.
+///
+/// The number of steps grows as `n → 2n + 1`, which keeps it odd for the Leisen-Reimer
+/// tree, and each pair of successive prices is combined by Richardson extrapolation,
+/// `P(m) + (P(m) − P(n)) / ((m / n)^p − 1)` for an error of order `n^−p`. The refinement
+/// stops once two successive estimates agree within the tolerance, and their difference is
+/// reported as the error estimate; the first estimate is the coarsest price itself.
+///
+/// # Arguments
+///
+/// * `engine` - The lattice engine, which prices copies of its option with each number of
+///   time steps in one workspace.
+/// * `order` - The order `p` of convergence of the engine in the number of steps.
+/// * `tolerance` - The largest acceptable difference between successive estimates.
+/// * `initial_steps` - The number of steps of the first, coarsest price.
+/// * `max_steps` - The largest number of steps to use.
+///
+/// # Returns
+///
+/// A tuple containing the extrapolated price, the number of steps of the finest lattice
+/// priced, and the estimated error. If `max_steps` is reached first, the error estimate
+/// exceeds the tolerance.
+pub fn price_to_tolerance<E: PricingEngine>(
+    engine: &E,
+    order: f64,
+    tolerance: f64,
+    initial_steps: usize,
+    max_steps: usize,
+) -> (f64, usize, f64) {
+    let mut option = engine.option().clone();
+    let mut workspace = Workspace::default();
+    let mut price_with = |steps: usize| {
+        option.n = steps;
+        engine.price_option(&option, &mut workspace)
+    };
+    let mut steps = initial_steps;
+    let mut price = price_with(steps);
+    let mut estimate = price;
+    let mut error = f64::INFINITY;
+    while error > tolerance && 2 * steps < max_steps {
+        let finer_steps = 2 * steps + 1;
+        let finer_price = price_with(finer_steps);
+        let ratio = (finer_steps as f64 / steps as f64).powf(order);
+        let extrapolated = finer_price + (finer_price - price) / (ratio - 1.0);
+        error = (extrapolated - estimate).abs();
+        (steps, price, estimate) = (finer_steps, finer_price, extrapolated);
+    }
+    (estimate, steps, error)
+}
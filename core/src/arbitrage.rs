@@ -0,0 +1,124 @@
+// arbitrage.rs
+
+use crate::numerics::{black_call, interpolate};
+
+/// The tolerance below which a violation is attributed to rounding rather than arbitrage.
+const TOLERANCE: f64 = 1e-10;
+
+/// Represents the static arbitrage found in a set of implied volatility quotes.
+pub struct ArbitrageReport {
+    /// The `(expiry, strike)` of each quote at which the undiscounted call price is not
+    /// convex in the strike, so a butterfly centred there has a negative price.
+    pub butterfly: Vec<(f64, f64)>,
+    /// The `(expiry, strike)` at which the total variance of an expiration is below that of
+    /// the previous expiration at the same forward moneyness, so a calendar spread has a
+    /// negative price. The strike is that of the quote of the previous expiration.
+    pub calendar: Vec<(f64, f64)>,
+}
+
+/// Finds the butterfly and calendar-spread arbitrage in a set of implied volatility quotes.
+///
+/// The quotes are grouped into smiles by expiration. Within each smile, the undiscounted
+/// Black call prices must be convex in the strike. Between consecutive expirations, the
+/// total variance `σ²T` must not decrease at a fixed forward moneyness `K / F`; the later
+/// smile's total variance is interpolated linearly in log-moneyness, and quotes outside
+/// its range are not compared.
+///
+/// # Arguments
+///
+/// * `strikes` - The strike prices of the quotes.
+/// * `expiries` - The times to expiration of the quotes (in years).
+/// * `vols` - The implied volatilities of the quotes.
+/// * `s0` - The initial stock price.
+/// * `carry` - The cost of carry `r − q` that determines the forward of each expiration.
+///
+/// # Returns
+///
+/// The `ArbitrageReport` listing the offending quotes.
+pub fn find_arbitrage(strikes: &[f64], expiries: &[f64], vols: &[f64], s0: f64, carry: f64) -> ArbitrageReport {
+    let mut maturities = expiries.to_vec();
+    maturities.sort_by(f64::total_cmp);
+    maturities.dedup();
+
+    // Each smile as its strictly increasing strikes and their total variances.
+    let smiles = maturities
+        .iter()
+        .map(|&t| {
+            let mut quotes = (0..strikes.len())
+                .filter(|&i| expiries[i] == t)
+                .map(|i| (strikes[i], vols[i] * vols[i] * t))
+                .collect::<Vec<_>>();
+            quotes.sort_by(|a, b| a.0.total_cmp(&b.0));
+            quotes.dedup_by(|a, b| a.0 == b.0);
+            (t, s0 * (carry * t).exp(), quotes)
+        })
+        .collect::<Vec<_>>();
+
+    let mut butterfly = Vec::new();
+    for (t, forward, quotes) in &smiles {
+        let calls = quotes.iter().map(|&(k, w)| black_call(*forward, k, w)).collect::<Vec<_>>();
+        for i in 1..quotes.len().saturating_sub(1) {
+            let left = (calls[i] - calls[i - 1]) / (quotes[i].0 - quotes[i - 1].0);
+            let right = (calls[i + 1] - calls[i]) / (quotes[i + 1].0 - quotes[i].0);
+            if right - left < -TOLERANCE {
+                butterfly.push((*t, quotes[i].0));
+            }
+        }
+    }
+
+    let mut calendar = Vec::new();
+    for pair in smiles.windows(2) {
+        let (_, near_forward, near) = &pair[0];
+        let (t, far_forward, far) = &pair[1];
+        let moneyness = far.iter().map(|&(k, _)| (k / far_forward).ln()).collect::<Vec<_>>();
+        let variances = far.iter().map(|&(_, w)| w).collect::<Vec<_>>();
+        for &(k, w) in near {
+            let x = (k / near_forward).ln();
+            if x < moneyness[0] || x > moneyness[moneyness.len() - 1] {
+                continue;
+            }
+            if interpolate(&moneyness, &variances, x) < w - TOLERANCE {
+                calendar.push((*t, k));
+            }
+        }
+    }
+
+    ArbitrageReport { butterfly, calendar }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRIKES: [f64; 5] = [80.0, 90.0, 100.0, 110.0, 120.0];
+
+    /// Quotes of two expirations with the given volatility at each strike.
+    fn quotes(near: [f64; 5], far: [f64; 5]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let strikes = STRIKES.iter().chain(&STRIKES).copied().collect();
+        let expiries = [0.5; 5].iter().chain(&[1.0; 5]).copied().collect();
+        let vols = near.iter().chain(&far).copied().collect();
+        (strikes, expiries, vols)
+    }
+
+    #[test]
+    fn finds_no_arbitrage_in_a_black_scholes_surface() {
+        let (strikes, expiries, vols) = quotes([0.2; 5], [0.2; 5]);
+        let report = find_arbitrage(&strikes, &expiries, &vols, 100.0, 0.03);
+        assert!(report.butterfly.is_empty() && report.calendar.is_empty());
+    }
+
+    #[test]
+    fn flags_a_spike_in_the_smile_as_butterfly_arbitrage() {
+        let (strikes, expiries, vols) = quotes([0.2, 0.2, 0.45, 0.2, 0.2], [0.2; 5]);
+        let report = find_arbitrage(&strikes, &expiries, &vols, 100.0, 0.0);
+        assert_eq!(report.butterfly, vec![(0.5, 100.0)]);
+    }
+
+    #[test]
+    fn flags_a_falling_total_variance_as_calendar_arbitrage() {
+        let (strikes, expiries, vols) = quotes([0.3; 5], [0.2; 5]);
+        let report = find_arbitrage(&strikes, &expiries, &vols, 100.0, 0.0);
+        assert!(report.butterfly.is_empty());
+        assert_eq!(report.calendar, STRIKES.iter().map(|&k| (1.0, k)).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,433 @@
+// asian_option.rs
+
+use crate::greeks::MonteCarloEstimator;
+use crate::numerics::norm_cdf;
+use crate::option_type::OptionType;
+use crate::pricing_error::PricingError;
+use crate::random::simulate_pairs;
+use crate::stock_option::StockOption;
+
+/// Identifies the role the average plays in the payoff of an Asian option.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum AsianStyle {
+    /// The average replaces the terminal stock price: `max(φ(A − K), 0)`.
+    AveragePrice,
+    /// The average replaces the strike: `max(φ(S_T − A), 0)`.
+    AverageStrike,
+}
+
+/// Represents a European Asian option on a discretely observed average.
+///
+/// The average is taken over `observations` equally spaced fixings in the averaging
+/// window `(window_start, t]`, the last fixing falling on the expiration date.
+#[derive(Clone)]
+pub struct AsianOption {
+    /// The underlying stock option.
+    pub option: StockOption,
+    /// Whether the average replaces the stock price or the strike.
+    pub style: AsianStyle,
+    /// The start of the averaging window (in years).
+    pub window_start: f64,
+    /// The number of fixings in the averaging window.
+    pub observations: usize,
+}
+
+impl AsianOption {
+    /// Creates a new `AsianOption` with the given stock option and averaging window.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying stock option.
+    /// * `style` - Whether the average replaces the stock price or the strike.
+    /// * `window_start` - The start of the averaging window (in years).
+    /// * `observations` - The number of fixings in the averaging window.
+    pub fn new(option: StockOption, style: AsianStyle, window_start: f64, observations: usize) -> Self {
+        AsianOption {
+            option,
+            style,
+            window_start,
+            observations: observations.max(1),
+        }
+    }
+
+    /// Checks that the averaging window lies within the life of the option and that the
+    /// parameters of the underlying option are valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `window_start` is not in `[0, t)` or if a
+    /// parameter of the option is outside its domain.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        self.option.validate()?;
+        if !(0.0..self.option.t).contains(&self.window_start) {
+            return Err(PricingError::InvalidInput(
+                "window_start must satisfy 0 <= window_start < t.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the fixing times of the average.
+    pub fn fixing_times(&self) -> Vec<f64> {
+        let spacing = (self.option.t - self.window_start) / self.observations as f64;
+        (1..=self.observations)
+            .map(|i| self.window_start + i as f64 * spacing)
+            .collect()
+    }
+
+    /// Calculates the payoff of the option.
+    ///
+    /// # Arguments
+    ///
+    /// * `average` - The average of the stock price over the fixings.
+    /// * `s` - The stock price at expiration.
+    ///
+    /// # Returns
+    ///
+    /// The payoff at expiration.
+    pub fn payoff(&self, average: f64, s: f64) -> f64 {
+        let (long, short) = match self.style {
+            AsianStyle::AveragePrice => (average, self.option.k),
+            AsianStyle::AverageStrike => (s, average),
+        };
+        if self.option.option_type == OptionType::Call {
+            (long - short).max(0.0)
+        } else {
+            (short - long).max(0.0)
+        }
+    }
+
+    /// Calculates the closed-form price of the corresponding geometric-average option.
+    ///
+    /// Under Black-Scholes dynamics the log of the geometric average is normal, with
+    /// mean `ln s0 + ν t̄` and variance `σ²/m² Σᵢ Σⱼ min(tᵢ, tⱼ)`, where `ν = b − σ²/2`,
+    /// `b` is the cost of carry, and `t̄` is the mean fixing time. With a dividend
+    /// schedule, `s0` is replaced by the net spot of the schedule, so the price is that of
+    /// the average of the net process.
+    ///
+    /// # Returns
+    ///
+    /// The price of the geometric-average option.
+    pub fn geometric_price(&self) -> f64 {
+        let times = self.fixing_times();
+        let m = times.len() as f64;
+
+        let mean_time = times.iter().sum::<f64>() / m;
+        let mut covariance_sum = 0.0;
+        for (i, &ti) in times.iter().enumerate() {
+            // min(tᵢ, tⱼ) = tᵢ for j > i, counted twice, plus the diagonal term.
+            covariance_sum += ti * (1.0 + 2.0 * (times.len() - i - 1) as f64);
+        }
+        self.geometric_price_from_moments(mean_time, covariance_sum / (m * m))
+    }
+
+    /// Calculates the closed-form price of the geometric-average option with continuous averaging.
+    ///
+    /// This is the limit of [`AsianOption::geometric_price`] as the number of fixings
+    /// grows, for which the mean fixing time is `t₀ + τ/2` and the variance of the log of
+    /// the average is `σ² (t₀ + τ/3)`, where `τ = t − t₀` is the length of the window.
+    ///
+    /// # Returns
+    ///
+    /// The price of the continuously averaged geometric option.
+    pub fn continuous_geometric_price(&self) -> f64 {
+        let tau = self.option.t - self.window_start;
+        self.geometric_price_from_moments(self.window_start + tau / 2.0, self.window_start + tau / 3.0)
+    }
+
+    /// Prices a geometric-average option from the moments of its averaging window.
+    ///
+    /// The average-price option is a Black formula on the average, and the
+    /// average-strike option is a Margrabe exchange of the terminal price for the
+    /// average, using the covariance `σ² t̄` of their logs.
+    ///
+    /// # Arguments
+    ///
+    /// * `mean_time` - The mean fixing time `t̄`.
+    /// * `time_variance` - The variance of the log of the average divided by `σ²`.
+    ///
+    /// # Returns
+    ///
+    /// The price of the geometric-average option.
+    fn geometric_price_from_moments(&self, mean_time: f64, time_variance: f64) -> f64 {
+        let option = &self.option;
+        let (r, b, sigma, t) = (option.r, option.cost_of_carry(), option.sigma, option.t);
+
+        let average_variance = sigma.powi(2) * time_variance;
+        let average_mean = option.net_spot().ln() + (b - sigma.powi(2) / 2.0) * mean_time;
+        let average_forward = (average_mean + average_variance / 2.0).exp();
+
+        let (long, short, variance) = match self.style {
+            AsianStyle::AveragePrice => (average_forward, option.k, average_variance),
+            AsianStyle::AverageStrike => {
+                let forward = option.net_spot() * (b * t).exp();
+                let variance = sigma.powi(2) * t + average_variance - 2.0 * sigma.powi(2) * mean_time;
+                (forward, average_forward, variance)
+            }
+        };
+
+        let discount = (-r * t).exp();
+        if variance <= 0.0 {
+            let intrinsic = if option.option_type == OptionType::Call { long - short } else { short - long };
+            return discount * intrinsic.max(0.0);
+        }
+        let deviation = variance.sqrt();
+        let d1 = ((long / short).ln() + variance / 2.0) / deviation;
+        let d2 = d1 - deviation;
+        if option.option_type == OptionType::Call {
+            discount * (long * norm_cdf(d1) - short * norm_cdf(d2))
+        } else {
+            discount * (short * norm_cdf(-d2) - long * norm_cdf(-d1))
+        }
+    }
+
+    /// Calculates the Monte Carlo price of the arithmetic-average option.
+    ///
+    /// Paths are sampled exactly at the fixing times with antithetic variates. The
+    /// geometric-average option on the same path is used as a control variate, with the
+    /// optimal coefficient estimated from the sample, which removes most of the variance
+    /// because the two averages are very highly correlated. With a dividend schedule the
+    /// paths follow the stock price net of the escrowed dividends, which is mapped to the
+    /// stock price at each fixing, and the control variate averages the net price.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The number of simulated paths (rounded up to an even number).
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the Monte Carlo price and its standard error.
+    pub fn monte_carlo_price(&self, paths: usize, seed: u64) -> (f64, f64) {
+        let option = &self.option;
+        let drift = option.cost_of_carry() - option.sigma.powi(2) / 2.0;
+        let times = self.fixing_times();
+        let m = times.len() as f64;
+
+        let pairs = paths.div_ceil(2).max(1);
+        let samples = simulate_pairs(pairs, seed, |rng| {
+            let mut log_x = [option.net_spot().ln(); 2];
+            let mut arithmetic_sum = [0.0; 2];
+            let mut log_sum = [0.0; 2];
+            let mut previous = 0.0;
+            for &time in &times {
+                let dt = time - previous;
+                previous = time;
+                let z = rng.next_normal();
+                for (branch, &sign) in [1.0, -1.0].iter().enumerate() {
+                    log_x[branch] += drift * dt + option.sigma * dt.sqrt() * sign * z;
+                    arithmetic_sum[branch] += option.stock_price_at(log_x[branch].exp(), time);
+                    log_sum[branch] += log_x[branch];
+                }
+            }
+            let mut arithmetic = 0.0;
+            let mut geometric = 0.0;
+            for branch in 0..2 {
+                let x = log_x[branch].exp();
+                arithmetic += 0.5 * self.payoff(arithmetic_sum[branch] / m, option.stock_price_at(x, option.t));
+                geometric += 0.5 * self.payoff((log_sum[branch] / m).exp(), x);
+            }
+            (arithmetic, geometric)
+        });
+
+        let count = pairs as f64;
+        let mean_arithmetic = samples.iter().map(|s| s.0).sum::<f64>() / count;
+        let mean_geometric = samples.iter().map(|s| s.1).sum::<f64>() / count;
+        let mut covariance = 0.0;
+        let mut geometric_variance = 0.0;
+        for &(a, g) in &samples {
+            covariance += (a - mean_arithmetic) * (g - mean_geometric);
+            geometric_variance += (g - mean_geometric).powi(2);
+        }
+        let coefficient = if geometric_variance > 0.0 {
+            covariance / geometric_variance
+        } else {
+            0.0
+        };
+
+        let discount = (-option.r * option.t).exp();
+        let control_mean = self.geometric_price() / discount;
+        let estimate = mean_arithmetic - coefficient * (mean_geometric - control_mean);
+        let residual_variance = samples
+            .iter()
+            .map(|&(a, g)| {
+                (a - coefficient * g - (mean_arithmetic - coefficient * mean_geometric)).powi(2)
+            })
+            .sum::<f64>()
+            / count;
+
+        (discount * estimate, discount * (residual_variance / count).sqrt())
+    }
+
+    /// Calculates the Monte Carlo delta and vega of the arithmetic-average option
+    /// without revaluing it on bumped parameters.
+    ///
+    /// Paths are sampled as in `monte_carlo_price`, without the control variate, and
+    /// follow the price `Xᵢ` net of escrowed dividends. The pathwise estimator
+    /// differentiates the payoff along each path, using `∂Sᵢ/∂s0 = aᵢXᵢ/X₀` and
+    /// `∂Sᵢ/∂σ = aᵢXᵢ(Wᵢ − σtᵢ)`, where `aᵢ = ∂Sᵢ/∂Xᵢ` is the product of the proportional
+    /// dividends paid by the fixing and `Wᵢ` is the Brownian motion at the fixing. The
+    /// likelihood-ratio estimator multiplies the payoff by the score of the path density:
+    /// `Z₁/(X₀ σ √Δt₁)` for delta and `Σⱼ ((Zⱼ² − 1)/σ − Zⱼ√Δtⱼ)` for vega,
+    /// where `Zⱼ` are the standard normal increments between fixings.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The number of simulated paths (rounded up to an even number).
+    /// * `seed` - The seed of the random number generator.
+    /// * `estimator` - The estimator of the Greeks.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the delta, its standard error, the vega, and its standard error.
+    pub fn monte_carlo_greeks(&self, paths: usize, seed: u64, estimator: MonteCarloEstimator) -> (f64, f64, f64, f64) {
+        let option = &self.option;
+        let sigma = option.sigma;
+        let drift = option.cost_of_carry() - sigma.powi(2) / 2.0;
+        let times = self.fixing_times();
+        let m = times.len() as f64;
+        let phi = option.option_type.sign();
+
+        let x0 = option.net_spot();
+
+        let pairs = paths.div_ceil(2).max(1);
+        let samples = simulate_pairs(pairs, seed, |rng| {
+            let normals = times.iter().map(|_| rng.next_normal()).collect::<Vec<_>>();
+            let mut sample = (0.0, 0.0);
+            for sign in [1.0, -1.0] {
+                let mut log_x = x0.ln();
+                let mut w = 0.0;
+                let mut previous = 0.0;
+                let (mut sum, mut sum_delta, mut sum_vega) = (0.0, 0.0, 0.0);
+                let (mut s, mut moving, mut s_vega) = (option.s0, 0.0, 0.0);
+                let mut vega_score = 0.0;
+                for (&time, &normal) in times.iter().zip(&normals) {
+                    let dt = time - previous;
+                    previous = time;
+                    let z = sign * normal;
+                    w += dt.sqrt() * z;
+                    log_x += drift * dt + sigma * dt.sqrt() * z;
+                    s = option.stock_price_at(log_x.exp(), time);
+                    // The stock price is affine in the net price; only this part of it
+                    // moves with the net price, and so with s0 and σ.
+                    moving = s - option.stock_price_at(0.0, time);
+                    s_vega = moving * (w - sigma * time);
+                    sum += s;
+                    sum_delta += moving / x0;
+                    sum_vega += s_vega;
+                    vega_score += (z * z - 1.0) / sigma - z * dt.sqrt();
+                }
+                let average = sum / m;
+                let (delta, vega) = match estimator {
+                    MonteCarloEstimator::Pathwise => {
+                        // The derivatives of the two sides of the payoff; the net price
+                        // scales with the net spot, so a fixing's delta is its moving part
+                        // divided by the net spot.
+                        let (long, short, long_delta, short_delta, long_vega, short_vega) = match self.style {
+                            AsianStyle::AveragePrice => (average, option.k, sum_delta / m, 0.0, sum_vega / m, 0.0),
+                            AsianStyle::AverageStrike => (s, average, moving / x0, sum_delta / m, s_vega, sum_vega / m),
+                        };
+                        if phi * (long - short) > 0.0 {
+                            (phi * (long_delta - short_delta), phi * (long_vega - short_vega))
+                        } else {
+                            (0.0, 0.0)
+                        }
+                    }
+                    MonteCarloEstimator::LikelihoodRatio => {
+                        let payoff = self.payoff(average, s);
+                        let delta_score = sign * normals[0] / (x0 * sigma * times[0].sqrt());
+                        (payoff * delta_score, payoff * vega_score)
+                    }
+                };
+                sample.0 += 0.5 * delta;
+                sample.1 += 0.5 * vega;
+            }
+            sample
+        });
+
+        let discount = (-option.r * option.t).exp();
+        let count = pairs as f64;
+        let statistics = |values: Vec<f64>| {
+            let mean = values.iter().sum::<f64>() / count;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+            (discount * mean, discount * (variance / count).sqrt())
+        };
+        let (delta, delta_error) = statistics(samples.iter().map(|s| s.0).collect());
+        let (vega, vega_error) = statistics(samples.iter().map(|s| s.1).collect());
+        (delta, delta_error, vega, vega_error)
+    }
+}
+
+/// Prices an `AsianOption` by Monte Carlo simulation, as a `PricingEngine`.
+///
+/// Every valuation draws the same paths from `seed`, so the revaluations of
+/// bump-and-revalue Greeks share their random numbers and their differences are not
+/// swamped by sampling noise.
+#[derive(Clone)]
+pub struct AsianMonteCarlo {
+    /// The option being priced.
+    pub asian: AsianOption,
+    /// The number of simulated paths.
+    pub paths: usize,
+    /// The seed of the random number generator.
+    pub seed: u64,
+}
+
+impl AsianMonteCarlo {
+    /// Creates a new `AsianMonteCarlo` engine for an Asian option.
+    ///
+    /// # Arguments
+    ///
+    /// * `asian` - The option to price.
+    /// * `paths` - The number of simulated paths.
+    /// * `seed` - The seed of the random number generator.
+    pub fn new(asian: AsianOption, paths: usize, seed: u64) -> Self {
+        AsianMonteCarlo { asian, paths, seed }
+    }
+
+    /// Calculates the Monte Carlo price of the option and its standard error.
+    pub fn price_with_error(&self) -> (f64, f64) {
+        self.asian.monte_carlo_price(self.paths, self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing_engine::PricingEngine;
+    use crate::pricing_error::PricingError;
+    use crate::option_type::ExerciseStyle;
+
+    #[test]
+    fn borrow_prices_like_an_equal_dividend_yield() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 1, 0.03, 0.2, OptionType::Call, ExerciseStyle::European).unwrap();
+        for style in [AsianStyle::AveragePrice, AsianStyle::AverageStrike] {
+            let dividend = AsianOption::new(option.clone(), style, 0.0, 12);
+            let borrow = AsianOption::new(option.with_yield_as_borrow(), style, 0.0, 12);
+            assert!((dividend.geometric_price() - borrow.geometric_price()).abs() < 1e-12);
+            assert_eq!(dividend.monte_carlo_price(2000, 1), borrow.monte_carlo_price(2000, 1));
+            let estimator = MonteCarloEstimator::Pathwise;
+            assert_eq!(dividend.monte_carlo_greeks(2000, 1, estimator), borrow.monte_carlo_greeks(2000, 1, estimator));
+        }
+    }
+
+    #[test]
+    fn engine_delta_agrees_with_the_pathwise_estimator() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 12, 0.0, 0.2, OptionType::Call, ExerciseStyle::European).unwrap();
+        let engine = AsianMonteCarlo::new(AsianOption::new(option, AsianStyle::AveragePrice, 0.0, 12), 20000, 3);
+        assert_eq!(engine.price(), engine.price_with_error().0);
+        let result = engine.greeks(Default::default(), 0.01).unwrap();
+        let (delta, delta_error, _, _) = engine.asian.monte_carlo_greeks(20000, 3, MonteCarloEstimator::Pathwise);
+        assert!((result.greeks.delta - delta).abs() < 4.0 * delta_error, "{} vs {delta}", result.greeks.delta);
+        assert_eq!(result.diagnostics.unwrap().paths, 20000);
+    }
+
+    #[test]
+    fn engine_rejects_early_exercise_and_a_window_past_expiry() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 12, 0.0, 0.2, OptionType::Put, ExerciseStyle::American).unwrap();
+        let american = AsianMonteCarlo::new(AsianOption::new(option.clone(), AsianStyle::AveragePrice, 0.0, 12), 100, 1);
+        assert!(matches!(american.try_price(), Err(PricingError::Unsupported(_))));
+        let late = AsianMonteCarlo::new(AsianOption::new(option, AsianStyle::AveragePrice, 1.0, 12), 100, 1);
+        assert!(matches!(late.validate(), Err(PricingError::InvalidInput(_))));
+    }
+}
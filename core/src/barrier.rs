@@ -0,0 +1,214 @@
+// barrier.rs
+
+/// The Broadie-Glasserman-Kou constant `-ζ(1/2) / √(2π)` used to shift discretely
+/// monitored barriers onto equivalent continuous barriers.
+const BGK_BETA: f64 = 0.582_597_157_939_010_6;
+
+/// Identifies the side from which the barrier is approached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BarrierDirection {
+    /// The barrier lies above the initial stock price.
+    Up,
+    /// The barrier lies below the initial stock price.
+    Down,
+}
+
+/// Identifies whether hitting the barrier activates or extinguishes the option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BarrierKind {
+    /// The option comes into existence when the barrier is hit.
+    KnockIn,
+    /// The option ceases to exist when the barrier is hit.
+    KnockOut,
+}
+
+/// Represents a single barrier attached to an option.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Barrier {
+    /// The barrier level.
+    pub level: f64,
+    /// The side from which the barrier is approached.
+    pub direction: BarrierDirection,
+    /// Whether hitting the barrier activates or extinguishes the option.
+    pub kind: BarrierKind,
+    /// The cash rebate paid when a knock-out option is extinguished or when a knock-in
+    /// option expires without being activated (at expiration).
+    pub rebate: f64,
+    /// When the rebate of a knocked-out option is paid. Knock-in rebates are always
+    /// paid at expiration.
+    pub rebate_timing: RebateTiming,
+    /// The time between barrier observations (in years), or `None` if the barrier is
+    /// monitored continuously.
+    pub monitoring_interval: Option<f64>,
+}
+
+impl Barrier {
+    /// Creates a new `Barrier` with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The barrier level.
+    /// * `direction` - The side from which the barrier is approached.
+    /// * `kind` - Whether hitting the barrier activates or extinguishes the option.
+    /// * `rebate` - The cash rebate paid if the option is knocked out or never knocked in.
+    ///
+    /// The barrier is monitored continuously and a knock-out rebate is paid when the
+    /// barrier is hit.
+    pub fn new(level: f64, direction: BarrierDirection, kind: BarrierKind, rebate: f64) -> Self {
+        Barrier {
+            level,
+            direction,
+            kind,
+            rebate,
+            rebate_timing: RebateTiming::AtHit,
+            monitoring_interval: None,
+        }
+    }
+
+    /// Determines at which time steps the barrier is observed.
+    ///
+    /// Observation dates fall at multiples of the monitoring interval up to expiration
+    /// and are mapped to the nearest time step. A continuously monitored barrier is
+    /// observed at every step.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time to expiration (in years).
+    /// * `n` - The number of time steps.
+    ///
+    /// # Returns
+    ///
+    /// A vector of length `n + 1` whose `i`-th entry is `true` if the barrier is observed at step `i`.
+    pub fn monitoring_schedule(&self, t: f64, n: usize) -> Vec<bool> {
+        match self.monitoring_interval {
+            None => vec![true; n + 1],
+            Some(interval) => {
+                let dt = t / n as f64;
+                let mut schedule = vec![false; n + 1];
+                // The barrier is always checked at inception so that breached barriers take effect immediately.
+                schedule[0] = true;
+                let dates = (t / interval + 1e-9).floor() as usize;
+                for m in 1..=dates {
+                    let step = ((m as f64 * interval / dt).round() as usize).min(n);
+                    schedule[step] = true;
+                }
+                schedule
+            }
+        }
+    }
+
+    /// Returns the continuous barrier equivalent to this barrier under the Broadie-Glasserman-Kou correction.
+    ///
+    /// A discretely monitored barrier is shifted away from the stock price by the factor
+    /// `exp(β σ √Δt)`, with `β ≈ 0.5826`, so that continuous-monitoring formulas can be
+    /// applied to it. A continuously monitored barrier is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `sigma` - The volatility of the underlying asset.
+    ///
+    /// # Returns
+    ///
+    /// The continuously monitored barrier with the shifted level.
+    pub fn continuity_corrected(&self, sigma: f64) -> Barrier {
+        let Some(interval) = self.monitoring_interval else {
+            return *self;
+        };
+        let shift = BGK_BETA * sigma * interval.sqrt();
+        let level = match self.direction {
+            BarrierDirection::Up => self.level * shift.exp(),
+            BarrierDirection::Down => self.level * (-shift).exp(),
+        };
+        Barrier {
+            level,
+            monitoring_interval: None,
+            ..*self
+        }
+    }
+
+    /// Checks whether a stock price is on or beyond the barrier.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The stock price.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the barrier is breached at the given stock price.
+    pub fn is_breached(&self, s: f64) -> bool {
+        match self.direction {
+            BarrierDirection::Up => s >= self.level,
+            BarrierDirection::Down => s <= self.level,
+        }
+    }
+}
+
+/// Identifies when the rebate of a knocked-out option is paid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RebateTiming {
+    /// The rebate is paid as soon as a barrier is hit.
+    AtHit,
+    /// The rebate is paid at the expiration of the option.
+    AtExpiry,
+}
+
+/// Represents a pair of barriers bracketing the initial stock price.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoubleBarrier {
+    /// The lower barrier level.
+    pub lower: f64,
+    /// The upper barrier level.
+    pub upper: f64,
+    /// Whether hitting either barrier activates or extinguishes the option.
+    pub kind: BarrierKind,
+    /// The cash rebate paid if the option is knocked out or never knocked in.
+    pub rebate: f64,
+    /// When the rebate of a knocked-out option is paid. Knock-in rebates are always
+    /// paid at expiration.
+    pub rebate_timing: RebateTiming,
+}
+
+impl DoubleBarrier {
+    /// Creates a new `DoubleBarrier` with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lower barrier level.
+    /// * `upper` - The upper barrier level.
+    /// * `kind` - Whether hitting either barrier activates or extinguishes the option.
+    /// * `rebate` - The cash rebate paid if the option is knocked out or never knocked in.
+    /// * `rebate_timing` - When the rebate of a knocked-out option is paid.
+    pub fn new(
+        lower: f64,
+        upper: f64,
+        kind: BarrierKind,
+        rebate: f64,
+        rebate_timing: RebateTiming,
+    ) -> Self {
+        DoubleBarrier {
+            lower,
+            upper,
+            kind,
+            rebate,
+            rebate_timing,
+        }
+    }
+
+    /// Checks whether a stock price is on or outside either barrier.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The stock price.
+    ///
+    /// # Returns
+    ///
+    /// `true` if either barrier is breached at the given stock price.
+    pub fn is_breached(&self, s: f64) -> bool {
+        s <= self.lower || s >= self.upper
+    }
+}
@@ -0,0 +1,281 @@
+// basket_option.rs
+
+use crate::numerics::{correlation_factor, norm_cdf};
+use crate::option_type::OptionType;
+use crate::pricing_error::PricingError;
+use crate::random::simulate_pairs;
+
+/// Represents a European option on a weighted basket of correlated assets.
+///
+/// The payoff is `max(B − K, 0)` for calls and `max(K − B, 0)` for puts, where
+/// `B = Σ wᵢ Sᵢ(T)` and each asset follows a geometric Brownian motion.
+#[derive(Clone)]
+pub struct BasketOption {
+    /// The initial prices of the assets.
+    pub spots: Vec<f64>,
+    /// The weights of the assets in the basket.
+    pub weights: Vec<f64>,
+    /// The volatilities of the assets.
+    pub sigmas: Vec<f64>,
+    /// The continuous dividend yields of the assets.
+    pub dividends: Vec<f64>,
+    /// The correlation matrix of the asset returns.
+    pub correlation: Vec<Vec<f64>>,
+    /// The strike price of the option.
+    pub k: f64,
+    /// The risk-free interest rate.
+    pub r: f64,
+    /// The time to expiration of the option (in years).
+    pub t: f64,
+    /// Whether the option is a call or a put.
+    pub option_type: OptionType,
+}
+
+impl BasketOption {
+    /// Creates a new `BasketOption` instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `spots` - The initial prices of the assets.
+    /// * `weights` - The weights of the assets in the basket.
+    /// * `sigmas` - The volatilities of the assets.
+    /// * `dividends` - The continuous dividend yields of the assets.
+    /// * `correlation` - The correlation matrix of the asset returns.
+    /// * `k` - The strike price of the option.
+    /// * `r` - The risk-free interest rate.
+    /// * `t` - The time to expiration of the option (in years).
+    /// * `option_type` - Whether the option is a call or a put.
+    ///
+    /// # Returns
+    ///
+    /// A new `BasketOption` instance with the provided parameters.
+    pub fn new(
+        spots: Vec<f64>,
+        weights: Vec<f64>,
+        sigmas: Vec<f64>,
+        dividends: Vec<f64>,
+        correlation: Vec<Vec<f64>>,
+        k: f64,
+        r: f64,
+        t: f64,
+        option_type: OptionType,
+    ) -> Self {
+        BasketOption {
+            spots,
+            weights,
+            sigmas,
+            dividends,
+            correlation,
+            k,
+            r,
+            t,
+            option_type,
+        }
+    }
+
+    /// Checks that the inputs describe the same assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if there are no assets or if the weights,
+    /// volatilities, dividend yields, and correlation matrix do not have one entry per asset.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        let n = self.spots.len();
+        let lengths = [self.weights.len(), self.sigmas.len(), self.dividends.len(), self.correlation.len()];
+        if n == 0 || lengths.iter().any(|&length| length != n) {
+            return Err(PricingError::InvalidInput(
+                "spots, weights, sigmas, divs, and correlation must all have the same non-zero length.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the forward prices of the assets at expiration.
+    pub fn forwards(&self) -> Vec<f64> {
+        self.spots
+            .iter()
+            .zip(&self.dividends)
+            .map(|(&s, &q)| s * ((self.r - q) * self.t).exp())
+            .collect()
+    }
+
+    /// Calculates the payoff for a given basket value.
+    fn payoff(&self, basket: f64) -> f64 {
+        if self.option_type == OptionType::Call {
+            (basket - self.k).max(0.0)
+        } else {
+            (self.k - basket).max(0.0)
+        }
+    }
+
+    /// Calculates the approximate price by matching the basket to a lognormal variable.
+    ///
+    /// The first two moments of the basket, `M₁ = Σ wᵢ Fᵢ` and
+    /// `M₂ = Σᵢ Σⱼ wᵢ wⱼ Fᵢ Fⱼ exp(ρᵢⱼ σᵢ σⱼ T)`, are matched to a lognormal forward with
+    /// volatility `√(ln(M₂ / M₁²) / T)`, which is then priced with the Black formula
+    /// (Levy, 1992). The approximation assumes positive weights and is a fast sanity
+    /// check for the Monte Carlo price.
+    ///
+    /// # Returns
+    ///
+    /// The approximate price of the basket option.
+    pub fn moment_matched_price(&self) -> f64 {
+        let weighted = self
+            .forwards()
+            .iter()
+            .zip(&self.weights)
+            .map(|(f, w)| f * w)
+            .collect::<Vec<_>>();
+        let first = weighted.iter().sum::<f64>();
+        let mut second = 0.0;
+        for (i, wi) in weighted.iter().enumerate() {
+            for (j, wj) in weighted.iter().enumerate() {
+                second += wi * wj * (self.correlation[i][j] * self.sigmas[i] * self.sigmas[j] * self.t).exp();
+            }
+        }
+
+        let discount = (-self.r * self.t).exp();
+        let variance = (second / first.powi(2)).ln();
+        if variance <= 0.0 {
+            return discount * self.payoff(first);
+        }
+        let d1 = ((first / self.k).ln() + variance / 2.0) / variance.sqrt();
+        let d2 = d1 - variance.sqrt();
+        if self.option_type == OptionType::Call {
+            discount * (first * norm_cdf(d1) - self.k * norm_cdf(d2))
+        } else {
+            discount * (self.k * norm_cdf(-d2) - first * norm_cdf(-d1))
+        }
+    }
+
+    /// Calculates the Monte Carlo price of the basket option.
+    ///
+    /// Terminal prices are sampled exactly with antithetic variates, using the Cholesky
+    /// factor of the correlation matrix to correlate the shocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The lower-triangular Cholesky factor of `correlation`.
+    /// * `paths` - The number of simulated paths (rounded up to an even number).
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the Monte Carlo price and its standard error.
+    pub fn monte_carlo_price(&self, factor: &[Vec<f64>], paths: usize, seed: u64) -> (f64, f64) {
+        let sqrt_t = self.t.sqrt();
+        let forwards = self.forwards();
+        let convexity = self
+            .sigmas
+            .iter()
+            .map(|sigma| -sigma.powi(2) * self.t / 2.0)
+            .collect::<Vec<_>>();
+
+        let pairs = paths.div_ceil(2).max(1);
+        let samples = simulate_pairs(pairs, seed, |rng| {
+            let z = rng.next_correlated_normals(factor);
+            let mut sample = 0.0;
+            for sign in [1.0, -1.0] {
+                let basket = (0..self.spots.len())
+                    .map(|i| {
+                        self.weights[i]
+                            * forwards[i]
+                            * (convexity[i] + sign * self.sigmas[i] * sqrt_t * z[i]).exp()
+                    })
+                    .sum::<f64>();
+                sample += 0.5 * self.payoff(basket);
+            }
+            sample
+        });
+        let sum = samples.iter().sum::<f64>();
+        let sum_squares = samples.iter().map(|sample| sample * sample).sum::<f64>();
+
+        let discount = (-self.r * self.t).exp();
+        let mean = sum / pairs as f64;
+        let variance = (sum_squares / pairs as f64 - mean * mean).max(0.0);
+        (discount * mean, discount * (variance / pairs as f64).sqrt())
+    }
+}
+
+/// Prices a `BasketOption` by Monte Carlo simulation, as a `MultiAssetEngine`.
+///
+/// Every valuation draws the same paths from `seed`, so revaluations at bumped asset
+/// prices share their random numbers.
+#[derive(Clone)]
+pub struct BasketMonteCarlo {
+    /// The option being priced.
+    pub basket: BasketOption,
+    /// The Cholesky factor of the correlation matrix of the option.
+    pub factor: Vec<Vec<f64>>,
+    /// The number of simulated paths.
+    pub paths: usize,
+    /// The seed of the random number generator.
+    pub seed: u64,
+}
+
+impl BasketMonteCarlo {
+    /// Creates a new `BasketMonteCarlo` engine for a basket option.
+    ///
+    /// # Arguments
+    ///
+    /// * `basket` - The option to price.
+    /// * `paths` - The number of simulated paths.
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Returns
+    ///
+    /// The engine, with the Cholesky factor of the correlation matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `BasketOption::validate` and `correlation_factor`.
+    pub fn new(basket: BasketOption, paths: usize, seed: u64) -> Result<Self, PricingError> {
+        basket.validate()?;
+        let factor = correlation_factor(&basket.correlation)?;
+        Ok(BasketMonteCarlo { basket, factor, paths, seed })
+    }
+
+    /// Calculates the Monte Carlo price of the option and its standard error.
+    pub fn price_with_error(&self) -> (f64, f64) {
+        self.basket.monte_carlo_price(&self.factor, self.paths, self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::option_type::ExerciseStyle;
+    use crate::pricing_engine::MultiAssetEngine;
+    use crate::stock_option::StockOption;
+
+    #[test]
+    fn delta_of_a_single_asset_basket_is_the_black_scholes_delta() {
+        let basket = BasketOption::new(
+            vec![100.0], vec![1.0], vec![0.2], vec![0.0], vec![vec![1.0]], 100.0, 0.05, 1.0, OptionType::Call,
+        );
+        let engine = BasketMonteCarlo::new(basket, 200000, 7).unwrap();
+        let (price, greeks) = engine.greeks(0.01).unwrap();
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 1, 0.0, 0.2, OptionType::Call, ExerciseStyle::European).unwrap();
+        let bs = BlackScholesOption::new(option);
+        assert!((price - bs.price()).abs() < 0.1, "{price} vs {}", bs.price());
+        let delta = norm_cdf(bs.d1());
+        assert!((greeks.deltas[0] - delta).abs() < 0.01, "{} vs {delta}", greeks.deltas[0]);
+    }
+
+    #[test]
+    fn engine_rejects_inconsistent_inputs() {
+        let basket = |weights: Vec<f64>, correlation: Vec<Vec<f64>>| {
+            let spots = vec![100.0, 100.0];
+            let basket = BasketOption::new(
+                spots, weights, vec![0.2; 2], vec![0.0; 2], correlation, 100.0, 0.05, 1.0, OptionType::Call,
+            );
+            BasketMonteCarlo::new(basket, 100, 1)
+        };
+        let identity = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        assert!(basket(vec![0.5, 0.5], identity.clone()).is_ok());
+        assert!(matches!(basket(vec![1.0], identity), Err(PricingError::InvalidInput(_))));
+        let singular = vec![vec![1.0, 1.5], vec![1.5, 1.0]];
+        assert!(matches!(basket(vec![0.5, 0.5], singular), Err(PricingError::InvalidInput(_))));
+    }
+}
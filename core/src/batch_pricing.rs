@@ -0,0 +1,175 @@
+// batch_pricing.rs
+
+use rayon::prelude::*;
+
+use crate::binomial_crr_option::BinomialCRROption;
+use crate::binomial_lr_option::BinomialLROption;
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::black_scholes_option::BlackScholesOption;
+use crate::finite_difference_option::FiniteDifferenceOption;
+use crate::greeks::GreeksConfig;
+use crate::option_type::{ExerciseStyle, OptionType};
+use crate::precision::Precision;
+use crate::pricing_engine::{PricingEngine, DEFAULT_SPOT_BUMP};
+pub use crate::pricing_engine::PricingResult;
+use crate::pricing_error::PricingError;
+use crate::stock_option::StockOption;
+use crate::worker_threads;
+use crate::workspace::Workspace;
+
+/// The fewest options in each task of a batch, below which the cost of scheduling the task
+/// outweighs the work.
+const MINIMUM_OPTIONS_PER_TASK: usize = 4;
+
+/// The number of spatial nodes of the finite-difference engine in a batch.
+const SPACE_STEPS: usize = 201;
+
+/// Identifies the engine that prices an option of a batch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BatchEngine {
+    /// The Cox-Ross-Rubinstein tree.
+    Crr,
+    /// The Leisen-Reimer tree.
+    Lr,
+    /// The Crank-Nicolson finite-difference grid.
+    Fd,
+    /// The Black-Scholes formula, for European options only.
+    Analytic,
+}
+
+/// Describes one option of a batch and how to price it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionSpec {
+    /// The initial stock price.
+    pub s0: f64,
+    /// The strike price of the option.
+    pub k: f64,
+    /// The risk-free interest rate.
+    pub r: f64,
+    /// The time to expiration of the option (in years).
+    pub t: f64,
+    /// The continuous dividend yield of the underlying asset.
+    pub div: f64,
+    /// The volatility of the underlying asset.
+    pub sigma: f64,
+    /// Whether the option is a call or a put.
+    pub option_type: OptionType,
+    /// Whether the option is European-style or American-style.
+    pub exercise: ExerciseStyle,
+    /// The number of time steps of the tree or finite-difference grid.
+    pub n: usize,
+    /// The engine that prices the option.
+    pub engine: BatchEngine,
+    /// The floating-point type of backward induction on the tree engines.
+    pub precision: Precision,
+}
+
+impl OptionSpec {
+    /// Creates the stock option described by the specification.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `StockOption::new`.
+    fn stock_option(&self) -> Result<StockOption, PricingError> {
+        let (s0, k, r, t, div, sigma) = (self.s0, self.k, self.r, self.t, self.div, self.sigma);
+        StockOption::new(s0, k, r, t, self.n, div, sigma, self.option_type, self.exercise)
+    }
+
+    /// Checks that the parameters of the option are valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `StockOption::validate`.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        self.stock_option().map(|_| ())
+    }
+
+    /// Validates the option and prices it and its Greeks by bump-and-revalue with the
+    /// default bumps.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The buffers of the tree engines, which the valuation reuses and
+    ///   hands back for the next option.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `PricingEngine::greeks` for the engine of the option.
+    fn price(&self, workspace: &mut Workspace) -> Result<PricingResult, PricingError> {
+        let option = self.stock_option()?;
+        let tree = |option| {
+            let mut tree = BinomialTreeOption::new(option);
+            tree.precision = self.precision;
+            tree
+        };
+        match self.engine {
+            BatchEngine::Crr => greeks(BinomialCRROption::new(tree(option)), workspace),
+            BatchEngine::Lr => greeks(BinomialLROption::new(tree(option)), workspace),
+            BatchEngine::Fd => greeks(FiniteDifferenceOption::new(option, SPACE_STEPS), workspace),
+            BatchEngine::Analytic => greeks(BlackScholesOption::new(option), workspace),
+        }
+    }
+}
+
+/// Validates the option of an engine and calculates its price and Greeks with the default
+/// bumps, in the buffers of a workspace.
+fn greeks<E: PricingEngine>(engine: E, workspace: &mut Workspace) -> Result<PricingResult, PricingError> {
+    engine.greeks_in(GreeksConfig::default(), DEFAULT_SPOT_BUMP, workspace)
+}
+
+/// Prices a batch of options in parallel.
+///
+/// The options are split into tasks on the worker thread pool. The tree engines of a task
+/// share one workspace, so its options allocate their trees only when a tree grows. Each
+/// option is validated before it is priced, and an invalid option fails on its own
+/// without stopping the rest of the batch.
+///
+/// # Arguments
+///
+/// * `specs` - The options to price.
+///
+/// # Returns
+///
+/// The price and Greeks of each option, or the error of `PricingEngine::greeks` for its
+/// engine, in the order of `specs`.
+pub fn price_batch(specs: &[OptionSpec]) -> Vec<Result<PricingResult, PricingError>> {
+    worker_threads::install(|| {
+        specs
+            .par_iter()
+            .with_min_len(MINIMUM_OPTIONS_PER_TASK)
+            .map_init(Workspace::default, |workspace, spec| spec.price(workspace))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_invalid_option_fails_without_stopping_the_batch() {
+        let valid = OptionSpec {
+            s0: 100.0,
+            k: 100.0,
+            r: 0.05,
+            t: 1.0,
+            div: 0.0,
+            sigma: 0.2,
+            option_type: OptionType::Call,
+            exercise: ExerciseStyle::American,
+            n: 101,
+            engine: BatchEngine::Lr,
+            precision: Precision::Double,
+        };
+        let invalid = OptionSpec { sigma: -0.2, ..valid };
+        let analytic = OptionSpec { exercise: ExerciseStyle::European, engine: BatchEngine::Analytic, ..valid };
+        let results = price_batch(&[valid, invalid, analytic]);
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[1], Err(PricingError::InvalidInput(_))));
+        let tree = results[0].as_ref().unwrap().price;
+        let formula = results[2].as_ref().unwrap().price;
+        assert!((tree - formula).abs() < 1e-2, "{tree} vs {formula}");
+    }
+}
@@ -0,0 +1,282 @@
+// binomial_crr_option.rs
+
+use crate::barrier::BarrierDirection;
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::stock_option::StockOption;
+use crate::workspace::Workspace;
+
+/// The relative tolerance by which a barrier moved onto a row of nodes is shifted so that
+/// the nodes on it count as breached despite rounding.
+const NODE_TOLERANCE: f64 = 1e-9;
+
+/// Sets the level of one of the barriers of an option.
+type LevelSetter = fn(&mut BinomialCRROption, f64);
+
+/// Represents a binomial CRR (Cox-Ross-Rubinstein) option pricing model.
+///
+/// The up and down factors are `e^{±σ√Δt}`, so unlike the plain `BinomialTreeOption`
+/// the tree is derived from the volatility rather than from user-supplied moves.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinomialCRROption {
+    /// The underlying binomial tree option.
+    pub tree: BinomialTreeOption,
+    /// Whether barrier options are priced by interpolating between barriers placed on
+    /// the rows of nodes on either side of the true barrier.
+    pub interpolate_barriers: bool,
+}
+
+impl BinomialCRROption {
+    /// Creates a new `BinomialCRROption` with the given binomial tree option.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The binomial tree option representing the underlying asset and option parameters.
+    pub fn new(tree: BinomialTreeOption) -> Self {
+        BinomialCRROption {
+            tree,
+            interpolate_barriers: false,
+        }
+    }
+
+    /// Calculates the price of the option using the Cox-Ross-Rubinstein tree.
+    ///
+    /// With `interpolate_barriers`, each barrier is moved onto the rows of nodes just
+    /// inside and just outside it, where the lattice prices barrier options accurately
+    /// (Boyle and Lau, 1994), and the prices are interpolated linearly in the log of the
+    /// barrier level. Otherwise the price jumps whenever a row of nodes crosses a barrier,
+    /// which makes bumped Greeks meaningless near it.
+    ///
+    /// The barriers are moved on a scratch tree, so the tree itself is never changed.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price(&self) -> f64 {
+        self.price_option(&self.tree.option, &mut Workspace::default())
+    }
+
+    /// Calculates the price of another option on a Cox-Ross-Rubinstein tree with the
+    /// settings of this one, as `price` does for its own option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to price.
+    /// * `workspace` - The buffers of the traversals, reused from earlier valuations.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price_option(&self, option: &StockOption, workspace: &mut Workspace) -> f64 {
+        self.in_scratch(option, workspace, BinomialCRROption::scratch_price)
+    }
+
+    /// Runs a calculation on a scratch copy of this engine that prices `option` with the
+    /// buffers of `workspace`, and hands the buffers back afterwards.
+    pub(crate) fn in_scratch<T>(
+        &self,
+        option: &StockOption,
+        workspace: &mut Workspace,
+        run: impl FnOnce(&mut BinomialCRROption) -> T,
+    ) -> T {
+        let mut scratch = BinomialCRROption {
+            tree: self.tree.scratch(option, workspace),
+            interpolate_barriers: self.interpolate_barriers,
+        };
+        let result = run(&mut scratch);
+        scratch.tree.release(workspace);
+        result
+    }
+
+    /// Calculates the price of the option of a scratch engine, moving its barriers onto
+    /// the rows of nodes when interpolating.
+    fn scratch_price(&mut self) -> f64 {
+        if !self.interpolate_barriers {
+            return self.lattice_price();
+        }
+
+        let original = (self.tree.option.barrier, self.tree.option.double_barrier);
+        // Each barrier as its level, whether it is a lower barrier, and a setter.
+        let mut barriers: Vec<(f64, bool, LevelSetter)> = Vec::new();
+        if let Some(barrier) = original.0 {
+            barriers.push((
+                barrier.level,
+                barrier.direction == BarrierDirection::Down,
+                |option, level| {
+                    if let Some(barrier) = option.tree.option.barrier.as_mut() {
+                        barrier.level = level;
+                    }
+                },
+            ));
+        }
+        if let Some(barrier) = original.1 {
+            barriers.push((barrier.lower, true, |option, level| {
+                if let Some(barrier) = option.tree.option.double_barrier.as_mut() {
+                    barrier.lower = level;
+                }
+            }));
+            barriers.push((barrier.upper, false, |option, level| {
+                if let Some(barrier) = option.tree.option.double_barrier.as_mut() {
+                    barrier.upper = level;
+                }
+            }));
+        }
+
+        // The tree grows from the spot net of the escrowed cash dividends, whose present
+        // value is added back at each node, so the rows of nodes at the root lie at that
+        // present value plus the net spot times a power of the up factor.
+        let option = &self.tree.option;
+        let spacing = option.sigma * option.dt().sqrt();
+        let net_spot = option.net_spot();
+        let escrow = option.s0 - net_spot;
+        let bracketing = barriers
+            .iter()
+            .map(|&(level, is_lower, _)| {
+                let position = ((level - escrow) / net_spot).ln() / spacing;
+                let weight = position - position.floor();
+                let shift = if is_lower { 1.0 + NODE_TOLERANCE } else { 1.0 - NODE_TOLERANCE };
+                let row = |offset: f64| (escrow + net_spot * ((position.floor() + offset) * spacing).exp()) * shift;
+                [(row(0.0), 1.0 - weight), (row(1.0), weight)]
+            })
+            .collect::<Vec<_>>();
+        if bracketing.iter().flatten().any(|(_, weight)| !weight.is_finite()) {
+            // A barrier below the escrowed dividends lies beneath every row of nodes.
+            return self.lattice_price();
+        }
+
+        // Interpolate multilinearly over every combination of bracketing rows.
+        let mut price = 0.0;
+        for combination in 0..1usize << barriers.len() {
+            let mut weight = 1.0;
+            for (index, (&(_, _, set_level), rows)) in barriers.iter().zip(&bracketing).enumerate() {
+                let (level, row_weight) = rows[(combination >> index) & 1];
+                set_level(self, level);
+                weight *= row_weight;
+            }
+            if weight > 0.0 {
+                price += weight * self.lattice_price();
+            }
+        }
+        self.tree.option.barrier = original.0;
+        self.tree.option.double_barrier = original.1;
+        price
+    }
+
+    /// Extracts the early exercise boundary of the Cox-Ross-Rubinstein tree, as for
+    /// `BinomialTreeOption::exercise_boundary`.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(t, s_star)` pairs giving the critical stock price at each time step.
+    pub fn exercise_boundary(&self) -> Vec<(f64, f64)> {
+        self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            scratch.tree.setup_crr_parameters();
+            scratch.tree.init_stock_price_tree();
+            scratch.tree.exercise_boundary()
+        })
+    }
+
+    /// Prices the option at several strikes on one Cox-Ross-Rubinstein tree, whose nodes
+    /// do not depend on the strike, as for `BinomialTreeOption::price_strikes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `strikes` - The strike prices.
+    ///
+    /// # Returns
+    ///
+    /// The price of the option at each strike, for one contract.
+    pub fn price_strikes(&self, strikes: &[f64]) -> Vec<f64> {
+        let factor = self.tree.option.contract_factor();
+        let prices = self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            scratch.tree.setup_crr_parameters();
+            scratch.tree.init_pricing_tree();
+            scratch.tree.price_strikes(strikes)
+        });
+        prices.into_iter().map(|price| price * factor).collect()
+    }
+
+    /// Calculates the price of the option on the lattice with its barriers as given.
+    fn lattice_price(&mut self) -> f64 {
+        self.tree.setup_crr_parameters();
+        self.tree.init_pricing_tree();
+        let payoffs = self.tree.begin_tree_traversal();
+        let price = payoffs[0];
+        self.tree.values = payoffs;
+        price
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::barrier::{Barrier, BarrierKind};
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::dividend_schedule::{Dividend, DividendKind, DividendSchedule};
+    use crate::option_type::{ExerciseStyle, OptionType};
+
+    #[test]
+    fn borrow_prices_like_an_equal_dividend_yield() {
+        let price = |option: StockOption| BinomialCRROption::new(BinomialTreeOption::new(option)).price();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 101, 0.03, 0.2, option_type, ExerciseStyle::American).unwrap();
+            assert!((price(option.clone()) - price(option.with_yield_as_borrow())).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn log_space_prices_like_the_products() {
+        for (exercise, option_type) in [(ExerciseStyle::European, OptionType::Call), (ExerciseStyle::American, OptionType::Put)] {
+            let option = StockOption::new(100.0, 110.0, 0.05, 2.0, 1001, 0.02, 0.4, option_type, exercise).unwrap();
+            let plain = BinomialCRROption::new(BinomialTreeOption::new(option.clone())).price();
+            let mut logged = option;
+            logged.log_space = true;
+            let logged = BinomialCRROption::new(BinomialTreeOption::new(logged)).price();
+            assert!((plain - logged).abs() < 1e-10 * plain, "{plain} vs {logged}");
+        }
+    }
+
+    #[test]
+    fn log_space_stays_finite_where_the_products_overflow() {
+        let mut option = StockOption::new(100.0, 100.0, 0.05, 30.0, 501, 0.0, 6.0, OptionType::Call, ExerciseStyle::American).unwrap();
+        let plain = BinomialCRROption::new(BinomialTreeOption::new(option.clone())).price();
+        option.log_space = true;
+        let logged = BinomialCRROption::new(BinomialTreeOption::new(option)).price();
+        assert!(!plain.is_finite(), "{plain}");
+        assert!(logged > 0.0 && logged <= 100.0, "{logged}");
+    }
+
+    #[test]
+    fn strikes_price_like_separate_trees() {
+        let strikes = [60.0, 85.5, 99.0, 100.0, 101.0, 120.0, 180.0];
+        for (exercise, option_type) in [(ExerciseStyle::European, OptionType::Call), (ExerciseStyle::American, OptionType::Put)] {
+            let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 301, 0.02, 0.3, option_type, exercise).unwrap();
+            let prices = BinomialCRROption::new(BinomialTreeOption::new(option.clone())).price_strikes(&strikes);
+            for (&k, price) in strikes.iter().zip(prices) {
+                let mut single = option.clone();
+                single.k = k;
+                let expected = BinomialCRROption::new(BinomialTreeOption::new(single)).price();
+                assert!((price - expected).abs() < 1e-12 * expected.max(1.0), "{k}: {price} vs {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn interpolated_barriers_follow_the_escrowed_dividends() {
+        // Without a cost of carry, a cash dividend at expiration is escrowed at its full
+        // amount over the whole life, so the net price is a geometric Brownian motion
+        // that knocks out when the stock price falls to the barrier.
+        let option = |s0, level| {
+            let mut option = StockOption::new(s0, 100.0, 0.05, 1.0, 400, 0.05, 0.25, OptionType::Call, ExerciseStyle::European).unwrap();
+            option.barrier = Some(Barrier::new(level, BarrierDirection::Down, BarrierKind::KnockOut, 0.0));
+            option
+        };
+        let mut paying = option(100.0, 90.0);
+        paying.dividends = Some(DividendSchedule::new(vec![Dividend { time: 1.0, amount: 5.0, kind: DividendKind::Cash }]).unwrap());
+        let mut engine = BinomialCRROption::new(BinomialTreeOption::new(paying));
+        engine.interpolate_barriers = true;
+        let exact = BlackScholesOption::new(option(95.0, 85.0)).price();
+        let price = engine.price();
+        assert!((price - exact).abs() < 0.02, "{price} vs {exact}");
+    }
+}
@@ -0,0 +1,464 @@
+// binomial_lr_option.rs
+
+use core::f64::consts::E;
+use std::sync::{Mutex, PoisonError};
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::stock_option::StockOption;
+use crate::workspace::Workspace;
+
+/// The inputs of the Leisen-Reimer parameters: the adjusted spot price, strike price, cost
+/// of carry, volatility, and time to expiration, with the number of time steps.
+type SetupKey = ([f64; 5], usize);
+
+/// The number of parameter setups a `SetupCache` keeps, enough for the option and all the
+/// bumped revaluations of a Greeks calculation.
+const SETUP_CACHE_CAPACITY: usize = 16;
+
+/// The Leisen-Reimer parameters `(p, u, d)` of recent setups, keyed by their inputs, with
+/// the most recently used first.
+///
+/// Bumped Greeks revalue the option on several parameter sets in turn, so a single entry
+/// would be evicted by every bump; a few entries let a repeated risk run find all of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct SetupCache {
+    /// The cached inputs and parameters.
+    entries: Vec<(SetupKey, [f64; 3])>,
+}
+
+impl SetupCache {
+    /// Returns the parameters cached for `key`, or sets them up and caches them, evicting
+    /// the least recently used entry when the cache is full.
+    fn get_or_insert_with(&mut self, key: SetupKey, setup: impl FnOnce() -> [f64; 3]) -> [f64; 3] {
+        let entry = match self.entries.iter().position(|(cached, _)| *cached == key) {
+            Some(index) => self.entries.remove(index),
+            None => {
+                self.entries.truncate(SETUP_CACHE_CAPACITY - 1);
+                (key, setup())
+            }
+        };
+        self.entries.insert(0, entry);
+        entry.1
+    }
+}
+
+/// The `SetupCache` of an engine, shared by its valuations through `&self`.
+///
+/// Each valuation works on a copy of the cache and stores it back when it is done, so
+/// valuations on several threads never wait for each other; the last one to finish keeps
+/// its entries.
+#[derive(Debug, Default)]
+struct SharedSetupCache(Mutex<SetupCache>);
+
+impl SharedSetupCache {
+    /// Returns a copy of the cached setups.
+    fn load(&self) -> SetupCache {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Replaces the cached setups.
+    fn store(&self, cache: SetupCache) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = cache;
+    }
+
+    /// Returns the cached setups of an engine held exclusively.
+    fn get_mut(&mut self) -> &mut SetupCache {
+        self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for SharedSetupCache {
+    fn clone(&self) -> Self {
+        SharedSetupCache(Mutex::new(self.load()))
+    }
+}
+
+impl PartialEq for SharedSetupCache {
+    /// The cache only saves work, so engines compare equal whatever it holds.
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+/// Represents a binomial LR (Leisen-Reimer) option pricing model.
+///
+/// The Leisen-Reimer model is a modification of the binomial tree option pricing model
+/// that improves the convergence speed and accuracy of the option price calculation. It
+/// requires an odd number of time steps, so an option with an even `n` is priced on a
+/// tree with `n + 1` steps.
+///
+/// # Example
+///
+/// ```
+/// use numerical_options_core::binomial_lr_option::BinomialLROption;
+/// use numerical_options_core::binomial_tree_option::BinomialTreeOption;
+/// use numerical_options_core::option_type::{ExerciseStyle, OptionType};
+/// use numerical_options_core::stock_option::StockOption;
+///
+/// let option = StockOption::new(
+///     50.0, 52.0, 0.05, 2.0, 101, 0.0, 0.3, OptionType::Put, ExerciseStyle::American,
+/// ).unwrap();
+/// let lr_option = BinomialLROption::new(BinomialTreeOption::new(option));
+/// let price = lr_option.price();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinomialLROption {
+    /// The underlying binomial tree option.
+    pub tree: BinomialTreeOption,
+    /// The probability parameter used in the Leisen-Reimer model.
+    ///
+    /// This parameter is calculated based on the option parameters and is used to
+    /// determine the up and down move probabilities in the binomial tree.
+    pub p: f64,
+    /// The results `(p, u, d)` of recent parameter setups, keyed by their inputs, kept
+    /// across the valuations of this engine.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    setup_cache: SharedSetupCache,
+}
+
+impl BinomialLROption {
+    /// Creates a new `BinomialLROption` with the given binomial tree option.
+    ///
+    /// The `p` parameter is initialized to 0.0 and will be calculated later using
+    /// the `setup_parameters` method.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The binomial tree option representing the underlying asset and option parameters.
+    pub fn new(tree: BinomialTreeOption) -> Self {
+        BinomialLROption { tree, p: 0.0, setup_cache: SharedSetupCache::default() }
+    }
+
+    /// Calculates the number of time steps of the Leisen-Reimer tree of an option.
+    ///
+    /// The Peizer-Pratt inversion matches the binomial distribution to the normal one
+    /// only for an odd number of steps; with an even number the tree converges at first
+    /// order and oscillates. An even number of steps is therefore rounded up.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of time steps of the option.
+    ///
+    /// # Returns
+    ///
+    /// `n` if it is odd, and `n + 1` otherwise.
+    pub fn odd_steps(n: usize) -> usize {
+        n | 1
+    }
+
+    /// Sets up the parameters for the binomial LR option pricing model.
+    ///
+    /// This method calculates the values of `p`, `u`, `d`, `qu`, and `qd` based on the
+    /// option parameters stored in the `tree` field. These parameters are used to determine
+    /// the probabilities and move factors in the binomial tree.
+    ///
+    /// The calculations are based on the Leisen-Reimer model, which uses a modified version
+    /// of the Cox-Ross-Rubinstein (CRR) binomial tree model. The Peizer-Pratt inversion
+    /// uses the number of steps of the tree, which the pricing methods make odd with
+    /// `odd_steps`.
+    ///
+    /// The results of the last few setups are cached with their inputs, and the pricing
+    /// methods keep the cache on the engine, so revaluing the option or any of its bumps
+    /// with the same engine, as repeated risk runs do, skips the inversion.
+    pub fn setup_parameters(&mut self) {
+        let option = &self.tree.option;
+        let key = ([option.adjusted_spot(), option.k, option.cost_of_carry(), option.sigma, option.t], option.n);
+        let mut cache = std::mem::take(self.setup_cache.get_mut());
+        let [p, u, d] = cache.get_or_insert_with(key, || {
+            let ([s, k, carry, sigma, t], n) = key;
+            let d1 = ((s / k).ln() + (carry + (sigma.powi(2) / 2.0)) * t) / (sigma * t.sqrt());
+            let d2 = ((s / k).ln() + (carry - (sigma.powi(2) / 2.0)) * t) / (sigma * t.sqrt());
+
+            let pbar = self.pp_2_inversion(d1, n);
+            let p = self.pp_2_inversion(d2, n);
+
+            let growth = self.tree.option.growth();
+            let u = growth * pbar / p;
+            let d = (growth - p * u) / (1.0 - p);
+            [p, u, d]
+        });
+        *self.setup_cache.get_mut() = cache;
+        self.p = p;
+        self.tree.u = u;
+        self.tree.d = d;
+        self.tree.qu = p;
+        self.tree.qd = 1.0 - p;
+    }
+
+    /// Calculates the price of the option using the Leisen-Reimer tree.
+    ///
+    /// This method sets up the Leisen-Reimer parameters, initializes the stock price tree,
+    /// and traverses the tree to calculate the option price, all on a scratch tree, so
+    /// that the engine can be shared between threads.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price(&self) -> f64 {
+        self.price_option(&self.tree.option, &mut Workspace::default())
+    }
+
+    /// Calculates the price of another option on a Leisen-Reimer tree with the settings of
+    /// this one, as `price` does for its own option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to price.
+    /// * `workspace` - The buffers of the traversal, reused from earlier valuations.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price_option(&self, option: &StockOption, workspace: &mut Workspace) -> f64 {
+        self.in_scratch(option, workspace, BinomialLROption::lattice_price)
+    }
+
+    /// Calculates the price of the option together with the bound on the error introduced
+    /// by the truncation of the tree, if any.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the price of the option and the upper bound on its truncation
+    /// error, as recorded in `BinomialTreeOption::truncation_error`.
+    pub fn price_truncated(&self) -> (f64, f64) {
+        self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            (scratch.lattice_price(), scratch.tree.truncation_error)
+        })
+    }
+
+    /// Calculates the probability of early exercise and the expected exercise time on the
+    /// Leisen-Reimer tree, as for `BinomialTreeOption::exercise_statistics`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the option price, the probability of early exercise, and the
+    /// expected time of exercise (in years).
+    pub fn exercise_statistics(&self) -> (f64, f64, f64) {
+        self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            scratch.setup_parameters();
+            scratch.tree.init_stock_price_tree();
+            scratch.tree.exercise_statistics()
+        })
+    }
+
+    /// Extracts the early exercise boundary of the Leisen-Reimer tree, as for
+    /// `BinomialTreeOption::exercise_boundary`.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(t, s_star)` pairs giving the critical stock price at each time step.
+    pub fn exercise_boundary(&self) -> Vec<(f64, f64)> {
+        self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            scratch.setup_parameters();
+            scratch.tree.init_stock_price_tree();
+            scratch.tree.exercise_boundary()
+        })
+    }
+
+    /// Prices the option and its European twin on the same Leisen-Reimer tree, as for
+    /// `BinomialTreeOption::price_with_european`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the price of the option and the price of its European twin.
+    pub fn price_with_european(&self) -> (f64, f64) {
+        self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            scratch.setup_parameters();
+            scratch.tree.init_stock_price_tree();
+            scratch.tree.price_with_european()
+        })
+    }
+
+    /// Runs a calculation on a scratch copy of this engine that prices `option` with the
+    /// buffers of `workspace` and a copy of the parameter cache of this engine, and hands
+    /// them back afterwards.
+    ///
+    /// The scratch tree has `odd_steps(option.n)` time steps.
+    pub(crate) fn in_scratch<T>(
+        &self,
+        option: &StockOption,
+        workspace: &mut Workspace,
+        run: impl FnOnce(&mut BinomialLROption) -> T,
+    ) -> T {
+        let mut scratch = BinomialLROption {
+            setup_cache: self.setup_cache.clone(),
+            tree: self.tree.scratch(option, workspace),
+            p: 0.0,
+        };
+        scratch.tree.option.n = Self::odd_steps(option.n);
+        let result = run(&mut scratch);
+        self.setup_cache.store(std::mem::take(scratch.setup_cache.get_mut()));
+        scratch.tree.release(workspace);
+        result
+    }
+
+    /// Calculates the price of the option of a scratch engine, leaving its parameters and
+    /// buffers set up.
+    fn lattice_price(&mut self) -> f64 {
+        self.setup_parameters();
+        self.tree.init_pricing_tree();
+        let payoffs = self.tree.begin_tree_traversal();
+        let price = payoffs[0];
+        self.tree.values = payoffs;
+        price
+    }
+
+    /// Calculates the pp 2 inversion used in the Leisen-Reimer model.
+    ///
+    /// This function is a helper method used in the `setup_parameters` method to calculate
+    /// the values of `p` and `pbar`. It approximates the inverse of the cumulative standard
+    /// normal distribution function using a modified version of the Beasley-Springer-Moro algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `z` - The z-score (number of standard deviations from the mean).
+    /// * `n` - The number of periods in the binomial tree.
+    ///
+    /// # Returns
+    ///
+    /// The approximate value of the cumulative standard normal distribution function at `z`.
+    fn pp_2_inversion(&self, z: f64, n: usize) -> f64 {
+        let n = n as f64;
+        let p = 0.5
+            + z.signum()
+                * (0.25
+                    - 0.25
+                        * E.powf(
+                            -((z / (n + 1.0 / 3.0 + 0.1 / (n + 1.0))).powi(2))
+                                * (n + 1.0 / 6.0),
+                        ))
+                .sqrt();
+
+        if p.is_nan() {
+            if z < 0.0 {
+                0.0
+            } else {
+                1.0
+            }
+        } else {
+            p
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binomial_crr_option::BinomialCRROption;
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::option_type::{ExerciseStyle, OptionType};
+
+    #[test]
+    fn borrow_prices_like_an_equal_dividend_yield() {
+        let price = |option: StockOption| BinomialLROption::new(BinomialTreeOption::new(option)).price();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 101, 0.03, 0.2, option_type, ExerciseStyle::American).unwrap();
+            assert!((price(option.clone()) - price(option.with_yield_as_borrow())).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn setup_cache_keeps_every_bump_of_a_greeks_run() {
+        let option = StockOption::new(50.0, 52.0, 0.05, 2.0, 101, 0.0, 0.3, OptionType::Put, ExerciseStyle::American).unwrap();
+        let engine = BinomialLROption::new(BinomialTreeOption::new(option.clone()));
+        let bumps = [(0.0, 0.0), (1.0, 0.0), (-1.0, 0.0), (0.0, 0.01), (0.0, -0.01)];
+        let bumped = |(ds, dsigma): (f64, f64)| {
+            let mut bumped = option.clone();
+            bumped.s0 += ds;
+            bumped.sigma += dsigma;
+            bumped
+        };
+        let mut workspace = Workspace::default();
+        let first = bumps.map(|bump| engine.price_option(&bumped(bump), &mut workspace));
+        let entries = engine.setup_cache.load().entries;
+        assert_eq!(entries.len(), bumps.len());
+        let second = bumps.map(|bump| engine.price_option(&bumped(bump), &mut workspace));
+        assert_eq!(first, second);
+        assert_eq!(engine.setup_cache.load().entries.len(), bumps.len());
+        assert_eq!(engine.setup_cache.load().entries[0], entries[0]);
+    }
+
+    #[test]
+    fn second_price_reuses_the_cached_setup() {
+        let option = StockOption::new(50.0, 52.0, 0.05, 2.0, 101, 0.0, 0.3, OptionType::Put, ExerciseStyle::American).unwrap();
+        let mut engine = BinomialLROption::new(BinomialTreeOption::new(option));
+        let first = engine.price();
+        let mut cache = engine.setup_cache.load();
+        assert_eq!(cache.entries.len(), 1);
+        // A price that follows the cached moves rather than fresh ones shows a hit.
+        let [p, u, d] = &mut cache.entries[0].1;
+        (*p, *u, *d) = (0.5, *u * 1.01, *d * 1.01);
+        *engine.setup_cache.get_mut() = cache;
+        assert_ne!(engine.price(), first);
+        assert_eq!(engine.setup_cache.load().entries.len(), 1);
+    }
+
+    #[test]
+    fn european_prices_converge_at_second_order() {
+        let option = |n| StockOption::new(100.0, 105.0, 0.05, 1.0, n, 0.02, 0.25, OptionType::Put, ExerciseStyle::European).unwrap();
+        let exact = BlackScholesOption::new(option(1)).price();
+        let error = |n| (BinomialLROption::new(BinomialTreeOption::new(option(n))).price() - exact).abs();
+        let (coarse, fine) = (error(101), error(201));
+        assert!(coarse < 1e-3, "{coarse}");
+        // Doubling the steps divides the error by about four.
+        assert!(fine < coarse / 3.0, "{coarse} then {fine}");
+    }
+
+    #[test]
+    fn american_prices_converge() {
+        let option = |n| StockOption::new(100.0, 100.0, 0.1, 1.0, n, 0.0, 0.2, OptionType::Put, ExerciseStyle::American).unwrap();
+        let price = |n| BinomialLROption::new(BinomialTreeOption::new(option(n))).price();
+        let prices = [201, 401, 801, 1601].map(price);
+        // Early exercise limits the convergence to first order, from below.
+        for window in prices.windows(3) {
+            let (first, second) = (window[1] - window[0], window[2] - window[1]);
+            assert!(first > 0.0 && second > 0.0, "{prices:?}");
+            assert!(second < 0.7 * first, "{prices:?}");
+        }
+        let crr = BinomialCRROption::new(BinomialTreeOption::new(option(1601))).price();
+        assert!((prices[3] - crr).abs() < 1e-3, "{} vs {crr}", prices[3]);
+    }
+
+    #[test]
+    fn truncated_prices_lie_within_their_error_bound() {
+        for (exercise, option_type) in [(ExerciseStyle::European, OptionType::Call), (ExerciseStyle::American, OptionType::Put)] {
+            let option = StockOption::new(100.0, 110.0, 0.05, 1.0, 501, 0.02, 0.3, option_type, exercise).unwrap();
+            let full = BinomialLROption::new(BinomialTreeOption::new(option.clone())).price();
+            for epsilon in [1e-6, 1e-9, 1e-12] {
+                let mut tree = BinomialTreeOption::new(option.clone());
+                tree.truncation = Some(epsilon);
+                let (price, bound) = BinomialLROption::new(tree).price_truncated();
+                assert!(bound > 0.0, "{epsilon}");
+                assert!((price - full).abs() <= bound, "{epsilon}: {price} vs {full} within {bound}");
+            }
+        }
+    }
+
+    #[test]
+    fn matches_the_quantlib_equity_option_example() {
+        // QuantLib's EquityOption example prices this put on 801 Leisen-Reimer steps at
+        // 3.844306 when European and 4.486096 when American.
+        let price = |exercise| {
+            let option = StockOption::new(36.0, 40.0, 0.06, 1.0, 801, 0.0, 0.2, OptionType::Put, exercise).unwrap();
+            BinomialLROption::new(BinomialTreeOption::new(option)).price()
+        };
+        let european = price(ExerciseStyle::European);
+        let american = price(ExerciseStyle::American);
+        assert!((european - 3.844306).abs() < 5e-5, "{european}");
+        assert!((american - 4.486096).abs() < 5e-5, "{american}");
+    }
+
+    #[test]
+    fn even_step_counts_are_priced_on_the_next_odd_tree() {
+        assert_eq!(BinomialLROption::odd_steps(100), 101);
+        assert_eq!(BinomialLROption::odd_steps(101), 101);
+        for exercise in [ExerciseStyle::European, ExerciseStyle::American] {
+            let price = |n| {
+                let option = StockOption::new(50.0, 52.0, 0.05, 2.0, n, 0.0, 0.3, OptionType::Put, exercise).unwrap();
+                BinomialLROption::new(BinomialTreeOption::new(option)).price()
+            };
+            assert_eq!(price(4), price(5));
+            assert_eq!(price(100), price(101));
+        }
+    }
+}
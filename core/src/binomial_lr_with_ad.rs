@@ -0,0 +1,202 @@
+// binomial_lr_with_ad.rs
+
+use crate::binomial_lr_option::BinomialLROption;
+use crate::dual::Dual;
+use crate::greeks::{Greeks, ThetaConvention};
+use crate::option_type::OptionType;
+use crate::pricing_engine::{EngineDiagnostics, PricingResult};
+use crate::stock_option::StockOption;
+
+/// The inputs differentiated by `BinomialLRWithAD`: the stock price, the volatility, the
+/// risk-free interest rate, and the time to expiration, in that order.
+type Number = Dual<4>;
+
+/// Represents a binomial LR (Leisen-Reimer) option whose Greeks are calculated by
+/// automatic differentiation.
+///
+/// The tree is built and traversed once in dual-number arithmetic, so delta, gamma, theta,
+/// vega, and rho are the exact derivatives of the Leisen-Reimer price, including the
+/// dependence of the move sizes and probabilities on the parameters. Unlike
+/// `BinomialLRWithGreeks`, the results do not depend on any bump size. Only the vanilla
+/// payoff is supported, and barriers are ignored. As for `BinomialLROption`, an even
+/// number of steps is rounded up to an odd one.
+pub struct BinomialLRWithAD {
+    /// The option to be priced.
+    pub option: StockOption,
+    /// How theta is reported.
+    pub theta: ThetaConvention,
+}
+
+impl BinomialLRWithAD {
+    /// Creates a new `BinomialLRWithAD` instance with the given option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to be priced.
+    ///
+    /// # Returns
+    ///
+    /// A new `BinomialLRWithAD` instance, which reports theta as `∂V/∂T`.
+    pub fn new(option: StockOption) -> Self {
+        BinomialLRWithAD {
+            option,
+            theta: ThetaConvention::default(),
+        }
+    }
+
+    /// Calculates the Peizer-Pratt method 2 inversion on dual numbers, as in
+    /// `BinomialLROption`.
+    ///
+    /// `1 − e^{−y}` is evaluated with `exp_m1`, because near `z = 0` the subtraction loses
+    /// the digits the derivatives are made of.
+    fn pp_2_inversion(z: Number, n: usize) -> Number {
+        let n = n as f64;
+        let x = z / (n + 1.0 / 3.0 + 0.1 / (n + 1.0));
+        if z.value == 0.0 {
+            // The square root has an infinite slope at zero, but the inversion itself is
+            // smooth there: `p = 1/2 + x √(n + 1/6) / 2 + O(x³)`.
+            return x * (0.5 * (n + 1.0 / 6.0).sqrt()) + 0.5;
+        }
+        let p = (-(-(x.powi(2) * (n + 1.0 / 6.0))).exp_m1()).sqrt() * (0.5 * z.value.signum()) + 0.5;
+        if p.value.is_nan() {
+            Number::constant(if z.value < 0.0 { 0.0 } else { 1.0 })
+        } else {
+            p
+        }
+    }
+
+    /// Calculates the option price and Greeks (delta, gamma, theta, vega, rho).
+    ///
+    /// # Returns
+    ///
+    /// The option price and its Greeks, as for `BinomialLRWithGreeks::price`, with theta in
+    /// the convention of `theta`, and the diagnostics of the tree.
+    pub fn price(&self) -> PricingResult {
+        // The Leisen-Reimer tree needs an odd number of steps.
+        let n = BinomialLROption::odd_steps(self.option.n);
+        let odd = (n != self.option.n).then(|| StockOption { n, ..self.option.clone() });
+        let option = odd.as_ref().unwrap_or(&self.option);
+        let s0 = Number::variable(option.s0, 0);
+        let sigma = Number::variable(option.sigma, 1);
+        let r = Number::variable(option.r, 2);
+        let t = Number::variable(option.t, 3);
+
+        let carry = if option.is_futures { Number::constant(0.0) } else { r - option.total_yield() };
+        let drift = sigma.powi(2) * 0.5;
+        let log_moneyness = (s0 / option.k).ln();
+        let denominator = sigma * t.sqrt();
+        let d1 = (log_moneyness + (carry + drift) * t) / denominator;
+        let d2 = (log_moneyness + (carry - drift) * t) / denominator;
+        let pbar = Self::pp_2_inversion(d1, n);
+        let p = Self::pp_2_inversion(d2, n);
+
+        let dt = t / n as f64;
+        let growth = (carry * dt).exp();
+        let u = growth * pbar / p;
+        let d = (growth - p * u) / (1.0 - p);
+        let (qu, qd, discount) = (p, 1.0 - p, (-(r * dt)).exp());
+        let d_over_u = d / u;
+
+        let exercise_value = |s: Number| {
+            let value = if option.option_type == OptionType::Call { s - option.k } else { option.k - s };
+            value.max(Number::constant(0.0))
+        };
+        let schedule = option.exercise_schedule();
+
+        // The stock prices of a row, from the highest node down.
+        let mut prices = Vec::with_capacity(n + 1);
+        prices.push(s0 * u.powi(n as i32));
+        for j in 0..n {
+            prices.push(prices[j] * d_over_u);
+        }
+        let mut values = prices.iter().map(|&s| exercise_value(s)).collect::<Vec<_>>();
+        for i in (0..n).rev() {
+            prices.pop();
+            for s in prices.iter_mut() {
+                *s = *s / u;
+            }
+            values = values
+                .windows(2)
+                .zip(&prices)
+                .map(|(w, &s)| {
+                    let continuation = (w[0] * qu + w[1] * qd) * discount;
+                    if schedule[i] {
+                        continuation.max(exercise_value(s))
+                    } else {
+                        continuation
+                    }
+                })
+                .collect();
+        }
+
+        let value = values[0];
+        let [delta, vega, rho, theta] = value.gradient;
+        let theta = self.theta.report(theta, option, value.value, delta);
+        PricingResult {
+            price: value.value,
+            greeks: Greeks { delta, gamma: value.curvature, theta, vega, rho },
+            diagnostics: Some(EngineDiagnostics {
+                engine: "lr",
+                time_steps: n,
+                space_steps: 0,
+                tree_parameters: Some((u.value, d.value, qu.value)),
+                paths: 0,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::option_type::ExerciseStyle;
+
+    #[test]
+    fn borrow_prices_like_an_equal_dividend_yield() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 101, 0.03, 0.2, OptionType::Call, ExerciseStyle::American).unwrap();
+        let dividend = BinomialLRWithAD::new(option.clone()).price();
+        let borrow = BinomialLRWithAD::new(option.with_yield_as_borrow()).price();
+        assert!((dividend.price - borrow.price).abs() < 1e-12);
+        assert!((dividend.greeks.delta - borrow.greeks.delta).abs() < 1e-12);
+        assert!((dividend.greeks.rho - borrow.greeks.rho).abs() < 1e-12);
+    }
+
+    #[test]
+    fn greeks_are_continuous_where_d2_vanishes() {
+        // With b = σ²/2 an at-the-money option has d2 = 0.
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 101, 0.03, 0.2, OptionType::Call, ExerciseStyle::American).unwrap();
+        let mut nearby = option.clone();
+        nearby.sigma += 1e-7;
+        let (at, near) = (BinomialLRWithAD::new(option).price(), BinomialLRWithAD::new(nearby).price());
+        for (a, b) in [(at.greeks.delta, near.greeks.delta), (at.greeks.gamma, near.greeks.gamma), (at.greeks.vega, near.greeks.vega), (at.greeks.rho, near.greeks.rho)] {
+            assert!((a - b).abs() < 1e-4 * b.abs().max(1.0), "{a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn greeks_match_central_differences_of_the_price() {
+        for (exercise, option_type) in [(ExerciseStyle::European, OptionType::Call), (ExerciseStyle::American, OptionType::Put)] {
+            let option = StockOption::new(100.0, 105.0, 0.05, 1.0, 101, 0.02, 0.25, option_type, exercise).unwrap();
+            let result = BinomialLRWithAD::new(option.clone()).price();
+            let price = |bump: &dyn Fn(&mut StockOption)| {
+                let mut bumped = option.clone();
+                bump(&mut bumped);
+                BinomialLRWithAD::new(bumped).price().price
+            };
+            let central = |h: f64, bump: &dyn Fn(&mut StockOption, f64)| {
+                (price(&|o| bump(o, h)) - price(&|o| bump(o, -h))) / (2.0 * h)
+            };
+            let delta = central(1e-3, &|o, h| o.s0 += h);
+            // The exercise region of the tree changes within a few cents of the spot, so the
+            // bumps stay below that.
+            let gamma = (price(&|o| o.s0 += 0.01) - 2.0 * result.price + price(&|o| o.s0 -= 0.01)) / 1e-4;
+            let vega = central(1e-5, &|o, h| o.sigma += h);
+            let rho = central(1e-5, &|o, h| o.r += h);
+            let theta = central(1e-5, &|o, h| o.t += h);
+            let greeks = result.greeks;
+            for (name, ad, bumped) in [("delta", greeks.delta, delta), ("gamma", greeks.gamma, gamma), ("vega", greeks.vega, vega), ("rho", greeks.rho, rho), ("theta", greeks.theta, theta)] {
+                assert!((ad - bumped).abs() < 1e-4 * bumped.abs().max(1.0), "{exercise:?} {name}: {ad} vs {bumped}");
+            }
+        }
+    }
+}
@@ -0,0 +1,360 @@
+// binomial_lr_with_greeks.rs
+
+use crate::binomial_lr_option::BinomialLROption;
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::greeks::{CrossGreeks, DifferenceScheme, ExtendedGreeks, Greeks, GreeksConfig};
+use crate::pricing_engine::{PricingEngine, PricingResult};
+use crate::stock_option::StockOption;
+use crate::workspace::Workspace;
+
+/// A row of a spot ladder: the stock price followed by the option price, delta, gamma,
+/// theta, vega, and rho.
+pub type LadderRow = (f64, f64, f64, f64, f64, f64, f64);
+
+/// Represents a binomial LR (Leisen-Reimer) option with Greeks calculation.
+///
+/// This struct extends the `BinomialLROption` to include the calculation of option Greeks,
+/// such as delta, gamma, theta, vega, and rho.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinomialLRWithGreeks {
+    /// The underlying binomial LR option.
+    pub lr_option: BinomialLROption,
+    /// The bumps used for theta, vega, and rho.
+    pub config: GreeksConfig,
+}
+
+impl BinomialLRWithGreeks {
+    /// Creates a new `BinomialLRWithGreeks` instance with the given `BinomialLROption`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lr_option` - The binomial LR option to be used for Greeks calculation.
+    ///
+    /// The Greeks are calculated with the default `GreeksConfig`.
+    pub fn new(lr_option: BinomialLROption) -> Self {
+        BinomialLRWithGreeks {
+            lr_option,
+            config: GreeksConfig::default(),
+        }
+    }
+
+    /// Traverses the extended Leisen-Reimer tree of an option, whose root level has three
+    /// nodes, on a scratch copy of the engine.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to value.
+    /// * `workspace` - The buffers and parameter cache of the traversal, shared by the
+    ///   revaluations of the bumped Greeks.
+    ///
+    /// # Returns
+    ///
+    /// The stock prices of the three root nodes, in decreasing order, and the option
+    /// values at them.
+    fn root_values(&self, option: &StockOption, workspace: &mut Workspace) -> ([f64; 3], [f64; 3]) {
+        self.lr_option.in_scratch(option, workspace, |scratch| {
+            scratch.setup_parameters();
+            let root = new_stock_price_tree(&mut scratch.tree);
+            let payoffs = scratch.tree.begin_tree_traversal();
+            let values = [payoffs[0], payoffs[payoffs.len() / 2], payoffs[payoffs.len() - 1]];
+            scratch.tree.values = payoffs;
+            (root, values)
+        })
+    }
+
+    /// Calculates the option price and Greeks (delta, gamma, theta, vega, rho).
+    ///
+    /// This method first sets up the binomial LR option parameters and generates the stock price tree.
+    /// It then calculates the option payoffs using the `begin_tree_traversal` method from the binomial LR option.
+    /// Finally, it computes the option price and various Greeks based on the calculated payoffs and stock prices.
+    ///
+    /// # Returns
+    ///
+    /// The option price and its Greeks, with the diagnostics of the Leisen-Reimer engine:
+    /// - `delta`: The option's delta (rate of change of option price with respect to the underlying asset price).
+    /// - `gamma`: The option's gamma (rate of change of delta with respect to the underlying asset price).
+    /// - `theta`: The option's theta (rate of change of option price with respect to time), in
+    ///   the theta convention of the configuration.
+    /// - `vega`: The option's vega (sensitivity of option price to changes in volatility).
+    /// - `rho`: The option's rho (sensitivity of option price to changes in the risk-free interest rate).
+    ///
+    /// The bumped revaluations price copies of the option, which is never changed.
+    pub fn price(&self) -> PricingResult {
+        let (price, greeks) = self.greeks_of(&self.lr_option.tree.option, &mut Workspace::default());
+        PricingResult { price, greeks, diagnostics: Some(self.lr_option.diagnostics()) }
+    }
+
+    /// Calculates the price and Greeks of an option, as `price` does for the option of
+    /// the engine.
+    fn greeks_of(&self, option: &StockOption, workspace: &mut Workspace) -> (f64, Greeks) {
+        let ([s_up, _, s_down], [payoff_up, option_value, payoff_down]) = self.root_values(option, workspace);
+
+        let ds_up = s_up - option.s0;
+        let ds_down = option.s0 - s_down;
+        let ds = s_up - s_down;
+        let dv = payoff_up - payoff_down;
+
+        // Calculate delta as the change in option value divided by the change in stock price
+        let delta = dv / ds;
+
+        // Calculate gamma as the change in delta divided by the change in stock price
+        let gamma = ((payoff_up - option_value) / ds_up - (option_value - payoff_down) / ds_down)
+            / ((option.s0 + s_up) / 2.0 - (option.s0 + s_down) / 2.0);
+
+        // Calculate theta as the change in option value divided by the change in time to
+        // expiration, bumping the time to expiration downward
+        let dt = self.config.absolute_bump(self.config.time_bump, option.t);
+        let theta = self.sensitivity(option, |o| &mut o.t, -dt, option_value, workspace);
+        let theta = self.config.theta.report(theta, option, option_value, delta);
+
+        // Calculate vega as the change in option value divided by the change in volatility
+        let dv = self.config.absolute_bump(self.config.vol_bump, option.sigma);
+        let vega = self.sensitivity(option, |o| &mut o.sigma, dv, option_value, workspace);
+
+        // Calculate rho as the change in option value divided by the change in interest rate
+        let dr = self.config.absolute_bump(self.config.rate_bump, option.r);
+        let rho = self.sensitivity(option, |o| &mut o.r, dr, option_value, workspace);
+
+        (option_value, Greeks { delta, gamma, theta, vega, rho })
+    }
+
+    /// Calculates the option price and Greeks at each of several stock prices.
+    ///
+    /// The Leisen-Reimer tree is centred on the strike as seen from the current stock
+    /// price, so each level needs its own tree; the engine, its configuration, and its
+    /// node storage are reused across the ladder. Each level prices a copy of the option.
+    ///
+    /// # Arguments
+    ///
+    /// * `spots` - The stock prices at which to evaluate the option.
+    ///
+    /// # Returns
+    ///
+    /// A vector with, for each stock price, a tuple containing the stock price and the
+    /// option price, delta, gamma, theta, vega, and rho, as for `price`.
+    pub fn spot_ladder(&self, spots: &[f64]) -> Vec<LadderRow> {
+        let mut option = self.lr_option.tree.option.clone();
+        let mut workspace = Workspace::default();
+        spots
+            .iter()
+            .map(|&spot| {
+                option.s0 = spot;
+                let (value, greeks) = self.greeks_of(&option, &mut workspace);
+                (spot, value, greeks.delta, greeks.gamma, greeks.theta, greeks.vega, greeks.rho)
+            })
+            .collect()
+    }
+
+    /// Calculates the derivative of the option value with respect to one parameter by the
+    /// configured finite-difference scheme.
+    ///
+    /// One-sided differences revalue the option at `parameter + bump` only, so a negative
+    /// bump gives a backward difference.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option at the current parameters.
+    /// * `parameter` - Selects the parameter of the option to bump.
+    /// * `bump` - The absolute bump.
+    /// * `value` - The option value at the current parameters.
+    /// * `workspace` - The buffers of the revaluations.
+    ///
+    /// # Returns
+    ///
+    /// The estimated derivative.
+    fn sensitivity(
+        &self,
+        option: &StockOption,
+        parameter: fn(&mut StockOption) -> &mut f64,
+        bump: f64,
+        value: f64,
+        workspace: &mut Workspace,
+    ) -> f64 {
+        let (bumped_value, _, _) = self.bumped(option, parameter, bump, workspace);
+        match self.config.scheme {
+            DifferenceScheme::OneSided => (bumped_value - value) / bump,
+            DifferenceScheme::Central => {
+                let (opposite_value, _, _) = self.bumped(option, parameter, -bump, workspace);
+                (bumped_value - opposite_value) / (2.0 * bump)
+            }
+        }
+    }
+
+    /// Calculates the value, delta, and gamma of an option with one traversal of the
+    /// extended tree.
+    fn value_delta_gamma(&self, option: &StockOption, workspace: &mut Workspace) -> (f64, f64, f64) {
+        let ([s_up, s, s_down], [value_up, value, value_down]) = self.root_values(option, workspace);
+        let delta = (value_up - value_down) / (s_up - s_down);
+        let gamma = ((value_up - value) / (s_up - s) - (value - value_down) / (s - s_down)) / ((s_up - s_down) / 2.0);
+        (value, delta, gamma)
+    }
+
+    /// Calculates the second-order cross Greeks (vanna, volga, and charm) by central
+    /// differences.
+    ///
+    /// Each bumped tree also yields a delta, so vanna and volga take three trees and
+    /// charm two more. The bumps should be large enough to move the option across tree
+    /// nodes consistently; bumps much smaller than the defaults of the Python API (one
+    /// volatility point and one calendar day) amplify the discretization noise of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `vol_bump` - The absolute bump applied to the volatility.
+    /// * `time_bump` - The bump applied to the time to expiration (in years); it must be
+    ///   smaller than the time to expiration.
+    ///
+    /// # Returns
+    ///
+    /// The cross Greeks of the option.
+    pub fn cross_greeks(&self, vol_bump: f64, time_bump: f64) -> CrossGreeks {
+        let option = &self.lr_option.tree.option;
+        let mut workspace = Workspace::default();
+        let (value, _, _) = self.value_delta_gamma(option, &mut workspace);
+
+        let (value_up, delta_up, _) = self.bumped(option, |o| &mut o.sigma, vol_bump, &mut workspace);
+        let (value_down, delta_down, _) = self.bumped(option, |o| &mut o.sigma, -vol_bump, &mut workspace);
+
+        let (_, delta_earlier, _) = self.bumped(option, |o| &mut o.t, -time_bump, &mut workspace);
+        let (_, delta_later, _) = self.bumped(option, |o| &mut o.t, time_bump, &mut workspace);
+
+        CrossGreeks {
+            vanna: (delta_up - delta_down) / (2.0 * vol_bump),
+            volga: (value_up - 2.0 * value + value_down) / vol_bump.powi(2),
+            charm: (delta_earlier - delta_later) / (2.0 * time_bump),
+        }
+    }
+
+    /// Calculates the value, delta, and gamma of a copy of an option with one parameter
+    /// shifted by `amount`.
+    fn bumped(
+        &self,
+        option: &StockOption,
+        parameter: fn(&mut StockOption) -> &mut f64,
+        amount: f64,
+        workspace: &mut Workspace,
+    ) -> (f64, f64, f64) {
+        let mut bumped = option.clone();
+        *parameter(&mut bumped) += amount;
+        self.value_delta_gamma(&bumped, workspace)
+    }
+
+    /// Calculates the third-order spot Greeks (speed, zomma, and color) and the dividend
+    /// and strike sensitivities (psi, dual delta, and dual gamma) by central differences.
+    ///
+    /// The third-order Greeks are differences of the tree gamma. The strike bumps move the
+    /// centre of the Leisen-Reimer tree with the strike, so the dual Greeks vary smoothly
+    /// when the number of steps is odd.
+    ///
+    /// # Arguments
+    ///
+    /// * `spot_bump` - The bump applied to the stock price, relative to the stock price.
+    /// * `vol_bump` - The absolute bump applied to the volatility.
+    /// * `time_bump` - The bump applied to the time to expiration (in years); it must be
+    ///   smaller than the time to expiration.
+    /// * `div_bump` - The absolute bump applied to the dividend yield.
+    /// * `strike_bump` - The bump applied to the strike, relative to the strike.
+    ///
+    /// # Returns
+    ///
+    /// The extended Greeks of the option.
+    pub fn extended_greeks(
+        &self,
+        spot_bump: f64,
+        vol_bump: f64,
+        time_bump: f64,
+        div_bump: f64,
+        strike_bump: f64,
+    ) -> ExtendedGreeks {
+        let gamma = |(_, _, gamma): (f64, f64, f64)| gamma;
+        let value = |(value, _, _): (f64, f64, f64)| value;
+
+        let option = &self.lr_option.tree.option;
+        let mut workspace = Workspace::default();
+        let (center, _, _) = self.value_delta_gamma(option, &mut workspace);
+        let mut bumped = |parameter: fn(&mut StockOption) -> &mut f64, amount: f64| {
+            self.bumped(option, parameter, amount, &mut workspace)
+        };
+
+        let h = spot_bump * option.s0;
+        let speed = (gamma(bumped(|o| &mut o.s0, h)) - gamma(bumped(|o| &mut o.s0, -h))) / (2.0 * h);
+        let zomma = (gamma(bumped(|o| &mut o.sigma, vol_bump)) - gamma(bumped(|o| &mut o.sigma, -vol_bump)))
+            / (2.0 * vol_bump);
+        let color = (gamma(bumped(|o| &mut o.t, -time_bump)) - gamma(bumped(|o| &mut o.t, time_bump)))
+            / (2.0 * time_bump);
+        let psi = (value(bumped(|o| &mut o.div, div_bump)) - value(bumped(|o| &mut o.div, -div_bump)))
+            / (2.0 * div_bump);
+
+        let dk = strike_bump * option.k;
+        let (higher, lower) = (value(bumped(|o| &mut o.k, dk)), value(bumped(|o| &mut o.k, -dk)));
+        ExtendedGreeks {
+            speed,
+            zomma,
+            color,
+            psi,
+            dual_delta: (higher - lower) / (2.0 * dk),
+            dual_gamma: (higher - 2.0 * center + lower) / dk.powi(2),
+        }
+    }
+}
+
+/// Builds the stock price tree of a scratch Leisen-Reimer tree with three root nodes.
+///
+/// The root nodes are one step above, at, and one step below the stock price, so the
+/// values at the root give the delta and gamma without bumping. With discrete dividends
+/// the tree is built on the process returned by `net_spot` and mapped to stock prices
+/// level by level, as by `BinomialTreeOption::init_stock_price_tree`. The tree reuses the
+/// storage of the previous one, and only its terminal level is built when backward
+/// induction reads no other level, so the revaluations of the bumped Greeks share one
+/// allocation.
+///
+/// # Arguments
+///
+/// * `tree` - The tree, whose Leisen-Reimer parameters are already set up.
+///
+/// # Returns
+///
+/// The stock prices of the three root nodes, in decreasing order.
+fn new_stock_price_tree(tree: &mut BinomialTreeOption) -> [f64; 3] {
+    let u_over_d = tree.u / tree.d;
+    let d_over_u = tree.d / tree.u;
+
+    let x0 = tree.option.net_spot();
+    let root = [x0 * u_over_d, x0, x0 * d_over_u];
+    let (n, u, d, log_space) = (tree.option.n, tree.u, tree.d, tree.option.log_space);
+    if tree.reads_every_level() {
+        tree.option.sts.regrow(&root, n, u, d, log_space);
+    } else {
+        tree.option.sts.regrow_terminal(&root, n, u, d, log_space);
+    }
+    if tree.option.dividends.is_none() {
+        return root;
+    }
+    let mut sts = std::mem::take(&mut tree.option.sts);
+    sts.map(|i, x| tree.option.stock_price_at(x, tree.option.step_time(i)));
+    tree.option.sts = sts;
+    root.map(|x| tree.option.stock_price_at(x, 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dividend_schedule::{Dividend, DividendKind, DividendSchedule};
+    use crate::option_type::{ExerciseStyle, OptionType};
+
+    #[test]
+    fn prices_discrete_dividends_like_the_lr_tree() {
+        let dividends = DividendSchedule::new(vec![
+            Dividend { time: 0.25, amount: 3.0, kind: DividendKind::Cash },
+            Dividend { time: 0.5, amount: 0.02, kind: DividendKind::Proportional },
+        ])
+        .unwrap();
+        for exercise in [ExerciseStyle::European, ExerciseStyle::American] {
+            let mut option = StockOption::new(100.0, 100.0, 0.05, 1.0, 201, 0.0, 0.25, OptionType::Put, exercise).unwrap();
+            option.dividends = Some(dividends.clone());
+            let expected = BinomialLROption::new(BinomialTreeOption::new(option.clone())).price();
+            let result = BinomialLRWithGreeks::new(BinomialLROption::new(BinomialTreeOption::new(option))).price();
+            assert!((result.price - expected).abs() < 1e-10, "{:?}: {} vs {}", exercise, result.price, expected);
+        }
+    }
+}
@@ -0,0 +1,1036 @@
+// binomial_tree_option.rs
+
+use crate::barrier::{BarrierKind, RebateTiming};
+use crate::option_type::OptionType;
+use crate::payoff::{Payoff, PayoffKind};
+use crate::precision::{Precision, Real};
+use crate::price_tree::node_price;
+use crate::pricing_error::PricingError;
+use crate::stock_option::StockOption;
+use crate::workspace::Workspace;
+
+/// The number of nodes processed together by the inner loops of backward induction.
+///
+/// The loops work on fixed-size blocks of this many nodes, which the compiler turns into
+/// vector instructions; four `f64` lanes fill an AVX register.
+const LANES: usize = 4;
+
+/// Represents a binomial tree option pricing model.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinomialTreeOption {
+    /// The underlying stock option.
+    pub option: StockOption,
+    /// The up move of the plain tree, whose up factor is `1 + pu`.
+    ///
+    /// Only `setup_parameters` reads it; the CRR and LR trees derive their factors from the
+    /// volatility instead.
+    pub pu: f64,
+    /// The down move of the plain tree, whose down factor is `1 - pd`.
+    pub pd: f64,
+    /// The up factor in the binomial tree.
+    pub u: f64,
+    /// The down factor in the binomial tree.
+    pub d: f64,
+    /// The risk-neutral probability of an up move.
+    pub qu: f64,
+    /// The risk-neutral probability of a down move.
+    pub qd: f64,
+    /// The optional reach-probability threshold below which lattice nodes are dropped.
+    ///
+    /// When set to a positive epsilon, nodes whose risk-neutral probability of being
+    /// reached from the root is below it are not visited during backward induction.
+    pub truncation: Option<f64>,
+    /// The upper bound on the pricing error introduced by truncation in the last traversal.
+    pub truncation_error: f64,
+    /// The floating-point type of backward induction.
+    ///
+    /// Single precision applies to vanilla payoffs without barriers, truncation, or
+    /// discrete dividends; other options are always traversed in double precision.
+    pub precision: Precision,
+    /// The storage of the node values, kept between traversals.
+    ///
+    /// `begin_tree_traversal` fills it with the terminal payoffs and returns it, and the
+    /// pricing methods put it back once they have read the root values.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub values: Vec<f64>,
+}
+
+impl BinomialTreeOption {
+    /// Creates a new `BinomialTreeOption` instance with the given stock option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying stock option.
+    ///
+    /// # Returns
+    ///
+    /// A new `BinomialTreeOption` instance.
+    pub fn new(option: StockOption) -> Self {
+        BinomialTreeOption::with_moves(option, 0.0, 0.0)
+    }
+
+    /// Creates a new plain `BinomialTreeOption` with the given moves per step.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying stock option.
+    /// * `pu` - The up move, so that the up factor is `1 + pu`.
+    /// * `pd` - The down move, so that the down factor is `1 - pd`.
+    ///
+    /// # Returns
+    ///
+    /// A new `BinomialTreeOption` instance.
+    pub fn with_moves(option: StockOption, pu: f64, pd: f64) -> Self {
+        BinomialTreeOption {
+            option,
+            pu,
+            pd,
+            u: 0.0,
+            d: 0.0,
+            qu: 0.0,
+            qd: 0.0,
+            truncation: None,
+            truncation_error: 0.0,
+            precision: Precision::Double,
+            values: Vec::new(),
+        }
+    }
+
+    /// Replaces the buffers of the tree by those of a workspace, which the traversals run on
+    /// the tree itself, as by the engines built on it, reuse. The pricing methods that take
+    /// `&self` take their workspace as an argument instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace, typically taken from the engine of another option.
+    pub fn use_workspace(&mut self, workspace: Workspace) {
+        self.option.sts = workspace.stock_prices;
+        self.values = workspace.values;
+    }
+
+    /// Moves the buffers of the tree out into a workspace for another engine.
+    ///
+    /// # Returns
+    ///
+    /// The workspace, leaving the tree with empty buffers.
+    pub fn take_workspace(&mut self) -> Workspace {
+        Workspace {
+            stock_prices: std::mem::take(&mut self.option.sts),
+            values: std::mem::take(&mut self.values),
+        }
+    }
+
+    /// Creates a tree with the moves and settings of this one for pricing another option,
+    /// with the buffers of a workspace.
+    ///
+    /// The pricing methods that take `&self` set up and traverse such a scratch tree, so
+    /// that this tree and its option are never changed. `release` hands the buffers back.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to price, which the scratch tree copies.
+    /// * `workspace` - The workspace whose tree buffers the scratch tree takes.
+    ///
+    /// # Returns
+    ///
+    /// The scratch tree.
+    pub(crate) fn scratch(&self, option: &StockOption, workspace: &mut Workspace) -> BinomialTreeOption {
+        let mut tree = BinomialTreeOption::with_moves(option.clone(), self.pu, self.pd);
+        tree.truncation = self.truncation;
+        tree.precision = self.precision;
+        tree.option.sts = std::mem::take(&mut workspace.stock_prices);
+        tree.values = std::mem::take(&mut workspace.values);
+        tree
+    }
+
+    /// Hands the buffers of a scratch tree back to the workspace it took them from.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace that receives the buffers.
+    pub(crate) fn release(self, workspace: &mut Workspace) {
+        workspace.stock_prices = self.option.sts;
+        workspace.values = self.values;
+    }
+
+    /// Sets up the parameters for the binomial tree option pricing model.
+    ///
+    /// This method calculates the up factor, down factor, and risk-neutral probabilities
+    /// based on the underlying stock option parameters.
+    pub fn setup_parameters(&mut self) {
+        self.u = 1.0 + self.pu;
+        self.d = 1.0 - self.pd;
+        self.qu = (self.option.growth() - self.d) / (self.u - self.d);
+        self.qd = 1.0 - self.qu;
+    }
+
+    /// Sets up Cox-Ross-Rubinstein parameters for the binomial tree.
+    ///
+    /// The up factor is `e^{σ√Δt}` and the down factor its reciprocal, so the tree
+    /// recombines symmetrically in log-price. The risk-neutral probabilities are derived
+    /// from the cost of carry.
+    pub fn setup_crr_parameters(&mut self) {
+        let dt = self.option.dt();
+        self.u = (self.option.sigma * dt.sqrt()).exp();
+        self.d = 1.0 / self.u;
+        self.qu = (self.option.growth() - self.d) / (self.u - self.d);
+        self.qd = 1.0 - self.qu;
+    }
+
+    /// Checks that the risk-neutral probabilities set up for the tree lie in `[0, 1]`.
+    ///
+    /// A probability outside this range, which a Cox-Ross-Rubinstein tree has when the
+    /// drift over a step exceeds the volatility, means the tree is not arbitrage-free and
+    /// its prices are meaningless.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` with the probability if it is outside `[0, 1]`.
+    pub fn check_probabilities(&self) -> Result<(), PricingError> {
+        if (0.0..=1.0).contains(&self.qu) {
+            return Ok(());
+        }
+        Err(PricingError::InvalidInput(format!(
+            "The risk-neutral probability of an up move must be in [0, 1], not {}. \
+             Use more steps or a higher volatility.",
+            self.qu
+        )))
+    }
+
+    /// Checks that the moves `pu` and `pd` of the plain tree lie in `[0, 1]` and are not
+    /// both zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` naming the offending move.
+    pub fn check_moves(&self) -> Result<(), PricingError> {
+        for (name, value) in [("up move pu", self.pu), ("down move pd", self.pd)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(PricingError::InvalidInput(format!("The {name} must be in [0, 1], not {value}.")));
+            }
+        }
+        if self.pu + self.pd == 0.0 {
+            return Err(PricingError::InvalidInput(
+                "The plain binomial tree needs a positive up move pu or down move pd.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Initializes the stock price tree for the binomial option pricing model.
+    ///
+    /// This method constructs the stock price tree based on the initial stock price,
+    /// up factor, and down factor. With a dividend schedule, the tree is built on the
+    /// stock price net of the escrowed cash dividends. The storage of the previous tree is
+    /// reused, so repeated valuations of a bumped option allocate it only once.
+    pub fn init_stock_price_tree(&mut self) {
+        self.option.sts.regrow(&[self.option.net_spot()], self.option.n, self.u, self.d, self.option.log_space);
+        // With discrete dividends the tree recombines on the net process, whose nodes
+        // are then mapped to stock prices level by level.
+        if self.option.dividends.is_some() {
+            let mut sts = std::mem::take(&mut self.option.sts);
+            sts.map(|i, x| self.option.stock_price_at(x, self.option.step_time(i)));
+            self.option.sts = sts;
+        }
+    }
+
+    /// Checks whether backward induction reads the stock prices before expiration, which
+    /// it does with early exercise or barriers. In single precision the earlier levels are
+    /// derived from the terminal one instead.
+    pub fn reads_every_level(&self) -> bool {
+        !self.in_single_precision() && self.exercise_schedule()[..self.option.n].contains(&true)
+            || self.option.barrier.is_some()
+            || self.option.double_barrier.is_some()
+    }
+
+    /// Checks whether the traversal drops the nodes below the reach-probability threshold
+    /// of `truncation`.
+    ///
+    /// Truncation needs a bound on the value of every dropped node, which is derived from
+    /// the payoff over the stock prices the node can reach. It therefore applies only to
+    /// built-in payoffs without barriers, and not to early exercise with discrete
+    /// dividends, which move the stock prices between the levels. Every step must also
+    /// have probabilities in `[0, 1]`. Other options are priced on the full tree.
+    fn truncates(&self) -> bool {
+        let n = self.option.n;
+        self.truncation.is_some_and(|epsilon| epsilon > 0.0)
+            && self.option.barrier.is_none()
+            && self.option.double_barrier.is_none()
+            && !matches!(self.option.payoff, PayoffKind::Custom(_))
+            && !(self.option.dividends.is_some() && self.exercise_schedule()[..n].contains(&true))
+            && (0..n).all(|i| {
+                let (qu, qd, _) = self.step_parameters(i);
+                (0.0..=1.0).contains(&qu) && (0.0..=1.0).contains(&qd)
+            })
+    }
+
+    /// Initializes as much of the stock price tree as the pricing traversal reads.
+    ///
+    /// Without early exercise or barriers, backward induction only reads the stock prices
+    /// at expiration, so only the terminal level is built, and a truncated traversal
+    /// calculates the prices of the nodes it visits, so only the root is stored. Otherwise
+    /// the whole tree is built as by `init_stock_price_tree`.
+    pub fn init_pricing_tree(&mut self) {
+        if self.truncates() {
+            self.option.sts.regrow(&[self.option.net_spot()], 0, self.u, self.d, self.option.log_space);
+            return;
+        }
+        if self.reads_every_level() {
+            self.init_stock_price_tree();
+            return;
+        }
+        self.option.sts.regrow_terminal(&[self.option.net_spot()], self.option.n, self.u, self.d, self.option.log_space);
+        if self.option.dividends.is_some() {
+            let mut sts = std::mem::take(&mut self.option.sts);
+            sts.map(|i, x| self.option.stock_price_at(x, self.option.step_time(i)));
+            self.option.sts = sts;
+        }
+    }
+
+    /// Initializes the payoff tree for the binomial option pricing model.
+    ///
+    /// This method calculates the payoffs at the terminal nodes of the binomial tree
+    /// based on the stock prices and the option's payoff. Discontinuous payoffs are
+    /// averaged over the log-price cell of each node to smooth convergence.
+    ///
+    /// # Arguments
+    ///
+    /// * `payoffs` - The storage to fill, whose previous contents are discarded.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing the payoffs at the terminal nodes of the binomial tree, in the
+    /// storage of `payoffs`.
+    fn init_payoffs_tree(&self, mut payoffs: Vec<f64>) -> Vec<f64> {
+        payoffs.clear();
+        if let PayoffKind::Vanilla = self.option.payoff {
+            payoffs.resize(self.option.sts[self.option.n].len(), 0.0);
+            self.check_early_exercise(&mut payoffs, self.option.n);
+            return payoffs;
+        }
+        let half_width = (self.u / self.d).ln() / 2.0;
+        payoffs.extend(self.option.sts[self.option.n].iter().map(|&s| {
+            self.option
+                .payoff
+                .smoothed_value(s, self.option.k, self.option.option_type, half_width)
+        }));
+        payoffs
+    }
+
+    /// Checks for early exercise opportunity at a given node in the binomial tree.
+    ///
+    /// This method compares the payoffs with the intrinsic values at a given node
+    /// and replaces each payoff that is below its intrinsic value.
+    ///
+    /// # Arguments
+    ///
+    /// * `payoffs` - The payoffs at the current node, updated in place.
+    /// * `node` - The index of the current node in the binomial tree.
+    fn check_early_exercise(&self, payoffs: &mut [f64], node: usize) {
+        let prices = &self.option.sts[node][..payoffs.len()];
+        let k = self.option.k;
+        match (&self.option.payoff, self.option.option_type) {
+            (PayoffKind::Vanilla, OptionType::Call) => max_in_lanes(payoffs, prices, |s| (s - k).max(0.0)),
+            (PayoffKind::Vanilla, OptionType::Put) => max_in_lanes(payoffs, prices, |s| (k - s).max(0.0)),
+            _ => {
+                for (p, &s) in payoffs.iter_mut().zip(prices) {
+                    *p = p.max(self.exercise_value(s));
+                }
+            }
+        }
+    }
+
+    /// Traverses the binomial tree backward to calculate the option price.
+    ///
+    /// This method starts from the terminal payoffs and works backward through the tree,
+    /// calculating the option price at each node based on the risk-neutral probabilities
+    /// and the discount factor. It also checks for early exercise opportunities at the
+    /// steps allowed by the option's exercise schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `payoffs` - The payoffs at the terminal nodes of the binomial tree.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing the option prices at each node of the binomial tree.
+    fn traverse_tree(&mut self, mut payoffs: Vec<f64>) -> Vec<f64> {
+        if let Some(barrier) = self.option.barrier {
+            self.truncation_error = 0.0;
+            let monitored = barrier.monitoring_schedule(self.option.t, self.option.n);
+            return self.traverse_barrier_tree(
+                payoffs,
+                |i, s| monitored[i] && barrier.is_breached(s),
+                barrier.kind,
+                |i| match barrier.rebate_timing {
+                    RebateTiming::AtHit => barrier.rebate,
+                    RebateTiming::AtExpiry => barrier.rebate * self.option.discount_to_expiry(i),
+                },
+                barrier.rebate,
+            );
+        }
+
+        if let Some(barrier) = self.option.double_barrier {
+            self.truncation_error = 0.0;
+            return self.traverse_barrier_tree(
+                payoffs,
+                |_, s| barrier.is_breached(s),
+                barrier.kind,
+                |i| match barrier.rebate_timing {
+                    RebateTiming::AtHit => barrier.rebate,
+                    RebateTiming::AtExpiry => barrier.rebate * self.option.discount_to_expiry(i),
+                },
+                barrier.rebate,
+            );
+        }
+
+        self.truncation_error = 0.0;
+        let schedule = self.exercise_schedule();
+        for i in (0..self.option.n).rev() {
+            self.step_back(&mut payoffs, i);
+            if schedule[i] {
+                self.check_early_exercise(&mut payoffs, i);
+            }
+        }
+        payoffs
+    }
+
+    /// Determines at which time steps the tree checks for early exercise.
+    ///
+    /// This is the exercise schedule of the option, except that no step is checked when
+    /// early exercise provably never binds, as for an American call without dividends:
+    /// the traversal then collapses to the cheaper European one. The proof needs
+    /// probabilities within [0, 1], so it is not applied to a plain tree whose moves
+    /// admit arbitrage, nor to a volatility term structure, whose probabilities vary by
+    /// step.
+    ///
+    /// # Returns
+    ///
+    /// A vector of length `n + 1` indicating whether early exercise is checked at each step.
+    fn exercise_schedule(&self) -> Vec<bool> {
+        let never_binds = self.option.early_exercise_never_binds()
+            && self.option.vol_term.is_none()
+            && (0.0..=1.0).contains(&self.qu);
+        if never_binds {
+            return vec![false; self.option.n + 1];
+        }
+        self.option.exercise_schedule()
+    }
+
+    /// Calculates the risk-neutral probabilities and discount factor of one time step.
+    ///
+    /// Without a yield curve or volatility term structure these are the tree's own `qu`,
+    /// `qd`, and `df()`. Otherwise the up probability is shifted so that the expected growth
+    /// over the step matches the curve's forward rate over the length of the step, which
+    /// leaves the tree geometry unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The index of the time step, which runs from `step_time(i)` to `step_time(i + 1)`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the up probability, the down probability, and the discount factor.
+    fn step_parameters(&self, i: usize) -> (f64, f64, f64) {
+        if self.option.curve.is_none() && self.option.vol_term.is_none() {
+            return (self.qu, self.qd, self.option.df());
+        }
+        let qu = self.qu + (self.option.step_growth(i) - self.option.growth()) / (self.u - self.d);
+        (qu, 1.0 - qu, self.option.step_df(i))
+    }
+
+    /// Discounts the values of one level of the tree back to the level before it.
+    ///
+    /// The values are overwritten in place from the top node down, since each node only
+    /// reads itself and the node below it, and the buffer then shrinks by one node.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values at the nodes of step `i + 1`, replaced by the discounted
+    ///   expected values at the nodes of step `i`.
+    /// * `i` - The index of the step to which the values are discounted.
+    fn step_back<F: Real>(&self, values: &mut Vec<F>, i: usize) {
+        let (qu, qd, df) = self.step_parameters(i);
+        let (qu, qd, df) = (F::from_f64(qu), F::from_f64(qd), F::from_f64(df));
+        let nodes = values.len() - 1;
+        let mut j = 0;
+        // Each block reads its nodes and the node after them before writing, and the
+        // node after a block is only overwritten by the next block.
+        while j + LANES <= nodes {
+            let up: [F; LANES] = values[j..j + LANES].try_into().unwrap();
+            let down: [F; LANES] = values[j + 1..j + 1 + LANES].try_into().unwrap();
+            for lane in 0..LANES {
+                values[j + lane] = ((up[lane] * qu + down[lane] * qd) * df).flush();
+            }
+            j += LANES;
+        }
+        for j in j..nodes {
+            values[j] = ((values[j] * qu + values[j + 1] * qd) * df).flush();
+        }
+        values.pop();
+    }
+
+    /// Traverses the binomial tree backward while enforcing a barrier condition.
+    ///
+    /// Knock-out options are replaced by the knock-out rebate at every node where the
+    /// barrier is breached. Knock-in options are valued alongside the corresponding
+    /// vanilla option: at nodes where the barrier is breached the knock-in takes the
+    /// vanilla value, while elsewhere it cannot be exercised and is worth the knock-in
+    /// rebate at expiration.
+    ///
+    /// # Arguments
+    ///
+    /// * `payoffs` - The payoffs at the terminal nodes of the binomial tree.
+    /// * `is_breached` - Checks whether the barrier is breached at a time step and stock price.
+    /// * `kind` - Whether breaching the barrier activates or extinguishes the option.
+    /// * `knock_out_rebate` - The value at a given time step of the rebate paid on knock-out.
+    /// * `knock_in_rebate` - The rebate paid at expiration if a knock-in option is never activated.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing the option prices at the root nodes of the binomial tree.
+    fn traverse_barrier_tree<B, R>(
+        &self,
+        payoffs: Vec<f64>,
+        is_breached: B,
+        kind: BarrierKind,
+        knock_out_rebate: R,
+        knock_in_rebate: f64,
+    ) -> Vec<f64>
+    where
+        B: Fn(usize, f64) -> bool,
+        R: Fn(usize) -> f64,
+    {
+        let n = self.option.n;
+        let schedule = self.exercise_schedule();
+
+        let mut values = payoffs
+            .iter()
+            .zip(&self.option.sts[n])
+            .map(|(&payoff, &s)| match (kind, is_breached(n, s)) {
+                (BarrierKind::KnockOut, true) => knock_out_rebate(n),
+                (BarrierKind::KnockIn, false) => knock_in_rebate,
+                _ => payoff,
+            })
+            .collect::<Vec<_>>();
+        let mut vanilla = payoffs;
+
+        for i in (0..n).rev() {
+            self.step_back(&mut values, i);
+            if kind == BarrierKind::KnockIn {
+                self.step_back(&mut vanilla, i);
+                if schedule[i] {
+                    self.check_early_exercise(&mut vanilla, i);
+                }
+            } else if schedule[i] {
+                self.check_early_exercise(&mut values, i);
+            }
+
+            for (j, value) in values.iter_mut().enumerate() {
+                if is_breached(i, self.option.sts[i][j]) {
+                    *value = match kind {
+                        BarrierKind::KnockOut => knock_out_rebate(i),
+                        BarrierKind::KnockIn => vanilla[j],
+                    };
+                }
+            }
+        }
+
+        values
+    }
+
+    /// Traverses the binomial tree backward while skipping nodes with negligible reach probability.
+    ///
+    /// At each level only the contiguous band of nodes whose risk-neutral probability of
+    /// being reached from the root is at least `epsilon` is priced, with stock prices
+    /// calculated node by node rather than read from a stored tree. A forward pass
+    /// propagates the probability of reaching each node through the band, which weights
+    /// the error of the nodes that fall outside it.
+    ///
+    /// A child that falls outside the band is replaced by an estimate of its value: the
+    /// discounted payoff at its forward price, or its exercise value if larger and exercise
+    /// is allowed. The value of the child lies between the discounted extremes of the
+    /// payoff over the stock prices it can reach, discounted step by step for an early
+    /// exercise, so the error of the estimate is at most its distance to the farther
+    /// extreme. That error, weighted by the probability of reaching the child and
+    /// discounted to the root, is accumulated into the error bound.
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon` - The reach-probability threshold below which nodes are dropped.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the option price at the root node and the truncation error bound.
+    fn traverse_truncated_tree(&self, epsilon: f64) -> (Vec<f64>, f64) {
+        let option = &self.option;
+        let (n, u, d) = (option.n, self.u, self.d);
+        let (k, option_type, payoff) = (option.k, option.option_type, &option.payoff);
+        let root = option.net_spot();
+        let net_price = |i: usize, j: usize| node_price(root, i, j, u, d, option.log_space);
+        let price = |i: usize, j: usize| option.stock_price_at(net_price(i, j), option.step_time(i));
+
+        // The discount factor and growth of the net price from each level to expiration,
+        // and the extremes of the discount factor from each level to any later level.
+        let steps = (0..n).map(|i| self.step_parameters(i)).collect::<Vec<_>>();
+        let mut to_expiry = vec![1.0; n + 1];
+        let mut growth_to_expiry = vec![1.0; n + 1];
+        let mut largest_inverse = vec![1.0_f64; n + 1];
+        let mut smallest_inverse = vec![1.0_f64; n + 1];
+        let schedule = self.exercise_schedule();
+        let mut exercise_after = vec![false; n + 1];
+        for i in (0..n).rev() {
+            let (qu, qd, df) = steps[i];
+            to_expiry[i] = to_expiry[i + 1] * df;
+            growth_to_expiry[i] = growth_to_expiry[i + 1] * (qu * u + qd * d);
+            largest_inverse[i] = largest_inverse[i + 1].max(1.0 / to_expiry[i]);
+            smallest_inverse[i] = smallest_inverse[i + 1].min(1.0 / to_expiry[i]);
+            exercise_after[i] = exercise_after[i + 1] || schedule[i];
+        }
+
+        // The estimated value of a dropped node and the bound on its error.
+        let substitute = |i: usize, j: usize| {
+            let (top, bottom) = (price(n, j), price(n, j + n - i));
+            let bounds = |lower: f64, upper: f64| payoff.bounds(lower, upper, k, option_type).unwrap_or((0.0, 0.0));
+            let (payoff_low, payoff_high) = bounds(top.min(bottom), top.max(bottom));
+            let forward = option.stock_price_at(net_price(i, j) * growth_to_expiry[i], option.t);
+            let mut estimate = to_expiry[i] * payoff.value(forward, k, option_type);
+            let lower = to_expiry[i] * payoff_low;
+            let mut upper = to_expiry[i] * payoff_high;
+            if exercise_after[i] {
+                // Without discrete dividends the node and its descendants lie between the
+                // top and bottom terminal nodes it reaches, or at the node itself.
+                let s = price(i, j);
+                let (_, high) = bounds(s.min(top).min(bottom), s.max(top).max(bottom));
+                let discount = if high >= 0.0 { largest_inverse[i] } else { smallest_inverse[i] };
+                upper = upper.max(high * to_expiry[i] * discount);
+                if schedule[i] {
+                    estimate = estimate.max(self.exercise_value(s));
+                }
+            }
+            let estimate = estimate.max(lower).min(upper);
+            (estimate, (estimate - lower).max(upper - estimate))
+        };
+
+        // The log-probability of reaching node `j` of level `i` with the probabilities of the
+        // tree, which selects the band, and the contiguous band around the most likely node.
+        let mut ln_factorials = vec![0.0; n + 1];
+        for i in 1..=n {
+            ln_factorials[i] = ln_factorials[i - 1] + (i as f64).ln();
+        }
+        let (ln_qu, ln_qd, ln_epsilon) = (self.qu.ln(), self.qd.ln(), epsilon.ln());
+        let ln_pmf = |i: usize, j: usize| {
+            ln_factorials[i] - ln_factorials[j] - ln_factorials[i - j] + (i - j) as f64 * ln_qu + j as f64 * ln_qd
+        };
+        // The edges of the band move by about one node per level, so each level starts
+        // from the edges of the level before it.
+        let band = |i: usize, (first, last): (usize, usize)| {
+            let kept = |j: usize| ln_pmf(i, j) >= ln_epsilon;
+            let (mut first, mut last) = (first.min(i), last.min(i));
+            while first > 0 && kept(first - 1) {
+                first -= 1;
+            }
+            while first < last && !kept(first) {
+                first += 1;
+            }
+            while last < i && kept(last + 1) {
+                last += 1;
+            }
+            while last > first && !kept(last) {
+                last -= 1;
+            }
+            (first, last)
+        };
+
+        let mut bands = Vec::with_capacity(n + 1);
+        let mut dropped = vec![Vec::new(); n + 1];
+        let mut error_bound = 0.0;
+        let mut reach = vec![1.0];
+        let mut children = Vec::new();
+        let mut lo = 0;
+        let mut edges = (0, 0);
+        bands.push((0, 0));
+        for i in 0..n {
+            let (qu, qd, _) = steps[i];
+            children.clear();
+            children.resize(reach.len() + 1, 0.0);
+            for (offset, &probability) in reach.iter().enumerate() {
+                children[offset] += probability * qu;
+                children[offset + 1] += probability * qd;
+            }
+            // The band of the next level, limited to the children of this one.
+            edges = band(i + 1, edges);
+            let first = edges.0.clamp(lo, lo + reach.len()) - lo;
+            let last = edges.1.clamp(lo + first, lo + reach.len()) - lo;
+            for (offset, &probability) in children.iter().enumerate() {
+                if offset < first || offset > last {
+                    let (estimate, error) = substitute(i + 1, lo + offset);
+                    dropped[i + 1].push((lo + offset, estimate));
+                    error_bound += probability * to_expiry[0] / to_expiry[i + 1] * error;
+                }
+            }
+            reach.clear();
+            reach.extend_from_slice(&children[first..=last]);
+            lo += first;
+            bands.push((lo, lo + last - first));
+        }
+
+        let (lo, hi) = bands[n];
+        let half_width = (u / d).ln() / 2.0;
+        let mut values = (lo..=hi)
+            .map(|j| payoff.smoothed_value(price(n, j), k, option_type, half_width))
+            .collect::<Vec<_>>();
+        let mut level = Vec::new();
+        for i in (0..n).rev() {
+            let (child_lo, _) = bands[i + 1];
+            let (level_lo, level_hi) = bands[i];
+            let (qu, qd, df) = steps[i];
+            // Every child of the band is either in the band of the next level or dropped.
+            let child = |c: usize| match c.checked_sub(child_lo).and_then(|offset| values.get(offset)) {
+                Some(&value) => value,
+                None => dropped[i + 1].iter().find(|&&(node, _)| node == c).map_or(0.0, |&(_, value)| value),
+            };
+            level.clear();
+            level.extend((level_lo..=level_hi).map(|j| (child(j) * qu + child(j + 1) * qd) * df));
+            if schedule[i] {
+                // Early exercise excludes discrete dividends, so the stock prices are the net
+                // prices, which fall by `d / u` from node to node.
+                let mut s = net_price(i, level_lo);
+                for value in level.iter_mut() {
+                    *value = value.max(self.exercise_value(s));
+                    s *= d / u;
+                }
+            }
+            std::mem::swap(&mut values, &mut level);
+        }
+
+        (values, error_bound)
+    }
+
+    /// Traverses the binomial tree backward while recording where early exercise is optimal.
+    ///
+    /// Barriers and truncation are ignored. A node is in the exercise region when
+    /// exercise is allowed at its step and the exercise value is positive and exceeds
+    /// the continuation value.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the option price and, for each time step before expiration,
+    /// whether each node of the step is in the exercise region.
+    fn exercise_region(&self) -> (f64, Vec<Vec<bool>>) {
+        let n = self.option.n;
+        let schedule = self.exercise_schedule();
+        let mut region = vec![Vec::new(); n];
+        let mut payoffs = self.init_payoffs_tree(Vec::new());
+        for i in (0..n).rev() {
+            self.step_back(&mut payoffs, i);
+            region[i] = payoffs
+                .iter_mut()
+                .zip(&self.option.sts[i])
+                .map(|(value, &s)| {
+                    let exercise = self.exercise_value(s);
+                    let exercised = schedule[i] && exercise > 0.0 && exercise > *value;
+                    if exercised {
+                        *value = exercise;
+                    }
+                    exercised
+                })
+                .collect();
+        }
+        (payoffs[0], region)
+    }
+
+    /// Calculates the risk-neutral probability of early exercise and the expected exercise time.
+    ///
+    /// The tree parameters and stock price tree must already be set up. The holder is
+    /// assumed to exercise at the first node in the exercise region; options that are not
+    /// exercised early are counted as exercised (or lapsing) at expiration.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the option price, the probability that the option is exercised
+    /// before expiration, and the expected time of exercise (in years).
+    pub fn exercise_statistics(&self) -> (f64, f64, f64) {
+        let (price, region) = self.exercise_region();
+
+        // The probability of reaching each node of a step without having exercised.
+        let mut alive = vec![1.0];
+        let mut probability = 0.0;
+        let mut expected_time = 0.0;
+        for (i, exercised) in region.iter().enumerate() {
+            for (reach, &exercise) in alive.iter_mut().zip(exercised) {
+                if exercise {
+                    probability += *reach;
+                    expected_time += *reach * self.option.step_time(i);
+                    *reach = 0.0;
+                }
+            }
+            let (qu, qd, _) = self.step_parameters(i);
+            let mut next = vec![0.0; alive.len() + 1];
+            for (j, &reach) in alive.iter().enumerate() {
+                next[j] += reach * qu;
+                next[j + 1] += reach * qd;
+            }
+            alive = next;
+        }
+        expected_time += (1.0 - probability) * self.option.t;
+        (price, probability, expected_time)
+    }
+
+    /// Extracts the early exercise boundary from the binomial tree.
+    ///
+    /// The tree parameters and stock price tree must already be set up. At each time step
+    /// with an exercise region, the critical stock price is the node of the region closest
+    /// to the continuation region: the lowest exercised node for calls and the highest for
+    /// puts.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(t, s_star)` pairs, with `t` the time of the step (in years) and
+    /// `s_star` the critical stock price. Steps where exercise is never optimal are omitted.
+    pub fn exercise_boundary(&self) -> Vec<(f64, f64)> {
+        let (_, region) = self.exercise_region();
+        region
+            .iter()
+            .enumerate()
+            .filter_map(|(i, exercised)| {
+                let mut prices = exercised
+                    .iter()
+                    .zip(&self.option.sts[i])
+                    .filter(|(&exercise, _)| exercise)
+                    .map(|(_, &s)| s);
+                // The stock prices of a step are in decreasing order.
+                let s_star = match self.option.option_type {
+                    OptionType::Call => prices.next_back(),
+                    OptionType::Put => prices.next(),
+                };
+                s_star.map(|s_star| (self.option.step_time(i), s_star))
+            })
+            .collect()
+    }
+
+    /// Prices the option together with its European twin in a single backward traversal.
+    ///
+    /// The tree parameters and stock price tree must already be set up. Barriers and
+    /// truncation are ignored. Both options share the terminal payoffs and the discounted
+    /// expectations at each node; only the option itself may be exercised early.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the price of the option and the price of the European option
+    /// with the same terms.
+    pub fn price_with_european(&self) -> (f64, f64) {
+        let schedule = self.exercise_schedule();
+        let mut payoffs = self.init_payoffs_tree(Vec::new());
+        let mut european = payoffs.clone();
+        for i in (0..self.option.n).rev() {
+            self.step_back(&mut payoffs, i);
+            self.step_back(&mut european, i);
+            if schedule[i] {
+                self.check_early_exercise(&mut payoffs, i);
+            }
+        }
+        (payoffs[0], european[0])
+    }
+
+    /// Calculates the value of exercising the option immediately at a given stock price.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The stock price at the node.
+    ///
+    /// # Returns
+    ///
+    /// The intrinsic value of the option at the given stock price.
+    fn exercise_value(&self, s: f64) -> f64 {
+        self.option.payoff.value(s, self.option.k, self.option.option_type)
+    }
+
+    /// Prices the option at several strikes by one backward induction over a shared tree.
+    ///
+    /// The stock price tree does not depend on the strike, so it is built once by the
+    /// caller and the values of all strikes are inducted together, stored node by node
+    /// with the strikes of each node contiguous so that the inner loops run across
+    /// strikes. Each strike's price is identical to that of a separate traversal.
+    /// Barriers and truncation are not applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `strikes` - The strike prices.
+    ///
+    /// # Returns
+    ///
+    /// The price of the option at each strike, in order.
+    pub fn price_strikes(&self, strikes: &[f64]) -> Vec<f64> {
+        let (n, m) = (self.option.n, strikes.len());
+        let (payoff, option_type) = (&self.option.payoff, self.option.option_type);
+        let half_width = (self.u / self.d).ln() / 2.0;
+        let terminal = &self.option.sts[n];
+        let root_width = terminal.len() - n;
+        let mut values = terminal
+            .iter()
+            .flat_map(|&s| strikes.iter().map(move |&k| payoff.smoothed_value(s, k, option_type, half_width)))
+            .collect::<Vec<_>>();
+
+        let schedule = self.exercise_schedule();
+        for i in (0..n).rev() {
+            let (qu, qd, df) = self.step_parameters(i);
+            let nodes = root_width + i;
+            // The value of a strike at the node below is `m` places later and is only
+            // overwritten after it has been read.
+            for index in 0..nodes * m {
+                values[index] = (values[index] * qu + values[index + m] * qd) * df;
+            }
+            values.truncate(nodes * m);
+            if schedule[i] {
+                for (row, &s) in values.chunks_exact_mut(m).zip(&self.option.sts[i]) {
+                    for (value, &k) in row.iter_mut().zip(strikes) {
+                        *value = value.max(payoff.value(s, k, option_type));
+                    }
+                }
+            }
+        }
+        values.truncate(m);
+        values
+    }
+
+    /// Checks whether the option is traversed in single precision, which requires a vanilla
+    /// payoff without barriers, truncation, or discrete dividends.
+    fn in_single_precision(&self) -> bool {
+        self.precision == Precision::Single
+            && matches!(self.option.payoff, PayoffKind::Vanilla)
+            && self.option.barrier.is_none()
+            && self.option.double_barrier.is_none()
+            && self.option.dividends.is_none()
+            && self.truncation.is_none()
+    }
+
+    /// Traverses the tree backward with the node values and stock prices held in the
+    /// floating-point type `F`.
+    ///
+    /// Only the terminal level of the stock price tree is read. The stock prices of each
+    /// earlier level are the first nodes of the terminal level divided by a power of the up
+    /// factor, which is calculated in `f64` so that the prices do not drift over the tree.
+    ///
+    /// Rounding the probabilities and discount factor to `F` biases every step by the
+    /// same relative amount, which compounds over the tree. The compounded bias is
+    /// tracked in `f64`, applied to the exercise values, and divided out of the result.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing the option prices at the root nodes of the binomial tree.
+    fn traverse_in<F: Real>(&mut self) -> Vec<f64> {
+        self.truncation_error = 0.0;
+        let n = self.option.n;
+        let k = F::from_f64(self.option.k);
+        let zero = F::from_f64(0.0);
+        let sign = F::from_f64(self.option.option_type.sign());
+        let exercise = |s: F| sign * (s - k);
+        let terminal = self.option.sts[n].iter().map(|&s| F::from_f64(s)).collect::<Vec<_>>();
+        let mut values = vec![zero; terminal.len()];
+        max_in_lanes(&mut values, &terminal, |s| exercise(s).max(zero));
+
+        let schedule = self.exercise_schedule();
+        let rounded = |x: f64| F::from_f64(x).to_f64();
+        let mut bias = 1.0;
+        let mut level_factor = 1.0;
+        for i in (0..n).rev() {
+            self.step_back(&mut values, i);
+            let (qu, qd, df) = self.step_parameters(i);
+            bias *= (rounded(qu) + rounded(qd)) * rounded(df) / ((qu + qd) * df);
+            level_factor /= self.u;
+            if schedule[i] {
+                let (factor, scale) = (F::from_f64(level_factor), F::from_f64(bias));
+                let prices = &terminal[..values.len()];
+                max_in_lanes(&mut values, prices, |s| (exercise(s * factor) * scale).max(zero));
+            }
+        }
+        values.into_iter().map(|value| value.to_f64() / bias).collect()
+    }
+
+    /// Begins the traversal of the binomial tree to calculate the option price.
+    ///
+    /// This method initializes the payoffs at the terminal nodes and then traverses
+    /// the tree backward to calculate the option price at each node.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing the option prices at each node of the binomial tree.
+    pub fn begin_tree_traversal(&mut self) -> Vec<f64> {
+        if self.in_single_precision() {
+            return self.traverse_in::<f32>();
+        }
+        if let Some(epsilon) = self.truncation.filter(|_| self.truncates()) {
+            let (values, error_bound) = self.traverse_truncated_tree(epsilon);
+            self.truncation_error = error_bound;
+            return values;
+        }
+        let storage = std::mem::take(&mut self.values);
+        let payoffs = self.init_payoffs_tree(storage);
+        self.traverse_tree(payoffs)
+    }
+
+    /// Calculates the price of the option using the binomial tree model.
+    ///
+    /// This method sets up the parameters, initializes the stock price tree,
+    /// and traverses the tree to calculate the option price, all on a scratch tree, so
+    /// that the tree can be shared between threads.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price(&self) -> f64 {
+        self.price_option(&self.option, &mut Workspace::default())
+    }
+
+    /// Calculates the price of another option on a tree with the moves and settings of
+    /// this one, as `price` does for its own option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to price.
+    /// * `workspace` - The buffers of the traversal, reused from earlier valuations.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price_option(&self, option: &StockOption, workspace: &mut Workspace) -> f64 {
+        let mut tree = self.scratch(option, workspace);
+        let price = tree.lattice_price();
+        tree.release(workspace);
+        price
+    }
+
+    /// Calculates the price of the option on this tree, leaving its parameters and
+    /// buffers set up.
+    fn lattice_price(&mut self) -> f64 {
+        self.setup_parameters();
+        self.init_pricing_tree();
+        let payoffs = self.begin_tree_traversal();
+        let price = payoffs[0];
+        self.values = payoffs;
+        price
+    }
+}
+
+/// Raises each value to at least the exercise value at its stock price, in blocks of
+/// `LANES` nodes.
+///
+/// # Arguments
+///
+/// * `values` - The continuation values, updated in place.
+/// * `prices` - The stock price at each node, of the same length as `values`.
+/// * `exercise` - Calculates the exercise value at a stock price.
+fn max_in_lanes<F: Real, E: Fn(F) -> F>(values: &mut [F], prices: &[F], exercise: E) {
+    let mut value_blocks = values.chunks_exact_mut(LANES);
+    let mut price_blocks = prices.chunks_exact(LANES);
+    for (values, prices) in (&mut value_blocks).zip(&mut price_blocks) {
+        for lane in 0..LANES {
+            values[lane] = values[lane].max(exercise(prices[lane]));
+        }
+    }
+    for (value, &s) in value_blocks.into_remainder().iter_mut().zip(price_blocks.remainder()) {
+        *value = value.max(exercise(s));
+    }
+}
@@ -0,0 +1,700 @@
+// black_scholes_option.rs
+
+use crate::barrier::{Barrier, BarrierDirection, BarrierKind, DoubleBarrier, RebateTiming};
+use crate::greeks::{CrossGreeks, ExtendedGreeks};
+use crate::numerics::{norm_cdf, norm_pdf, simpson};
+use crate::option_type::OptionType;
+use crate::payoff::PayoffKind;
+use crate::stock_option::StockOption;
+
+/// The number of standard deviations on either side of the mean over which custom
+/// payoffs are integrated.
+const CUSTOM_PAYOFF_RANGE: f64 = 10.0;
+
+/// The number of Simpson intervals used to integrate custom payoffs.
+const CUSTOM_PAYOFF_INTERVALS: usize = 20_000;
+
+/// Represents the analytic Black-Scholes-Merton pricing model for European options.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlackScholesOption {
+    /// The underlying stock option.
+    pub option: StockOption,
+}
+
+impl BlackScholesOption {
+    /// Creates a new `BlackScholesOption` instance with the given stock option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying stock option.
+    ///
+    /// # Returns
+    ///
+    /// A new `BlackScholesOption` instance.
+    pub fn new(option: StockOption) -> Self {
+        BlackScholesOption { option }
+    }
+
+    /// Calculates the `d1` term of the Black-Scholes formula.
+    pub fn d1(&self) -> f64 {
+        let option = &self.option;
+        ((option.adjusted_spot() / option.k).ln() + (option.cost_of_carry() + option.sigma.powi(2) / 2.0) * option.t)
+            / (option.sigma * option.t.sqrt())
+    }
+
+    /// Calculates the `d2` term of the Black-Scholes formula.
+    pub fn d2(&self) -> f64 {
+        self.d1() - self.option.sigma * self.option.t.sqrt()
+    }
+
+    /// Calculates the price of the option.
+    ///
+    /// Options with a single barrier are priced with the Reiner-Rubinstein formulas and
+    /// options with a double barrier with the Ikeda-Kunitomo series; all other options
+    /// are priced with the Black-Scholes-Merton formula.
+    ///
+    /// # Returns
+    ///
+    /// The analytic price of the option.
+    pub fn price(&self) -> f64 {
+        if let Some(barrier) = &self.option.barrier {
+            return self.barrier_price(barrier);
+        }
+        match &self.option.double_barrier {
+            Some(barrier) => self.double_barrier_price(barrier),
+            None => self.vanilla_price(),
+        }
+    }
+
+    /// Calculates the Black-Scholes-Merton price of the option without barriers.
+    ///
+    /// Digital options are priced from the exercise probabilities: a cash-or-nothing
+    /// option is worth `C e^{-rT} N(±d2)` and an asset-or-nothing option `S e^{-qT} N(±d1)`.
+    /// Gap options combine the two with the payout strike in place of `C`, and power
+    /// options use the Black formula on the lognormal variable `S^p`. Custom payoffs are
+    /// integrated numerically against the lognormal distribution of `S_T`.
+    ///
+    /// # Returns
+    ///
+    /// The price of the option, ignoring any barrier.
+    pub fn vanilla_price(&self) -> f64 {
+        let option = &self.option;
+        let discounted_spot = option.adjusted_spot() * ((option.cost_of_carry() - option.r) * option.t).exp();
+        let discounted_strike = option.k * (-option.r * option.t).exp();
+        let phi = option.option_type.sign();
+        match option.payoff {
+            PayoffKind::Vanilla if option.option_type == OptionType::Call => {
+                discounted_spot * norm_cdf(self.d1()) - discounted_strike * norm_cdf(self.d2())
+            }
+            PayoffKind::Vanilla => {
+                discounted_strike * norm_cdf(-self.d2()) - discounted_spot * norm_cdf(-self.d1())
+            }
+            PayoffKind::CashOrNothing { cash } => {
+                cash * (-option.r * option.t).exp() * norm_cdf(phi * self.d2())
+            }
+            PayoffKind::AssetOrNothing => discounted_spot * norm_cdf(phi * self.d1()),
+            PayoffKind::Gap { payout_strike } => {
+                let discounted_payout = payout_strike * (-option.r * option.t).exp();
+                phi * (discounted_spot * norm_cdf(phi * self.d1())
+                    - discounted_payout * norm_cdf(phi * self.d2()))
+            }
+            PayoffKind::Power { exponent } => {
+                // S^p is lognormal with volatility |p|σ and the forward below.
+                let variance = (exponent * option.sigma).powi(2) * option.t;
+                let forward = option.adjusted_spot().powf(exponent)
+                    * ((exponent * option.cost_of_carry()
+                        + exponent * (exponent - 1.0) * option.sigma.powi(2) / 2.0)
+                        * option.t)
+                        .exp();
+                let d1 = ((forward / option.k).ln() + variance / 2.0) / variance.sqrt();
+                let d2 = d1 - variance.sqrt();
+                phi * (-option.r * option.t).exp()
+                    * (forward * norm_cdf(phi * d1) - option.k * norm_cdf(phi * d2))
+            }
+            PayoffKind::Custom(ref payoff) => {
+                // Integrate the payoff against the lognormal terminal distribution.
+                let drift = (option.cost_of_carry() - option.sigma.powi(2) / 2.0) * option.t;
+                let sigma_sqrt_t = option.sigma * option.t.sqrt();
+                let expectation = simpson(
+                    |z| {
+                        let s = option.adjusted_spot() * (drift + sigma_sqrt_t * z).exp();
+                        payoff.value(s, option.k, option.option_type) * norm_pdf(z)
+                    },
+                    -CUSTOM_PAYOFF_RANGE,
+                    CUSTOM_PAYOFF_RANGE,
+                    CUSTOM_PAYOFF_INTERVALS,
+                );
+                (-option.r * option.t).exp() * expectation
+            }
+        }
+    }
+
+    /// Calculates the analytic second-order cross Greeks of a vanilla option.
+    ///
+    /// With `b` the cost of carry and `n` the standard normal density, vanna is
+    /// `−e^{(b−r)T} n(d1) d2 / σ`, volga is `vega · d1 d2 / σ`, and charm is
+    /// `−e^{(b−r)T} [n(d1) (b / (σ√T) − d2 / (2T)) ± (b − r) N(±d1)]` for calls (+) and puts (−),
+    /// as in Haug's "Complete Guide to Option Pricing Formulas", evaluated at the adjusted
+    /// spot as by `vanilla_price`. The payoff and any barriers of the option are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The cross Greeks of the option.
+    pub fn cross_greeks(&self) -> CrossGreeks {
+        let option = &self.option;
+        let (d1, d2) = (self.d1(), self.d2());
+        let (r, t, sigma) = (option.r, option.t, option.sigma);
+        let b = option.cost_of_carry();
+        let carry = ((b - r) * t).exp();
+        let phi = option.option_type.sign();
+        let vega = option.adjusted_spot() * carry * norm_pdf(d1) * t.sqrt();
+
+        CrossGreeks {
+            vanna: -carry * norm_pdf(d1) * d2 / sigma,
+            volga: vega * d1 * d2 / sigma,
+            charm: -carry
+                * (norm_pdf(d1) * (b / (sigma * t.sqrt()) - d2 / (2.0 * t))
+                    + phi * (b - r) * norm_cdf(phi * d1)),
+        }
+    }
+
+    /// Calculates the analytic third-order spot Greeks and the dividend and strike
+    /// sensitivities of a vanilla option.
+    ///
+    /// With `Γ = e^{(b−r)T} n(d1) / (Sσ√T)` the gamma of the option, speed is
+    /// `−Γ / S (1 + d1 / (σ√T))`, zomma is `Γ (d1 d2 − 1) / σ`, and color is
+    /// `Γ [r − b + b d1 / (σ√T) + (1 − d1 d2) / (2T)]`. Psi is `∓T S e^{(b−r)T} N(±d1)`,
+    /// dual delta is `∓e^{-rT} N(±d2)`, and dual gamma is `e^{-rT} n(d2) / (Kσ√T)`, with the
+    /// upper signs for calls, and `S` is the adjusted spot as in `vanilla_price`. Options on
+    /// futures have no dividend sensitivity. The payoff and any barriers of the option are
+    /// ignored.
+    ///
+    /// # Returns
+    ///
+    /// The extended Greeks of the option.
+    pub fn extended_greeks(&self) -> ExtendedGreeks {
+        let option = &self.option;
+        let (d1, d2) = (self.d1(), self.d2());
+        let (s, r, t, sigma) = (option.adjusted_spot(), option.r, option.t, option.sigma);
+        let b = option.cost_of_carry();
+        let sigma_sqrt_t = sigma * t.sqrt();
+        let carry = ((b - r) * t).exp();
+        let discount = (-r * t).exp();
+        let phi = option.option_type.sign();
+        let gamma = carry * norm_pdf(d1) / (s * sigma_sqrt_t);
+
+        ExtendedGreeks {
+            speed: -gamma / s * (1.0 + d1 / sigma_sqrt_t),
+            zomma: gamma * (d1 * d2 - 1.0) / sigma,
+            color: gamma * (r - b + b * d1 / sigma_sqrt_t + (1.0 - d1 * d2) / (2.0 * t)),
+            psi: if option.is_futures {
+                0.0
+            } else {
+                -phi * t * s * carry * norm_cdf(phi * d1)
+            },
+            dual_delta: -phi * discount * norm_cdf(phi * d2),
+            dual_gamma: discount * norm_pdf(d2) / (option.k * sigma_sqrt_t),
+        }
+    }
+
+    /// Calculates the Reiner-Rubinstein price of a single-barrier option.
+    ///
+    /// The price is assembled from the six building blocks `A` to `F` of Reiner and
+    /// Rubinstein (1991), following the presentation in Haug's "Complete Guide to Option
+    /// Pricing Formulas". A knock-out rebate is paid when the barrier is hit or at
+    /// expiration, according to the rebate timing of the barrier, and a knock-in rebate
+    /// is paid at expiration if the barrier was never hit.
+    ///
+    /// Discretely monitored barriers are priced by applying the formulas to the
+    /// Broadie-Glasserman-Kou continuity-corrected barrier. Discrete dividends are not
+    /// supported, since the barrier is monitored on the stock price itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `barrier` - The barrier attached to the option.
+    ///
+    /// # Returns
+    ///
+    /// The analytic price of the barrier option.
+    pub fn barrier_price(&self, barrier: &Barrier) -> f64 {
+        let option = &self.option;
+        let barrier = barrier.continuity_corrected(option.sigma);
+        let (s, x, h, rebate) = (option.s0, option.k, barrier.level, barrier.rebate);
+        let (r, t, sigma) = (option.r, option.t, option.sigma);
+
+        if barrier.is_breached(s) {
+            return match barrier.kind {
+                BarrierKind::KnockOut => rebate * self.one_touch(&barrier, barrier.rebate_timing),
+                BarrierKind::KnockIn => self.vanilla_price(),
+            };
+        }
+
+        let b = option.cost_of_carry();
+        let sigma_sqrt_t = sigma * t.sqrt();
+        let mu = (b - sigma.powi(2) / 2.0) / sigma.powi(2);
+        let phi = option.option_type.sign();
+        let eta = match barrier.direction {
+            BarrierDirection::Down => 1.0,
+            BarrierDirection::Up => -1.0,
+        };
+
+        let x1 = (s / x).ln() / sigma_sqrt_t + (1.0 + mu) * sigma_sqrt_t;
+        let x2 = (s / h).ln() / sigma_sqrt_t + (1.0 + mu) * sigma_sqrt_t;
+        let y1 = (h * h / (s * x)).ln() / sigma_sqrt_t + (1.0 + mu) * sigma_sqrt_t;
+        let y2 = (h / s).ln() / sigma_sqrt_t + (1.0 + mu) * sigma_sqrt_t;
+
+        let carry = ((b - r) * t).exp();
+        let discount = (-r * t).exp();
+        let hs = h / s;
+
+        let a = phi * s * carry * norm_cdf(phi * x1)
+            - phi * x * discount * norm_cdf(phi * x1 - phi * sigma_sqrt_t);
+        let bb = phi * s * carry * norm_cdf(phi * x2)
+            - phi * x * discount * norm_cdf(phi * x2 - phi * sigma_sqrt_t);
+        let c = phi * s * carry * hs.powf(2.0 * (mu + 1.0)) * norm_cdf(eta * y1)
+            - phi * x * discount * hs.powf(2.0 * mu) * norm_cdf(eta * y1 - eta * sigma_sqrt_t);
+        let d = phi * s * carry * hs.powf(2.0 * (mu + 1.0)) * norm_cdf(eta * y2)
+            - phi * x * discount * hs.powf(2.0 * mu) * norm_cdf(eta * y2 - eta * sigma_sqrt_t);
+        let e = rebate * self.no_touch(&barrier);
+        let f = rebate * self.one_touch(&barrier, barrier.rebate_timing);
+
+        let strike_above = x > h;
+        match (barrier.kind, barrier.direction, option.option_type, strike_above) {
+            (BarrierKind::KnockIn, BarrierDirection::Down, OptionType::Call, true) => c + e,
+            (BarrierKind::KnockIn, BarrierDirection::Down, OptionType::Call, false) => a - bb + d + e,
+            (BarrierKind::KnockIn, BarrierDirection::Up, OptionType::Call, true) => a + e,
+            (BarrierKind::KnockIn, BarrierDirection::Up, OptionType::Call, false) => bb - c + d + e,
+            (BarrierKind::KnockIn, BarrierDirection::Down, OptionType::Put, true) => bb - c + d + e,
+            (BarrierKind::KnockIn, BarrierDirection::Down, OptionType::Put, false) => a + e,
+            (BarrierKind::KnockIn, BarrierDirection::Up, OptionType::Put, true) => a - bb + d + e,
+            (BarrierKind::KnockIn, BarrierDirection::Up, OptionType::Put, false) => c + e,
+            (BarrierKind::KnockOut, BarrierDirection::Down, OptionType::Call, true) => a - c + f,
+            (BarrierKind::KnockOut, BarrierDirection::Down, OptionType::Call, false) => bb - d + f,
+            (BarrierKind::KnockOut, BarrierDirection::Up, OptionType::Call, true) => f,
+            (BarrierKind::KnockOut, BarrierDirection::Up, OptionType::Call, false) => a - bb + c - d + f,
+            (BarrierKind::KnockOut, BarrierDirection::Down, OptionType::Put, true) => a - bb + c - d + f,
+            (BarrierKind::KnockOut, BarrierDirection::Down, OptionType::Put, false) => f,
+            (BarrierKind::KnockOut, BarrierDirection::Up, OptionType::Put, true) => bb - d + f,
+            (BarrierKind::KnockOut, BarrierDirection::Up, OptionType::Put, false) => a - c + f,
+        }
+    }
+
+    /// Calculates the value of a one-touch binary paying one unit when a single barrier is hit.
+    ///
+    /// Paid at the hitting time, this is the Reiner-Rubinstein rebate term
+    /// `(H/S)^{μ+λ} N(ηz) + (H/S)^{μ−λ} N(ηz − 2ηλσ√T)`. Paid at expiration, it is the
+    /// discounted probability of hitting the barrier before expiration. Discretely
+    /// monitored barriers are continuity-corrected as in `barrier_price`.
+    ///
+    /// # Arguments
+    ///
+    /// * `barrier` - The barrier; its kind and rebate are ignored.
+    /// * `timing` - When the unit amount is paid.
+    ///
+    /// # Returns
+    ///
+    /// The present value of the one-touch payment.
+    pub fn one_touch(&self, barrier: &Barrier, timing: RebateTiming) -> f64 {
+        let option = &self.option;
+        let barrier = barrier.continuity_corrected(option.sigma);
+        let (r, t, sigma) = (option.r, option.t, option.sigma);
+        if timing == RebateTiming::AtExpiry {
+            return (-r * t).exp() - self.no_touch(&barrier);
+        }
+        if barrier.is_breached(option.s0) {
+            return 1.0;
+        }
+
+        let sigma_sqrt_t = sigma * t.sqrt();
+        let mu = (option.cost_of_carry() - sigma.powi(2) / 2.0) / sigma.powi(2);
+        let lambda = (mu.powi(2) + 2.0 * r / sigma.powi(2)).sqrt();
+        let eta = match barrier.direction {
+            BarrierDirection::Down => 1.0,
+            BarrierDirection::Up => -1.0,
+        };
+        let hs = barrier.level / option.s0;
+        let z = hs.ln() / sigma_sqrt_t + lambda * sigma_sqrt_t;
+        hs.powf(mu + lambda) * norm_cdf(eta * z)
+            + hs.powf(mu - lambda) * norm_cdf(eta * z - 2.0 * eta * lambda * sigma_sqrt_t)
+    }
+
+    /// Calculates the value of a no-touch binary paying one unit at expiration if a single
+    /// barrier is never hit.
+    ///
+    /// This is the Reiner-Rubinstein knock-in rebate term: the discounted probability that
+    /// the stock price stays on the near side of the barrier until expiration.
+    ///
+    /// # Arguments
+    ///
+    /// * `barrier` - The barrier; its kind and rebate are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The present value of the no-touch payment.
+    pub fn no_touch(&self, barrier: &Barrier) -> f64 {
+        let option = &self.option;
+        let barrier = barrier.continuity_corrected(option.sigma);
+        if barrier.is_breached(option.s0) {
+            return 0.0;
+        }
+
+        let (s, h, r, t, sigma) = (option.s0, barrier.level, option.r, option.t, option.sigma);
+        let sigma_sqrt_t = sigma * t.sqrt();
+        let mu = (option.cost_of_carry() - sigma.powi(2) / 2.0) / sigma.powi(2);
+        let eta = match barrier.direction {
+            BarrierDirection::Down => 1.0,
+            BarrierDirection::Up => -1.0,
+        };
+        let x2 = (s / h).ln() / sigma_sqrt_t + (1.0 + mu) * sigma_sqrt_t;
+        let y2 = (h / s).ln() / sigma_sqrt_t + (1.0 + mu) * sigma_sqrt_t;
+        (-r * t).exp()
+            * (norm_cdf(eta * x2 - eta * sigma_sqrt_t)
+                - (h / s).powf(2.0 * mu) * norm_cdf(eta * y2 - eta * sigma_sqrt_t))
+    }
+
+    /// Calculates the price of a continuously monitored double-barrier option.
+    ///
+    /// The knock-out value without rebate is the Ikeda-Kunitomo (1992) series for flat
+    /// barriers, and knock-in options follow from in-out parity with the vanilla option.
+    /// Rebates are valued from the double-no-touch probability of Hui (1996): a rebate
+    /// paid at expiration is worth `R (e^{-rT} - DNT(T))`, while a rebate paid at the
+    /// hitting time is obtained by integrating the discounted hitting-time distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `barrier` - The double barrier attached to the option.
+    ///
+    /// # Returns
+    ///
+    /// The analytic price of the double-barrier option.
+    pub fn double_barrier_price(&self, barrier: &DoubleBarrier) -> f64 {
+        let option = &self.option;
+        let (s, x, l, u) = (option.s0, option.k, barrier.lower, barrier.upper);
+        let (r, t, sigma) = (option.r, option.t, option.sigma);
+        let b = option.cost_of_carry();
+
+        if barrier.is_breached(s) {
+            return match (barrier.kind, barrier.rebate_timing) {
+                (BarrierKind::KnockOut, RebateTiming::AtHit) => barrier.rebate,
+                (BarrierKind::KnockOut, RebateTiming::AtExpiry) => barrier.rebate * (-r * t).exp(),
+                (BarrierKind::KnockIn, _) => self.vanilla_price(),
+            };
+        }
+
+        let sigma_sqrt_t = sigma * t.sqrt();
+        let drift = (b + sigma.powi(2) / 2.0) * t;
+        let mu = 2.0 * b / sigma.powi(2) + 1.0;
+        let d = |numerator: f64| (numerator.ln() + drift) / sigma_sqrt_t;
+
+        // With flat barriers, both the call and the put use F = U and E = L.
+        let mut asset_sum = 0.0;
+        let mut strike_sum = 0.0;
+        for n in -10..=10 {
+            let un = u.powi(n);
+            let ln = l.powi(n);
+            let reflected = l.powi(n + 1) / (un * s);
+            let direct_weight = (un / ln).powf(mu);
+            let reflected_weight = reflected.powf(mu);
+            let direct_strike_weight = (un / ln).powf(mu - 2.0);
+            let reflected_strike_weight = reflected.powf(mu - 2.0);
+
+            let (d1, d2, d3, d4) = if option.option_type == OptionType::Call {
+                (
+                    d(s * un * un / (x * ln * ln)),
+                    d(s * un * un / (u * ln * ln)),
+                    d(l.powi(2 * n + 2) / (x * s * un * un)),
+                    d(l.powi(2 * n + 2) / (u * s * un * un)),
+                )
+            } else {
+                (
+                    d(s * un * un / (l * ln * ln)),
+                    d(s * un * un / (x * ln * ln)),
+                    d(l.powi(2 * n + 2) / (l * s * un * un)),
+                    d(l.powi(2 * n + 2) / (x * s * un * un)),
+                )
+            };
+
+            asset_sum += direct_weight * (norm_cdf(d1) - norm_cdf(d2))
+                - reflected_weight * (norm_cdf(d3) - norm_cdf(d4));
+            strike_sum += direct_strike_weight
+                * (norm_cdf(d1 - sigma_sqrt_t) - norm_cdf(d2 - sigma_sqrt_t))
+                - reflected_strike_weight
+                    * (norm_cdf(d3 - sigma_sqrt_t) - norm_cdf(d4 - sigma_sqrt_t));
+        }
+
+        let asset_term = s * ((b - r) * t).exp() * asset_sum;
+        let strike_term = x * (-r * t).exp() * strike_sum;
+        let knock_out = if option.option_type == OptionType::Call {
+            asset_term - strike_term
+        } else {
+            strike_term - asset_term
+        };
+
+        match barrier.kind {
+            BarrierKind::KnockIn => {
+                self.vanilla_price() - knock_out + barrier.rebate * self.double_no_touch(t)
+            }
+            BarrierKind::KnockOut => {
+                let rebate_value = match barrier.rebate_timing {
+                    RebateTiming::AtExpiry => (-r * t).exp() - self.double_no_touch(t),
+                    RebateTiming::AtHit => {
+                        // E[e^{-rτ}; τ ≤ T] = e^{-rT} P(τ ≤ T) + r ∫ e^{-rs} P(τ ≤ s) ds.
+                        let hit_probability = |time: f64| 1.0 - (r * time).exp() * self.double_no_touch(time);
+                        (-r * t).exp() * hit_probability(t)
+                            + r * simpson(|time| (-r * time).exp() * hit_probability(time), 0.0, t, 200)
+                    }
+                };
+                knock_out + barrier.rebate * rebate_value
+            }
+        }
+    }
+
+    /// Calculates the value of a double-no-touch binary paying one unit at time `time`.
+    ///
+    /// This is Hui's (1996) Fourier-sine series for the discounted probability that the
+    /// stock price stays strictly between the two barriers of the option until `time`.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The time (in years) at which the unit amount is paid.
+    ///
+    /// # Returns
+    ///
+    /// The present value of the double-no-touch payment.
+    pub fn double_no_touch(&self, time: f64) -> f64 {
+        let option = &self.option;
+        let barrier = match &option.double_barrier {
+            Some(barrier) => barrier,
+            None => return (-option.r * time).exp(),
+        };
+        if time <= 0.0 {
+            return if barrier.is_breached(option.s0) { 0.0 } else { 1.0 };
+        }
+
+        let (s, l, u) = (option.s0, barrier.lower, barrier.upper);
+        let sigma2 = option.sigma.powi(2);
+        let b = option.cost_of_carry();
+        let z = (u / l).ln();
+        let alpha = -0.5 * (2.0 * b / sigma2 - 1.0);
+        let beta = -0.25 * (2.0 * b / sigma2 - 1.0).powi(2) - 2.0 * option.r / sigma2;
+
+        let mut value = 0.0;
+        for i in 1..=500 {
+            let frequency = i as f64 * std::f64::consts::PI / z;
+            let decay = (-0.5 * (frequency.powi(2) - beta) * sigma2 * time).exp();
+            if decay < 1e-18 {
+                break;
+            }
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            value += 2.0 * std::f64::consts::PI * i as f64 / z.powi(2)
+                * ((s / l).powf(alpha) - sign * (s / u).powf(alpha))
+                / (alpha.powi(2) + frequency.powi(2))
+                * (frequency * (s / l).ln()).sin()
+                * decay;
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dividend_schedule::{Dividend, DividendKind, DividendSchedule};
+    use crate::option_type::ExerciseStyle;
+    use crate::pricing_engine::PricingEngine;
+    use crate::pricing_error::PricingError;
+
+    #[test]
+    fn borrow_prices_like_an_equal_dividend_yield() {
+        let price = |option: StockOption| BlackScholesOption::new(option).price();
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 1, 0.03, 0.2, option_type, ExerciseStyle::European).unwrap();
+            assert!((price(option.clone()) - price(option.with_yield_as_borrow())).abs() < 1e-12);
+        }
+    }
+
+    fn european(s0: f64, k: f64, r: f64, t: f64, div: f64, sigma: f64, option_type: OptionType) -> BlackScholesOption {
+        BlackScholesOption::new(StockOption::new(s0, k, r, t, 1, div, sigma, option_type, ExerciseStyle::European).unwrap())
+    }
+
+    #[test]
+    fn matches_haug_reference_prices() {
+        // Haug, "The Complete Guide to Option Pricing Formulas", sections 1.1.1 and 1.1.6.
+        let cases = [
+            (european(60.0, 65.0, 0.08, 0.25, 0.0, 0.3, OptionType::Call), 2.1334),
+            (european(100.0, 95.0, 0.1, 0.5, 0.05, 0.2, OptionType::Put), 2.4648),
+            (european(75.0, 70.0, 0.1, 0.5, 0.05, 0.35, OptionType::Put), 4.0870),
+        ];
+        for (option, expected) in cases {
+            assert!((option.price() - expected).abs() < 5e-5, "{} vs {expected}", option.price());
+        }
+    }
+
+    #[test]
+    fn satisfies_put_call_parity() {
+        for k in [50.0, 90.0, 100.0, 110.0, 200.0] {
+            for t in [0.01, 0.5, 5.0] {
+                let call = european(100.0, k, 0.05, t, 0.02, 0.25, OptionType::Call).price();
+                let put = european(100.0, k, 0.05, t, 0.02, 0.25, OptionType::Put).price();
+                let forward = 100.0 * (-0.02 * t).exp() - k * (-0.05 * t).exp();
+                assert!((call - put - forward).abs() < 1e-10, "{k} {t}: {call} - {put} vs {forward}");
+            }
+        }
+    }
+
+    #[test]
+    fn tends_to_its_limits() {
+        // Without volatility the option is worth its discounted intrinsic value on the forward.
+        for k in [90.0, 110.0] {
+            let forward = 100.0 * (-0.02_f64).exp() - k * (-0.05_f64).exp();
+            let call = european(100.0, k, 0.05, 1.0, 0.02, 1e-8, OptionType::Call).price();
+            let put = european(100.0, k, 0.05, 1.0, 0.02, 1e-8, OptionType::Put).price();
+            assert!((call - forward.max(0.0)).abs() < 1e-10, "{call}");
+            assert!((put - (-forward).max(0.0)).abs() < 1e-10, "{put}");
+        }
+        // With unbounded volatility a call is worth the asset and a put the discounted strike.
+        let call = european(100.0, 100.0, 0.05, 1.0, 0.02, 50.0, OptionType::Call).price();
+        let put = european(100.0, 100.0, 0.05, 1.0, 0.02, 50.0, OptionType::Put).price();
+        assert!((call - 100.0 * (-0.02_f64).exp()).abs() < 1e-8, "{call}");
+        assert!((put - 100.0 * (-0.05_f64).exp()).abs() < 1e-8, "{put}");
+        // Far from the strike a call is worthless or a forward.
+        assert!(european(100.0, 1e4, 0.05, 1.0, 0.02, 0.2, OptionType::Call).price() < 1e-12);
+        let deep = european(100.0, 1.0, 0.05, 1.0, 0.02, 0.2, OptionType::Call).price();
+        assert!((deep - (100.0 * (-0.02_f64).exp() - (-0.05_f64).exp())).abs() < 1e-12, "{deep}");
+    }
+
+    #[test]
+    fn barriers_match_the_haug_table() {
+        // Haug, table 4-13: S = 100, T = 0.5, r = 0.08, b = 0.04, σ = 0.25, rebate 3.
+        use crate::barrier::{BarrierDirection::*, BarrierKind::*};
+        let cases = [
+            (Down, KnockOut, OptionType::Call, 95.0, [9.0246, 6.7924, 4.8759]),
+            (Down, KnockOut, OptionType::Call, 100.0, [3.0, 3.0, 3.0]),
+            (Up, KnockOut, OptionType::Call, 105.0, [2.6789, 2.3580, 2.3453]),
+            (Down, KnockIn, OptionType::Call, 95.0, [7.7627, 4.0109, 2.0576]),
+            (Down, KnockIn, OptionType::Call, 100.0, [13.8333, 7.8494, 3.9795]),
+            (Up, KnockIn, OptionType::Call, 105.0, [14.1112, 8.4482, 4.5910]),
+            (Down, KnockOut, OptionType::Put, 95.0, [2.2798, 2.2947, 2.6252]),
+            (Down, KnockOut, OptionType::Put, 100.0, [3.0, 3.0, 3.0]),
+            (Up, KnockOut, OptionType::Put, 105.0, [3.7760, 5.4932, 7.5187]),
+            (Down, KnockIn, OptionType::Put, 95.0, [2.9586, 6.5677, 11.9752]),
+            (Down, KnockIn, OptionType::Put, 100.0, [2.2845, 5.9085, 11.6465]),
+            (Up, KnockIn, OptionType::Put, 105.0, [1.4653, 3.3721, 7.0846]),
+        ];
+        for (direction, kind, option_type, level, expected) in cases {
+            for (k, expected) in [90.0, 100.0, 110.0].into_iter().zip(expected) {
+                let mut option = european(100.0, k, 0.08, 0.5, 0.04, 0.25, option_type);
+                option.option.barrier = Some(Barrier::new(level, direction, kind, 3.0));
+                let price = option.price();
+                assert!((price - expected).abs() < 5e-5, "{direction:?} {kind:?} {option_type:?} {level} {k}: {price} vs {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn knock_in_and_knock_out_sum_to_the_vanilla() {
+        use crate::barrier::{BarrierDirection::*, BarrierKind::*};
+        for (direction, level) in [(Down, 90.0), (Up, 115.0)] {
+            for option_type in [OptionType::Call, OptionType::Put] {
+                for k in [85.0, 100.0, 120.0] {
+                    let vanilla = european(100.0, k, 0.05, 1.0, 0.02, 0.3, option_type);
+                    let with = |kind| {
+                        let mut option = vanilla.clone();
+                        option.option.barrier = Some(Barrier::new(level, direction, kind, 0.0));
+                        option.price()
+                    };
+                    let sum = with(KnockIn) + with(KnockOut);
+                    assert!((sum - vanilla.price()).abs() < 1e-10, "{sum} vs {}", vanilla.price());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn higher_order_greeks_use_the_dividend_adjusted_spot() {
+        let mut paying = european(100.0, 100.0, 0.05, 1.0, 0.0, 0.25, OptionType::Put);
+        paying.option.dividends =
+            Some(DividendSchedule::new(vec![Dividend { time: 0.5, amount: 5.0, kind: DividendKind::Cash }]).unwrap());
+        let netted = european(paying.option.adjusted_spot(), 100.0, 0.05, 1.0, 0.0, 0.25, OptionType::Put);
+        let (cross, expected) = (paying.cross_greeks(), netted.cross_greeks());
+        for (value, expected) in [(cross.vanna, expected.vanna), (cross.volga, expected.volga), (cross.charm, expected.charm)] {
+            assert!((value - expected).abs() < 1e-12, "{value} vs {expected}");
+        }
+        let (extended, expected) = (paying.extended_greeks(), netted.extended_greeks());
+        for (value, expected) in [
+            (extended.speed, expected.speed),
+            (extended.zomma, expected.zomma),
+            (extended.color, expected.color),
+            (extended.psi, expected.psi),
+        ] {
+            assert!((value - expected).abs() < 1e-12, "{value} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn rejects_barriers_with_discrete_dividends() {
+        use crate::barrier::{BarrierDirection::Down, BarrierKind::KnockOut};
+        let mut option = european(100.0, 100.0, 0.05, 1.0, 0.0, 0.25, OptionType::Call);
+        option.option.dividends =
+            Some(DividendSchedule::new(vec![Dividend { time: 0.5, amount: 5.0, kind: DividendKind::Cash }]).unwrap());
+        assert!(option.try_price().is_ok());
+        option.option.barrier = Some(Barrier::new(90.0, Down, KnockOut, 0.0));
+        assert!(matches!(option.try_price(), Err(PricingError::Unsupported(_))));
+    }
+
+    #[test]
+    fn double_barriers_match_the_haug_table() {
+        // Haug, "The Complete Guide to Option Pricing Formulas", table 4-13 (flat barriers).
+        let cases = [
+            (50.0, 150.0, [4.3515, 6.1644, 7.0373]),
+            (60.0, 140.0, [4.3505, 5.8500, 5.7726]),
+            (70.0, 130.0, [4.3139, 4.8293, 3.7765]),
+            (80.0, 120.0, [3.7516, 2.6387, 1.4903]),
+            (90.0, 110.0, [1.2055, 0.3098, 0.0477]),
+        ];
+        for (lower, upper, expected) in cases {
+            for (sigma, expected) in [0.15, 0.25, 0.35].into_iter().zip(expected) {
+                let mut option = european(100.0, 100.0, 0.1, 0.25, 0.0, sigma, OptionType::Call);
+                option.option.double_barrier =
+                    Some(DoubleBarrier::new(lower, upper, BarrierKind::KnockOut, 0.0, RebateTiming::AtExpiry));
+                let price = option.price();
+                assert!((price - expected).abs() < 1e-4, "{lower} {upper} {sigma}: {price} vs {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn double_knock_in_and_knock_out_sum_to_the_vanilla() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let vanilla = european(100.0, 100.0, 0.05, 0.5, 0.02, 0.25, option_type);
+            let with = |kind| {
+                let mut option = vanilla.clone();
+                option.option.double_barrier = Some(DoubleBarrier::new(80.0, 125.0, kind, 0.0, RebateTiming::AtExpiry));
+                option.price()
+            };
+            let sum = with(BarrierKind::KnockIn) + with(BarrierKind::KnockOut);
+            assert!((sum - vanilla.price()).abs() < 1e-8, "{sum} vs {}", vanilla.price());
+        }
+    }
+
+    #[test]
+    fn double_no_touch_tends_to_its_limits() {
+        let mut option = european(100.0, 100.0, 0.05, 0.5, 0.0, 0.2, OptionType::Call);
+        option.option.double_barrier = Some(DoubleBarrier::new(1.0, 10_000.0, BarrierKind::KnockOut, 0.0, RebateTiming::AtExpiry));
+        assert!((option.double_no_touch(0.5) - (-0.05f64 * 0.5).exp()).abs() < 1e-8);
+        option.option.double_barrier = Some(DoubleBarrier::new(99.0, 101.0, BarrierKind::KnockOut, 0.0, RebateTiming::AtExpiry));
+        assert!(option.double_no_touch(0.5) < 1e-8);
+    }
+
+    #[test]
+    fn breached_double_barrier_pays_the_rebate_by_its_timing() {
+        let mut option = european(100.0, 100.0, 0.05, 0.5, 0.0, 0.2, OptionType::Call);
+        let with = |option: &mut BlackScholesOption, timing| {
+            option.option.double_barrier = Some(DoubleBarrier::new(100.0, 120.0, BarrierKind::KnockOut, 2.0, timing));
+            option.price()
+        };
+        assert_eq!(with(&mut option, RebateTiming::AtHit), 2.0);
+        assert!((with(&mut option, RebateTiming::AtExpiry) - 2.0 * (-0.05f64 * 0.5).exp()).abs() < 1e-12);
+    }
+}
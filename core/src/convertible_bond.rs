@@ -0,0 +1,227 @@
+// convertible_bond.rs
+
+use crate::binomial_tree_option::BinomialTreeOption;
+
+/// Represents a convertible bond on a Cox-Ross-Rubinstein lattice.
+///
+/// The holder may convert the bond into `conversion_ratio` shares at any time, the issuer
+/// may call it back at the prevailing call price, and the holder may put it back to the
+/// issuer on the put dates. Following Tsiveriotis and Fernandes (1998), the value at each
+/// node is split into a cash-only component, which is exposed to the issuer's credit and
+/// discounted at `r + credit_spread`, and an equity component, which is discounted at `r`.
+///
+/// At every node the holder's continuation value is first capped at the call price (if the
+/// bond is callable) and floored at the put price (if it is puttable). Any coupon paid at
+/// that step is then added, and the holder converts when the shares are worth more.
+/// Conversion moves the whole value into the equity component, while redemption, calls,
+/// puts, and coupons are cash.
+pub struct ConvertibleBond {
+    /// The underlying binomial tree; the strike of its option is unused.
+    pub tree: BinomialTreeOption,
+    /// The face value repaid at maturity.
+    pub face: f64,
+    /// The number of shares received on conversion.
+    pub conversion_ratio: f64,
+    /// The credit spread over the risk-free rate used to discount the cash component.
+    pub credit_spread: f64,
+    /// The coupons as `(time, amount)` pairs.
+    pub coupons: Vec<(f64, f64)>,
+    /// The call schedule as `(start time, call price)` pairs.
+    ///
+    /// From each start time the bond may be called at any step at that price, until the
+    /// next entry takes over.
+    pub call_schedule: Vec<(f64, f64)>,
+    /// The put schedule as `(time, put price)` pairs, each exercisable only on its date.
+    pub put_schedule: Vec<(f64, f64)>,
+}
+
+impl ConvertibleBond {
+    /// Creates a new `ConvertibleBond` without coupons, calls, or puts.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The binomial tree option representing the underlying stock and maturity.
+    /// * `face` - The face value repaid at maturity.
+    /// * `conversion_ratio` - The number of shares received on conversion.
+    /// * `credit_spread` - The credit spread over the risk-free rate.
+    ///
+    /// # Returns
+    ///
+    /// A new `ConvertibleBond` instance.
+    pub fn new(tree: BinomialTreeOption, face: f64, conversion_ratio: f64, credit_spread: f64) -> Self {
+        ConvertibleBond {
+            tree,
+            face,
+            conversion_ratio,
+            credit_spread,
+            coupons: Vec::new(),
+            call_schedule: Vec::new(),
+            put_schedule: Vec::new(),
+        }
+    }
+
+    /// Generates a regular coupon schedule that runs backward from maturity.
+    ///
+    /// # Arguments
+    ///
+    /// * `face` - The face value of the bond.
+    /// * `coupon_rate` - The annual coupon rate.
+    /// * `frequency` - The number of coupons per year; zero gives a zero-coupon bond.
+    /// * `t` - The maturity of the bond (in years).
+    ///
+    /// # Returns
+    ///
+    /// The coupons as `(time, amount)` pairs.
+    pub fn coupon_schedule(face: f64, coupon_rate: f64, frequency: usize, t: f64) -> Vec<(f64, f64)> {
+        if frequency == 0 || coupon_rate == 0.0 {
+            return Vec::new();
+        }
+        let period = 1.0 / frequency as f64;
+        let amount = face * coupon_rate * period;
+        (0..)
+            .map(|j| t - j as f64 * period)
+            .take_while(|&time| time > 1e-9)
+            .map(|time| (time, amount))
+            .collect()
+    }
+
+    /// Maps a time to the nearest step of the lattice.
+    fn step(&self, time: f64) -> usize {
+        (time / self.tree.option.dt()).round().clamp(0.0, self.tree.option.n as f64) as usize
+    }
+
+    /// Returns the call price in force at a given step, if the bond is callable there.
+    fn call_price(&self, step: usize) -> Option<f64> {
+        self.call_schedule
+            .iter()
+            .filter(|&&(start, _)| self.step(start) <= step)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|&(_, price)| price)
+    }
+
+    /// Applies the call, put, coupon, and conversion decisions at a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The stock price at the node.
+    /// * `total` - The holder's continuation value.
+    /// * `cash` - The cash component of the continuation value.
+    /// * `call_price` - The call price in force at the step, if any.
+    /// * `put_price` - The put price at the step, if any.
+    /// * `coupon` - The coupon paid at the step.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the total value and its cash component.
+    fn decide(
+        &self,
+        s: f64,
+        total: f64,
+        cash: f64,
+        call_price: Option<f64>,
+        put_price: Option<f64>,
+        coupon: f64,
+    ) -> (f64, f64) {
+        let (mut total, mut cash) = (total, cash);
+        if let Some(call) = call_price.filter(|&call| total > call) {
+            total = call;
+            cash = call;
+        }
+        if let Some(put) = put_price.filter(|&put| put > total) {
+            total = put;
+            cash = put;
+        }
+        total += coupon;
+        cash += coupon;
+
+        let conversion = self.conversion_ratio * s;
+        if conversion > total {
+            (conversion, 0.0)
+        } else {
+            (total, cash)
+        }
+    }
+
+    /// Calculates the price of the convertible bond.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the price of the bond and its cash-only (bond) component; the
+    /// remainder is the equity component.
+    pub fn price(&mut self) -> (f64, f64) {
+        self.tree.setup_crr_parameters();
+        self.tree.init_stock_price_tree();
+
+        let option = &self.tree.option;
+        let n = option.n;
+        let (qu, qd) = (self.tree.qu, self.tree.qd);
+        let equity_discount = option.df();
+        let cash_discount = (-(option.r + self.credit_spread) * option.dt()).exp();
+
+        let mut coupon_at = vec![0.0; n + 1];
+        for &(time, amount) in &self.coupons {
+            let step = self.step(time);
+            if step > 0 {
+                coupon_at[step] += amount;
+            }
+        }
+        let mut put_at = vec![None; n + 1];
+        for &(time, price) in &self.put_schedule {
+            put_at[self.step(time)] = Some(price);
+        }
+
+        let mut values = option.sts[n]
+            .iter()
+            .map(|&s| self.decide(s, self.face, self.face, None, None, coupon_at[n]))
+            .collect::<Vec<_>>();
+        for i in (0..n).rev() {
+            let call_price = self.call_price(i);
+            values = values
+                .windows(2)
+                .zip(&option.sts[i])
+                .map(|(w, &s)| {
+                    let ((total_up, cash_up), (total_down, cash_down)) = (w[0], w[1]);
+                    let cash = (cash_up * qu + cash_down * qd) * cash_discount;
+                    let equity = ((total_up - cash_up) * qu + (total_down - cash_down) * qd) * equity_discount;
+                    self.decide(s, equity + cash, cash, call_price, put_at[i], coupon_at[i])
+                })
+                .collect();
+        }
+        values[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::option_type::{ExerciseStyle, OptionType};
+    use crate::stock_option::StockOption;
+
+    fn bond(conversion_ratio: f64) -> ConvertibleBond {
+        let tree = BinomialTreeOption::new(
+            StockOption::new(50.0, 1.0, 0.05, 5.0, 200, 0.0, 0.3, OptionType::Call, ExerciseStyle::American).unwrap(),
+        );
+        let mut bond = ConvertibleBond::new(tree, 100.0, conversion_ratio, 0.02);
+        bond.coupons = ConvertibleBond::coupon_schedule(100.0, 0.04, 2, 5.0);
+        bond
+    }
+
+    #[test]
+    fn without_conversion_is_a_straight_bond() {
+        let (price, cash) = bond(0.0).price();
+        let discount = |time: f64| (-0.07 * time).exp();
+        let straight = 100.0 * discount(5.0) + (1..=10).map(|j| 2.0 * discount(j as f64 * 0.5)).sum::<f64>();
+        assert!((price - straight).abs() < 1e-9, "{price} vs {straight}");
+        assert!((cash - price).abs() < 1e-12);
+    }
+
+    #[test]
+    fn is_worth_at_least_its_conversion_and_straight_values() {
+        let straight = bond(0.0).price().0;
+        for conversion_ratio in [1.0, 2.0, 4.0] {
+            let (price, cash) = bond(conversion_ratio).price();
+            assert!(price >= straight && price >= conversion_ratio * 50.0, "{conversion_ratio}: {price}");
+            assert!((0.0..price).contains(&cash));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// corporate_action.rs
+
+/// Identifies a corporate action that changes the terms of listed options.
+#[derive(Clone, Copy)]
+pub enum CorporateAction {
+    /// A stock split with the number of new shares per old share, such as 2 for a
+    /// 2-for-1 split or 0.1 for a 1-for-10 reverse split.
+    Split(f64),
+    /// A special cash dividend per share, which listed options adjust for by reducing
+    /// the strike.
+    SpecialDividend(f64),
+}
+
+/// Represents the terms of an option contract that corporate actions adjust.
+#[derive(Clone, Copy)]
+pub struct ContractTerms {
+    /// The price of the underlying asset.
+    pub spot: f64,
+    /// The strike price of the option.
+    pub strike: f64,
+    /// The number of shares delivered per contract.
+    pub multiplier: f64,
+}
+
+impl CorporateAction {
+    /// Adjusts the terms of a contract for the action, so that its value is unchanged.
+    ///
+    /// A split divides the spot and strike by the split ratio and multiplies the
+    /// deliverable by it. A special dividend lowers the spot and strike by the dividend
+    /// and leaves the deliverable alone, so the intrinsic value of every option is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `terms` - The terms before the action.
+    ///
+    /// # Returns
+    ///
+    /// The terms after the action.
+    pub fn adjust(&self, terms: ContractTerms) -> ContractTerms {
+        match *self {
+            CorporateAction::Split(ratio) => ContractTerms {
+                spot: terms.spot / ratio,
+                strike: terms.strike / ratio,
+                multiplier: terms.multiplier * ratio,
+            },
+            CorporateAction::SpecialDividend(amount) => ContractTerms {
+                spot: terms.spot - amount,
+                strike: terms.strike - amount,
+                multiplier: terms.multiplier,
+            },
+        }
+    }
+
+    /// Calculates the factor that scales prices before the action onto the basis after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `cum_price` - The last price before the action takes effect.
+    ///
+    /// # Returns
+    ///
+    /// `1 / ratio` for a split, or `(S - D) / S` for a special dividend `D`.
+    pub fn price_factor(&self, cum_price: f64) -> f64 {
+        match *self {
+            CorporateAction::Split(ratio) => 1.0 / ratio,
+            CorporateAction::SpecialDividend(amount) => (cum_price - amount) / cum_price,
+        }
+    }
+}
+
+/// Back-adjusts a price series through corporate actions so that it has no jumps on the
+/// ex-dates.
+///
+/// Each price before an action is multiplied by the action's price factor, measured at
+/// the last price before the action, so that returns across the ex-date reflect only the
+/// market move. Prices on and after the last action are unchanged.
+///
+/// # Arguments
+///
+/// * `prices` - The unadjusted prices, oldest first.
+/// * `actions` - The actions with the index of the first price on their new basis.
+///
+/// # Returns
+///
+/// The adjusted prices.
+pub fn back_adjust(prices: &[f64], actions: &[(usize, CorporateAction)]) -> Vec<f64> {
+    let mut adjusted = prices.to_vec();
+    for &(index, action) in actions {
+        let factor = action.price_factor(prices[index - 1]);
+        for price in &mut adjusted[..index] {
+            *price *= factor;
+        }
+    }
+    adjusted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TERMS: ContractTerms = ContractTerms { spot: 100.0, strike: 90.0, multiplier: 100.0 };
+
+    #[test]
+    fn split_scales_the_strike_and_multiplier() {
+        let adjusted = CorporateAction::Split(2.0).adjust(TERMS);
+        assert_eq!((adjusted.spot, adjusted.strike, adjusted.multiplier), (50.0, 45.0, 200.0));
+        let value = |terms: ContractTerms| (terms.spot - terms.strike) * terms.multiplier;
+        assert_eq!(value(adjusted), value(TERMS));
+    }
+
+    #[test]
+    fn special_dividend_lowers_the_strike() {
+        let adjusted = CorporateAction::SpecialDividend(5.0).adjust(TERMS);
+        assert_eq!((adjusted.spot, adjusted.strike, adjusted.multiplier), (95.0, 85.0, 100.0));
+    }
+
+    #[test]
+    fn back_adjusts_prices_before_each_action() {
+        let prices = [100.0, 102.0, 51.0, 48.0, 46.0];
+        let actions = [(2, CorporateAction::Split(2.0)), (4, CorporateAction::SpecialDividend(2.0))];
+        let factor = 46.0 / 48.0;
+        let expected = [50.0 * factor, 51.0 * factor, 51.0 * factor, 48.0 * factor, 46.0];
+        for (price, expected) in back_adjust(&prices, &actions).iter().zip(expected) {
+            assert!((price - expected).abs() < 1e-12, "{price} vs {expected}");
+        }
+    }
+}
@@ -0,0 +1,217 @@
+// day_count.rs
+
+use crate::pricing_error::PricingError;
+use crate::trading_calendar::{TradingCalendar, TRADING_DAYS_PER_YEAR};
+
+/// Represents a calendar date in the proleptic Gregorian calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    /// The year.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u32,
+    /// The day of the month, from 1 to 31.
+    pub day: u32,
+}
+
+impl Date {
+    /// Creates a new `Date` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year.
+    /// * `month` - The month, from 1 to 12.
+    /// * `day` - The day of the month, from 1 to the length of the month.
+    ///
+    /// # Returns
+    ///
+    /// A new `Date` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the month is not from 1 to 12 or the day
+    /// does not exist in the month, such as 30 February.
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, PricingError> {
+        if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+            return Err(PricingError::InvalidInput(format!("Invalid date {year}-{month:02}-{day:02}.")));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// Calculates the number of days since 1 January 1970.
+    ///
+    /// This is the days-from-civil algorithm of Howard Hinnant, which counts in 400-year
+    /// eras starting on 1 March so that the leap day falls at the end of each year.
+    ///
+    /// # Returns
+    ///
+    /// The serial day number of the date, negative before 1970.
+    pub fn serial(&self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Creates the date with a given number of days since 1 January 1970, the inverse of
+    /// `serial`.
+    ///
+    /// # Arguments
+    ///
+    /// * `serial` - The serial day number.
+    ///
+    /// # Returns
+    ///
+    /// The `Date` with that serial day number.
+    pub fn from_serial(serial: i64) -> Self {
+        let days = serial + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date { year: year as i32, month: month as u32, day: day as u32 }
+    }
+
+    /// Calculates the date a number of days away.
+    ///
+    /// # Arguments
+    ///
+    /// * `days` - The number of days to add, negative to move backward.
+    ///
+    /// # Returns
+    ///
+    /// The shifted `Date`.
+    pub fn add_days(&self, days: i64) -> Self {
+        Date::from_serial(self.serial() + days)
+    }
+
+    /// Calculates the day of the week.
+    ///
+    /// # Returns
+    ///
+    /// The day of the week, from 0 for Monday to 6 for Sunday.
+    pub fn weekday(&self) -> u32 {
+        // 1 January 1970 was a Thursday.
+        (self.serial() + 3).rem_euclid(7) as u32
+    }
+}
+
+/// Returns the number of days in a month of the Gregorian calendar.
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Identifies the convention that turns a period between two dates into a year fraction.
+#[derive(Clone)]
+pub enum DayCount {
+    /// Actual days divided by 365.
+    Act365,
+    /// Actual days divided by 360.
+    Act360,
+    /// The 30/360 bond basis, which counts every month as 30 days.
+    Thirty360,
+    /// Business days of a trading calendar divided by 252.
+    Business252(TradingCalendar),
+}
+
+impl DayCount {
+    /// Calculates the year fraction between two dates.
+    ///
+    /// Under 30/360 the day of the start date is capped at 30, and the day of the end date
+    /// is capped at 30 when the start day was capped, as in the ISDA bond basis. Business
+    /// days are counted in `(start, end]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start date of the period.
+    /// * `end` - The end date of the period.
+    ///
+    /// # Returns
+    ///
+    /// The length of the period in years, negative if `end` is before `start`.
+    pub fn year_fraction(&self, start: Date, end: Date) -> f64 {
+        match self {
+            DayCount::Act365 => (end.serial() - start.serial()) as f64 / 365.0,
+            DayCount::Act360 => (end.serial() - start.serial()) as f64 / 360.0,
+            DayCount::Thirty360 => {
+                let start_day = start.day.min(30);
+                let end_day = if start_day == 30 { end.day.min(30) } else { end.day };
+                let days = 360 * (i64::from(end.year) - i64::from(start.year))
+                    + 30 * (i64::from(end.month) - i64::from(start.month))
+                    + (i64::from(end_day) - i64::from(start_day));
+                days as f64 / 360.0
+            }
+            DayCount::Business252(calendar) => calendar.business_days(start, end) as f64 / TRADING_DAYS_PER_YEAR,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn new_rejects_dates_that_do_not_exist() {
+        assert!(Date::new(2026, 2, 30).is_err());
+        assert!(Date::new(2023, 2, 29).is_err());
+        assert!(Date::new(1900, 2, 29).is_err());
+        assert!(Date::new(2026, 4, 31).is_err());
+        assert!(Date::new(2026, 13, 1).is_err());
+        assert!(Date::new(2026, 0, 1).is_err());
+        assert!(Date::new(2026, 1, 0).is_err());
+        assert!(Date::new(2024, 2, 29).is_ok());
+        assert!(Date::new(2000, 2, 29).is_ok());
+        assert!(Date::new(2026, 12, 31).is_ok());
+    }
+
+    #[test]
+    fn serial_numbers_round_trip() {
+        assert_eq!(date(1970, 1, 1).serial(), 0);
+        assert_eq!(date(2000, 3, 1).serial() - date(2000, 2, 28).serial(), 2);
+        for serial in [-800_000, -1, 0, 59, 11_016, 20_376, 3_000_000] {
+            assert_eq!(Date::from_serial(serial).serial(), serial);
+        }
+        assert_eq!(date(2024, 12, 31).add_days(1), date(2025, 1, 1));
+        // 15 October 2026 is a Thursday.
+        assert_eq!(date(2026, 10, 15).weekday(), 3);
+    }
+
+    #[test]
+    fn actual_day_counts_divide_the_days_between_the_dates() {
+        let (start, end) = (date(2026, 1, 15), date(2026, 7, 15));
+        assert_eq!(DayCount::Act360.year_fraction(start, end), 181.0 / 360.0);
+        assert_eq!(DayCount::Act365.year_fraction(start, end), 181.0 / 365.0);
+        assert_eq!(DayCount::Act360.year_fraction(end, start), -181.0 / 360.0);
+        assert_eq!(DayCount::Act365.year_fraction(date(2024, 1, 1), date(2025, 1, 1)), 366.0 / 365.0);
+    }
+
+    #[test]
+    fn thirty_360_caps_the_days_at_the_end_of_the_month() {
+        let days = |start, end| DayCount::Thirty360.year_fraction(start, end) * 360.0;
+        assert_eq!(days(date(2026, 1, 15), date(2026, 7, 15)), 180.0);
+        // A start on the 31st counts from the 30th, and then so does an end on the 31st.
+        assert_eq!(days(date(2026, 1, 31), date(2026, 3, 31)), 60.0);
+        assert_eq!(days(date(2026, 3, 30), date(2026, 5, 31)), 60.0);
+        // An end on the 31st is kept when the start is before the 30th.
+        assert_eq!(days(date(2026, 3, 15), date(2026, 5, 31)), 76.0);
+        // The end of February is not adjusted under the bond basis.
+        assert_eq!(days(date(2026, 2, 28), date(2026, 3, 31)), 33.0);
+        assert_eq!(days(date(2025, 12, 31), date(2026, 12, 31)), 360.0);
+    }
+}
@@ -0,0 +1,141 @@
+// dividend_schedule.rs
+
+use crate::pricing_error::PricingError;
+
+/// Identifies how a scheduled dividend is paid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DividendKind {
+    /// A fixed cash amount per share.
+    Cash,
+    /// A fraction of the stock price just before the ex-dividend date.
+    Proportional,
+}
+
+/// Represents a single dividend with a known ex-dividend date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dividend {
+    /// The time of the ex-dividend date (in years).
+    pub time: f64,
+    /// The cash amount, or the fraction of the stock price for a proportional dividend.
+    pub amount: f64,
+    /// Whether the dividend is a cash amount or a fraction of the stock price.
+    pub kind: DividendKind,
+}
+
+/// Represents a deterministic schedule of discrete dividends.
+///
+/// The stock price is modelled with the escrowed-dividend approach: the cash dividends
+/// paid up to expiration are set aside at the cost of carry, and the remainder `X`
+/// follows a geometric Brownian motion that drops by the proportional dividends on their
+/// ex-dates. At time `t` the stock price is
+/// `S_t = X_t Π_{tᵢ ≤ t} (1 − δᵢ) + Σ_{t < tᵢ ≤ T} Dᵢ e^{−b (tᵢ − t)}`, so the lattice, the
+/// finite-difference grid, and the Monte Carlo paths are all built on `X` and mapped to
+/// the stock price through `stock_price`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DividendSchedule {
+    /// The dividends, sorted by ex-dividend date.
+    pub dividends: Vec<Dividend>,
+}
+
+impl DividendSchedule {
+    /// Creates a new `DividendSchedule` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `dividends` - The dividends, in any order.
+    ///
+    /// # Returns
+    ///
+    /// A new `DividendSchedule` instance with the dividends sorted by ex-dividend date.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if a time or amount is negative or not finite,
+    /// or if a proportional dividend is not below 1.
+    pub fn new(mut dividends: Vec<Dividend>) -> Result<Self, PricingError> {
+        let invalid = |d: &Dividend| {
+            !(d.time >= 0.0 && d.amount >= 0.0 && d.time.is_finite() && d.amount.is_finite())
+                || (d.kind == DividendKind::Proportional && d.amount >= 1.0)
+        };
+        if dividends.iter().any(invalid) {
+            return Err(PricingError::InvalidInput(
+                "Invalid dividend. Times and amounts must be non-negative and proportional dividends below 1."
+                    .to_string(),
+            ));
+        }
+        dividends.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(DividendSchedule { dividends })
+    }
+
+    /// Calculates the present value at `from` of the cash dividends with ex-dates in `(from, to]`.
+    fn escrow(&self, carry: f64, from: f64, to: f64) -> f64 {
+        self.dividends
+            .iter()
+            .filter(|d| d.kind == DividendKind::Cash && d.time > from && d.time <= to)
+            .map(|d| d.amount * (-carry * (d.time - from)).exp())
+            .sum()
+    }
+
+    /// Calculates the factor by which the proportional dividends with ex-dates in `(0, t]`
+    /// reduce the stock price.
+    fn proportional_factor(&self, t: f64) -> f64 {
+        self.dividends
+            .iter()
+            .filter(|d| d.kind == DividendKind::Proportional && d.time > 0.0 && d.time <= t)
+            .map(|d| 1.0 - d.amount)
+            .product()
+    }
+
+    /// Calculates the initial value `X₀` of the stock price net of the escrowed cash dividends.
+    ///
+    /// # Arguments
+    ///
+    /// * `s0` - The initial stock price.
+    /// * `carry` - The cost of carry `b` at which the dividends are escrowed.
+    /// * `t` - The expiration of the option (in years); later dividends are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The initial stock price minus the present value of the cash dividends up to `t`.
+    pub fn net_spot(&self, s0: f64, carry: f64, t: f64) -> f64 {
+        s0 - self.escrow(carry, 0.0, t)
+    }
+
+    /// Calculates the forward price of the stock.
+    ///
+    /// This is the forward shared by every engine: the net spot grown at the cost of carry
+    /// and reduced by the proportional dividends, `X₀ e^{bT} Π (1 − δᵢ)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s0` - The initial stock price.
+    /// * `carry` - The cost of carry `b`, net of any continuous dividend yield.
+    /// * `t` - The delivery time of the forward (in years).
+    ///
+    /// # Returns
+    ///
+    /// The forward price of the stock for delivery at `t`.
+    pub fn forward(&self, s0: f64, carry: f64, t: f64) -> f64 {
+        self.net_spot(s0, carry, t) * (carry * t).exp() * self.proportional_factor(t)
+    }
+
+    /// Maps a value of the net process `X` to the stock price.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The value of the net process at `time`.
+    /// * `carry` - The cost of carry `b` at which the dividends are escrowed.
+    /// * `time` - The time of the value (in years).
+    /// * `t` - The expiration of the option (in years).
+    ///
+    /// # Returns
+    ///
+    /// The stock price after the proportional dividends up to `time`, plus the present
+    /// value of the cash dividends still to be paid before `t`.
+    pub fn stock_price(&self, x: f64, carry: f64, time: f64, t: f64) -> f64 {
+        x * self.proportional_factor(time) + self.escrow(carry, time, t)
+    }
+}
@@ -0,0 +1,234 @@
+// dual.rs
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Represents a truncated Taylor expansion for forward-mode automatic differentiation.
+///
+/// A `Dual` carries a value, its gradient with respect to `N` inputs, and its second
+/// derivative with respect to the first input. Arithmetic on duals applies the chain rule,
+/// so evaluating a pricer on duals yields its exact derivatives in the same pass, free of
+/// the truncation and round-off errors of bump-and-revalue.
+#[derive(Clone, Copy)]
+pub struct Dual<const N: usize> {
+    /// The value.
+    pub value: f64,
+    /// The first derivatives with respect to each input.
+    pub gradient: [f64; N],
+    /// The second derivative with respect to the first input.
+    pub curvature: f64,
+}
+
+impl<const N: usize> Dual<N> {
+    /// Creates a dual that does not depend on any input.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value.
+    pub fn constant(value: f64) -> Self {
+        Dual {
+            value,
+            gradient: [0.0; N],
+            curvature: 0.0,
+        }
+    }
+
+    /// Creates a dual for an input.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the input.
+    /// * `index` - The position of the input in the gradient.
+    pub fn variable(value: f64, index: usize) -> Self {
+        let mut gradient = [0.0; N];
+        gradient[index] = 1.0;
+        Dual {
+            value,
+            gradient,
+            curvature: 0.0,
+        }
+    }
+
+    /// Applies a function given its value and first two derivatives at `self.value`.
+    fn chain(self, value: f64, first: f64, second: f64) -> Self {
+        Dual {
+            value,
+            gradient: self.gradient.map(|g| first * g),
+            curvature: first * self.curvature + second * self.gradient[0].powi(2),
+        }
+    }
+
+    /// Calculates the exponential.
+    pub fn exp(self) -> Self {
+        let value = self.value.exp();
+        self.chain(value, value, value)
+    }
+
+    /// Calculates `exp(x) − 1`, accurately for `x` near zero.
+    pub fn exp_m1(self) -> Self {
+        let exp = self.value.exp();
+        self.chain(self.value.exp_m1(), exp, exp)
+    }
+
+    /// Calculates the natural logarithm.
+    pub fn ln(self) -> Self {
+        self.chain(self.value.ln(), 1.0 / self.value, -1.0 / self.value.powi(2))
+    }
+
+    /// Calculates the square root.
+    pub fn sqrt(self) -> Self {
+        let value = self.value.sqrt();
+        self.chain(value, 0.5 / value, -0.25 / (value * self.value))
+    }
+
+    /// Raises the dual to an integer power.
+    pub fn powi(self, n: i32) -> Self {
+        let x = self.value;
+        let n_f64 = f64::from(n);
+        self.chain(
+            x.powi(n),
+            n_f64 * x.powi(n - 1),
+            n_f64 * (n_f64 - 1.0) * x.powi(n - 2),
+        )
+    }
+
+    /// Calculates the reciprocal.
+    pub fn recip(self) -> Self {
+        let x = self.value;
+        self.chain(1.0 / x, -1.0 / x.powi(2), 2.0 / x.powi(3))
+    }
+
+    /// Returns the larger of two duals, with the derivatives of the branch taken.
+    pub fn max(self, other: Self) -> Self {
+        if other.value > self.value {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<const N: usize> Add for Dual<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut gradient = self.gradient;
+        for (g, o) in gradient.iter_mut().zip(other.gradient) {
+            *g += o;
+        }
+        Dual {
+            value: self.value + other.value,
+            gradient,
+            curvature: self.curvature + other.curvature,
+        }
+    }
+}
+
+impl<const N: usize> Neg for Dual<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Dual {
+            value: -self.value,
+            gradient: self.gradient.map(|g| -g),
+            curvature: -self.curvature,
+        }
+    }
+}
+
+impl<const N: usize> Sub for Dual<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const N: usize> Mul for Dual<N> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let mut gradient = self.gradient;
+        for (g, o) in gradient.iter_mut().zip(other.gradient) {
+            *g = *g * other.value + self.value * o;
+        }
+        Dual {
+            value: self.value * other.value,
+            gradient,
+            curvature: self.curvature * other.value
+                + 2.0 * self.gradient[0] * other.gradient[0]
+                + self.value * other.curvature,
+        }
+    }
+}
+
+impl<const N: usize> Div for Dual<N> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.recip()
+    }
+}
+
+impl<const N: usize> Add<f64> for Dual<N> {
+    type Output = Self;
+
+    fn add(self, other: f64) -> Self {
+        Dual {
+            value: self.value + other,
+            ..self
+        }
+    }
+}
+
+impl<const N: usize> Sub<f64> for Dual<N> {
+    type Output = Self;
+
+    fn sub(self, other: f64) -> Self {
+        self + -other
+    }
+}
+
+impl<const N: usize> Mul<f64> for Dual<N> {
+    type Output = Self;
+
+    fn mul(self, other: f64) -> Self {
+        Dual {
+            value: self.value * other,
+            gradient: self.gradient.map(|g| g * other),
+            curvature: self.curvature * other,
+        }
+    }
+}
+
+impl<const N: usize> Div<f64> for Dual<N> {
+    type Output = Self;
+
+    fn div(self, other: f64) -> Self {
+        self * (1.0 / other)
+    }
+}
+
+impl<const N: usize> Add<Dual<N>> for f64 {
+    type Output = Dual<N>;
+
+    fn add(self, other: Dual<N>) -> Dual<N> {
+        other + self
+    }
+}
+
+impl<const N: usize> Sub<Dual<N>> for f64 {
+    type Output = Dual<N>;
+
+    fn sub(self, other: Dual<N>) -> Dual<N> {
+        -other + self
+    }
+}
+
+impl<const N: usize> Mul<Dual<N>> for f64 {
+    type Output = Dual<N>;
+
+    fn mul(self, other: Dual<N>) -> Dual<N> {
+        other * self
+    }
+}
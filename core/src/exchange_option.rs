@@ -0,0 +1,108 @@
+// exchange_option.rs
+
+use crate::numerics::norm_cdf;
+use crate::two_asset_option::TwoAssetOption;
+
+/// Represents the Margrabe formula for the European option to exchange asset 2 for asset 1.
+///
+/// The payoff is `max(S1 − S2, 0)`. The strike and payoff of the underlying
+/// `TwoAssetOption` are ignored.
+pub struct ExchangeOption {
+    /// The underlying two-asset option.
+    pub option: TwoAssetOption,
+}
+
+impl ExchangeOption {
+    /// Creates a new `ExchangeOption` instance with the given two-asset option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying two-asset option.
+    ///
+    /// # Returns
+    ///
+    /// A new `ExchangeOption` instance.
+    pub fn new(option: TwoAssetOption) -> Self {
+        ExchangeOption { option }
+    }
+
+    /// Calculates the Margrabe (1978) price of the European exchange option.
+    ///
+    /// Measured in units of asset 2 the option is a call on `S1 / S2` with unit strike
+    /// and volatility `σ = √(σ1² − 2ρσ1σ2 + σ2²)`, so the price does not depend on the
+    /// risk-free rate:
+    ///
+    /// `S1 e^{-q1 T} N(d1) − S2 e^{-q2 T} N(d2)`, with
+    /// `d1 = [ln(S1 / S2) + (q2 − q1 + σ²/2) T] / (σ√T)` and `d2 = d1 − σ√T`.
+    ///
+    /// # Returns
+    ///
+    /// The price of the option.
+    pub fn margrabe_price(&self) -> f64 {
+        let option = &self.option;
+        let t = option.t;
+        let carried1 = option.s1 * (-option.div1 * t).exp();
+        let carried2 = option.s2 * (-option.div2 * t).exp();
+        let sigma = (option.sigma1.powi(2) - 2.0 * option.rho * option.sigma1 * option.sigma2
+            + option.sigma2.powi(2))
+        .sqrt();
+        let sigma_sqrt_t = sigma * t.sqrt();
+        if sigma_sqrt_t <= 0.0 {
+            return (carried1 - carried2).max(0.0);
+        }
+
+        let d1 = ((carried1 / carried2).ln() + sigma_sqrt_t.powi(2) / 2.0) / sigma_sqrt_t;
+        let d2 = d1 - sigma_sqrt_t;
+        carried1 * norm_cdf(d1) - carried2 * norm_cdf(d2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::option_type::{ExerciseStyle, OptionType};
+    use crate::stock_option::StockOption;
+    use crate::two_asset_binomial_tree::TwoAssetBinomialTree;
+    use crate::two_asset_option::TwoAssetPayoff;
+
+    fn exchange(s1: f64, s2: f64, r: f64, t: f64, div1: f64, div2: f64, sigma1: f64, sigma2: f64, rho: f64) -> ExchangeOption {
+        ExchangeOption::new(TwoAssetOption::new(
+            s1, s2, 0.0, r, t, 1, div1, div2, sigma1, sigma2, rho, TwoAssetPayoff::Exchange, ExerciseStyle::European,
+        ))
+    }
+
+    #[test]
+    fn with_a_riskless_second_asset_is_a_black_scholes_call() {
+        // The second asset grows deterministically to S2 e^{(r − q2) T}, which is the strike.
+        let price = exchange(100.0, 90.0, 0.05, 0.5, 0.02, 0.03, 0.25, 0.0, 0.3).margrabe_price();
+        let strike = 90.0 * ((0.05f64 - 0.03) * 0.5).exp();
+        let call = BlackScholesOption::new(
+            StockOption::new(100.0, strike, 0.05, 0.5, 1, 0.02, 0.25, OptionType::Call, ExerciseStyle::European).unwrap(),
+        )
+        .price();
+        assert!((price - call).abs() < 1e-12, "{price} vs {call}");
+    }
+
+    #[test]
+    fn matches_the_two_asset_tree() {
+        let option = exchange(22.0, 20.0, 0.1, 0.1, 0.06, 0.04, 0.2, 0.25, -0.5);
+        let mut lattice = option.option.clone();
+        lattice.n = 400;
+        let tree = TwoAssetBinomialTree::new(lattice).price();
+        let price = option.margrabe_price();
+        assert!((price - tree).abs() < 2e-3, "{price} vs {tree}");
+    }
+
+    #[test]
+    fn does_not_depend_on_the_rate_and_is_symmetric_in_the_assets() {
+        let price = exchange(100.0, 95.0, 0.05, 1.0, 0.01, 0.02, 0.3, 0.2, 0.4).margrabe_price();
+        let other_rate = exchange(100.0, 95.0, 0.2, 1.0, 0.01, 0.02, 0.3, 0.2, 0.4).margrabe_price();
+        assert!((price - other_rate).abs() < 1e-12);
+        // Exchanging asset 1 for asset 2 is the reverse option, and the pair is worth the
+        // difference of the carried spots.
+        let reverse = exchange(95.0, 100.0, 0.05, 1.0, 0.02, 0.01, 0.2, 0.3, 0.4).margrabe_price();
+        let carried = 100.0 * (-0.01f64).exp() - 95.0 * (-0.02f64).exp();
+        assert!((price - reverse - carried).abs() < 1e-12, "{price} {reverse}");
+    }
+}
@@ -0,0 +1,143 @@
+// fd_grid.rs
+
+/// Represents a uniform spatial grid in log-price used by the finite-difference engines.
+pub struct Grid {
+    /// The log-prices at the grid nodes.
+    pub points: Vec<f64>,
+    /// The spacing between adjacent nodes in log-price.
+    pub spacing: f64,
+}
+
+impl Grid {
+    /// Creates a log-price grid centered on the initial price of an asset.
+    ///
+    /// The grid spans `width` standard deviations of the terminal log-price on either
+    /// side of `ln(s0)` and always has an odd number of nodes, so that `s0` itself lies
+    /// exactly on the middle node.
+    ///
+    /// # Arguments
+    ///
+    /// * `s0` - The initial price of the asset.
+    /// * `sigma` - The volatility of the asset.
+    /// * `t` - The time to expiration (in years).
+    /// * `nodes` - The requested number of grid nodes.
+    /// * `width` - The half-width of the grid in standard deviations.
+    ///
+    /// # Returns
+    ///
+    /// A new `Grid` instance.
+    pub fn log_price(s0: f64, sigma: f64, t: f64, nodes: usize, width: f64) -> Self {
+        let nodes = nodes.max(3) | 1;
+        let half_width = width * (sigma * t.sqrt()).max(0.05);
+        let spacing = 2.0 * half_width / (nodes - 1) as f64;
+        let lower = s0.ln() - half_width;
+        let points = (0..nodes).map(|i| lower + i as f64 * spacing).collect();
+        Grid { points, spacing }
+    }
+
+    /// Creates a log-price grid spanning a fixed range of asset prices.
+    ///
+    /// This is used when the edges of the grid must coincide with barriers.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The asset price at the first node.
+    /// * `upper` - The asset price at the last node.
+    /// * `nodes` - The number of grid nodes.
+    ///
+    /// # Returns
+    ///
+    /// A new `Grid` instance.
+    pub fn log_range(lower: f64, upper: f64, nodes: usize) -> Self {
+        let nodes = nodes.max(3);
+        let spacing = (upper / lower).ln() / (nodes - 1) as f64;
+        let points = (0..nodes).map(|i| lower.ln() + i as f64 * spacing).collect();
+        Grid { points, spacing }
+    }
+
+    /// Returns the number of nodes in the grid.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns whether the grid has no nodes, which its constructors never produce.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the index of the middle node, which corresponds to the initial price.
+    pub fn center(&self) -> usize {
+        self.points.len() / 2
+    }
+
+    /// Returns the asset prices at the grid nodes.
+    pub fn prices(&self) -> Vec<f64> {
+        self.points.iter().map(|x| x.exp()).collect()
+    }
+}
+
+/// Calculates the Dirichlet boundary value of an option at an edge of the grid.
+///
+/// Far from the strike, option values approach the discounted payoff evaluated at the
+/// forward prices of the underlying assets. This is exact for payoffs that are linear
+/// in the asymptotic region (deep in- or out-of-the-money vanilla, spread, and rainbow
+/// payoffs), so it can be shared by one- and multi-asset engines.
+///
+/// # Arguments
+///
+/// * `payoff` - The payoff as a function of the asset prices.
+/// * `prices` - The asset prices at the boundary node.
+/// * `dividends` - The continuous dividend yields of the assets.
+/// * `r` - The risk-free interest rate.
+/// * `tau` - The time remaining until expiration.
+///
+/// # Returns
+///
+/// The boundary value of the option.
+pub fn forward_boundary_value<F: Fn(&[f64]) -> f64>(
+    payoff: F,
+    prices: &[f64],
+    dividends: &[f64],
+    r: f64,
+    tau: f64,
+) -> f64 {
+    let forwards = prices
+        .iter()
+        .zip(dividends)
+        .map(|(&s, &q)| s * ((r - q) * tau).exp())
+        .collect::<Vec<_>>();
+    (-r * tau).exp() * payoff(&forwards)
+}
+
+/// Solves a tridiagonal linear system using the Thomas algorithm.
+///
+/// # Arguments
+///
+/// * `lower` - The sub-diagonal coefficients (the first entry is ignored).
+/// * `diag` - The diagonal coefficients.
+/// * `upper` - The super-diagonal coefficients (the last entry is ignored).
+/// * `rhs` - The right-hand side of the system.
+///
+/// # Returns
+///
+/// The solution vector.
+pub fn solve_tridiagonal(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = rhs.len();
+    let mut c_prime = vec![0.0; n];
+    let mut d_prime = vec![0.0; n];
+
+    c_prime[0] = upper[0] / diag[0];
+    d_prime[0] = rhs[0] / diag[0];
+    for i in 1..n {
+        let denominator = diag[i] - lower[i] * c_prime[i - 1];
+        c_prime[i] = upper[i] / denominator;
+        d_prime[i] = (rhs[i] - lower[i] * d_prime[i - 1]) / denominator;
+    }
+
+    let mut solution = vec![0.0; n];
+    solution[n - 1] = d_prime[n - 1];
+    for i in (0..n - 1).rev() {
+        solution[i] = d_prime[i] - c_prime[i] * solution[i + 1];
+    }
+    solution
+}
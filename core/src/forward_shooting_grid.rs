@@ -0,0 +1,211 @@
+// forward_shooting_grid.rs
+
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::numerics::interpolate;
+use crate::pricing_error::PricingError;
+
+/// Identifies the path-dependent auxiliary state carried at each node of the lattice.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum AuxiliaryState {
+    /// The arithmetic average of the stock prices observed at every step from `start`
+    /// onward, tracked on a grid of `points` representative values per node. Before the
+    /// averaging window opens the auxiliary value is simply the current stock price.
+    Average { points: usize, start: usize },
+    /// The maximum stock price observed so far.
+    Maximum,
+    /// The minimum stock price observed so far.
+    Minimum,
+}
+
+impl AuxiliaryState {
+    /// Creates the state of an average whose window opens at a time rather than a step.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The number of representative averages per node.
+    /// * `window_start` - The time (in years) at which the averaging window opens.
+    /// * `t` - The time to expiration of the option (in years).
+    /// * `n` - The number of time steps in the tree.
+    ///
+    /// # Returns
+    ///
+    /// `AuxiliaryState::Average` starting at the step nearest to `window_start`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `window_start` is not in `[0, t)`.
+    pub fn average_from(points: usize, window_start: f64, t: f64, n: usize) -> Result<Self, PricingError> {
+        if !(0.0..t).contains(&window_start) {
+            return Err(PricingError::InvalidInput(
+                "window_start must satisfy 0 <= window_start < t.".to_string(),
+            ));
+        }
+        let start = ((window_start / t * n as f64).round() as usize).min(n.saturating_sub(1));
+        Ok(AuxiliaryState::Average { points, start })
+    }
+}
+
+/// Represents a forward-shooting-grid (FSG) extension of the binomial tree.
+///
+/// Each node of a Cox-Ross-Rubinstein tree carries a grid of representative values of
+/// an auxiliary path variable. During backward induction, the auxiliary value is
+/// "shot forward" to each child node with the state transition, and the child's value
+/// is read off its grid by linear interpolation (Hull-White / Barraquand-Pudet).
+///
+/// For running extrema the representative values are exactly the lattice prices
+/// `s0 u^m`, so no interpolation error is introduced.
+pub struct ForwardShootingGrid {
+    /// The underlying binomial tree option.
+    pub tree: BinomialTreeOption,
+    /// The auxiliary path variable carried at each node.
+    pub state: AuxiliaryState,
+    /// The payoff as a function of the stock price and the auxiliary value.
+    ///
+    /// The same function is used for early exercise at the steps allowed by the exercise schedule.
+    pub payoff: Box<dyn Fn(f64, f64) -> f64 + Send + Sync>,
+}
+
+impl ForwardShootingGrid {
+    /// Creates a new `ForwardShootingGrid` with the given tree, auxiliary state, and payoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The binomial tree option representing the underlying asset and option parameters.
+    /// * `state` - The auxiliary path variable carried at each node.
+    /// * `payoff` - The payoff as a function of the stock price and the auxiliary value.
+    pub fn new(
+        tree: BinomialTreeOption,
+        state: AuxiliaryState,
+        payoff: Box<dyn Fn(f64, f64) -> f64 + Send + Sync>,
+    ) -> Self {
+        ForwardShootingGrid { tree, state, payoff }
+    }
+
+    /// Calculates the representative auxiliary values at a node of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - The time step of the node.
+    /// * `j` - The number of down moves leading to the node.
+    ///
+    /// # Returns
+    ///
+    /// The increasing grid of auxiliary values reachable at the node.
+    pub fn auxiliary_grid(&self, step: usize, j: usize) -> Vec<f64> {
+        let s0 = self.tree.option.s0;
+        let u = self.tree.u;
+        let ups = step - j;
+        let net = step as i32 - 2 * j as i32;
+
+        match self.state {
+            AuxiliaryState::Maximum => {
+                (net.max(0)..=ups as i32).map(|m| s0 * u.powi(m)).collect()
+            }
+            AuxiliaryState::Minimum => {
+                (-(j as i32)..=net.min(0)).map(|m| s0 * u.powi(m)).collect()
+            }
+            AuxiliaryState::Average { start, .. } if step <= start => vec![s0 * u.powi(net)],
+            AuxiliaryState::Average { points, start } => {
+                // The extreme averages come from taking all up moves first or all down moves
+                // first, as these paths bound the stock price at every step.
+                let path_average = |first_up: bool| {
+                    let mut level = 0;
+                    let mut sum = if start == 0 { s0 } else { 0.0 };
+                    for m in 0..step {
+                        let up = if first_up { m < ups } else { m >= j };
+                        level += if up { 1 } else { -1 };
+                        if m + 1 >= start {
+                            sum += s0 * u.powi(level);
+                        }
+                    }
+                    sum / (step - start + 1) as f64
+                };
+                let lowest = path_average(false);
+                let highest = path_average(true);
+                if points < 2 || highest - lowest <= 1e-12 * highest {
+                    return vec![lowest];
+                }
+                let ratio = (highest / lowest).ln() / (points - 1) as f64;
+                (0..points).map(|m| lowest * (ratio * m as f64).exp()).collect()
+            }
+        }
+    }
+
+    /// Updates the auxiliary value when moving to a child node.
+    ///
+    /// # Arguments
+    ///
+    /// * `aux` - The auxiliary value at the parent node.
+    /// * `s_next` - The stock price at the child node.
+    /// * `step_next` - The time step of the child node.
+    ///
+    /// # Returns
+    ///
+    /// The auxiliary value at the child node.
+    pub fn update(&self, aux: f64, s_next: f64, step_next: usize) -> f64 {
+        match self.state {
+            AuxiliaryState::Maximum => aux.max(s_next),
+            AuxiliaryState::Minimum => aux.min(s_next),
+            AuxiliaryState::Average { start, .. } if step_next <= start => s_next,
+            AuxiliaryState::Average { start, .. } => {
+                let count = (step_next - start) as f64;
+                (aux * count + s_next) / (count + 1.0)
+            }
+        }
+    }
+
+    /// Calculates the price of the path-dependent option on the forward shooting grid.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price(&mut self) -> f64 {
+        self.tree.setup_crr_parameters();
+        self.tree.init_stock_price_tree();
+
+        let option = &self.tree.option;
+        let n = option.n;
+        let discount = (-option.r * option.dt()).exp();
+        let schedule = option.exercise_schedule();
+
+        let mut grids = (0..=n).map(|j| self.auxiliary_grid(n, j)).collect::<Vec<_>>();
+        let mut values = grids
+            .iter()
+            .zip(&option.sts[n])
+            .map(|(grid, &s)| grid.iter().map(|&a| (self.payoff)(s, a)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        for i in (0..n).rev() {
+            let level_grids = (0..=i).map(|j| self.auxiliary_grid(i, j)).collect::<Vec<_>>();
+            let s_up = &option.sts[i + 1];
+            let level_values = level_grids
+                .iter()
+                .enumerate()
+                .map(|(j, grid)| {
+                    let s = option.sts[i][j];
+                    grid.iter()
+                        .map(|&a| {
+                            let up = interpolate(&grids[j], &values[j], self.update(a, s_up[j], i + 1));
+                            let down = interpolate(
+                                &grids[j + 1],
+                                &values[j + 1],
+                                self.update(a, s_up[j + 1], i + 1),
+                            );
+                            let continuation = (up * self.tree.qu + down * self.tree.qd) * discount;
+                            if schedule[i] {
+                                continuation.max((self.payoff)(s, a))
+                            } else {
+                                continuation
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            grids = level_grids;
+            values = level_values;
+        }
+
+        interpolate(&grids[0], &values[0], option.s0)
+    }
+}
@@ -0,0 +1,312 @@
+// forward_start_option.rs
+
+use crate::numerics::black_call;
+use crate::option_type::OptionType;
+use crate::pricing_error::PricingError;
+use crate::random::{simulate_pairs, Rng};
+use crate::stock_option::StockOption;
+
+/// Calculates the Black-Scholes price of an option on a stock with unit spot price.
+///
+/// By homogeneity, an option whose strike is `α S(t₀)` is worth `S(t₀)` times this price
+/// with strike `α`, which is what makes forward-start structures tractable.
+fn unit_price(option: &StockOption, strike: f64, tau: f64, option_type: OptionType) -> f64 {
+    let forward = (option.cost_of_carry() * tau).exp();
+    let call = black_call(forward, strike, option.sigma.powi(2) * tau);
+    let value = match option_type {
+        OptionType::Call => call,
+        OptionType::Put => call - forward + strike,
+    };
+    (-option.r * tau).exp() * value
+}
+
+/// Simulates the gross returns of the stock over consecutive periods with antithetic variates.
+///
+/// # Arguments
+///
+/// * `option` - The stock option providing the rate, dividend yield, and volatility.
+/// * `dates` - The increasing end dates of the periods; the first period starts at time 0.
+/// * `rng` - The random number generator.
+///
+/// # Returns
+///
+/// The ratios `S(tᵢ) / S(tᵢ₋₁)` along a path and its antithetic twin.
+fn simulate_returns(option: &StockOption, dates: &[f64], rng: &mut Rng) -> [Vec<f64>; 2] {
+    let drift = option.cost_of_carry() - option.sigma.powi(2) / 2.0;
+    let mut returns = [Vec::with_capacity(dates.len()), Vec::with_capacity(dates.len())];
+    let mut previous = 0.0;
+    for &date in dates {
+        let dt = date - previous;
+        previous = date;
+        let shock = option.sigma * dt.sqrt() * rng.next_normal();
+        returns[0].push((drift * dt + shock).exp());
+        returns[1].push((drift * dt - shock).exp());
+    }
+    returns
+}
+
+/// Calculates the mean and standard error of antithetic pair averages.
+fn sample_statistics(samples: &[f64]) -> (f64, f64) {
+    let pairs = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / pairs;
+    let sum_squares = samples.iter().map(|sample| sample * sample).sum::<f64>();
+    let variance = (sum_squares / pairs - mean * mean).max(0.0);
+    (mean, (variance / pairs).sqrt())
+}
+
+/// Represents a European forward-start option.
+///
+/// The option comes into existence at `start` with the strike set to `moneyness` times
+/// the stock price on that date, and expires at `option.t`. The strike of the underlying
+/// `StockOption` is ignored.
+pub struct ForwardStartOption {
+    /// The underlying stock option.
+    pub option: StockOption,
+    /// The time (in years) at which the strike is set.
+    pub start: f64,
+    /// The strike as a multiple of the stock price at `start`.
+    pub moneyness: f64,
+}
+
+impl ForwardStartOption {
+    /// Creates a new `ForwardStartOption` with the given stock option and strike-setting date.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying stock option.
+    /// * `start` - The time (in years) at which the strike is set.
+    /// * `moneyness` - The strike as a multiple of the stock price at `start`.
+    pub fn new(option: StockOption, start: f64, moneyness: f64) -> Self {
+        ForwardStartOption {
+            option,
+            start,
+            moneyness,
+        }
+    }
+
+    /// Checks that the strike is set before expiration and that the parameters of the
+    /// underlying option are valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `start` is not in `[0, t)` or if a parameter
+    /// of the option is outside its domain.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        self.option.validate()?;
+        if !(0.0..self.option.t).contains(&self.start) {
+            return Err(PricingError::InvalidInput("start must satisfy 0 <= start < t.".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Calculates the closed-form price of the forward-start option.
+    ///
+    /// By homogeneity the option is worth `S(t₀)` units of an option with unit spot and
+    /// strike `α`, and a claim to `S(t₀)` paid at `t₀` is worth `s0 e^{(b−r) t₀}` today,
+    /// where `b` is the cost of carry (Rubinstein, 1991).
+    ///
+    /// # Returns
+    ///
+    /// The price of the forward-start option.
+    pub fn price(&self) -> f64 {
+        let option = &self.option;
+        let tau = option.t - self.start;
+        option.s0
+            * ((option.cost_of_carry() - option.r) * self.start).exp()
+            * unit_price(option, self.moneyness, tau, option.option_type)
+    }
+
+    /// Calculates the Monte Carlo price of the forward-start option.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The number of simulated paths (rounded up to an even number).
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the Monte Carlo price and its standard error.
+    pub fn monte_carlo_price(&self, paths: usize, seed: u64) -> (f64, f64) {
+        let option = &self.option;
+        let dates = [self.start, option.t];
+        let payoff = |returns: &[f64]| {
+            let s_start = option.s0 * returns[0];
+            let s_end = s_start * returns[1];
+            let strike = self.moneyness * s_start;
+            if option.option_type == OptionType::Call {
+                (s_end - strike).max(0.0)
+            } else {
+                (strike - s_end).max(0.0)
+            }
+        };
+
+        let samples = simulate_pairs(paths.div_ceil(2).max(1), seed, |rng| {
+            let [path, twin] = simulate_returns(option, &dates, rng);
+            0.5 * (payoff(&path) + payoff(&twin))
+        });
+
+        let discount = (-option.r * option.t).exp();
+        let (mean, error) = sample_statistics(&samples);
+        (discount * mean, discount * error)
+    }
+}
+
+/// Represents a cliquet (ratchet) option built from a schedule of forward-start periods.
+///
+/// The return of each period, `S(tᵢ) / S(tᵢ₋₁) − 1`, is clipped to the local floor and
+/// cap, and the clipped returns are summed. The sum is then clipped to the global floor
+/// and cap and paid at expiration as a fraction of the notional.
+pub struct CliquetOption {
+    /// The underlying stock option (only the rate, dividend yield, volatility, and expiry are used).
+    pub option: StockOption,
+    /// The increasing dates (in years) at which the periods end; the first period starts at time 0.
+    pub reset_dates: Vec<f64>,
+    /// The notional amount the summed return is paid on.
+    pub notional: f64,
+    /// The minimum return credited for each period.
+    pub local_floor: Option<f64>,
+    /// The maximum return credited for each period.
+    pub local_cap: Option<f64>,
+    /// The minimum total return paid at expiration.
+    pub global_floor: Option<f64>,
+    /// The maximum total return paid at expiration.
+    pub global_cap: Option<f64>,
+}
+
+impl CliquetOption {
+    /// Creates a new `CliquetOption` without caps or floors.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying stock option.
+    /// * `reset_dates` - The increasing dates (in years) at which the periods end.
+    /// * `notional` - The notional amount the summed return is paid on.
+    pub fn new(option: StockOption, reset_dates: Vec<f64>, notional: f64) -> Self {
+        CliquetOption {
+            option,
+            reset_dates,
+            notional,
+            local_floor: None,
+            local_cap: None,
+            global_floor: None,
+            global_cap: None,
+        }
+    }
+
+    /// Checks that the reset dates are positive and increasing and that the parameters of
+    /// the underlying option are valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the reset dates are empty, not positive, or
+    /// not increasing, or if a parameter of the option is outside its domain.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        let increasing = self.reset_dates.windows(2).all(|w| w[0] < w[1]);
+        if !matches!(self.reset_dates.first(), Some(&first) if first > 0.0 && increasing) {
+            return Err(PricingError::InvalidInput(
+                "reset_dates must be a non-empty list of positive, increasing dates.".to_string(),
+            ));
+        }
+        self.option.validate()
+    }
+
+    /// Clips a value to an optional floor and cap.
+    fn clip(value: f64, floor: Option<f64>, cap: Option<f64>) -> f64 {
+        let floored = floor.map_or(value, |f| value.max(f));
+        cap.map_or(floored, |c| floored.min(c))
+    }
+
+    /// Calculates the payoff of the cliquet for the gross returns of each period.
+    pub fn payoff(&self, returns: &[f64]) -> f64 {
+        let total = returns
+            .iter()
+            .map(|&ratio| Self::clip(ratio - 1.0, self.local_floor, self.local_cap))
+            .sum::<f64>();
+        self.notional * Self::clip(total, self.global_floor, self.global_cap)
+    }
+
+    /// Calculates the closed-form price of a cliquet without global caps or floors.
+    ///
+    /// Without global bounds the payoff is a sum of locally clipped returns, each of which
+    /// is a forward-start call spread: `E[clip(R)] = F + e^{rτ} (C(1 + F) − C(1 + C))`,
+    /// where `C(K)` is the unit-spot call price over the period.
+    ///
+    /// # Returns
+    ///
+    /// The price of the cliquet, or `None` if a global floor or cap is set.
+    pub fn price(&self) -> Option<f64> {
+        if self.global_floor.is_some() || self.global_cap.is_some() {
+            return None;
+        }
+        let option = &self.option;
+        let mut previous = 0.0;
+        let mut expected_total = 0.0;
+        for &date in &self.reset_dates {
+            let tau = date - previous;
+            previous = date;
+            let growth = (option.r * tau).exp();
+            let call = |strike: f64| growth * unit_price(option, strike, tau, OptionType::Call);
+            let floored = match self.local_floor {
+                Some(floor) => floor + call(1.0 + floor),
+                None => (option.cost_of_carry() * tau).exp() - 1.0,
+            };
+            expected_total += match self.local_cap {
+                Some(cap) => floored - call(1.0 + cap),
+                None => floored,
+            };
+        }
+        Some(self.notional * (-option.r * option.t).exp() * expected_total)
+    }
+
+    /// Calculates the Monte Carlo price of the cliquet.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The number of simulated paths (rounded up to an even number).
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the Monte Carlo price and its standard error.
+    pub fn monte_carlo_price(&self, paths: usize, seed: u64) -> (f64, f64) {
+        let samples = simulate_pairs(paths.div_ceil(2).max(1), seed, |rng| {
+            let [path, twin] = simulate_returns(&self.option, &self.reset_dates, rng);
+            0.5 * (self.payoff(&path) + self.payoff(&twin))
+        });
+
+        let discount = (-self.option.r * self.option.t).exp();
+        let (mean, error) = sample_statistics(&samples);
+        (discount * mean, discount * error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::option_type::ExerciseStyle;
+
+    #[test]
+    fn starting_now_prices_like_a_vanilla_option() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 1, 0.02, 0.25, option_type, ExerciseStyle::European).unwrap();
+            let vanilla = BlackScholesOption::new(StockOption { k: 110.0, ..option.clone() }).price();
+            let forward_start = ForwardStartOption::new(option, 0.0, 1.1).price();
+            assert!((forward_start - vanilla).abs() < 1e-12, "{forward_start} vs {vanilla}");
+        }
+    }
+
+    #[test]
+    fn borrow_prices_like_an_equal_dividend_yield() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 1, 0.03, 0.2, OptionType::Call, ExerciseStyle::European).unwrap();
+        let dividend = ForwardStartOption::new(option.clone(), 0.25, 1.0);
+        let borrow = ForwardStartOption::new(option.with_yield_as_borrow(), 0.25, 1.0);
+        assert!((dividend.price() - borrow.price()).abs() < 1e-12);
+        assert_eq!(dividend.monte_carlo_price(2000, 1), borrow.monte_carlo_price(2000, 1));
+
+        let dates = vec![0.25, 0.5, 0.75, 1.0];
+        let dividend = CliquetOption::new(option.clone(), dates.clone(), 1.0);
+        let borrow = CliquetOption::new(option.with_yield_as_borrow(), dates, 1.0);
+        assert!((dividend.price().unwrap() - borrow.price().unwrap()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,186 @@
+// fx_option.rs
+
+use crate::numerics::{norm_cdf, norm_pdf};
+use crate::option_type::{ExerciseStyle, OptionType};
+use crate::pricing_error::PricingError;
+use crate::stock_option::StockOption;
+
+/// Represents the Greeks of an FX option under the usual FX-market conventions.
+pub struct FxGreeks {
+    /// The sensitivity of the price to the spot rate: `φ e^{-r_f T} N(φ d1)`.
+    pub spot_delta: f64,
+    /// The spot delta net of the premium when the premium is paid in foreign currency:
+    /// `φ (K / S) e^{-r_d T} N(φ d2)`.
+    pub premium_adjusted_delta: f64,
+    /// The sensitivity of the undiscounted price to the forward rate: `φ N(φ d1)`.
+    pub forward_delta: f64,
+    /// The sensitivity of the spot delta to the spot rate.
+    pub gamma: f64,
+    /// The sensitivity of the price to the volatility.
+    pub vega: f64,
+    /// The sensitivity of the price to the domestic interest rate.
+    pub domestic_rho: f64,
+    /// The sensitivity of the price to the foreign interest rate.
+    pub foreign_rho: f64,
+}
+
+/// Represents a European or American option on an exchange rate.
+///
+/// The spot rate is quoted in domestic currency per unit of foreign currency, and the
+/// option is priced with the Garman-Kohlhagen (1983) model, in which the foreign
+/// interest rate plays the role of a continuous dividend yield.
+pub struct FxOption {
+    /// The spot exchange rate (domestic units per foreign unit).
+    pub spot: f64,
+    /// The strike exchange rate.
+    pub k: f64,
+    /// The domestic risk-free interest rate.
+    pub domestic_rate: f64,
+    /// The foreign risk-free interest rate.
+    pub foreign_rate: f64,
+    /// The time to expiration of the option (in years).
+    pub t: f64,
+    /// The volatility of the exchange rate.
+    pub sigma: f64,
+    /// Whether the option is a call or a put on the foreign currency.
+    pub option_type: OptionType,
+}
+
+impl FxOption {
+    /// Creates a new `FxOption` instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `spot` - The spot exchange rate (domestic units per foreign unit).
+    /// * `k` - The strike exchange rate.
+    /// * `domestic_rate` - The domestic risk-free interest rate.
+    /// * `foreign_rate` - The foreign risk-free interest rate.
+    /// * `t` - The time to expiration of the option (in years).
+    /// * `sigma` - The volatility of the exchange rate.
+    /// * `option_type` - Whether the option is a call or a put on the foreign currency.
+    ///
+    /// # Returns
+    ///
+    /// A new `FxOption` instance with the provided parameters.
+    pub fn new(
+        spot: f64,
+        k: f64,
+        domestic_rate: f64,
+        foreign_rate: f64,
+        t: f64,
+        sigma: f64,
+        option_type: OptionType,
+    ) -> Self {
+        FxOption {
+            spot,
+            k,
+            domestic_rate,
+            foreign_rate,
+            t,
+            sigma,
+            option_type,
+        }
+    }
+
+    /// Converts the FX option into the equivalent stock option for the lattice engines.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of time steps in the binomial tree.
+    /// * `exercise` - Whether the option is European-style or American-style.
+    ///
+    /// # Returns
+    ///
+    /// A `StockOption` whose dividend yield is the foreign interest rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `StockOption::new`.
+    pub fn stock_option(&self, n: usize, exercise: ExerciseStyle) -> Result<StockOption, PricingError> {
+        StockOption::new(
+            self.spot,
+            self.k,
+            self.domestic_rate,
+            self.t,
+            n,
+            self.foreign_rate,
+            self.sigma,
+            self.option_type,
+            exercise,
+        )
+    }
+
+    /// Calculates the forward exchange rate at expiration.
+    pub fn forward(&self) -> f64 {
+        self.spot * ((self.domestic_rate - self.foreign_rate) * self.t).exp()
+    }
+
+    /// Calculates the `d1` and `d2` terms of the Garman-Kohlhagen formula.
+    fn d1_d2(&self) -> (f64, f64) {
+        let sigma_sqrt_t = self.sigma * self.t.sqrt();
+        let d1 = ((self.forward() / self.k).ln() + sigma_sqrt_t.powi(2) / 2.0) / sigma_sqrt_t;
+        (d1, d1 - sigma_sqrt_t)
+    }
+
+    /// Calculates the Garman-Kohlhagen price of the European option, in domestic currency
+    /// per unit of foreign notional.
+    ///
+    /// # Returns
+    ///
+    /// The price of the option.
+    pub fn price(&self) -> f64 {
+        let (d1, d2) = self.d1_d2();
+        let phi = self.option_type.sign();
+        phi * (self.spot * (-self.foreign_rate * self.t).exp() * norm_cdf(phi * d1)
+            - self.k * (-self.domestic_rate * self.t).exp() * norm_cdf(phi * d2))
+    }
+
+    /// Calculates the analytic Greeks of the European option.
+    ///
+    /// The premium-adjusted delta is the delta quoted when the premium is paid in the
+    /// foreign currency, which is the convention for pairs such as USD/JPY and EUR/USD
+    /// from the USD side. It subtracts the premium, converted at spot, from the spot delta.
+    ///
+    /// # Returns
+    ///
+    /// The Greeks of the option.
+    pub fn greeks(&self) -> FxGreeks {
+        let (d1, d2) = self.d1_d2();
+        let phi = self.option_type.sign();
+        let foreign_discount = (-self.foreign_rate * self.t).exp();
+        let domestic_discount = (-self.domestic_rate * self.t).exp();
+        let sqrt_t = self.t.sqrt();
+
+        FxGreeks {
+            spot_delta: phi * foreign_discount * norm_cdf(phi * d1),
+            premium_adjusted_delta: phi * self.k / self.spot * domestic_discount * norm_cdf(phi * d2),
+            forward_delta: phi * norm_cdf(phi * d1),
+            gamma: foreign_discount * norm_pdf(d1) / (self.spot * self.sigma * sqrt_t),
+            vega: self.spot * foreign_discount * norm_pdf(d1) * sqrt_t,
+            domestic_rho: phi * self.k * self.t * domestic_discount * norm_cdf(phi * d2),
+            foreign_rho: -phi * self.spot * self.t * foreign_discount * norm_cdf(phi * d1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes_option::BlackScholesOption;
+
+    #[test]
+    fn matches_the_haug_reference_price() {
+        // Haug, "The Complete Guide to Option Pricing Formulas", section 1.1.6.
+        let price = FxOption::new(1.56, 1.60, 0.06, 0.08, 0.5, 0.12, OptionType::Call).price();
+        assert!((price - 0.0291).abs() < 5e-5, "{price}");
+    }
+
+    #[test]
+    fn is_black_scholes_with_the_foreign_rate_as_dividend_yield() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let fx = FxOption::new(1.10, 1.12, 0.03, 0.01, 0.75, 0.09, option_type);
+            let stock = BlackScholesOption::new(fx.stock_option(1, ExerciseStyle::European).unwrap());
+            assert!((fx.price() - stock.price()).abs() < 1e-12, "{} vs {}", fx.price(), stock.price());
+        }
+    }
+}
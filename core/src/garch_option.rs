@@ -0,0 +1,359 @@
+// garch_option.rs
+
+use core::f64::consts::PI;
+use num_complex::Complex64;
+use crate::numerics::simpson;
+use crate::option_type::OptionType;
+use crate::pricing_error::PricingError;
+use crate::random::simulate_pairs;
+use crate::stock_option::StockOption;
+pub use crate::trading_calendar::TRADING_DAYS_PER_YEAR;
+
+/// Identifies the GARCH(1,1) variance dynamics used by the Monte Carlo pricer.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GarchModel {
+    /// The Heston-Nandi model: `h' = ω + βh + α(z − γ√h)²`.
+    HestonNandi,
+    /// The Duan NGARCH model: `h' = ω + βh + αh(z − γ)²`.
+    Duan,
+}
+
+impl GarchModel {
+    /// Calculates the stationary daily variance implied by daily GARCH parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `omega` - The constant term of the variance equation.
+    /// * `alpha` - The ARCH coefficient of the variance equation.
+    /// * `beta` - The GARCH coefficient of the variance equation.
+    /// * `gamma` - The leverage coefficient of the variance equation.
+    ///
+    /// # Returns
+    ///
+    /// The unconditional daily variance under the physical measure.
+    pub fn unconditional_variance(self, omega: f64, alpha: f64, beta: f64, gamma: f64) -> f64 {
+        match self {
+            GarchModel::HestonNandi => (omega + alpha) / (1.0 - beta - alpha * gamma.powi(2)),
+            GarchModel::Duan => omega / (1.0 - beta - alpha * (1.0 + gamma.powi(2))),
+        }
+    }
+}
+
+/// Represents a European option on an asset whose daily returns follow a GARCH(1,1) process.
+///
+/// All GARCH parameters are expressed per trading day, exactly as they come out of a
+/// maximum-likelihood fit on daily log returns. The annual rate, dividend yield, borrow
+/// rate, and maturity of the underlying `StockOption` are converted to daily steps
+/// internally.
+#[derive(Clone)]
+pub struct GarchOption {
+    /// The underlying stock option.
+    pub option: StockOption,
+    /// The constant term of the variance equation.
+    pub omega: f64,
+    /// The ARCH coefficient of the variance equation.
+    pub alpha: f64,
+    /// The GARCH (persistence) coefficient of the variance equation.
+    pub beta: f64,
+    /// The leverage (asymmetry) coefficient of the variance equation.
+    pub gamma: f64,
+    /// The price of risk in the physical return equation.
+    pub lambda: f64,
+    /// The conditional variance of the first daily return.
+    pub h0: f64,
+}
+
+impl GarchOption {
+    /// Creates a new `GarchOption` with the given stock option and daily GARCH parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying stock option.
+    /// * `omega` - The constant term of the variance equation.
+    /// * `alpha` - The ARCH coefficient of the variance equation.
+    /// * `beta` - The GARCH coefficient of the variance equation.
+    /// * `gamma` - The leverage coefficient of the variance equation.
+    /// * `lambda` - The price of risk in the physical return equation.
+    /// * `h0` - The conditional variance of the first daily return.
+    pub fn new(
+        option: StockOption,
+        omega: f64,
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+        lambda: f64,
+        h0: f64,
+    ) -> Self {
+        GarchOption {
+            option,
+            omega,
+            alpha,
+            beta,
+            gamma,
+            lambda,
+            h0,
+        }
+    }
+
+    /// Calculates the stationary daily variance implied by the GARCH parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The GARCH dynamics whose unconditional variance is required.
+    ///
+    /// # Returns
+    ///
+    /// The unconditional daily variance under the physical measure.
+    pub fn unconditional_variance(&self, model: GarchModel) -> f64 {
+        model.unconditional_variance(self.omega, self.alpha, self.beta, self.gamma)
+    }
+
+    /// Sets the conditional variance of the first daily return, and the volatility of the
+    /// underlying option to its annualized value.
+    ///
+    /// # Arguments
+    ///
+    /// * `h0` - The conditional variance of the first daily return, or `None` for the
+    ///   unconditional variance of `model`.
+    /// * `model` - The GARCH dynamics whose unconditional variance is the default.
+    pub fn set_initial_variance(&mut self, h0: Option<f64>, model: GarchModel) {
+        self.h0 = h0.unwrap_or_else(|| self.unconditional_variance(model));
+        self.option.sigma = (self.h0 * TRADING_DAYS_PER_YEAR).sqrt();
+    }
+
+    /// Checks that the initial variance is positive and that the parameters of the
+    /// underlying option are valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `h0` is not positive, which happens for the
+    /// unconditional variance of non-stationary parameters, or if a parameter of the
+    /// option is outside its domain.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        Self::check_initial_variance(self.h0)?;
+        self.option.validate()
+    }
+
+    /// Checks that an initial daily variance is positive.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `h0` is not positive, which happens for the
+    /// unconditional variance of non-stationary parameters.
+    pub fn check_initial_variance(h0: f64) -> Result<(), PricingError> {
+        if h0.is_nan() || h0 <= 0.0 {
+            return Err(PricingError::InvalidInput(
+                "Invalid h0. The initial variance must be positive (check the stationarity of the parameters)."
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Calculates the number of daily steps until expiration.
+    pub fn days(&self) -> usize {
+        ((self.option.t * TRADING_DAYS_PER_YEAR).round() as usize).max(1)
+    }
+
+    /// Calculates the Heston-Nandi closed-form price of the European option.
+    ///
+    /// The risk-neutral generating function `E[S_T^φ]` is built with the backward
+    /// recursion of Heston and Nandi (2000), and the two probabilities of the
+    /// Black-Scholes-like decomposition are recovered by Fourier inversion. Puts are
+    /// priced through put-call parity.
+    ///
+    /// # Returns
+    ///
+    /// The closed-form price of the option.
+    pub fn closed_form_price(&self) -> f64 {
+        let days = self.days();
+        let r = self.option.r / TRADING_DAYS_PER_YEAR;
+        let q = (self.option.r - self.option.cost_of_carry()) / TRADING_DAYS_PER_YEAR;
+        let gamma_star = self.gamma + self.lambda + 0.5;
+        let log_s0 = self.option.s0.ln();
+        let log_k = self.option.k.ln();
+
+        let generating_function = |phi: Complex64| {
+            let mut a = Complex64::new(0.0, 0.0);
+            let mut b = Complex64::new(0.0, 0.0);
+            for _ in 0..days {
+                let denominator = 1.0 - 2.0 * self.alpha * b;
+                let a_next = a + phi * (r - q) + b * self.omega - 0.5 * denominator.ln();
+                let b_next = phi * (gamma_star - 0.5) - 0.5 * gamma_star.powi(2)
+                    + self.beta * b
+                    + 0.5 * (phi - gamma_star).powi(2) / denominator;
+                a = a_next;
+                b = b_next;
+            }
+            (phi * log_s0 + a + b * self.h0).exp()
+        };
+
+        // Integrand of the inversion formula, `Re[K^{-iφ} f(iφ + shift) / (iφ)]`.
+        let integrand = |phi: f64, shift: f64| {
+            let i_phi = Complex64::new(0.0, phi);
+            let value = (-i_phi * log_k).exp() * generating_function(i_phi + shift) / i_phi;
+            value.re
+        };
+
+        let upper = (40.0 / (days as f64 * self.h0).sqrt()).clamp(50.0, 2000.0);
+        let intervals = 4000;
+        let lower = 1e-8;
+
+        let forward = self.option.s0 * ((r - q) * days as f64).exp();
+        let asset_term =
+            forward / 2.0 + simpson(|phi| integrand(phi, 1.0), lower, upper, intervals) / PI;
+        let exercise_probability =
+            0.5 + simpson(|phi| integrand(phi, 0.0), lower, upper, intervals) / PI;
+
+        let discount = (-r * days as f64).exp();
+        let call = discount * (asset_term - self.option.k * exercise_probability);
+
+        if self.option.option_type == OptionType::Call {
+            call
+        } else {
+            call - self.option.s0 * (-q * days as f64).exp() + self.option.k * discount
+        }
+    }
+
+    /// Calculates the Monte Carlo price of the European option under risk-neutral GARCH dynamics.
+    ///
+    /// Daily log returns are simulated with antithetic variates, using the risk-neutral
+    /// form of either the Heston-Nandi or the Duan NGARCH variance recursion.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The GARCH dynamics to simulate.
+    /// * `paths` - The number of simulated paths (rounded up to an even number).
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the Monte Carlo price and its standard error.
+    pub fn monte_carlo_price(&self, model: GarchModel, paths: usize, seed: u64) -> (f64, f64) {
+        let days = self.days();
+        let r = self.option.r / TRADING_DAYS_PER_YEAR;
+        let q = (self.option.r - self.option.cost_of_carry()) / TRADING_DAYS_PER_YEAR;
+        let gamma_star = match model {
+            GarchModel::HestonNandi => self.gamma + self.lambda + 0.5,
+            GarchModel::Duan => self.gamma + self.lambda,
+        };
+
+        let next_variance = |h: f64, z: f64| match model {
+            GarchModel::HestonNandi => {
+                self.omega + self.beta * h + self.alpha * (z - gamma_star * h.sqrt()).powi(2)
+            }
+            GarchModel::Duan => self.omega + self.beta * h + self.alpha * h * (z - gamma_star).powi(2),
+        };
+
+        let payoff = |s: f64| {
+            if self.option.option_type == OptionType::Call {
+                (s - self.option.k).max(0.0)
+            } else {
+                (self.option.k - s).max(0.0)
+            }
+        };
+
+        let pairs = paths.div_ceil(2).max(1);
+        let samples = simulate_pairs(pairs, seed, |rng| {
+            let mut log_s = [self.option.s0.ln(); 2];
+            let mut h = [self.h0; 2];
+            for _ in 0..days {
+                let z = rng.next_normal();
+                for (branch, &sign) in [1.0, -1.0].iter().enumerate() {
+                    let shock = sign * z;
+                    log_s[branch] += r - q - 0.5 * h[branch] + h[branch].sqrt() * shock;
+                    h[branch] = next_variance(h[branch], shock);
+                }
+            }
+            0.5 * (payoff(log_s[0].exp()) + payoff(log_s[1].exp()))
+        });
+        let sum = samples.iter().sum::<f64>();
+        let sum_squares = samples.iter().map(|sample| sample * sample).sum::<f64>();
+
+        let discount = (-r * days as f64).exp();
+        let mean = sum / pairs as f64;
+        let variance = (sum_squares / pairs as f64 - mean * mean).max(0.0);
+        (discount * mean, discount * (variance / pairs as f64).sqrt())
+    }
+}
+
+/// Prices a `GarchOption` by Monte Carlo simulation, as a `PricingEngine`.
+///
+/// Every valuation draws the same paths from `seed`, so the revaluations of
+/// bump-and-revalue Greeks share their random numbers. The volatility of the underlying
+/// `StockOption` plays no part in the GARCH dynamics, so its vega is zero.
+#[derive(Clone)]
+pub struct GarchMonteCarlo {
+    /// The option being priced.
+    pub garch: GarchOption,
+    /// The GARCH dynamics to simulate.
+    pub model: GarchModel,
+    /// The number of simulated paths.
+    pub paths: usize,
+    /// The seed of the random number generator.
+    pub seed: u64,
+}
+
+impl GarchMonteCarlo {
+    /// Creates a new `GarchMonteCarlo` engine for a GARCH option.
+    ///
+    /// # Arguments
+    ///
+    /// * `garch` - The option to price.
+    /// * `model` - The GARCH dynamics to simulate.
+    /// * `paths` - The number of simulated paths.
+    /// * `seed` - The seed of the random number generator.
+    pub fn new(garch: GarchOption, model: GarchModel, paths: usize, seed: u64) -> Self {
+        GarchMonteCarlo { garch, model, paths, seed }
+    }
+
+    /// Calculates the Monte Carlo price of the option and its standard error.
+    pub fn price_with_error(&self) -> (f64, f64) {
+        self.garch.monte_carlo_price(self.model, self.paths, self.seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::pricing_engine::PricingEngine;
+    use crate::option_type::ExerciseStyle;
+
+    #[test]
+    fn borrow_prices_like_an_equal_dividend_yield() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 0.5, 1, 0.03, 0.2, OptionType::Call, ExerciseStyle::European).unwrap();
+        let garch = |option: StockOption| GarchOption::new(option, 5e-6, 1.3e-6, 0.59, 421.0, 0.0, 1.6e-4);
+        let (dividend, borrow) = (garch(option.clone()), garch(option.with_yield_as_borrow()));
+        assert!((dividend.closed_form_price() - borrow.closed_form_price()).abs() < 1e-12);
+        let model = GarchModel::HestonNandi;
+        assert_eq!(dividend.monte_carlo_price(model, 2000, 1), borrow.monte_carlo_price(model, 2000, 1));
+    }
+
+    #[test]
+    fn engine_prices_near_the_heston_nandi_closed_form() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 0.5, 126, 0.0, 0.2, OptionType::Call, ExerciseStyle::European).unwrap();
+        let garch = GarchOption::new(option, 5e-6, 1.3e-6, 0.59, 421.0, 0.0, 1.6e-4);
+        let closed_form = garch.closed_form_price();
+        let engine = GarchMonteCarlo::new(garch, GarchModel::HestonNandi, 20000, 5);
+        let (price, error) = engine.price_with_error();
+        assert_eq!(engine.try_price().unwrap(), price);
+        assert!((price - closed_form).abs() < 4.0 * error, "{price} vs {closed_form}");
+        let result = engine.greeks(Default::default(), 0.01).unwrap();
+        assert!(result.greeks.delta > 0.0 && result.greeks.delta < 1.0);
+        assert_eq!(result.greeks.vega, 0.0);
+    }
+
+    #[test]
+    fn closed_form_with_constant_variance_is_black_scholes() {
+        // Without ARCH or GARCH terms the daily variance stays at omega.
+        for (k, option_type) in [(90.0, OptionType::Call), (100.0, OptionType::Put), (115.0, OptionType::Call)] {
+            let option = StockOption::new(100.0, k, 0.05, 0.5, 126, 0.02, 0.2, option_type, ExerciseStyle::European).unwrap();
+            let omega = 0.2_f64.powi(2) / 252.0;
+            let garch = GarchOption::new(option.clone(), omega, 0.0, 0.0, 0.0, 0.0, omega);
+            let black_scholes = BlackScholesOption::new(option).price();
+            let closed_form = garch.closed_form_price();
+            assert!((closed_form - black_scholes).abs() < 1e-6, "{k}: {closed_form} vs {black_scholes}");
+        }
+    }
+}
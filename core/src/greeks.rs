@@ -0,0 +1,243 @@
+// greeks.rs
+
+use crate::trading_calendar::TRADING_DAYS_PER_YEAR;
+use crate::stock_option::StockOption;
+
+/// The number of calendar days per year used for theta per calendar day.
+pub const CALENDAR_DAYS_PER_YEAR: f64 = 365.0;
+
+/// The first-order Greeks of an option, and its gamma.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Greeks {
+    /// The sensitivity of the price to the stock price.
+    pub delta: f64,
+    /// The sensitivity of delta to the stock price.
+    pub gamma: f64,
+    /// The sensitivity of the price to the passage of time, in the theta convention of
+    /// the calculation.
+    pub theta: f64,
+    /// The sensitivity of the price to the volatility.
+    pub vega: f64,
+    /// The sensitivity of the price to the interest rate.
+    pub rho: f64,
+}
+
+/// Identifies the finite-difference scheme used for bump-and-revalue Greeks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DifferenceScheme {
+    /// One-sided differences, which need one bumped revaluation per Greek.
+    OneSided,
+    /// Central differences, which need two bumped revaluations per Greek but are
+    /// accurate to second order in the bump.
+    Central,
+}
+
+/// Identifies how bump sizes are interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BumpStyle {
+    /// The bump is added to the parameter.
+    Absolute,
+    /// The bump is a fraction of the parameter. If the parameter is zero, the bump is
+    /// applied as an absolute bump instead.
+    Relative,
+}
+
+/// Identifies the estimator used for Monte Carlo Greeks.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MonteCarloEstimator {
+    /// Differentiates the discounted payoff along each path. It has the lowest variance
+    /// but needs a payoff that is continuous in the parameters.
+    Pathwise,
+    /// Weights the payoff by the derivative of the log-density of the path. It applies
+    /// to any payoff, including discontinuous ones, at the cost of a higher variance.
+    LikelihoodRatio,
+}
+
+/// Identifies the unit of time in which theta is reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ThetaUnit {
+    /// The derivative of the value with respect to the time to expiration, `∂V/∂T`,
+    /// which is positive for most long options.
+    TimeToExpiration,
+    /// The change in value per year as time passes, `−∂V/∂T`.
+    Year,
+    /// The change in value per calendar day as time passes, `−∂V/∂T / 365`.
+    CalendarDay,
+    /// The change in value per trading day as time passes, `−∂V/∂T / 252`.
+    TradingDay,
+}
+
+/// Controls how theta is reported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThetaConvention {
+    /// The unit of time, which also decides the sign.
+    pub unit: ThetaUnit,
+    /// Whether theta includes the carry of the position: the interest on its value and
+    /// the drift of the stock at the cost of carry. Without carry, theta is the pure time
+    /// decay `−½σ²S²Γ` of the Black-Scholes equation, per year as time passes.
+    pub include_carry: bool,
+}
+
+impl Default for ThetaConvention {
+    fn default() -> Self {
+        ThetaConvention {
+            unit: ThetaUnit::TimeToExpiration,
+            include_carry: true,
+        }
+    }
+}
+
+impl ThetaConvention {
+    /// Converts a theta computed as `∂V/∂T` to this convention.
+    ///
+    /// The carry is removed through the Black-Scholes equation, whose terms in the
+    /// time to expiration are `∂V/∂T = −rV + bSΔ + ½σ²S²Γ` with `b` the cost of carry.
+    ///
+    /// # Arguments
+    ///
+    /// * `theta` - The derivative of the value with respect to the time to expiration.
+    /// * `option` - The option, for its stock price, interest rate, and cost of carry.
+    /// * `value` - The value of the option.
+    /// * `delta` - The delta of the option.
+    ///
+    /// # Returns
+    ///
+    /// The theta in this convention.
+    pub fn report(&self, theta: f64, option: &StockOption, value: f64, delta: f64) -> f64 {
+        let theta = if self.include_carry {
+            theta
+        } else {
+            theta + option.r * value - option.cost_of_carry() * option.s0 * delta
+        };
+        match self.unit {
+            ThetaUnit::TimeToExpiration => theta,
+            ThetaUnit::Year => -theta,
+            ThetaUnit::CalendarDay => -theta / CALENDAR_DAYS_PER_YEAR,
+            ThetaUnit::TradingDay => -theta / TRADING_DAYS_PER_YEAR,
+        }
+    }
+}
+
+/// Controls the bumps used for the vega, rho, and theta of the lattice engines.
+///
+/// The default reproduces the original behaviour: one-sided absolute bumps of one
+/// volatility point, one percentage point of interest rate, and 0.0001 years. For
+/// low-volatility or short-dated options, smaller or relative bumps and central
+/// differences are more accurate. The configuration also decides how theta is reported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreeksConfig {
+    /// The bump applied to the volatility for vega.
+    pub vol_bump: f64,
+    /// The bump applied to the risk-free interest rate for rho.
+    pub rate_bump: f64,
+    /// The bump applied to the time to expiration for theta. One-sided theta shortens
+    /// the time to expiration, so the bump must be smaller than it.
+    pub time_bump: f64,
+    /// Whether one-sided or central differences are taken.
+    pub scheme: DifferenceScheme,
+    /// Whether the bumps are absolute or relative to the parameters.
+    pub style: BumpStyle,
+    /// How theta is reported.
+    pub theta: ThetaConvention,
+}
+
+impl Default for GreeksConfig {
+    fn default() -> Self {
+        GreeksConfig {
+            vol_bump: 0.01,
+            rate_bump: 0.01,
+            time_bump: 0.0001,
+            scheme: DifferenceScheme::OneSided,
+            style: BumpStyle::Absolute,
+            theta: ThetaConvention::default(),
+        }
+    }
+}
+
+impl GreeksConfig {
+    /// Converts a configured bump into the absolute bump for a parameter value.
+    ///
+    /// # Arguments
+    ///
+    /// * `bump` - The configured bump.
+    /// * `value` - The current value of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// The absolute bump to apply.
+    pub fn absolute_bump(&self, bump: f64, value: f64) -> f64 {
+        match self.style {
+            BumpStyle::Relative if value != 0.0 => bump * value.abs(),
+            _ => bump,
+        }
+    }
+}
+
+/// Represents the second-order cross Greeks of an option.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrossGreeks {
+    /// The sensitivity of delta to the volatility, `∂²V/∂S∂σ`.
+    pub vanna: f64,
+    /// The sensitivity of vega to the volatility, `∂²V/∂σ²`.
+    pub volga: f64,
+    /// The rate at which delta changes as time passes, `−∂²V/∂S∂T`.
+    pub charm: f64,
+}
+
+/// Represents the third-order spot Greeks of an option, together with its sensitivities
+/// to the dividend yield and the strike.
+///
+/// The strike sensitivities also describe the risk-neutral distribution of the stock
+/// price at expiration: `P(S_T ≤ K) = e^{rT} ∂C/∂K + 1` for a call, and its density is
+/// `e^{rT} ∂²V/∂K²` for both calls and puts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedGreeks {
+    /// The sensitivity of gamma to the stock price, `∂³V/∂S³`.
+    pub speed: f64,
+    /// The sensitivity of gamma to the volatility, `∂³V/∂S²∂σ`.
+    pub zomma: f64,
+    /// The rate at which gamma changes as time passes, `−∂³V/∂S²∂T`.
+    pub color: f64,
+    /// The sensitivity of the price to the dividend yield, `∂V/∂q`.
+    pub psi: f64,
+    /// The sensitivity of the price to the strike, `∂V/∂K`.
+    pub dual_delta: f64,
+    /// The second derivative of the price with respect to the strike, `∂²V/∂K²`.
+    pub dual_gamma: f64,
+}
+
+/// Represents the Greeks of an option on several assets, one entry per asset.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiAssetGreeks {
+    /// The sensitivity of the price to each asset, `∂V/∂Sᵢ`.
+    pub deltas: Vec<f64>,
+    /// The second derivative of the price with respect to each asset, `∂²V/∂Sᵢ²`.
+    pub gammas: Vec<f64>,
+}
+
+/// Represents the Greeks of an option on two assets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoAssetGreeks {
+    /// The sensitivity of the price to the first asset, `∂V/∂S1`.
+    pub delta1: f64,
+    /// The sensitivity of the price to the second asset, `∂V/∂S2`.
+    pub delta2: f64,
+    /// The second derivative of the price with respect to the first asset, `∂²V/∂S1²`.
+    pub gamma1: f64,
+    /// The second derivative of the price with respect to the second asset, `∂²V/∂S2²`.
+    pub gamma2: f64,
+    /// The cross-gamma, `∂²V/∂S1∂S2`.
+    pub cross_gamma: f64,
+    /// The sensitivity of the price to the correlation, `∂V/∂ρ`.
+    pub cega: f64,
+}
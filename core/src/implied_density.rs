@@ -0,0 +1,76 @@
+// implied_density.rs
+
+use crate::numerics::black_call;
+
+/// The strike bump, relative to the strike, of the finite differences.
+const RELATIVE_STRIKE_BUMP: f64 = 1e-3;
+
+/// Calculates the risk-neutral density and distribution function of the underlying asset
+/// at expiration implied by a volatility smile.
+///
+/// By Breeden and Litzenberger (1978), the undiscounted call price `c(K)` satisfies
+/// `∂c/∂K = −P(S_T > K)` and `∂²c/∂K² = q(K)`. The smile is turned into undiscounted Black
+/// call prices and differentiated twice in the strike by central differences, so its own
+/// slope and curvature in the strike enter the density.
+///
+/// # Arguments
+///
+/// * `volatility` - The implied volatility of the smile at a strike.
+/// * `forward` - The forward price of the underlying asset at expiration.
+/// * `t` - The time to expiration (in years).
+/// * `strikes` - The strike prices at which to evaluate the density.
+///
+/// # Returns
+///
+/// A vector of the density and the cumulative distribution function at each strike.
+pub fn implied_density<V: Fn(f64) -> f64>(volatility: V, forward: f64, t: f64, strikes: &[f64]) -> Vec<(f64, f64)> {
+    let call = |k: f64| black_call(forward, k, volatility(k).powi(2) * t);
+    strikes
+        .iter()
+        .map(|&k| {
+            let h = RELATIVE_STRIKE_BUMP * k;
+            let (down, middle, up) = (call(k - h), call(k), call(k + h));
+            let density = (up - 2.0 * middle + down) / (h * h);
+            let distribution = 1.0 + (up - down) / (2.0 * h);
+            (density, distribution)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numerics::{norm_cdf, norm_pdf};
+
+    /// Integrates the density, and the density times the strike, by the trapezoidal rule.
+    fn moments<V: Fn(f64) -> f64>(volatility: V) -> (f64, f64) {
+        let strikes = (1..=1600).map(|i| i as f64 * 0.25).collect::<Vec<_>>();
+        let density = implied_density(volatility, 100.0, 1.0, &strikes);
+        let (mut mass, mut mean) = (0.0, 0.0);
+        for i in 1..strikes.len() {
+            let h = strikes[i] - strikes[i - 1];
+            mass += h * (density[i].0 + density[i - 1].0) / 2.0;
+            mean += h * (strikes[i] * density[i].0 + strikes[i - 1] * density[i - 1].0) / 2.0;
+        }
+        (mass, mean)
+    }
+
+    #[test]
+    fn integrates_to_one_with_the_forward_as_mean() {
+        for (mass, mean) in [moments(|_| 0.25), moments(|k: f64| 0.25 - 0.1 * (k / 100.0).ln())] {
+            assert!((mass - 1.0).abs() < 1e-3, "{mass}");
+            assert!((mean - 100.0).abs() < 0.1, "{mean}");
+        }
+    }
+
+    #[test]
+    fn flat_smile_gives_the_lognormal_distribution() {
+        let points = implied_density(|_| 0.25, 100.0, 1.0, &[80.0, 100.0, 125.0]);
+        for (&k, &(density, distribution)) in [80.0, 100.0, 125.0].iter().zip(&points) {
+            let z = ((k / 100.0f64).ln() + 0.25 * 0.25 / 2.0) / 0.25;
+            let expected = norm_pdf(z) / (k * 0.25);
+            assert!((density - expected).abs() < 1e-6, "{k}: {density} vs {expected}");
+            assert!((distribution - norm_cdf(z)).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,570 @@
+// implied_volatility.rs
+
+use rayon::prelude::*;
+
+use crate::binomial_lr_option::BinomialLROption;
+use crate::numerics::{brent, erfcx, norm_cdf, norm_inv_cdf};
+use crate::option_type::OptionType;
+use crate::worker_threads;
+use crate::workspace::Workspace;
+use std::f64::consts::{PI, SQRT_2};
+
+/// The smallest admissible control parameter of a rational cubic interpolant.
+const MINIMUM_CONTROL_PARAMETER: f64 = -(1.0 - 1.490_116_119_384_765_6e-8);
+
+/// The control parameter above which a rational cubic interpolant is linear.
+const MAXIMUM_CONTROL_PARAMETER: f64 = 2.0 / (f64::EPSILON * f64::EPSILON);
+
+/// The maximum number of Householder iterations. Two suffice for machine precision from
+/// the rational initial guess; the remainder guard against pathological inputs.
+const MAXIMUM_ITERATIONS: usize = 8;
+
+/// The lowest volatility searched by the tree inversion.
+const MINIMUM_TREE_VOLATILITY: f64 = 1e-4;
+
+/// The highest volatility searched by the tree inversion.
+const MAXIMUM_TREE_VOLATILITY: f64 = 10.0;
+
+/// The fewest quotes in each task of a batch inversion, below which the cost of scheduling
+/// the task outweighs the work.
+const MINIMUM_QUOTES_PER_TASK: usize = 64;
+
+/// Calculates the Black-Scholes implied volatility of a European option price.
+///
+/// This follows Jäckel's "Let's Be Rational" (2015): the price is normalised to
+/// `β = V / (D √(F K))`, reduced to an out-of-the-money call on the log-moneyness
+/// `x = ln(F / K) ≤ 0`, and inverted for the total volatility `s = σ√T`. The initial guess
+/// is a rational cubic interpolation of the inverse in one of four branches, which is
+/// refined by Householder iterations of third order on an objective that is nearly linear
+/// in that branch. This recovers the volatility to close to machine precision in two
+/// iterations, even far in the wings.
+///
+/// # Arguments
+///
+/// * `price` - The price of the option.
+/// * `forward` - The forward price of the underlying asset at expiration.
+/// * `k` - The strike price of the option.
+/// * `t` - The time to expiration of the option (in years).
+/// * `discount` - The discount factor to expiration.
+/// * `option_type` - Whether the option is a call or a put.
+///
+/// # Returns
+///
+/// The implied volatility, which is zero at the intrinsic value, or `None` if the price
+/// is below the intrinsic value or not below the price of the asset (calls) or the
+/// discounted strike (puts).
+pub fn implied_volatility(
+    price: f64,
+    forward: f64,
+    k: f64,
+    t: f64,
+    discount: f64,
+    option_type: OptionType,
+) -> Option<f64> {
+    if !(forward > 0.0 && k > 0.0 && t > 0.0 && discount > 0.0) {
+        return None;
+    }
+    let mut x = (forward / k).ln();
+    let mut beta = price / (discount * (forward * k).sqrt());
+    let theta = option_type.sign();
+
+    // Reduce to an out-of-the-money call by put-call parity and symmetry.
+    if theta * x > 0.0 {
+        beta -= theta * ((x / 2.0).exp() - (-x / 2.0).exp());
+    }
+    x = -x.abs();
+    if beta == 0.0 {
+        return Some(0.0);
+    }
+    if !(beta > 0.0 && beta < (x / 2.0).exp()) {
+        return None;
+    }
+    Some(total_volatility(beta, x) / t.sqrt())
+}
+
+/// Calculates the Black-Scholes implied volatilities of a chain of European option prices
+/// in parallel.
+///
+/// The quotes are split into tasks on the worker thread pool, and each quote is inverted
+/// with `implied_volatility`.
+///
+/// # Arguments
+///
+/// * `prices` - The prices of the options.
+/// * `strikes` - The strike prices of the options.
+/// * `expiries` - The times to expiration of the options (in years).
+/// * `option_types` - Whether each option is a call or a put.
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+///
+/// # Returns
+///
+/// The implied volatility of each option, or NaN where `implied_volatility` has none.
+pub fn implied_volatilities(
+    prices: &[f64],
+    strikes: &[f64],
+    expiries: &[f64],
+    option_types: &[OptionType],
+    s0: f64,
+    r: f64,
+    div: f64,
+) -> Vec<f64> {
+    worker_threads::install(|| {
+        (0..prices.len())
+            .into_par_iter()
+            .with_min_len(MINIMUM_QUOTES_PER_TASK)
+            .map(|i| {
+                let t = expiries[i];
+                let forward = s0 * ((r - div) * t).exp();
+                implied_volatility(prices[i], forward, strikes[i], t, (-r * t).exp(), option_types[i])
+                    .unwrap_or(f64::NAN)
+            })
+            .collect()
+    })
+}
+
+/// Calculates the implied volatility of an American or Bermudan option price by inverting
+/// the Leisen-Reimer tree.
+///
+/// The Black-Scholes implied volatility of an American price absorbs the early-exercise
+/// premium and so overstates the volatility, most of all for deep in-the-money puts.
+/// Instead, the tree price is matched by Brent's method over the volatility. The search
+/// starts from a bracket around the Black-Scholes implied volatility of the price, which
+/// for a put bounds the American implied volatility from above, and widens it as needed.
+///
+/// # Arguments
+///
+/// * `engine` - The tree whose option is to be matched, which prices copies of it.
+/// * `price` - The price of the option.
+/// * `tolerance` - The absolute tolerance on the volatility.
+///
+/// # Returns
+///
+/// The implied volatility, or `None` if the price is not attained by any volatility in
+/// `[1e-4, 10]`.
+pub fn american_implied_volatility(engine: &BinomialLROption, price: f64, tolerance: f64) -> Option<f64> {
+    let option = &engine.tree.option;
+    let forward = option.s0 * (option.cost_of_carry() * option.t).exp();
+    let discount = (-option.r * option.t).exp();
+    let guess = implied_volatility(price, forward, option.k, option.t, discount, option.option_type)
+        .filter(|&guess| guess > MINIMUM_TREE_VOLATILITY)
+        .unwrap_or(0.2)
+        .min(MAXIMUM_TREE_VOLATILITY);
+
+    let mut trial = option.clone();
+    let mut workspace = Workspace::default();
+    let mut error = |sigma: f64| {
+        trial.sigma = sigma;
+        engine.price_option(&trial, &mut workspace) - price
+    };
+    // Widen the bracket geometrically from the guess until it contains the root.
+    let (mut lower, mut upper) = (guess / 1.5, guess);
+    while lower > MINIMUM_TREE_VOLATILITY && error(lower) > 0.0 {
+        upper = lower;
+        lower = (lower / 2.0).max(MINIMUM_TREE_VOLATILITY);
+    }
+    while upper < MAXIMUM_TREE_VOLATILITY && error(upper) < 0.0 {
+        lower = upper;
+        upper = (upper * 2.0).min(MAXIMUM_TREE_VOLATILITY);
+    }
+    brent(&mut error, lower, upper, tolerance, 100)
+}
+
+/// Calculates the normalised price of an out-of-the-money call,
+/// `e^{x/2} Φ(x/s + s/2) − e^{−x/2} Φ(x/s − s/2)`.
+///
+/// Below the inflection point `s = √(2|x|)` both arguments are negative, and the price is
+/// formed from scaled complementary error functions with a common Gaussian factor to
+/// avoid cancellation.
+fn normalised_black_call(x: f64, s: f64) -> f64 {
+    if s <= 0.0 {
+        return 0.0;
+    }
+    let h = x / s;
+    let t = s / 2.0;
+    if h + t < 0.0 {
+        (-(h * h + t * t) / 2.0).exp() * (erfcx(-(h + t) / SQRT_2) - erfcx((t - h) / SQRT_2)) / 2.0
+    } else {
+        (x / 2.0).exp() * norm_cdf(h + t) - (-x / 2.0).exp() * norm_cdf(h - t)
+    }
+}
+
+/// Calculates the derivative of the normalised call price with respect to `s`.
+fn normalised_vega(x: f64, s: f64) -> f64 {
+    let h = x / s;
+    let t = s / 2.0;
+    (-(h * h + t * t) / 2.0).exp() / (2.0 * PI).sqrt()
+}
+
+/// Calculates the Householder correction factor of the third order for a Newton step,
+/// given the ratios of the second and third derivatives to the first.
+fn householder_factor(newton: f64, halley: f64, third: f64) -> f64 {
+    (1.0 + 0.5 * halley * newton) / (1.0 + newton * (halley + third * newton / 6.0))
+}
+
+/// Calculates the lower map `f(s) = 2π|x|/√27 Φ(−|x|/(s√3))³`, which is nearly linear in
+/// the normalised price for small volatilities, and its first two derivatives with
+/// respect to the price.
+fn lower_map(x: f64, s: f64) -> (f64, f64, f64) {
+    let ax = x.abs();
+    let z = ax / (s * 3.0_f64.sqrt());
+    let y = z * z;
+    let s2 = s * s;
+    let phi_cdf = norm_cdf(-z);
+    let phi_pdf = (-y / 2.0).exp() / (2.0 * PI).sqrt();
+    let second = PI / 6.0 * y / (s2 * s)
+        * phi_cdf
+        * (8.0 * 3.0_f64.sqrt() * s * ax + (3.0 * s2 * (s2 - 8.0) - 8.0 * x * x) * phi_cdf / phi_pdf)
+        * (2.0 * y + 0.25 * s2).exp();
+    let first = 2.0 * PI * y * phi_cdf * phi_cdf * (y + 0.125 * s2).exp();
+    let value = 2.0 * PI / 27.0_f64.sqrt() * ax * phi_cdf.powi(3);
+    (value, first, second)
+}
+
+/// Inverts the lower map for `s`.
+fn inverse_lower_map(x: f64, f: f64) -> f64 {
+    if f <= 0.0 {
+        return 0.0;
+    }
+    let ax = x.abs();
+    (ax / (3.0_f64.sqrt() * norm_inv_cdf((f / (2.0 * PI / 27.0_f64.sqrt() * ax)).cbrt()))).abs()
+}
+
+/// Calculates the upper map `f(s) = Φ(−s/2)`, which is nearly linear in the normalised
+/// price for large volatilities, and its first two derivatives with respect to the price.
+fn upper_map(x: f64, s: f64) -> (f64, f64, f64) {
+    let value = norm_cdf(-s / 2.0);
+    if x == 0.0 {
+        return (value, -0.5, 0.0);
+    }
+    let w = (x / s).powi(2);
+    let first = -0.5 * (w / 2.0).exp();
+    let second = (PI / 2.0).sqrt() * (w + 0.125 * s * s).exp() * w / s;
+    (value, first, second)
+}
+
+/// Evaluates the Delbourgo-Gregory rational cubic interpolant between two points with
+/// given slopes and control parameter.
+#[allow(clippy::too_many_arguments)]
+fn rational_cubic(x: f64, x_l: f64, x_r: f64, y_l: f64, y_r: f64, d_l: f64, d_r: f64, r: f64) -> f64 {
+    let h = x_r - x_l;
+    if h.abs() <= 0.0 {
+        return (y_l + y_r) / 2.0;
+    }
+    let t = (x - x_l) / h;
+    if r >= MAXIMUM_CONTROL_PARAMETER {
+        return y_r * t + y_l * (1.0 - t);
+    }
+    let omt = 1.0 - t;
+    let (t2, omt2) = (t * t, omt * omt);
+    (y_r * t2 * t + (r * y_r - h * d_r) * t2 * omt + (r * y_l + h * d_l) * t * omt2 + y_l * omt2 * omt)
+        / (1.0 + (r - 3.0) * t * omt)
+}
+
+/// Calculates the smallest control parameter that preserves the monotonicity and
+/// convexity of the data.
+fn minimum_control_parameter(d_l: f64, d_r: f64, slope: f64, prefer_shape: bool) -> f64 {
+    let monotonic = d_l * slope >= 0.0 && d_r * slope >= 0.0;
+    let convex = d_l <= slope && slope <= d_r;
+    let concave = d_l >= slope && slope >= d_r;
+    if !monotonic && !convex && !concave {
+        return MINIMUM_CONTROL_PARAMETER;
+    }
+    let mut r1 = f64::MIN;
+    let mut r2 = f64::MIN;
+    if monotonic {
+        if slope != 0.0 {
+            r1 = (d_r + d_l) / slope;
+        } else if prefer_shape {
+            r1 = MAXIMUM_CONTROL_PARAMETER;
+        }
+    }
+    if convex || concave {
+        let (slope_minus_left, right_minus_slope) = (slope - d_l, d_r - slope);
+        if slope_minus_left != 0.0 && right_minus_slope != 0.0 {
+            let spread = d_r - d_l;
+            r2 = (spread / right_minus_slope).abs().max((spread / slope_minus_left).abs());
+        } else if prefer_shape {
+            r2 = MAXIMUM_CONTROL_PARAMETER;
+        }
+    } else if monotonic && prefer_shape {
+        r2 = MAXIMUM_CONTROL_PARAMETER;
+    }
+    MINIMUM_CONTROL_PARAMETER.max(r1.max(r2))
+}
+
+/// Calculates the control parameter of a shape-preserving rational cubic interpolant that
+/// matches a second derivative at one end.
+#[allow(clippy::too_many_arguments)]
+fn control_parameter(
+    x_l: f64,
+    x_r: f64,
+    y_l: f64,
+    y_r: f64,
+    d_l: f64,
+    d_r: f64,
+    second_derivative: f64,
+    at_left: bool,
+    prefer_shape: bool,
+) -> f64 {
+    let h = x_r - x_l;
+    let slope = (y_r - y_l) / h;
+    let numerator = 0.5 * h * second_derivative + (d_r - d_l);
+    let denominator = if at_left { slope - d_l } else { d_r - slope };
+    let r = if numerator == 0.0 {
+        0.0
+    } else if denominator == 0.0 {
+        if numerator > 0.0 {
+            MAXIMUM_CONTROL_PARAMETER
+        } else {
+            MINIMUM_CONTROL_PARAMETER
+        }
+    } else {
+        numerator / denominator
+    };
+    r.max(minimum_control_parameter(d_l, d_r, slope, prefer_shape))
+}
+
+/// Inverts the normalised price of an out-of-the-money call for the total volatility.
+fn total_volatility(beta: f64, x: f64) -> f64 {
+    if x == 0.0 {
+        return -2.0 * norm_inv_cdf((1.0 - beta) / 2.0);
+    }
+    let b_max = (x / 2.0).exp();
+    let s_c = (2.0 * x.abs()).sqrt();
+    let b_c = normalised_black_call(x, s_c);
+    let v_c = normalised_vega(x, s_c);
+    let mut s_left = f64::MIN_POSITIVE;
+    let mut s_right = f64::MAX;
+
+    if beta < b_c {
+        let s_l = s_c - b_c / v_c;
+        let b_l = normalised_black_call(x, s_l);
+        if beta < b_l {
+            // Lower branch: iterate on 1/ln(b), which is nearly linear in s.
+            let (f_l, d_f_l, d2_f_l) = lower_map(x, s_l);
+            let r = control_parameter(0.0, b_l, 0.0, f_l, 1.0, d_f_l, d2_f_l, false, true);
+            let mut f = rational_cubic(beta, 0.0, b_l, 0.0, f_l, 1.0, d_f_l, r);
+            if f <= 0.0 {
+                let t = beta / b_l;
+                f = (f_l * t + b_l * (1.0 - t)) * t;
+            }
+            let mut s = inverse_lower_map(x, f);
+            s_right = s_l;
+            let ln_beta = beta.ln();
+            for _ in 0..MAXIMUM_ITERATIONS {
+                let b = normalised_black_call(x, s);
+                let bp = normalised_vega(x, s);
+                update_bracket(b, beta, s, &mut s_left, &mut s_right);
+                let ds = if b <= 0.0 || bp <= 0.0 {
+                    0.5 * (s_left + s_right) - s
+                } else {
+                    let ln_b = b.ln();
+                    let bpob = bp / b;
+                    let h = x / s;
+                    let b_halley = h * h / s - s / 4.0;
+                    let b_third = b_halley * b_halley - 3.0 * (h / s).powi(2) - 0.25;
+                    let newton = (ln_beta - ln_b) * ln_b / ln_beta / bpob;
+                    let lambda = 1.0 / ln_b;
+                    let one_plus_two_lambda = 1.0 + 2.0 * lambda;
+                    let halley = b_halley - bpob * one_plus_two_lambda;
+                    let third = b_third + bpob * bpob * (2.0 + 6.0 * lambda * (1.0 + lambda))
+                        - b_halley * bpob * 3.0 * one_plus_two_lambda;
+                    newton * householder_factor(newton, halley, third)
+                };
+                let ds = ds.max(-0.5 * s);
+                s += ds;
+                if ds.abs() <= f64::EPSILON * s {
+                    break;
+                }
+            }
+            return s;
+        }
+        let v_l = normalised_vega(x, s_l);
+        let r = control_parameter(b_l, b_c, s_l, s_c, 1.0 / v_l, 1.0 / v_c, 0.0, false, false);
+        let s = rational_cubic(beta, b_l, b_c, s_l, s_c, 1.0 / v_l, 1.0 / v_c, r);
+        s_left = s_l;
+        s_right = s_c;
+        return central_iterations(beta, x, s, s_left, s_right);
+    }
+
+    let s_h = if v_c > f64::MIN_POSITIVE { s_c + (b_max - b_c) / v_c } else { s_c };
+    let b_h = normalised_black_call(x, s_h);
+    if beta <= b_h {
+        let v_h = normalised_vega(x, s_h);
+        let r = control_parameter(b_c, b_h, s_c, s_h, 1.0 / v_c, 1.0 / v_h, 0.0, true, false);
+        let s = rational_cubic(beta, b_c, b_h, s_c, s_h, 1.0 / v_c, 1.0 / v_h, r);
+        return central_iterations(beta, x, s, s_c, s_h);
+    }
+
+    // Upper branch: iterate on ln((b_max − β)/(b_max − b)).
+    let (f_h, d_f_h, d2_f_h) = upper_map(x, s_h);
+    let mut f = 0.0;
+    if d2_f_h > -f64::EPSILON.sqrt() && d2_f_h < 0.0 {
+        let r = control_parameter(b_h, b_max, f_h, 0.0, d_f_h, -0.5, d2_f_h, true, true);
+        f = rational_cubic(beta, b_h, b_max, f_h, 0.0, d_f_h, -0.5, r);
+    }
+    if f <= 0.0 {
+        let h = b_max - b_h;
+        let t = (beta - b_h) / h;
+        f = (f_h * (1.0 - t) + 0.5 * h * t) * (1.0 - t);
+    }
+    let mut s = -2.0 * norm_inv_cdf(f);
+    s_left = s_h;
+    for _ in 0..MAXIMUM_ITERATIONS {
+        let b = normalised_black_call(x, s);
+        let bp = normalised_vega(x, s);
+        update_bracket(b, beta, s, &mut s_left, &mut s_right);
+        let ds = if b >= b_max || bp <= f64::MIN_POSITIVE {
+            0.5 * (s_left + s_right.min(2.0 * s_left.max(s))) - s
+        } else {
+            let b_max_minus_b = b_max - b;
+            let g = ((b_max - beta) / b_max_minus_b).ln();
+            let gp = bp / b_max_minus_b;
+            let h = x / s;
+            let b_halley = h * h / s - s / 4.0;
+            let b_third = b_halley * b_halley - 3.0 * (h / s).powi(2) - 0.25;
+            let newton = -g / gp;
+            let halley = b_halley + gp;
+            let third = b_third + gp * (2.0 * gp + 3.0 * b_halley);
+            newton * householder_factor(newton, halley, third)
+        };
+        let ds = ds.max(-0.5 * s);
+        s += ds;
+        if ds.abs() <= f64::EPSILON * s {
+            break;
+        }
+    }
+    s
+}
+
+/// Refines the total volatility in the central branches by Householder iterations on the
+/// normalised price itself.
+fn central_iterations(beta: f64, x: f64, mut s: f64, mut s_left: f64, mut s_right: f64) -> f64 {
+    for _ in 0..MAXIMUM_ITERATIONS {
+        let b = normalised_black_call(x, s);
+        let bp = normalised_vega(x, s);
+        update_bracket(b, beta, s, &mut s_left, &mut s_right);
+        let newton = (beta - b) / bp;
+        let h = x / s;
+        let halley = h * h / s - s / 4.0;
+        let third = halley * halley - 3.0 * (h / s).powi(2) - 0.25;
+        let ds = (newton * householder_factor(newton, halley, third)).max(-0.5 * s);
+        s += ds;
+        if ds.abs() <= f64::EPSILON * s {
+            break;
+        }
+    }
+    s
+}
+
+/// Narrows the bracket around the root after evaluating the price at `s`.
+fn update_bracket(b: f64, beta: f64, s: f64, s_left: &mut f64, s_right: &mut f64) {
+    if b > beta && s < *s_right {
+        *s_right = s;
+    } else if b < beta && s > *s_left {
+        *s_left = s;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binomial_tree_option::BinomialTreeOption;
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::option_type::ExerciseStyle;
+    use crate::stock_option::StockOption;
+
+    fn black_scholes(k: f64, t: f64, sigma: f64, option_type: OptionType) -> f64 {
+        BlackScholesOption::new(StockOption::new(100.0, k, 0.05, t, 1, 0.02, sigma, option_type, ExerciseStyle::European).unwrap()).price()
+    }
+
+    /// Whether each side of the forward is priced with its out-of-the-money option, whose
+    /// price holds the whole time value and so determines the volatility best.
+    fn out_of_the_money(k: f64, t: f64) -> OptionType {
+        if k >= 100.0 * (0.03 * t).exp() { OptionType::Call } else { OptionType::Put }
+    }
+
+    #[test]
+    fn european_volatilities_round_trip() {
+        for k in [50.0, 80.0, 95.0, 100.0, 105.0, 125.0, 200.0] {
+            for t in [0.02_f64, 0.25, 1.0, 10.0] {
+                for sigma in [0.05, 0.2, 0.6, 2.0] {
+                    let (forward, discount) = (100.0 * (0.03 * t).exp(), (-0.05 * t).exp());
+                    let option_type = out_of_the_money(k, t);
+                    let price = black_scholes(k, t, sigma, option_type);
+                    // Below this the Black-Scholes price itself has lost digits to cancellation.
+                    if price < 1e-6 {
+                        continue;
+                    }
+                    let implied = implied_volatility(price, forward, k, t, discount, option_type).unwrap();
+                    assert!((implied - sigma).abs() < 1e-10 * sigma, "{k} {t} {sigma}: {implied}");
+                    // The in-the-money option has the same volatility, to the precision its
+                    // time value allows, and reprices to its own price.
+                    let other = if option_type == OptionType::Call { OptionType::Put } else { OptionType::Call };
+                    let price = black_scholes(k, t, sigma, other);
+                    let implied = implied_volatility(price, forward, k, t, discount, other).unwrap();
+                    let repriced = black_scholes(k, t, implied, other);
+                    assert!((repriced - price).abs() < 1e-13 * price, "{k} {t} {sigma}: {repriced} vs {price}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn normalised_prices_invert_to_machine_precision() {
+        // The wings, far beyond quoted prices, use the exact normalised price.
+        for x in [-40.0, -10.0, -2.0, -0.5, -0.01, 0.0] {
+            for s in [0.001, 0.01, 0.1, 0.5, 1.0, 3.0, 10.0] {
+                let beta = normalised_black_call(x, s);
+                if beta < f64::MIN_POSITIVE {
+                    continue;
+                }
+                let (forward, k) = (x.exp(), 1.0);
+                let implied = implied_volatility(beta * forward.sqrt(), forward, k, 1.0, 1.0, OptionType::Call).unwrap();
+                // A rounding of the price moves the volatility by its relative size over the
+                // relative vega, which is large near the upper bound of the price.
+                let condition = beta / normalised_vega(x, s);
+                assert!((implied - s).abs() < 1e-12 * s + 1e-14 * condition, "{x} {s}: {implied}");
+            }
+        }
+    }
+
+    #[test]
+    fn prices_outside_the_no_arbitrage_bounds_have_no_volatility() {
+        let (forward, discount) = (100.0 * 0.03_f64.exp(), (-0.05_f64).exp());
+        assert_eq!(implied_volatility(0.0, forward, forward, 1.0, discount, OptionType::Call), Some(0.0));
+        assert_eq!(implied_volatility(0.0, forward, 110.0, 1.0, discount, OptionType::Call), Some(0.0));
+        assert_eq!(implied_volatility(-0.01, forward, 110.0, 1.0, discount, OptionType::Call), None);
+        assert_eq!(implied_volatility(forward * discount, forward, 90.0, 1.0, discount, OptionType::Call), None);
+        assert_eq!(implied_volatility(90.0 * discount, forward, 90.0, 1.0, discount, OptionType::Put), None);
+        assert_eq!(implied_volatility(1.0, forward, 90.0, 0.0, discount, OptionType::Put), None);
+    }
+
+    #[test]
+    fn chains_invert_like_single_quotes() {
+        let strikes = (0..200).map(|i| 60.0 + i as f64 * 0.5).collect::<Vec<_>>();
+        let expiries = (0..200).map(|i| 0.1 + (i % 7) as f64 * 0.3).collect::<Vec<_>>();
+        let types = (0..200).map(|i| out_of_the_money(strikes[i], expiries[i])).collect::<Vec<_>>();
+        let prices = (0..200).map(|i| black_scholes(strikes[i], expiries[i], 0.3, types[i])).collect::<Vec<_>>();
+        let implied = implied_volatilities(&prices, &strikes, &expiries, &types, 100.0, 0.05, 0.02);
+        for (i, &sigma) in implied.iter().enumerate() {
+            let forward = 100.0 * (0.03 * expiries[i]).exp();
+            let single = implied_volatility(prices[i], forward, strikes[i], expiries[i], (-0.05 * expiries[i]).exp(), types[i]);
+            assert_eq!(Some(sigma), single);
+            assert!((sigma - 0.3).abs() < 1e-10, "{i}: {sigma}");
+        }
+    }
+
+    #[test]
+    fn american_volatilities_round_trip() {
+        for (k, sigma) in [(80.0, 0.15), (100.0, 0.3), (130.0, 0.5)] {
+            let option = StockOption::new(100.0, k, 0.05, 1.0, 101, 0.02, sigma, OptionType::Put, ExerciseStyle::American).unwrap();
+            let engine = BinomialLROption::new(BinomialTreeOption::new(option));
+            let price = engine.price();
+            let implied = american_implied_volatility(&engine, price, 1e-10).unwrap();
+            assert!((implied - sigma).abs() < 1e-8, "{k} {sigma}: {implied}");
+        }
+    }
+}
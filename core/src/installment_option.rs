@@ -0,0 +1,151 @@
+// installment_option.rs
+
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::payoff::Payoff;
+
+/// Represents an installment option on a Cox-Ross-Rubinstein lattice.
+///
+/// Instead of paying the whole premium up front, the holder pays `installment` on each
+/// installment date and may stop paying at any of them, letting the option lapse. The
+/// option is therefore a chain of compound options: on an installment date the holder
+/// keeps the option only if its continuation value exceeds the installment. American
+/// installment options may also be exercised at any step, which ends the payments.
+pub struct InstallmentOption {
+    /// The underlying binomial tree option.
+    pub tree: BinomialTreeOption,
+    /// The amount paid on each installment date.
+    pub installment: f64,
+    /// The times (in years) of the installments after inception.
+    pub installment_dates: Vec<f64>,
+}
+
+impl InstallmentOption {
+    /// Creates a new `InstallmentOption` instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The binomial tree option representing the underlying asset and option parameters.
+    /// * `installment` - The amount paid on each installment date.
+    /// * `installment_dates` - The times (in years) of the installments after inception.
+    ///
+    /// # Returns
+    ///
+    /// A new `InstallmentOption` instance.
+    pub fn new(tree: BinomialTreeOption, installment: f64, installment_dates: Vec<f64>) -> Self {
+        InstallmentOption {
+            tree,
+            installment,
+            installment_dates,
+        }
+    }
+
+    /// Calculates the up-front premium of the installment option.
+    ///
+    /// Installment dates are mapped to the nearest step of the tree; dates at inception
+    /// or outside the life of the option are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The value of the option at inception, net of the future installments.
+    pub fn price(&mut self) -> f64 {
+        self.tree.setup_crr_parameters();
+        self.tree.init_stock_price_tree();
+
+        let option = &self.tree.option;
+        let n = option.n;
+        let (qu, qd, discount) = (self.tree.qu, self.tree.qd, option.df());
+        let mut payment_at = vec![0.0; n + 1];
+        for &date in &self.installment_dates {
+            let step = (date / option.dt()).round();
+            if (1.0..=n as f64).contains(&step) {
+                payment_at[step as usize] = self.installment;
+            }
+        }
+        let exercise_value = |s: f64| option.payoff.value(s, option.k, option.option_type);
+        let schedule = option.exercise_schedule();
+
+        let mut values = option.sts[n]
+            .iter()
+            .map(|&s| (exercise_value(s) - payment_at[n]).max(0.0))
+            .collect::<Vec<_>>();
+        for i in (0..n).rev() {
+            values = values
+                .windows(2)
+                .zip(&option.sts[i])
+                .map(|(w, &s)| {
+                    let continuation = (w[0] * qu + w[1] * qd) * discount - payment_at[i];
+                    let value = if schedule[i] {
+                        continuation.max(exercise_value(s))
+                    } else {
+                        continuation
+                    };
+                    if payment_at[i] > 0.0 {
+                        value.max(0.0)
+                    } else {
+                        value
+                    }
+                })
+                .collect();
+        }
+        values[0]
+    }
+
+    /// Calculates the fair installment when the first installment is paid at inception.
+    ///
+    /// The fair installment `c` makes the up-front premium equal to one installment, so
+    /// the holder pays the same amount at inception and on every installment date. The
+    /// premium net of the first payment decreases in `c`, and the root is found by
+    /// bisection on `[0, P(0)]`, where `P(0)` is the price without installments.
+    ///
+    /// # Returns
+    ///
+    /// The fair installment.
+    pub fn fair_installment(&mut self) -> f64 {
+        let original = self.installment;
+        let mut lower = 0.0;
+        self.installment = 0.0;
+        let mut upper = self.price();
+        for _ in 0..60 {
+            let middle = (lower + upper) / 2.0;
+            self.installment = middle;
+            if self.price() > middle {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        self.installment = original;
+        (lower + upper) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binomial_crr_option::BinomialCRROption;
+    use crate::option_type::{ExerciseStyle, OptionType};
+    use crate::stock_option::StockOption;
+
+    fn tree(exercise: ExerciseStyle) -> BinomialTreeOption {
+        BinomialTreeOption::new(StockOption::new(100.0, 100.0, 0.05, 1.0, 100, 0.0, 0.25, OptionType::Put, exercise).unwrap())
+    }
+
+    #[test]
+    fn without_installments_is_the_vanilla_option() {
+        for exercise in [ExerciseStyle::European, ExerciseStyle::American] {
+            let vanilla = BinomialCRROption::new(tree(exercise)).price();
+            let installment = InstallmentOption::new(tree(exercise), 1.0, Vec::new()).price();
+            assert!((installment - vanilla).abs() < 1e-10, "{exercise:?}: {installment} vs {vanilla}");
+        }
+    }
+
+    #[test]
+    fn fair_installment_equals_the_up_front_premium() {
+        let mut option = InstallmentOption::new(tree(ExerciseStyle::European), 0.0, vec![0.25, 0.5, 0.75]);
+        let fair = option.fair_installment();
+        option.installment = fair;
+        let premium = option.price();
+        assert!(fair > 0.0);
+        assert!((premium - fair).abs() < 1e-9, "{premium} vs {fair}");
+    }
+}
@@ -0,0 +1,74 @@
+// lib.rs
+
+//! Option pricing models in pure Rust: binomial trees, finite differences, closed forms,
+//! Monte Carlo simulation, and the volatility and market data tools around them.
+//!
+//! The crate has no Python dependency. The `numerical_options_rs` crate wraps it as the
+//! `libnumerical_options_rs` Python module.
+
+#![allow(clippy::too_many_arguments)]
+
+pub mod stock_option;
+pub mod binomial_tree_option;
+pub mod price_tree;
+pub mod binomial_lr_option;
+pub mod binomial_crr_option;
+pub mod binomial_lr_with_greeks;
+pub mod binomial_lr_with_ad;
+pub mod trinomial_tree_option;
+pub mod dual;
+pub mod numerics;
+pub mod random;
+pub mod garch_option;
+pub mod two_asset_option;
+pub mod two_asset_binomial_tree;
+pub mod fd_grid;
+pub mod finite_difference_option;
+pub mod adi_solver;
+pub mod employee_stock_option;
+pub mod forward_shooting_grid;
+pub mod barrier;
+pub mod black_scholes_option;
+pub mod greeks;
+pub mod asian_option;
+pub mod payoff;
+pub mod payoff_expression;
+pub mod forward_start_option;
+pub mod shout_option;
+pub mod convertible_bond;
+pub mod swing_option;
+pub mod installment_option;
+pub mod spread_option;
+pub mod exchange_option;
+pub mod basket_option;
+pub mod quanto_option;
+pub mod fx_option;
+pub mod rainbow_option;
+pub mod touch_option;
+pub mod option_strategy;
+pub mod real_option;
+pub mod pricing_engine;
+pub mod implied_volatility;
+pub mod vol_surface;
+pub mod svi;
+pub mod sabr;
+pub mod heston;
+pub mod put_call_parity;
+pub mod arbitrage;
+pub mod implied_density;
+pub mod yield_curve;
+pub mod dividend_schedule;
+pub mod vol_term_structure;
+pub mod day_count;
+pub mod trading_calendar;
+pub mod market_snapshot;
+pub mod option_chain;
+pub mod realized_vol;
+pub mod corporate_action;
+pub mod batch_pricing;
+pub mod precision;
+pub mod adaptive_steps;
+pub mod worker_threads;
+pub mod workspace;
+pub mod option_type;
+pub mod pricing_error;
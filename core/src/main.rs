@@ -0,0 +1,118 @@
+// main.rs
+
+//! Prices one option from the command line with any engine of the library.
+//!
+//! ```text
+//! numerical-options --spot 100 --strike 95 --expiry 1 --volatility 0.25 --rate 0.05 \
+//!     --type put --american --engine lr --steps 501
+//! ```
+
+use std::env;
+use std::error::Error;
+use std::process;
+
+use numerical_options_core::greeks::GreeksConfig;
+use numerical_options_core::option_type::{ExerciseStyle, OptionType};
+use numerical_options_core::pricing_engine::engine_by_name;
+use numerical_options_core::stock_option::StockOption;
+
+const USAGE: &str = "\
+Usage: numerical-options --spot S --strike K --expiry T --volatility SIGMA [options]
+
+Options:
+  --rate R            The risk-free interest rate. Defaults to 0.
+  --dividend-yield Q  The continuous dividend yield. Defaults to 0.
+  --type TYPE         \"call\" (the default) or \"put\".
+  --american          Allow early exercise.
+  --engine ENGINE     \"tree\", \"crr\", \"lr\" (the default), \"trinomial\", \"fd\", or \"analytic\".
+  --pu U, --pd D      The up and down moves of the \"tree\" engine, as fractions of the price.
+  --steps N           The number of time steps. Defaults to 501.
+  --space-steps M     The number of spatial nodes of the \"fd\" engine. Defaults to 201.
+  --help              Print this message.";
+
+fn main() {
+    if let Err(error) = run(env::args().skip(1)) {
+        eprintln!("error: {error}\n\n{USAGE}");
+        process::exit(2);
+    }
+}
+
+/// Parses the arguments, prices the option, and prints its price, Greeks, and engine.
+///
+/// # Arguments
+///
+/// * `args` - The command-line arguments, without the name of the program.
+///
+/// # Errors
+///
+/// Returns an error if an argument is unknown or malformed, or if the engine rejects the
+/// option.
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut builder = StockOption::builder().steps(501).option_type(OptionType::Call);
+    let mut engine = "lr".to_string();
+    let mut space_steps = 201;
+    let mut moves = (0.0, 0.0);
+    while let Some(flag) = args.next() {
+        if flag == "--help" {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        if flag == "--american" {
+            builder = builder.exercise(ExerciseStyle::American);
+            continue;
+        }
+        let value = args.next().ok_or_else(|| format!("{flag} needs a value."))?;
+        builder = match flag.as_str() {
+            "--spot" => builder.spot(value.parse()?),
+            "--strike" => builder.strike(value.parse()?),
+            "--expiry" => builder.expiry(value.parse()?),
+            "--volatility" => builder.volatility(value.parse()?),
+            "--rate" => builder.rate(value.parse()?),
+            "--dividend-yield" => builder.dividend_yield(value.parse()?),
+            "--steps" => builder.steps(value.parse()?),
+            "--type" => match value.as_str() {
+                "call" => builder.option_type(OptionType::Call),
+                "put" => builder.option_type(OptionType::Put),
+                _ => return Err(format!("Invalid type {value}. Must be 'call' or 'put'.").into()),
+            },
+            "--engine" => {
+                engine = value;
+                builder
+            }
+            "--space-steps" => {
+                space_steps = value.parse()?;
+                builder
+            }
+            "--pu" => {
+                moves.0 = value.parse()?;
+                builder
+            }
+            "--pd" => {
+                moves.1 = value.parse()?;
+                builder
+            }
+            _ => return Err(format!("Unknown argument {flag}.").into()),
+        };
+    }
+
+    let option = builder.build()?;
+    let engine = engine_by_name(&engine, option, moves, space_steps)?;
+    let result = engine.greeks(GreeksConfig::default(), 0.01)?;
+    let diagnostics = result.diagnostics.unwrap_or_else(|| engine.diagnostics());
+
+    println!("price  {:.6}", result.price);
+    println!("delta  {:.6}", result.greeks.delta);
+    println!("gamma  {:.6}", result.greeks.gamma);
+    println!("theta  {:.6}", result.greeks.theta);
+    println!("vega   {:.6}", result.greeks.vega);
+    println!("rho    {:.6}", result.greeks.rho);
+    print!("engine {} with {} time steps", diagnostics.engine, diagnostics.time_steps);
+    if diagnostics.space_steps > 0 {
+        print!(" and {} spatial nodes", diagnostics.space_steps);
+    }
+    if let Some((u, d, qu)) = diagnostics.tree_parameters {
+        print!(" (u = {u:.6}, d = {d:.6}, qu = {qu:.6})");
+    }
+    println!();
+    Ok(())
+}
@@ -0,0 +1,183 @@
+// market_snapshot.rs
+
+use crate::dividend_schedule::DividendSchedule;
+use crate::pricing_engine::PricingEngine;
+use crate::pricing_error::PricingError;
+use crate::workspace::Workspace;
+use crate::vol_surface::VolSurface;
+use crate::yield_curve::YieldCurve;
+
+/// Represents the market data used to price options on one underlying at one time.
+///
+/// With the `serde` feature a snapshot can be written to and read back from JSON, so that
+/// the inputs of a valuation can be stored next to its results and replayed later. The
+/// JSON mirrors the fields of the snapshot and of the market objects it holds, with
+/// enumerations written as the names of their variants.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketSnapshot {
+    /// The time at which the data was observed, as an ISO 8601 timestamp.
+    pub as_of: String,
+    /// The spot price of the underlying asset.
+    pub spot: f64,
+    /// The term structure of interest rates.
+    pub curve: YieldCurve,
+    /// The optional schedule of discrete dividends.
+    pub dividends: Option<DividendSchedule>,
+    /// The implied volatility surface.
+    pub surface: VolSurface,
+}
+
+impl MarketSnapshot {
+    /// Creates a new `MarketSnapshot` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `as_of` - The time at which the data was observed, as an ISO 8601 timestamp.
+    /// * `spot` - The spot price of the underlying asset.
+    /// * `curve` - The term structure of interest rates.
+    /// * `dividends` - The optional schedule of discrete dividends.
+    /// * `surface` - The implied volatility surface.
+    ///
+    /// # Returns
+    ///
+    /// A new `MarketSnapshot` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the spot price is not positive and finite.
+    pub fn new(
+        as_of: String,
+        spot: f64,
+        curve: YieldCurve,
+        dividends: Option<DividendSchedule>,
+        surface: VolSurface,
+    ) -> Result<Self, PricingError> {
+        if !(spot > 0.0 && spot.is_finite()) {
+            return Err(PricingError::InvalidInput(format!("The spot must be positive, not {spot}.")));
+        }
+        Ok(MarketSnapshot { as_of, spot, curve, dividends, surface })
+    }
+
+    /// Prices an option with all of its market data taken from the snapshot.
+    ///
+    /// The engine prices a copy of its option whose stock price is the spot, whose rates
+    /// come from the curve as in `YieldCurve::price`, whose dividends come from the
+    /// schedule, and whose volatility is read from the surface at its strike and expiration.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The engine pricing the option.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the price of the option and the volatility read from the surface.
+    pub fn price<E: PricingEngine>(&self, engine: &E) -> (f64, f64) {
+        let mut option = engine.option().clone();
+        option.s0 = self.spot;
+        option.dividends = self.dividends.clone();
+        let sigma = self.surface.volatility(option.k, option.t);
+        option.sigma = sigma;
+        self.curve.apply(&mut option);
+        (engine.price_option(&option, &mut Workspace::default()), sigma)
+    }
+
+    /// Writes the snapshot as JSON.
+    ///
+    /// # Returns
+    ///
+    /// The compact JSON text of the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the snapshot cannot be serialized.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, PricingError> {
+        serde_json::to_string(self)
+            .map_err(|error| PricingError::InvalidInput(format!("Could not write the snapshot as JSON: {error}")))
+    }
+
+    /// Reads a snapshot from JSON written by `to_json`.
+    ///
+    /// The market data is checked by the constructors of the snapshot, the curve, the
+    /// dividend schedule, and the surface, so a hand-edited snapshot is rejected with the
+    /// same errors as the original inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The JSON text of the snapshot.
+    ///
+    /// # Returns
+    ///
+    /// The snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the text is not valid JSON, misses a field,
+    /// or holds invalid market data.
+    #[cfg(feature = "serde")]
+    pub fn from_json(text: &str) -> Result<Self, PricingError> {
+        let snapshot: MarketSnapshot = serde_json::from_str(text)
+            .map_err(|error| PricingError::InvalidInput(format!("Invalid market snapshot JSON: {error}")))?;
+        let dividends = snapshot.dividends.map(|schedule| DividendSchedule::new(schedule.dividends)).transpose()?;
+        MarketSnapshot::new(
+            snapshot.as_of,
+            snapshot.spot,
+            YieldCurve::new(snapshot.curve.tenors, snapshot.curve.zero_rates)?,
+            dividends,
+            snapshot.surface,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::dividend_schedule::{Dividend, DividendKind};
+    use crate::vol_surface::SmileInterpolation;
+
+    fn snapshot() -> MarketSnapshot {
+        let dividends = DividendSchedule::new(vec![
+            Dividend { time: 0.25, amount: 1.5, kind: DividendKind::Cash },
+            Dividend { time: 0.75, amount: 0.01, kind: DividendKind::Proportional },
+        ])
+        .unwrap();
+        MarketSnapshot::new(
+            "2024-03-15T16:00:00Z".to_string(),
+            101.25,
+            YieldCurve::new(vec![0.25, 1.0, 2.0], vec![0.031, 0.0405, 0.045]).unwrap(),
+            Some(dividends),
+            VolSurface::new(
+                vec![0.5, 1.0],
+                vec![90.0, 100.0, 110.0],
+                vec![vec![0.25, 0.21, 0.19], vec![0.24, 0.215, 0.2]],
+                SmileInterpolation::CubicSpline,
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn json_round_trip_restores_the_snapshot() {
+        let original = snapshot();
+        let restored = MarketSnapshot::from_json(&original.to_json().unwrap()).unwrap();
+        assert_eq!(restored, original);
+        assert_eq!(restored.surface.volatility(95.0, 0.7), original.surface.volatility(95.0, 0.7));
+
+        let without_dividends = MarketSnapshot { dividends: None, ..snapshot() };
+        let restored = MarketSnapshot::from_json(&without_dividends.to_json().unwrap()).unwrap();
+        assert_eq!(restored, without_dividends);
+    }
+
+    #[test]
+    fn from_json_rejects_malformed_text_and_invalid_market_data() {
+        let invalid = |text: &str| matches!(MarketSnapshot::from_json(text), Err(PricingError::InvalidInput(_)));
+        let json = snapshot().to_json().unwrap();
+        assert!(invalid(&json[..json.len() - 1]));
+        assert!(invalid(&json.replace("\"spot\":101.25", "\"spot\":-101.25")));
+        assert!(invalid(&json.replace("[0.25,1.0,2.0]", "[1.0,0.25,2.0]")));
+        assert!(invalid(&json.replace("[90.0,100.0,110.0]", "[90.0,100.0]")));
+        assert!(invalid(&json.replace("\"CubicSpline\"", "\"Quadratic\"")));
+    }
+}
@@ -0,0 +1,229 @@
+// option_chain.rs
+
+use crate::implied_volatility::implied_volatilities;
+use crate::option_type::OptionType;
+
+/// The flag of a quote that has not updated recently.
+pub const FLAG_STALE: u32 = 1;
+
+/// The flag of a quote on a non-standard contract, such as one adjusted for a corporate action.
+pub const FLAG_NON_STANDARD: u32 = 1 << 1;
+
+/// The flag of a quote on a series whose trading is halted.
+pub const FLAG_HALTED: u32 = 1 << 2;
+
+/// Represents the market quote of one listed option.
+#[derive(Clone, Copy)]
+pub struct OptionQuote {
+    /// The strike price of the option.
+    pub strike: f64,
+    /// The time to expiration of the option (in years).
+    pub expiry: f64,
+    /// Whether the option is a call or a put.
+    pub option_type: OptionType,
+    /// The bid price.
+    pub bid: f64,
+    /// The ask price.
+    pub ask: f64,
+    /// The number of contracts traded in the session.
+    pub volume: u64,
+    /// The number of contracts open.
+    pub open_interest: u64,
+    /// The bitwise union of the `FLAG_*` constants that apply to the quote.
+    pub flags: u32,
+}
+
+impl OptionQuote {
+    /// Calculates the mid price of the quote.
+    pub fn mid(&self) -> f64 {
+        0.5 * (self.bid + self.ask)
+    }
+
+    /// Calculates the bid-ask spread of the quote.
+    pub fn spread(&self) -> f64 {
+        self.ask - self.bid
+    }
+}
+
+/// Identifies the quotes of a chain that are liquid enough to use.
+pub struct LiquidityFilter {
+    /// The smallest volume kept.
+    pub min_volume: u64,
+    /// The smallest open interest kept.
+    pub min_open_interest: u64,
+    /// The largest bid-ask spread kept, relative to the mid price.
+    pub max_relative_spread: f64,
+    /// Whether quotes with any flag set are kept.
+    pub keep_flagged: bool,
+}
+
+/// Represents the quotes of the listed options on one underlying, across strikes and
+/// expirations.
+#[derive(Clone)]
+pub struct OptionChain {
+    /// The quotes of the chain.
+    pub quotes: Vec<OptionQuote>,
+}
+
+impl OptionChain {
+    /// Creates a new `OptionChain` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `quotes` - The quotes of the chain.
+    ///
+    /// # Returns
+    ///
+    /// A new `OptionChain` instance.
+    pub fn new(quotes: Vec<OptionQuote>) -> Self {
+        OptionChain { quotes }
+    }
+
+    /// Keeps the quotes whose strike lies within a range of moneyness.
+    ///
+    /// Moneyness is the strike divided by the forward price to the quote's expiration, so
+    /// that one range selects comparable strikes across expirations.
+    ///
+    /// # Arguments
+    ///
+    /// * `s0` - The initial stock price.
+    /// * `r` - The risk-free interest rate.
+    /// * `div` - The continuous dividend yield of the underlying asset.
+    /// * `min_moneyness` - The lowest moneyness `K / F` kept.
+    /// * `max_moneyness` - The highest moneyness `K / F` kept.
+    ///
+    /// # Returns
+    ///
+    /// The chain of the quotes within the range, in their original order.
+    pub fn filter_moneyness(&self, s0: f64, r: f64, div: f64, min_moneyness: f64, max_moneyness: f64) -> Self {
+        self.filter(|quote| {
+            let moneyness = quote.strike / (s0 * ((r - div) * quote.expiry).exp());
+            (min_moneyness..=max_moneyness).contains(&moneyness)
+        })
+    }
+
+    /// Keeps the quotes that are liquid enough to use.
+    ///
+    /// Quotes with a non-positive mid price or a crossed market (ask below bid) are always
+    /// dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `liquidity` - The thresholds a quote must meet.
+    ///
+    /// # Returns
+    ///
+    /// The chain of the liquid quotes, in their original order.
+    pub fn filter_liquidity(&self, liquidity: &LiquidityFilter) -> Self {
+        self.filter(|quote| {
+            quote.mid() > 0.0
+                && quote.spread() >= 0.0
+                && quote.volume >= liquidity.min_volume
+                && quote.open_interest >= liquidity.min_open_interest
+                && quote.spread() <= liquidity.max_relative_spread * quote.mid()
+                && (liquidity.keep_flagged || quote.flags == 0)
+        })
+    }
+
+    /// Keeps the quotes that satisfy a predicate.
+    fn filter<P: Fn(&OptionQuote) -> bool>(&self, predicate: P) -> Self {
+        OptionChain::new(self.quotes.iter().filter(|quote| predicate(quote)).copied().collect())
+    }
+
+    /// Returns the strike price of each quote.
+    pub fn strikes(&self) -> Vec<f64> {
+        self.quotes.iter().map(|quote| quote.strike).collect()
+    }
+
+    /// Returns the time to expiration of each quote.
+    pub fn expiries(&self) -> Vec<f64> {
+        self.quotes.iter().map(|quote| quote.expiry).collect()
+    }
+
+    /// Returns the mid price of each quote.
+    pub fn mids(&self) -> Vec<f64> {
+        self.quotes.iter().map(OptionQuote::mid).collect()
+    }
+
+    /// Returns the bid-ask spread of each quote.
+    pub fn spreads(&self) -> Vec<f64> {
+        self.quotes.iter().map(OptionQuote::spread).collect()
+    }
+
+    /// Returns whether each quote is a call or a put.
+    pub fn option_types(&self) -> Vec<OptionType> {
+        self.quotes.iter().map(|quote| quote.option_type).collect()
+    }
+
+    /// Calculates the Black-Scholes implied volatility of the mid price of each quote.
+    ///
+    /// # Arguments
+    ///
+    /// * `s0` - The initial stock price.
+    /// * `r` - The risk-free interest rate.
+    /// * `div` - The continuous dividend yield of the underlying asset.
+    ///
+    /// # Returns
+    ///
+    /// The implied volatility of each quote, or NaN where the mid price is outside the
+    /// no-arbitrage bounds.
+    pub fn implied_volatilities(&self, s0: f64, r: f64, div: f64) -> Vec<f64> {
+        implied_volatilities(&self.mids(), &self.strikes(), &self.expiries(), &self.option_types(), s0, r, div)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numerics::black_call;
+
+    fn quote(strike: f64, expiry: f64, bid: f64, ask: f64, volume: u64, flags: u32) -> OptionQuote {
+        OptionQuote { strike, expiry, option_type: OptionType::Call, bid, ask, volume, open_interest: 100, flags }
+    }
+
+    #[test]
+    fn filters_by_moneyness_against_the_forward() {
+        // With r − q = 0.1 the one-year forward is 110.52, so a strike of 120 is at 1.086.
+        let chain = OptionChain::new(vec![
+            quote(80.0, 1.0, 1.0, 1.1, 10, 0),
+            quote(100.0, 1.0, 1.0, 1.1, 10, 0),
+            quote(120.0, 1.0, 1.0, 1.1, 10, 0),
+            quote(120.0, 0.0, 1.0, 1.1, 10, 0),
+        ]);
+        let kept = chain.filter_moneyness(100.0, 0.1, 0.0, 0.85, 1.1);
+        assert_eq!(kept.strikes(), vec![100.0, 120.0]);
+        assert_eq!(kept.expiries(), vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn filters_by_liquidity_and_flags() {
+        let chain = OptionChain::new(vec![
+            quote(100.0, 1.0, 2.0, 2.1, 10, 0),
+            quote(105.0, 1.0, 2.0, 2.1, 1, 0),
+            quote(110.0, 1.0, 2.0, 3.0, 10, 0),
+            quote(115.0, 1.0, 2.1, 2.0, 10, 0),
+            quote(120.0, 1.0, 2.0, 2.1, 10, FLAG_STALE | FLAG_HALTED),
+        ]);
+        let mut liquidity = LiquidityFilter { min_volume: 5, min_open_interest: 50, max_relative_spread: 0.1, keep_flagged: false };
+        assert_eq!(chain.filter_liquidity(&liquidity).strikes(), vec![100.0]);
+        liquidity.keep_flagged = true;
+        assert_eq!(chain.filter_liquidity(&liquidity).strikes(), vec![100.0, 120.0]);
+    }
+
+    #[test]
+    fn implied_volatilities_recover_the_mid_prices() {
+        let (s0, r, t): (f64, f64, f64) = (100.0, 0.05, 0.5);
+        let forward = s0 * (r * t).exp();
+        let quotes = [(90.0, 0.22), (100.0, 0.2), (110.0, 0.19)]
+            .iter()
+            .map(|&(k, vol)| {
+                let mid = (-r * t).exp() * black_call(forward, k, vol * vol * t);
+                quote(k, t, mid - 0.05, mid + 0.05, 10, 0)
+            })
+            .collect();
+        let vols = OptionChain::new(quotes).implied_volatilities(s0, r, 0.0);
+        for (vol, expected) in vols.iter().zip([0.22, 0.2, 0.19]) {
+            assert!((vol - expected).abs() < 1e-8, "{vol} vs {expected}");
+        }
+    }
+}
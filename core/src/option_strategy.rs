@@ -0,0 +1,200 @@
+// option_strategy.rs
+
+use crate::binomial_lr_option::BinomialLROption;
+use crate::binomial_lr_with_greeks::BinomialLRWithGreeks;
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::greeks::Greeks;
+use crate::option_type::OptionType;
+use crate::pricing_engine::PricingResult;
+use crate::pricing_error::PricingError;
+use crate::stock_option::StockOption;
+
+/// Identifies the instrument held in one leg of a strategy.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Instrument {
+    /// A call option.
+    Call,
+    /// A put option.
+    Put,
+    /// The underlying asset itself; the strike of the leg is ignored.
+    Underlying,
+}
+
+impl From<OptionType> for Instrument {
+    fn from(option_type: OptionType) -> Self {
+        match option_type {
+            OptionType::Call => Instrument::Call,
+            OptionType::Put => Instrument::Put,
+        }
+    }
+}
+
+/// Represents one leg of a strategy.
+#[derive(Clone, Copy)]
+pub struct Leg {
+    /// The instrument held.
+    pub instrument: Instrument,
+    /// The strike price of an option leg.
+    pub k: f64,
+    /// The number of units held; negative quantities are short positions.
+    pub quantity: f64,
+}
+
+impl Leg {
+    /// Creates a new `Leg` with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `instrument` - The instrument held.
+    /// * `k` - The strike price of an option leg.
+    /// * `quantity` - The number of units held; negative quantities are short positions.
+    pub fn new(instrument: Instrument, k: f64, quantity: f64) -> Self {
+        Leg { instrument, k, quantity }
+    }
+}
+
+/// Represents a static portfolio of options on the same underlying and expiration.
+///
+/// All legs share the market parameters and exercise style, so the price and Greeks
+/// of the strategy are the quantity-weighted sums of those of its legs.
+pub struct OptionStrategy {
+    /// The legs of the strategy.
+    pub legs: Vec<Leg>,
+}
+
+impl OptionStrategy {
+    /// Creates a new `OptionStrategy` from its legs.
+    ///
+    /// # Arguments
+    ///
+    /// * `legs` - The legs of the strategy.
+    ///
+    /// # Returns
+    ///
+    /// A new `OptionStrategy` instance.
+    pub fn new(legs: Vec<Leg>) -> Self {
+        OptionStrategy { legs }
+    }
+
+    /// Creates a vertical spread: a bull call spread (long the lower strike, short the
+    /// upper) or a bear put spread (long the upper strike, short the lower).
+    ///
+    /// A bull call spread is also known as a capped call, since its payoff is that of a
+    /// call on the lower strike capped at `upper − lower`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lower strike price.
+    /// * `upper` - The upper strike price.
+    /// * `option_type` - Whether the legs are calls or puts.
+    pub fn vertical_spread(lower: f64, upper: f64, option_type: OptionType) -> Self {
+        let legs = match option_type {
+            OptionType::Call => vec![Leg::new(Instrument::Call, lower, 1.0), Leg::new(Instrument::Call, upper, -1.0)],
+            OptionType::Put => vec![Leg::new(Instrument::Put, upper, 1.0), Leg::new(Instrument::Put, lower, -1.0)],
+        };
+        OptionStrategy::new(legs)
+    }
+
+    /// Creates a collar: long the underlying, long a protective put, and short a call
+    /// that finances it.
+    ///
+    /// # Arguments
+    ///
+    /// * `put_strike` - The strike price of the long put.
+    /// * `call_strike` - The strike price of the short call.
+    pub fn collar(put_strike: f64, call_strike: f64) -> Self {
+        OptionStrategy::new(vec![
+            Leg::new(Instrument::Underlying, 0.0, 1.0),
+            Leg::new(Instrument::Put, put_strike, 1.0),
+            Leg::new(Instrument::Call, call_strike, -1.0),
+        ])
+    }
+
+    /// Creates a long straddle: a call and a put with the same strike.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The common strike price.
+    pub fn straddle(k: f64) -> Self {
+        OptionStrategy::strangle(k, k)
+    }
+
+    /// Creates a long strangle: a put on the lower strike and a call on the upper strike.
+    ///
+    /// # Arguments
+    ///
+    /// * `put_strike` - The strike price of the put.
+    /// * `call_strike` - The strike price of the call.
+    pub fn strangle(put_strike: f64, call_strike: f64) -> Self {
+        OptionStrategy::new(vec![
+            Leg::new(Instrument::Put, put_strike, 1.0),
+            Leg::new(Instrument::Call, call_strike, 1.0),
+        ])
+    }
+
+    /// Creates a long butterfly: long one option on each wing and short two on the body.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lower strike price.
+    /// * `middle` - The middle strike price.
+    /// * `upper` - The upper strike price.
+    /// * `option_type` - Whether the legs are calls or puts.
+    pub fn butterfly(lower: f64, middle: f64, upper: f64, option_type: OptionType) -> Self {
+        let instrument = Instrument::from(option_type);
+        OptionStrategy::new(vec![
+            Leg::new(instrument, lower, 1.0),
+            Leg::new(instrument, middle, -2.0),
+            Leg::new(instrument, upper, 1.0),
+        ])
+    }
+
+    /// Calculates the price and Greeks of the strategy with the binomial LR model.
+    ///
+    /// Each option leg is priced on its own Leisen-Reimer tree, whose nodes are centred on
+    /// the strike of the leg. The underlying leg is worth the initial stock price and has a
+    /// delta of one and no other Greeks.
+    ///
+    /// # Arguments
+    ///
+    /// * `s0` - The initial stock price, which is the value of an underlying leg.
+    /// * `option_for` - Builds the stock option of a leg from its strike and whether it is
+    ///   a call or a put.
+    ///
+    /// # Returns
+    ///
+    /// The price and Greeks of the strategy, as for `BinomialLRWithGreeks::price`, without
+    /// diagnostics since each leg has its own tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of `option_for`.
+    pub fn price_and_greeks<F>(&self, s0: f64, option_for: F) -> Result<PricingResult, PricingError>
+    where
+        F: Fn(f64, OptionType) -> Result<StockOption, PricingError>,
+    {
+        let mut total = [0.0; 6];
+        for leg in &self.legs {
+            let greeks = match leg.instrument {
+                Instrument::Underlying => [s0, 1.0, 0.0, 0.0, 0.0, 0.0],
+                Instrument::Call | Instrument::Put => {
+                    let option_type = match leg.instrument {
+                        Instrument::Put => OptionType::Put,
+                        _ => OptionType::Call,
+                    };
+                    let stock_option = option_for(leg.k, option_type)?;
+                    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+                    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+                    let result = BinomialLRWithGreeks::new(binomial_lr_option).price();
+                    let Greeks { delta, gamma, theta, vega, rho } = result.greeks;
+                    [result.price, delta, gamma, theta, vega, rho]
+                }
+            };
+            for (sum, value) in total.iter_mut().zip(greeks) {
+                *sum += leg.quantity * value;
+            }
+        }
+        let [price, delta, gamma, theta, vega, rho] = total;
+        Ok(PricingResult { price, greeks: Greeks { delta, gamma, theta, vega, rho }, diagnostics: None })
+    }
+}
@@ -0,0 +1,35 @@
+// option_type.rs
+
+/// Identifies whether an option gives the right to buy or to sell the underlying asset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OptionType {
+    /// The right to buy the underlying asset at the strike price.
+    Call,
+    /// The right to sell the underlying asset at the strike price.
+    Put,
+}
+
+impl OptionType {
+    /// Returns the sign `φ` of the payoff `φ(S − K)`.
+    ///
+    /// # Returns
+    ///
+    /// 1 for a call and −1 for a put.
+    pub fn sign(self) -> f64 {
+        match self {
+            OptionType::Call => 1.0,
+            OptionType::Put => -1.0,
+        }
+    }
+}
+
+/// Identifies when an option may be exercised.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExerciseStyle {
+    /// Exercise only at expiration.
+    European,
+    /// Exercise at any time up to expiration.
+    American,
+}
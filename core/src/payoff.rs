@@ -0,0 +1,183 @@
+// payoff.rs
+
+use crate::option_type::OptionType;
+use std::fmt;
+use std::sync::Arc;
+
+/// Calculates the payoff of a single-asset option at exercise.
+///
+/// The lattice and finite-difference engines evaluate the payoff through this trait at
+/// the terminal nodes and wherever early exercise is allowed, so any implementation can
+/// be priced with them by wrapping it in `PayoffKind::Custom`.
+pub trait Payoff {
+    /// Calculates the payoff at a given stock price.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The stock price.
+    /// * `k` - The strike price of the option.
+    /// * `option_type` - Whether the option is a call or a put.
+    ///
+    /// # Returns
+    ///
+    /// The payoff of the option.
+    fn value(&self, s: f64, k: f64, option_type: OptionType) -> f64;
+
+    /// Calculates the payoff averaged over a log-price cell centered on a stock price.
+    ///
+    /// Lattices use this for the terminal payoffs. The default implementation does no
+    /// averaging, which suits continuous payoffs.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The stock price at the center of the cell.
+    /// * `k` - The strike price of the option.
+    /// * `option_type` - Whether the option is a call or a put.
+    /// * `half_width` - The half-width of the cell in log-price.
+    ///
+    /// # Returns
+    ///
+    /// The smoothed payoff of the option.
+    fn smoothed_value(&self, s: f64, k: f64, option_type: OptionType, half_width: f64) -> f64 {
+        let _ = half_width;
+        self.value(s, k, option_type)
+    }
+}
+
+/// A payoff given by a closure of the stock price, ignoring the strike and option type.
+pub struct ClosurePayoff<F: Fn(f64) -> f64>(pub F);
+
+impl<F: Fn(f64) -> f64> Payoff for ClosurePayoff<F> {
+    fn value(&self, s: f64, _k: f64, _option_type: OptionType) -> f64 {
+        (self.0)(s)
+    }
+}
+
+/// Identifies the payoff of a single-asset option at exercise.
+///
+/// A `Custom` payoff is compared by identity and cannot be serialized.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PayoffKind {
+    /// The standard payoff: `max(S − K, 0)` for calls and `max(K − S, 0)` for puts.
+    Vanilla,
+    /// Pays a fixed amount of cash if the option finishes in the money.
+    CashOrNothing { cash: f64 },
+    /// Pays the stock price if the option finishes in the money.
+    AssetOrNothing,
+    /// Pays `φ(S − payout_strike)` if the option finishes in the money with respect to
+    /// the trigger strike `K`, which may make the payoff negative.
+    Gap { payout_strike: f64 },
+    /// The asymmetric power payoff: `max(S^p − K, 0)` for calls and `max(K − S^p, 0)` for puts.
+    Power { exponent: f64 },
+    /// A user-defined payoff.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn Payoff + Send + Sync>),
+}
+
+/// Shows a user-defined payoff by its address, since its implementation is opaque.
+impl fmt::Debug for dyn Payoff + Send + Sync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Payoff({:p})", self)
+    }
+}
+
+/// Compares user-defined payoffs by identity, since their implementations are opaque.
+impl PartialEq for dyn Payoff + Send + Sync {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+impl Payoff for PayoffKind {
+    fn value(&self, s: f64, k: f64, option_type: OptionType) -> f64 {
+        let in_the_money = match option_type {
+            OptionType::Call => s > k,
+            OptionType::Put => s < k,
+        };
+        match (self, option_type) {
+            (PayoffKind::Vanilla, OptionType::Call) => (s - k).max(0.0),
+            (PayoffKind::Vanilla, OptionType::Put) => (k - s).max(0.0),
+            (&PayoffKind::CashOrNothing { cash }, _) if in_the_money => cash,
+            (PayoffKind::AssetOrNothing, _) if in_the_money => s,
+            (&PayoffKind::Gap { payout_strike }, OptionType::Call) if in_the_money => s - payout_strike,
+            (&PayoffKind::Gap { payout_strike }, OptionType::Put) if in_the_money => payout_strike - s,
+            (&PayoffKind::Power { exponent }, OptionType::Call) => (s.powf(exponent) - k).max(0.0),
+            (&PayoffKind::Power { exponent }, OptionType::Put) => (k - s.powf(exponent)).max(0.0),
+            (PayoffKind::Custom(payoff), _) => payoff.value(s, k, option_type),
+            _ => 0.0,
+        }
+    }
+
+    /// Discontinuous payoffs are averaged over `[ln s − h, ln s + h]`, which removes the
+    /// odd-even oscillation of lattice prices (and the spikes in lattice Greeks) caused
+    /// by the strike falling at different positions between nodes. Continuous payoffs
+    /// are returned unchanged, and custom payoffs are smoothed by their own implementation.
+    fn smoothed_value(&self, s: f64, k: f64, option_type: OptionType, half_width: f64) -> f64 {
+        if let PayoffKind::Custom(payoff) = self {
+            return payoff.smoothed_value(s, k, option_type, half_width);
+        }
+        let continuous = matches!(self, PayoffKind::Vanilla | PayoffKind::Power { .. });
+        if continuous || half_width <= 0.0 {
+            return self.value(s, k, option_type);
+        }
+
+        // The in-the-money part of the cell, in log-price.
+        let (lower, upper) = ((s.ln() - half_width), (s.ln() + half_width));
+        let (from, to) = match option_type {
+            OptionType::Call => (lower.max(k.ln()), upper),
+            OptionType::Put => (lower, upper.min(k.ln())),
+        };
+        if from >= to {
+            return 0.0;
+        }
+
+        let probability = (to - from) / (upper - lower);
+        // Weighted so that a cell lying entirely in the money pays exactly `s`.
+        let asset = s * (to.exp() - from.exp()) / (upper.exp() - lower.exp());
+        match (self, option_type) {
+            (&PayoffKind::CashOrNothing { cash }, _) => cash * probability,
+            (&PayoffKind::Gap { payout_strike }, OptionType::Call) => asset - payout_strike * probability,
+            (&PayoffKind::Gap { payout_strike }, OptionType::Put) => payout_strike * probability - asset,
+            _ => asset,
+        }
+    }
+}
+
+impl PayoffKind {
+    /// Bounds the payoff over an interval of stock prices.
+    ///
+    /// Every built-in payoff is monotone on either side of its trigger, the strike or, for
+    /// a power payoff, the stock price whose power is the strike. Its extremes over the
+    /// interval are therefore among its values at the ends of the interval and on either
+    /// side of the trigger.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lowest stock price of the interval.
+    /// * `upper` - The highest stock price of the interval.
+    /// * `k` - The strike price of the option.
+    /// * `option_type` - Whether the option is a call or a put.
+    ///
+    /// # Returns
+    ///
+    /// The smallest and largest payoff over the interval, or `None` for a custom payoff,
+    /// whose shape is unknown.
+    pub fn bounds(&self, lower: f64, upper: f64, k: f64, option_type: OptionType) -> Option<(f64, f64)> {
+        let trigger = match self {
+            PayoffKind::Custom(_) => return None,
+            &PayoffKind::Power { exponent } if k > 0.0 => Some(k.powf(1.0 / exponent)),
+            PayoffKind::Power { .. } => None,
+            _ => Some(k),
+        };
+        let mut prices = vec![lower, upper];
+        if let Some(trigger) = trigger.filter(|&trigger| lower < trigger && trigger < upper) {
+            let nudge = trigger * 4.0 * f64::EPSILON;
+            prices.extend([trigger - nudge, trigger, trigger + nudge]);
+        }
+        let values = prices.into_iter().map(|s| self.value(s.clamp(lower, upper), k, option_type));
+        Some(values.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| {
+            (low.min(value), high.max(value))
+        }))
+    }
+}
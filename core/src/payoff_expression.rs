@@ -0,0 +1,453 @@
+// payoff_expression.rs
+
+use std::collections::HashMap;
+
+use crate::option_type::OptionType;
+use crate::payoff::Payoff;
+
+/// The deepest nesting of operators, parentheses, and function calls that a payoff
+/// expression may have, which keeps parsing and evaluation well within the stack.
+const MAX_DEPTH: usize = 256;
+
+/// Represents a node of a parsed payoff expression.
+enum Expr {
+    /// A numeric literal or a named parameter resolved at parse time.
+    Constant(f64),
+    /// The stock price `S`.
+    Spot,
+    /// The strike price `K` of the option.
+    Strike,
+    /// The negation of an expression.
+    Negate(Box<Expr>),
+    /// A binary operator applied to two expressions.
+    Binary(Operator, Box<Expr>, Box<Expr>),
+    /// A built-in function applied to its arguments.
+    Call(Function, Vec<Expr>),
+}
+
+/// Identifies the binary operators of the payoff language.
+#[derive(Clone, Copy)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+}
+
+/// Identifies the built-in functions of the payoff language.
+#[derive(Clone, Copy)]
+enum Function {
+    Max,
+    Min,
+    Abs,
+    Exp,
+    Ln,
+    Sqrt,
+}
+
+impl Function {
+    /// Looks up a function by name, returning it with its number of arguments
+    /// (`None` for variadic functions).
+    fn lookup(name: &str) -> Option<(Function, Option<usize>)> {
+        match name {
+            "max" => Some((Function::Max, None)),
+            "min" => Some((Function::Min, None)),
+            "abs" => Some((Function::Abs, Some(1))),
+            "exp" => Some((Function::Exp, Some(1))),
+            "ln" | "log" => Some((Function::Ln, Some(1))),
+            "sqrt" => Some((Function::Sqrt, Some(1))),
+            _ => None,
+        }
+    }
+}
+
+impl Expr {
+    /// Evaluates the expression at a given stock price and strike.
+    fn evaluate(&self, s: f64, k: f64) -> f64 {
+        match self {
+            Expr::Constant(value) => *value,
+            Expr::Spot => s,
+            Expr::Strike => k,
+            Expr::Negate(operand) => -operand.evaluate(s, k),
+            Expr::Binary(operator, left, right) => {
+                let (a, b) = (left.evaluate(s, k), right.evaluate(s, k));
+                match operator {
+                    Operator::Add => a + b,
+                    Operator::Subtract => a - b,
+                    Operator::Multiply => a * b,
+                    Operator::Divide => a / b,
+                    Operator::Power => a.powf(b),
+                    Operator::Less => f64::from(u8::from(a < b)),
+                    Operator::Greater => f64::from(u8::from(a > b)),
+                    Operator::LessEqual => f64::from(u8::from(a <= b)),
+                    Operator::GreaterEqual => f64::from(u8::from(a >= b)),
+                }
+            }
+            Expr::Call(function, arguments) => {
+                let mut values = arguments.iter().map(|argument| argument.evaluate(s, k));
+                match function {
+                    Function::Max => values.fold(f64::NEG_INFINITY, f64::max),
+                    Function::Min => values.fold(f64::INFINITY, f64::min),
+                    Function::Abs => values.next().unwrap_or(f64::NAN).abs(),
+                    Function::Exp => values.next().unwrap_or(f64::NAN).exp(),
+                    Function::Ln => values.next().unwrap_or(f64::NAN).ln(),
+                    Function::Sqrt => values.next().unwrap_or(f64::NAN).sqrt(),
+                }
+            }
+        }
+    }
+}
+
+/// Represents a payoff written as an arithmetic expression of the stock price.
+///
+/// The language supports numbers, the stock price `S`, the strike `K`, named parameters,
+/// the operators `+ - * / ^` with the usual precedence, the comparisons `< > <= >=`
+/// (which evaluate to 1 or 0, so `10 * (S > K)` is a digital call), parentheses, and
+/// the functions `max`, `min`, `abs`, `exp`, `ln` (or `log`), and `sqrt`. For example,
+/// `max(S - K, 0) + 2 * max(K2 - S, 0)` with `K2` supplied as a parameter.
+///
+/// Expressions are parsed once into a tree, so evaluating them at each lattice node
+/// costs no more than a few arithmetic operations.
+pub struct PayoffExpression {
+    /// The root of the parsed expression.
+    root: Expr,
+}
+
+impl PayoffExpression {
+    /// Parses a payoff expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The text of the expression.
+    /// * `parameters` - The values of the named parameters used in the expression.
+    ///
+    /// # Returns
+    ///
+    /// The parsed expression, or a message describing the first syntax error or unknown
+    /// name, or that the expression is nested more than `MAX_DEPTH` levels deep.
+    pub fn parse(source: &str, parameters: &HashMap<String, f64>) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            depth: 0,
+            parameters,
+        };
+        let root = parser.comparison()?;
+        match parser.peek() {
+            None => Ok(PayoffExpression { root }),
+            Some(token) => Err(format!("Unexpected '{}' in payoff expression.", token)),
+        }
+    }
+}
+
+impl Payoff for PayoffExpression {
+    fn value(&self, s: f64, k: f64, _option_type: OptionType) -> f64 {
+        self.root.evaluate(s, k)
+    }
+}
+
+/// Represents a lexical token of a payoff expression.
+#[derive(Clone)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Symbol(&'static str),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "{}", value),
+            Token::Name(name) => write!(f, "{}", name),
+            Token::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+/// Splits a payoff expression into tokens.
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    const SYMBOLS: [&str; 13] = ["<=", ">=", "+", "-", "*", "/", "^", "(", ")", ",", "<", ">", "**"];
+    let mut tokens = Vec::new();
+    let mut rest = source.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let length = if c.is_ascii_digit() || c == '.' {
+            let mut end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            // Accept an exponent such as `1e-3`.
+            if rest[end..].starts_with(['e', 'E']) {
+                let exponent = &rest[end + 1..];
+                let sign = usize::from(exponent.starts_with(['+', '-']));
+                let digits = exponent[sign..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(exponent.len() - sign);
+                if digits > 0 {
+                    end += 1 + sign + digits;
+                }
+            }
+            let number = rest[..end]
+                .parse()
+                .map_err(|_| format!("Invalid number '{}' in payoff expression.", &rest[..end]))?;
+            tokens.push(Token::Number(number));
+            end
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Name(rest[..end].to_string()));
+            end
+        } else {
+            // Prefer the longest matching symbol, and read `**` as `^`.
+            let symbol = SYMBOLS
+                .iter()
+                .filter(|symbol| rest.starts_with(**symbol))
+                .max_by_key(|symbol| symbol.len())
+                .ok_or_else(|| format!("Unexpected character '{}' in payoff expression.", c))?;
+            tokens.push(Token::Symbol(if *symbol == "**" { "^" } else { symbol }));
+            symbol.len()
+        };
+        rest = rest[length..].trim_start();
+    }
+    Ok(tokens)
+}
+
+/// A recursive-descent parser over the tokens of a payoff expression.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    /// The number of operators, parentheses, and calls enclosing the current token.
+    depth: usize,
+    parameters: &'a HashMap<String, f64>,
+}
+
+impl Parser<'_> {
+    /// Returns the next token without consuming it.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    /// Consumes the next token if it is the given symbol.
+    fn accept(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(next)) if *next == symbol) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Enters one more level of nesting, or reports an error past `MAX_DEPTH`.
+    fn descend(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(format!("Payoff expression is nested more than {} levels deep.", MAX_DEPTH));
+        }
+        Ok(())
+    }
+
+    /// Consumes the given symbol or reports an error.
+    fn expect(&mut self, symbol: &str) -> Result<(), String> {
+        if self.accept(symbol) {
+            Ok(())
+        } else {
+            match self.peek() {
+                Some(token) => Err(format!("Expected '{}' but found '{}' in payoff expression.", symbol, token)),
+                None => Err(format!("Expected '{}' at the end of the payoff expression.", symbol)),
+            }
+        }
+    }
+
+    /// Parses `additive (comparison additive)?`.
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let left = self.additive()?;
+        let comparisons = [
+            ("<=", Operator::LessEqual),
+            (">=", Operator::GreaterEqual),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+        ];
+        for (symbol, operator) in comparisons {
+            if self.accept(symbol) {
+                let right = self.additive()?;
+                return Ok(Expr::Binary(operator, Box::new(left), Box::new(right)));
+            }
+        }
+        Ok(left)
+    }
+
+    /// Parses `term (('+' | '-') term)*`.
+    ///
+    /// Each operator nests the terms before it one level deeper in the tree, so it counts
+    /// towards the depth of the operands after it.
+    fn additive(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut left = self.term()?;
+        loop {
+            let operator = if self.accept("+") {
+                Operator::Add
+            } else if self.accept("-") {
+                Operator::Subtract
+            } else {
+                self.depth = depth;
+                return Ok(left);
+            };
+            self.descend()?;
+            left = Expr::Binary(operator, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    /// Parses `unary (('*' | '/') unary)*`, counting the depth as `additive` does.
+    fn term(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut left = self.unary()?;
+        loop {
+            let operator = if self.accept("*") {
+                Operator::Multiply
+            } else if self.accept("/") {
+                Operator::Divide
+            } else {
+                self.depth = depth;
+                return Ok(left);
+            };
+            self.descend()?;
+            left = Expr::Binary(operator, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    /// Parses `'-' unary | '+' unary | power`.
+    ///
+    /// Every cycle of the recursion passes through here, so this is where the depth of
+    /// nesting is checked.
+    fn unary(&mut self) -> Result<Expr, String> {
+        self.descend()?;
+        let expr = if self.accept("-") {
+            Expr::Negate(Box::new(self.unary()?))
+        } else if self.accept("+") {
+            self.unary()?
+        } else {
+            self.power()?
+        };
+        self.depth -= 1;
+        Ok(expr)
+    }
+
+    /// Parses `primary ('^' unary)?`, which makes `^` right-associative and binds it
+    /// more tightly than unary minus on its left (`-S^2` is `-(S^2)`).
+    fn power(&mut self) -> Result<Expr, String> {
+        let base = self.primary()?;
+        if self.accept("^") {
+            let exponent = self.unary()?;
+            return Ok(Expr::Binary(Operator::Power, Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    /// Parses a number, a name, a function call, or a parenthesized expression.
+    fn primary(&mut self) -> Result<Expr, String> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| "Unexpected end of the payoff expression.".to_string())?;
+        self.position += 1;
+        match token {
+            Token::Number(value) => Ok(Expr::Constant(value)),
+            Token::Symbol("(") => {
+                let inner = self.comparison()?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Token::Name(name) if self.accept("(") => {
+                let (function, arity) = Function::lookup(&name)
+                    .ok_or_else(|| format!("Unknown function '{}' in payoff expression.", name))?;
+                let mut arguments = vec![self.comparison()?];
+                while self.accept(",") {
+                    arguments.push(self.comparison()?);
+                }
+                self.expect(")")?;
+                match arity {
+                    Some(count) if count != arguments.len() => Err(format!(
+                        "Function '{}' takes {} argument(s) but was given {}.",
+                        name,
+                        count,
+                        arguments.len()
+                    )),
+                    _ => Ok(Expr::Call(function, arguments)),
+                }
+            }
+            Token::Name(name) => match name.as_str() {
+                "S" => Ok(Expr::Spot),
+                "K" => Ok(Expr::Strike),
+                _ => self
+                    .parameters
+                    .get(&name)
+                    .map(|&value| Expr::Constant(value))
+                    .ok_or_else(|| format!("Unknown parameter '{}' in payoff expression.", name)),
+            },
+            Token::Symbol(symbol) => Err(format!("Unexpected '{}' in payoff expression.", symbol)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str) -> Result<f64, String> {
+        let parameters = HashMap::from([("K2".to_string(), 120.0)]);
+        PayoffExpression::parse(source, &parameters).map(|payoff| payoff.value(110.0, 100.0, OptionType::Call))
+    }
+
+    #[test]
+    fn operators_follow_the_usual_precedence() {
+        assert_eq!(evaluate("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(evaluate("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(evaluate("8 - 4 - 2"), Ok(2.0));
+        assert_eq!(evaluate("8 / 4 / 2"), Ok(1.0));
+        assert_eq!(evaluate("2 * 3 ^ 2"), Ok(18.0));
+        assert_eq!(evaluate("-2 ^ 2"), Ok(-4.0));
+        assert_eq!(evaluate("1 + 2 < 4"), Ok(1.0));
+        assert_eq!(evaluate("max(S - K, 0) + 2 * max(K2 - S, 0)"), Ok(30.0));
+    }
+
+    #[test]
+    fn power_is_right_associative() {
+        assert_eq!(evaluate("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(evaluate("2 ** 3 ** 2"), Ok(512.0));
+        assert_eq!(evaluate("(2 ^ 3) ^ 2"), Ok(64.0));
+        assert_eq!(evaluate("2 ^ -1"), Ok(0.5));
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        assert_eq!(evaluate("S - X"), Err("Unknown parameter 'X' in payoff expression.".to_string()));
+        assert_eq!(evaluate("foo(S)"), Err("Unknown function 'foo' in payoff expression.".to_string()));
+        assert!(evaluate("exp(S, K)").is_err());
+    }
+
+    #[test]
+    fn trailing_tokens_are_rejected() {
+        assert_eq!(evaluate("S K"), Err("Unexpected 'K' in payoff expression.".to_string()));
+        assert_eq!(evaluate("(S - K))"), Err("Unexpected ')' in payoff expression.".to_string()));
+        assert!(evaluate("S -").is_err());
+        assert!(evaluate("(S - K").is_err());
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected_without_overflowing_the_stack() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(evaluate(&nested(100)), Ok(1.0));
+        assert!(evaluate(&nested(100_000)).unwrap_err().contains("nested more than 256 levels"));
+        assert_eq!(evaluate(&format!("{}1", "-".repeat(100))), Ok(1.0));
+        assert!(evaluate(&format!("{}1", "-".repeat(100_000))).is_err());
+        assert!(evaluate(&format!("{}1", "2^".repeat(100_000))).is_err());
+        assert!(evaluate(&format!("{}1", "max(".repeat(100_000))).is_err());
+        assert!(evaluate(&format!("1{}", "+1".repeat(100_000))).is_err());
+        assert_eq!(evaluate(&format!("1{}", "+1".repeat(100))), Ok(101.0));
+    }
+}
@@ -0,0 +1,76 @@
+// precision.rs
+
+use std::ops::{Add, Mul, Sub};
+
+/// Identifies the floating-point type in which a lattice is traversed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Precision {
+    /// Single precision (`f32`), which halves the memory traffic of backward induction but
+    /// keeps only about six significant digits of the price.
+    Single,
+    /// Double precision (`f64`).
+    Double,
+}
+
+/// A floating-point type in which backward induction can run.
+///
+/// The parameters of a tree are always calculated in `f64` and converted once, so only
+/// the node values and stock prices are held in the narrower type.
+pub trait Real: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    /// Converts an `f64`, rounding to the nearest value of the type.
+    fn from_f64(value: f64) -> Self;
+
+    /// Converts the value to an `f64` exactly.
+    fn to_f64(self) -> f64;
+
+    /// Returns the larger of two values.
+    fn max(self, other: Self) -> Self;
+
+    /// Replaces a subnormal value by zero.
+    ///
+    /// Arithmetic on subnormals is many times slower, and the values far out in the wings
+    /// of a single-precision tree reach them within a few hundred steps. Double precision
+    /// keeps them.
+    fn flush(self) -> Self;
+}
+
+impl Real for f32 {
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn max(self, other: Self) -> Self {
+        f32::max(self, other)
+    }
+
+    fn flush(self) -> Self {
+        if self.abs() < f32::MIN_POSITIVE {
+            0.0
+        } else {
+            self
+        }
+    }
+}
+
+impl Real for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn max(self, other: Self) -> Self {
+        f64::max(self, other)
+    }
+
+    fn flush(self) -> Self {
+        self
+    }
+}
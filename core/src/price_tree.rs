@@ -0,0 +1,188 @@
+// price_tree.rs
+
+use std::ops::Index;
+
+/// The bound on the magnitude of a log stock price in log-space growth.
+///
+/// Stock prices up to about `1e299` leave room for the payoffs and the sums of backward
+/// induction without overflowing, and the nodes clamped at the bound are reached with
+/// negligible probability.
+const LOG_PRICE_LIMIT: f64 = 690.0;
+
+/// Represents the stock prices at the nodes of a recombining binomial tree.
+///
+/// The levels are stored one after another in a single flat vector: level `i` holds
+/// `w + i` nodes, where `w` is the number of root nodes, and starts at offset
+/// `i w + i (i - 1) / 2`. Indexing the tree by a level yields the slice of its nodes, in
+/// decreasing order of stock price.
+///
+/// A tree may hold only its terminal level, for traversals that never read the stock
+/// prices before expiration; indexing an earlier level of such a tree panics.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriceTree {
+    /// The stock prices of the stored levels, level by level.
+    values: Vec<f64>,
+    /// The number of nodes at the root level.
+    root_width: usize,
+    /// The first level stored.
+    first_level: usize,
+}
+
+impl PriceTree {
+    /// Replaces the tree by the one grown from the root nodes over `n` steps, reusing
+    /// its storage.
+    ///
+    /// Each level multiplies every node of the level before it by the up factor and
+    /// appends its lowest node multiplied by the down factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The stock prices of the root nodes, in decreasing order.
+    /// * `n` - The number of time steps.
+    /// * `u` - The up factor.
+    /// * `d` - The down factor.
+    /// * `log_space` - Whether each node is computed from its log price, as by
+    ///   `log_space_price`, instead of by the products.
+    pub fn regrow(&mut self, root: &[f64], n: usize, u: f64, d: f64, log_space: bool) {
+        let root_width = root.len();
+        let values = &mut self.values;
+        values.clear();
+        values.reserve((n + 1) * root_width + n * (n + 1) / 2);
+        self.root_width = root_width;
+        self.first_level = 0;
+        if log_space {
+            for i in 0..=n {
+                values.extend((0..root_width + i).map(|j| log_space_price(root, i, j, u, d)));
+            }
+            return;
+        }
+        values.extend_from_slice(root);
+        let mut start = 0;
+        for i in 1..=n {
+            let width = root_width + i - 1;
+            for j in start..start + width {
+                values.push(values[j] * u);
+            }
+            values.push(values[start + width - 1] * d);
+            start += width;
+        }
+    }
+
+    /// Replaces the tree by the terminal level of the tree grown by `regrow`, reusing its
+    /// storage.
+    ///
+    /// The terminal level is grown in a single buffer with the same products as `regrow`,
+    /// so its stock prices are identical, while the levels before it are never stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The stock prices of the root nodes, in decreasing order.
+    /// * `n` - The number of time steps.
+    /// * `u` - The up factor.
+    /// * `d` - The down factor.
+    /// * `log_space` - Whether each node is computed from its log price, as by
+    ///   `log_space_price`, instead of by the products.
+    pub fn regrow_terminal(&mut self, root: &[f64], n: usize, u: f64, d: f64, log_space: bool) {
+        let root_width = root.len();
+        let values = &mut self.values;
+        values.clear();
+        values.reserve(root_width + n);
+        self.root_width = root_width;
+        self.first_level = n;
+        if log_space {
+            values.extend((0..root_width + n).map(|j| log_space_price(root, n, j, u, d)));
+            return;
+        }
+        values.extend_from_slice(root);
+        for _ in 0..n {
+            values.push(values[values.len() - 1] * d);
+            let width = values.len() - 1;
+            for value in &mut values[..width] {
+                *value *= u;
+            }
+        }
+    }
+
+    /// Returns the offset of the first node of a level.
+    fn offset(&self, i: usize) -> usize {
+        let start = |i: usize| i * self.root_width + i * i.saturating_sub(1) / 2;
+        start(i) - start(self.first_level)
+    }
+
+    /// Replaces the stock price of every node.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Maps the level of a node and its stock price to the new stock price.
+    pub fn map<F: Fn(usize, f64) -> f64>(&mut self, f: F) {
+        let mut i = self.first_level;
+        let mut end = self.root_width + i;
+        for (index, value) in self.values.iter_mut().enumerate() {
+            if index == end {
+                i += 1;
+                end += self.root_width + i;
+            }
+            *value = f(i, *value);
+        }
+    }
+}
+
+/// Calculates the stock price at a node of a tree grown from a single root, without
+/// storing the tree.
+///
+/// Traversals that visit only some of the nodes, such as the truncated one, use this in
+/// place of `regrow`. The price agrees with the stored tree up to rounding.
+///
+/// # Arguments
+///
+/// * `root` - The stock price at the root node.
+/// * `i` - The level of the node.
+/// * `j` - The index of the node within its level, counting down moves.
+/// * `u` - The up factor.
+/// * `d` - The down factor.
+/// * `log_space` - Whether the price is computed from its log price, as by `regrow`.
+///
+/// # Returns
+///
+/// The stock price at the node.
+pub fn node_price(root: f64, i: usize, j: usize, u: f64, d: f64, log_space: bool) -> f64 {
+    if log_space {
+        return log_space_price(&[root], i, j, u, d);
+    }
+    root * u.powi((i - j) as i32) * d.powi(j as i32)
+}
+
+/// Calculates the stock price at a node of a tree from its log price.
+///
+/// Node `j` of level `i` descends from root node `min(j, w - 1)`, where `w` is the number
+/// of root nodes, by `j - (w - 1)` down moves when `j` exceeds `w - 1` and up moves
+/// otherwise. Its log price is summed and clamped to `±LOG_PRICE_LIMIT` before the single
+/// exponentiation.
+///
+/// # Arguments
+///
+/// * `root` - The stock prices of the root nodes, in decreasing order.
+/// * `i` - The level of the node.
+/// * `j` - The index of the node within its level.
+/// * `u` - The up factor.
+/// * `d` - The down factor.
+///
+/// # Returns
+///
+/// The stock price at the node.
+fn log_space_price(root: &[f64], i: usize, j: usize, u: f64, d: f64) -> f64 {
+    let last = root.len() - 1;
+    let downs = j.saturating_sub(last);
+    let log_price = root[j.min(last)].ln() + (i - downs) as f64 * u.ln() + downs as f64 * d.ln();
+    log_price.clamp(-LOG_PRICE_LIMIT, LOG_PRICE_LIMIT).exp()
+}
+
+impl Index<usize> for PriceTree {
+    type Output = [f64];
+
+    fn index(&self, i: usize) -> &[f64] {
+        let start = self.offset(i);
+        &self.values[start..start + self.root_width + i]
+    }
+}
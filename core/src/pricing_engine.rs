@@ -0,0 +1,873 @@
+// pricing_engine.rs
+
+use crate::binomial_crr_option::BinomialCRROption;
+use crate::binomial_lr_option::BinomialLROption;
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::black_scholes_option::BlackScholesOption;
+use crate::adi_solver::AdiSolver;
+use crate::asian_option::{AsianMonteCarlo, AsianOption};
+use crate::basket_option::BasketMonteCarlo;
+use crate::finite_difference_option::FiniteDifferenceOption;
+use crate::garch_option::{GarchMonteCarlo, GarchOption};
+use crate::greeks::{DifferenceScheme, Greeks, GreeksConfig, MultiAssetGreeks, TwoAssetGreeks};
+use crate::option_type::ExerciseStyle;
+use crate::pricing_error::PricingError;
+use crate::rainbow_option::RainbowMonteCarlo;
+use crate::stock_option::StockOption;
+use crate::trinomial_tree_option::TrinomialTreeOption;
+use crate::two_asset_binomial_tree::TwoAssetBinomialTree;
+use crate::two_asset_option::TwoAssetOption;
+use crate::workspace::Workspace;
+
+/// Describes how an engine discretizes its option.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EngineDiagnostics {
+    /// The name of the engine: one of `ENGINE_NAMES`, as accepted by the `engine` argument of
+    /// the Python functions, or that of a Monte Carlo engine, such as "asian_mc".
+    pub engine: &'static str,
+    /// The number of time steps of the tree or grid, or 0 for a closed form.
+    pub time_steps: usize,
+    /// The number of nodes of the spatial grid, or 0 for engines without one.
+    pub space_steps: usize,
+    /// The up factor, down factor, and risk-neutral probability of an up move of a tree.
+    pub tree_parameters: Option<(f64, f64, f64)>,
+    /// The number of simulated paths, or 0 for engines that do not simulate.
+    pub paths: usize,
+}
+
+/// The price and Greeks of an option, with the discretization of the engine that
+/// calculated them.
+///
+/// Unlike a positional tuple, fields can be added without breaking callers.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PricingResult {
+    /// The price of the option.
+    pub price: f64,
+    /// The Greeks of the option.
+    pub greeks: Greeks,
+    /// The discretization of the engine, or `None` if the result combines several
+    /// valuations, as for a strategy, or has no single engine behind it.
+    pub diagnostics: Option<EngineDiagnostics>,
+}
+
+/// A model that prices a single-asset `StockOption`.
+///
+/// Engines price through `&self`: they expose the option they price, and generic code
+/// bumps a copy of it and revalues the copy with `price_option`, keeping the scratch state
+/// of the valuations in an explicit `Workspace`. An engine is therefore never changed by
+/// pricing and can be shared between threads. The trait is object safe, so callers can
+/// choose an engine at run time as a `Box<dyn PricingEngine>`.
+pub trait PricingEngine {
+    /// Returns the option priced by the engine.
+    fn option(&self) -> &StockOption;
+
+    /// Calculates the price of another option with the model and settings of the engine,
+    /// as paid on its premium date after its payoff is settled, for one contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to price, typically a bumped copy of `option()`.
+    /// * `workspace` - The buffers of the valuation, reused from earlier valuations.
+    fn price_option(&self, option: &StockOption, workspace: &mut Workspace) -> f64;
+
+    /// Calculates the price of the option of the engine, as `price_option` does.
+    fn price(&self) -> f64 {
+        self.price_option(self.option(), &mut Workspace::default())
+    }
+
+    /// Checks that the option and the parameters the engine derives from it are valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `StockOption::validate` rejects the option.
+    fn validate(&self) -> Result<(), PricingError> {
+        self.option().validate()
+    }
+
+    /// Checks that the engine can price its option.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::Unsupported` if the engine cannot price the option.
+    fn check_supported(&self) -> Result<(), PricingError> {
+        Ok(())
+    }
+
+    /// Validates the option and prices it as `price` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `validate` rejects the option,
+    /// `PricingError::Unsupported` if the engine cannot price it, or
+    /// `PricingError::NumericalFailure` if the price is not finite.
+    fn try_price(&self) -> Result<f64, PricingError> {
+        self.validate()?;
+        self.check_supported()?;
+        let price = self.price();
+        if price.is_finite() {
+            Ok(price)
+        } else {
+            Err(PricingError::NumericalFailure(format!("The engine returned a price of {price}.")))
+        }
+    }
+
+    /// Validates the option and calculates its price and Greeks by bump-and-revalue, as
+    /// `BumpGreeks::price` does.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The bumps used for theta, vega, and rho.
+    /// * `spot_bump` - The stock price bump for delta and gamma, relative to the stock price.
+    ///
+    /// # Returns
+    ///
+    /// The price and Greeks of the option, with the diagnostics of the engine.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `try_price`.
+    fn greeks(&self, config: GreeksConfig, spot_bump: f64) -> Result<PricingResult, PricingError> {
+        self.greeks_in(config, spot_bump, &mut Workspace::default())
+    }
+
+    /// Validates the option and calculates its price and Greeks as `greeks` does, in the
+    /// buffers of a workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The bumps used for theta, vega, and rho.
+    /// * `spot_bump` - The stock price bump for delta and gamma, relative to the stock price.
+    /// * `workspace` - The buffers of the tree engines, which the revaluations reuse.
+    ///
+    /// # Returns
+    ///
+    /// The price and Greeks of the option, with the diagnostics of the engine.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `try_price`.
+    fn greeks_in(
+        &self,
+        config: GreeksConfig,
+        spot_bump: f64,
+        workspace: &mut Workspace,
+    ) -> Result<PricingResult, PricingError> {
+        self.validate()?;
+        self.check_supported()?;
+        let bump_greeks = BumpGreeks { engine: self, config, spot_bump };
+        let result = bump_greeks.price_in(workspace);
+        if result.price.is_finite() {
+            Ok(result)
+        } else {
+            Err(PricingError::NumericalFailure(format!("The engine returned a price of {}.", result.price)))
+        }
+    }
+
+    /// Describes the discretization the engine uses for its option with its current
+    /// parameters.
+    fn diagnostics(&self) -> EngineDiagnostics;
+}
+
+/// Lets `BumpGreeks` bump an engine it borrows, such as one behind a `Box<dyn PricingEngine>`.
+impl<E: PricingEngine + ?Sized> PricingEngine for &E {
+    fn option(&self) -> &StockOption {
+        (**self).option()
+    }
+
+    fn price_option(&self, option: &StockOption, workspace: &mut Workspace) -> f64 {
+        (**self).price_option(option, workspace)
+    }
+
+    fn price(&self) -> f64 {
+        (**self).price()
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        (**self).validate()
+    }
+
+    fn check_supported(&self) -> Result<(), PricingError> {
+        (**self).check_supported()
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        (**self).diagnostics()
+    }
+}
+
+impl PricingEngine for BinomialTreeOption {
+    fn option(&self) -> &StockOption {
+        &self.option
+    }
+
+    fn price_option(&self, option: &StockOption, workspace: &mut Workspace) -> f64 {
+        BinomialTreeOption::price_option(self, option, workspace) * option.contract_factor()
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        self.option.validate()?;
+        self.check_moves()?;
+        let mut scratch = self.scratch(&self.option, &mut Workspace::default());
+        scratch.setup_parameters();
+        scratch.check_probabilities()
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        let mut scratch = self.scratch(&self.option, &mut Workspace::default());
+        scratch.setup_parameters();
+        EngineDiagnostics {
+            engine: "tree",
+            time_steps: self.option.n,
+            space_steps: 0,
+            tree_parameters: Some((scratch.u, scratch.d, scratch.qu)),
+            paths: 0,
+        }
+    }
+}
+
+impl PricingEngine for BinomialCRROption {
+    fn option(&self) -> &StockOption {
+        &self.tree.option
+    }
+
+    fn price_option(&self, option: &StockOption, workspace: &mut Workspace) -> f64 {
+        BinomialCRROption::price_option(self, option, workspace) * option.contract_factor()
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        self.tree.option.validate()?;
+        self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            scratch.tree.setup_crr_parameters();
+            scratch.tree.check_probabilities()
+        })
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        let tree_parameters = self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            scratch.tree.setup_crr_parameters();
+            (scratch.tree.u, scratch.tree.d, scratch.tree.qu)
+        });
+        EngineDiagnostics {
+            engine: "crr",
+            time_steps: self.tree.option.n,
+            space_steps: 0,
+            tree_parameters: Some(tree_parameters),
+            paths: 0,
+        }
+    }
+}
+
+impl PricingEngine for BinomialLROption {
+    fn option(&self) -> &StockOption {
+        &self.tree.option
+    }
+
+    fn price_option(&self, option: &StockOption, workspace: &mut Workspace) -> f64 {
+        BinomialLROption::price_option(self, option, workspace) * option.contract_factor()
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        let tree_parameters = self.in_scratch(&self.tree.option, &mut Workspace::default(), |scratch| {
+            scratch.setup_parameters();
+            (scratch.tree.u, scratch.tree.d, scratch.tree.qu)
+        });
+        EngineDiagnostics {
+            engine: "lr",
+            time_steps: BinomialLROption::odd_steps(self.tree.option.n),
+            space_steps: 0,
+            tree_parameters: Some(tree_parameters),
+            paths: 0,
+        }
+    }
+}
+
+impl PricingEngine for TrinomialTreeOption {
+    fn option(&self) -> &StockOption {
+        &self.option
+    }
+
+    fn price_option(&self, option: &StockOption, _workspace: &mut Workspace) -> f64 {
+        TrinomialTreeOption::new(option.clone()).price() * option.contract_factor()
+    }
+
+    fn price(&self) -> f64 {
+        TrinomialTreeOption::price(self) * self.option.contract_factor()
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        self.option.validate()?;
+        self.check_probabilities()
+    }
+
+    fn check_supported(&self) -> Result<(), PricingError> {
+        let option = &self.option;
+        if option.barrier.is_some() || option.double_barrier.is_some() || option.vol_term.is_some() {
+            return Err(PricingError::Unsupported(
+                "The trinomial engine does not support barriers or volatility term structures.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        let log_move = self.log_move();
+        EngineDiagnostics {
+            engine: "trinomial",
+            time_steps: self.option.n,
+            space_steps: 0,
+            tree_parameters: Some((log_move.exp(), (-log_move).exp(), self.probabilities(0).0)),
+            paths: 0,
+        }
+    }
+}
+
+impl PricingEngine for FiniteDifferenceOption {
+    fn option(&self) -> &StockOption {
+        &self.option
+    }
+
+    fn price_option(&self, option: &StockOption, _workspace: &mut Workspace) -> f64 {
+        FiniteDifferenceOption::new(option.clone(), self.space_steps).price() * option.contract_factor()
+    }
+
+    fn price(&self) -> f64 {
+        FiniteDifferenceOption::price(self) * self.option.contract_factor()
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        EngineDiagnostics {
+            engine: "fd",
+            time_steps: self.option.n,
+            space_steps: self.space_steps,
+            tree_parameters: None,
+            paths: 0,
+        }
+    }
+}
+
+impl PricingEngine for BlackScholesOption {
+    fn option(&self) -> &StockOption {
+        &self.option
+    }
+
+    fn price_option(&self, option: &StockOption, _workspace: &mut Workspace) -> f64 {
+        BlackScholesOption::new(option.clone()).price() * option.contract_factor()
+    }
+
+    fn price(&self) -> f64 {
+        BlackScholesOption::price(self) * self.option.contract_factor()
+    }
+
+    fn check_supported(&self) -> Result<(), PricingError> {
+        if self.option.exercise == ExerciseStyle::American || self.option.exercise_dates.is_some() {
+            return Err(PricingError::Unsupported(
+                "The analytic engine only supports European options.".to_string(),
+            ));
+        }
+        if self.option.dividends.is_some() && (self.option.barrier.is_some() || self.option.double_barrier.is_some()) {
+            return Err(PricingError::Unsupported(
+                "The analytic engine does not support barriers with discrete dividends.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        EngineDiagnostics {
+            engine: "analytic",
+            time_steps: 0,
+            space_steps: 0,
+            tree_parameters: None,
+            paths: 0,
+        }
+    }
+}
+
+/// Checks that a Monte Carlo engine simulates at least one path.
+fn check_paths(paths: usize) -> Result<(), PricingError> {
+    if paths == 0 {
+        return Err(PricingError::InvalidInput("Invalid paths. Must be greater than zero.".to_string()));
+    }
+    Ok(())
+}
+
+/// Checks that a European-only engine is not asked to price early exercise.
+fn check_european(option: &StockOption, engine: &str) -> Result<(), PricingError> {
+    if option.exercise == ExerciseStyle::American || option.exercise_dates.is_some() {
+        return Err(PricingError::Unsupported(format!("The {engine} engine only supports European options.")));
+    }
+    Ok(())
+}
+
+impl PricingEngine for AsianMonteCarlo {
+    fn option(&self) -> &StockOption {
+        &self.asian.option
+    }
+
+    fn price_option(&self, option: &StockOption, _workspace: &mut Workspace) -> f64 {
+        let asian = AsianOption::new(option.clone(), self.asian.style, self.asian.window_start, self.asian.observations);
+        asian.monte_carlo_price(self.paths, self.seed).0 * option.contract_factor()
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        self.asian.validate()?;
+        check_paths(self.paths)
+    }
+
+    fn check_supported(&self) -> Result<(), PricingError> {
+        check_european(&self.asian.option, "Asian Monte Carlo")
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        EngineDiagnostics {
+            engine: "asian_mc",
+            time_steps: self.asian.observations,
+            space_steps: 0,
+            tree_parameters: None,
+            paths: self.paths,
+        }
+    }
+}
+
+impl PricingEngine for GarchMonteCarlo {
+    fn option(&self) -> &StockOption {
+        &self.garch.option
+    }
+
+    fn price_option(&self, option: &StockOption, _workspace: &mut Workspace) -> f64 {
+        let g = &self.garch;
+        let garch = GarchOption::new(option.clone(), g.omega, g.alpha, g.beta, g.gamma, g.lambda, g.h0);
+        garch.monte_carlo_price(self.model, self.paths, self.seed).0 * option.contract_factor()
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        self.garch.validate()?;
+        check_paths(self.paths)
+    }
+
+    fn check_supported(&self) -> Result<(), PricingError> {
+        check_european(&self.garch.option, "GARCH Monte Carlo")
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        EngineDiagnostics {
+            engine: "garch_mc",
+            time_steps: self.garch.days(),
+            space_steps: 0,
+            tree_parameters: None,
+            paths: self.paths,
+        }
+    }
+}
+
+/// The default bump of the stock price for delta and gamma, relative to the stock price.
+pub const DEFAULT_SPOT_BUMP: f64 = 0.01;
+
+/// The names of the engines created by `engine_by_name`. Callers without the `pu` and `pd`
+/// of the plain binomial tree accept all but the first.
+pub const ENGINE_NAMES: [&str; 6] = ["tree", "crr", "lr", "trinomial", "fd", "analytic"];
+
+/// Creates a pricing engine by name, so that callers can choose the model at run time.
+///
+/// # Arguments
+///
+/// * `name` - The name of the engine: "tree" (plain binomial tree), "crr"
+///   (Cox-Ross-Rubinstein), "lr" (Leisen-Reimer), "trinomial" (trinomial tree), "fd"
+///   (Crank-Nicolson), or "analytic" (Black-Scholes).
+/// * `option` - The option the engine prices.
+/// * `moves` - The up and down moves `(pu, pd)` of the "tree" engine.
+/// * `space_steps` - The number of spatial nodes of the "fd" engine.
+///
+/// # Returns
+///
+/// The engine.
+///
+/// # Errors
+///
+/// Returns `PricingError::InvalidInput` if the name is not one of `ENGINE_NAMES`.
+pub fn engine_by_name(
+    name: &str,
+    option: StockOption,
+    moves: (f64, f64),
+    space_steps: usize,
+) -> Result<Box<dyn PricingEngine + Send + Sync>, PricingError> {
+    Ok(match name {
+        "tree" => Box::new(BinomialTreeOption::with_moves(option, moves.0, moves.1)),
+        "crr" => Box::new(BinomialCRROption::new(BinomialTreeOption::new(option))),
+        "lr" => Box::new(BinomialLROption::new(BinomialTreeOption::new(option))),
+        "trinomial" => Box::new(TrinomialTreeOption::new(option)),
+        "fd" => Box::new(FiniteDifferenceOption::new(option, space_steps)),
+        "analytic" => Box::new(BlackScholesOption::new(option)),
+        _ => {
+            let names = ENGINE_NAMES.map(|name| format!("'{name}'"));
+            return Err(PricingError::InvalidInput(format!(
+                "Invalid engine '{name}'. Must be {}, or {}.",
+                names[..names.len() - 1].join(", "),
+                names[names.len() - 1]
+            )));
+        }
+    })
+}
+
+/// Calculates the Greeks of any pricing engine by bumping and revaluing it.
+///
+/// Delta and gamma are central differences in the stock price. Theta, vega, and rho use
+/// the bumps and difference scheme of the `GreeksConfig`, with the same conventions as
+/// `BinomialLRWithGreeks`: theta is taken from a shorter time to expiration when
+/// differences are one-sided, and reported in the convention of the configuration.
+///
+/// Each revaluation prices a bumped copy of the option with `PricingEngine::price_option`,
+/// so the option of the engine is never changed, and the revaluations share one workspace.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BumpGreeks<E: PricingEngine> {
+    /// The engine being bumped.
+    pub engine: E,
+    /// The bumps used for theta, vega, and rho.
+    pub config: GreeksConfig,
+    /// The bump applied to the stock price for delta and gamma, relative to the stock price.
+    ///
+    /// Lattice prices are piecewise smooth in the stock price, so the bump should span
+    /// several nodes of the lattice.
+    pub spot_bump: f64,
+}
+
+impl<E: PricingEngine> BumpGreeks<E> {
+    /// Creates a new `BumpGreeks` instance with the default `GreeksConfig` and a 1% spot bump.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The engine to bump.
+    ///
+    /// # Returns
+    ///
+    /// A new `BumpGreeks` instance.
+    pub fn new(engine: E) -> Self {
+        BumpGreeks {
+            engine,
+            config: GreeksConfig::default(),
+            spot_bump: DEFAULT_SPOT_BUMP,
+        }
+    }
+
+    /// Revalues a copy of the option with one parameter shifted by `amount`.
+    fn bumped(&self, parameter: fn(&mut StockOption) -> &mut f64, amount: f64, workspace: &mut Workspace) -> f64 {
+        let mut option = self.engine.option().clone();
+        *parameter(&mut option) += amount;
+        self.engine.price_option(&option, workspace)
+    }
+
+    /// Calculates the derivative of the option value with respect to one parameter by the
+    /// configured finite-difference scheme.
+    fn sensitivity(
+        &self,
+        parameter: fn(&mut StockOption) -> &mut f64,
+        bump: f64,
+        value: f64,
+        workspace: &mut Workspace,
+    ) -> f64 {
+        let bumped_value = self.bumped(parameter, bump, workspace);
+        match self.config.scheme {
+            DifferenceScheme::OneSided => (bumped_value - value) / bump,
+            DifferenceScheme::Central => (bumped_value - self.bumped(parameter, -bump, workspace)) / (2.0 * bump),
+        }
+    }
+
+    /// Calculates the option price and Greeks (delta, gamma, theta, vega, rho).
+    ///
+    /// # Returns
+    ///
+    /// The price and Greeks of the option, as for `BinomialLRWithGreeks::price`, with the
+    /// diagnostics of the engine.
+    pub fn price(&self) -> PricingResult {
+        self.price_in(&mut Workspace::default())
+    }
+
+    /// Calculates the option price and Greeks as `price` does, in the buffers of a
+    /// workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The buffers of the revaluations, reused from earlier valuations
+    ///   and handed on to later ones.
+    ///
+    /// # Returns
+    ///
+    /// The price and Greeks of the option, with the diagnostics of the engine.
+    pub fn price_in(&self, workspace: &mut Workspace) -> PricingResult {
+        let option = self.engine.option();
+        let value = self.engine.price_option(option, workspace);
+
+        let ds = self.spot_bump * option.s0;
+        let value_up = self.bumped(|o| &mut o.s0, ds, workspace);
+        let value_down = self.bumped(|o| &mut o.s0, -ds, workspace);
+        let delta = (value_up - value_down) / (2.0 * ds);
+        let gamma = (value_up - 2.0 * value + value_down) / ds.powi(2);
+
+        let dt = self.config.absolute_bump(self.config.time_bump, option.t);
+        let theta = self.sensitivity(|o| &mut o.t, -dt, value, workspace);
+        let theta = self.config.theta.report(theta, option, value, delta);
+        let dv = self.config.absolute_bump(self.config.vol_bump, option.sigma);
+        let vega = self.sensitivity(|o| &mut o.sigma, dv, value, workspace);
+        let dr = self.config.absolute_bump(self.config.rate_bump, option.r);
+        let rho = self.sensitivity(|o| &mut o.r, dr, value, workspace);
+
+        PricingResult {
+            price: value,
+            greeks: Greeks { delta, gamma, theta, vega, rho },
+            diagnostics: Some(self.engine.diagnostics()),
+        }
+    }
+}
+
+/// A model that prices a `TwoAssetOption`.
+///
+/// As with `PricingEngine`, pricing takes `&self` and bumped revaluations price copies of
+/// the option.
+pub trait TwoAssetEngine {
+    /// Returns the option priced by the engine.
+    fn option(&self) -> &TwoAssetOption;
+
+    /// Calculates the price of another option with the model and settings of the engine.
+    fn price_option(&self, option: &TwoAssetOption) -> f64;
+
+    /// Calculates the price of the option of the engine.
+    fn price(&self) -> f64 {
+        self.price_option(self.option())
+    }
+}
+
+impl TwoAssetEngine for TwoAssetBinomialTree {
+    fn option(&self) -> &TwoAssetOption {
+        &self.option
+    }
+
+    fn price_option(&self, option: &TwoAssetOption) -> f64 {
+        TwoAssetBinomialTree::price_option(self, option)
+    }
+}
+
+impl TwoAssetEngine for AdiSolver {
+    fn option(&self) -> &TwoAssetOption {
+        &self.option
+    }
+
+    fn price_option(&self, option: &TwoAssetOption) -> f64 {
+        AdiSolver::new(option.clone(), self.space_steps, self.scheme).price()
+    }
+
+    fn price(&self) -> f64 {
+        AdiSolver::price(self)
+    }
+}
+
+/// A model that prices an option on any number of assets.
+///
+/// As with `PricingEngine`, pricing takes `&self`, and bumped revaluations price the
+/// option of the engine at shifted asset prices.
+pub trait MultiAssetEngine {
+    /// Returns the initial prices of the assets.
+    fn spots(&self) -> &[f64];
+
+    /// Calculates the price of the option of the engine with the assets at other prices.
+    fn price_at(&self, spots: &[f64]) -> f64;
+
+    /// Calculates the price of the option of the engine.
+    fn price(&self) -> f64 {
+        self.price_at(self.spots())
+    }
+
+    /// Checks that the option and the settings of the engine are valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the engine cannot price its option.
+    fn validate(&self) -> Result<(), PricingError>;
+
+    /// Validates the option and calculates its price, and the delta and gamma of each
+    /// asset by central differences.
+    ///
+    /// # Arguments
+    ///
+    /// * `spot_bump` - The bump applied to each asset price, relative to that price.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the price and the Greeks of the option.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `validate`, and `PricingError::NumericalFailure` if the price
+    /// is not finite.
+    fn greeks(&self, spot_bump: f64) -> Result<(f64, MultiAssetGreeks), PricingError> {
+        self.validate()?;
+        let value = self.price();
+        if !value.is_finite() {
+            return Err(PricingError::NumericalFailure(format!("The engine returned a price of {value}.")));
+        }
+        let mut spots = self.spots().to_vec();
+        let mut greeks = MultiAssetGreeks::default();
+        for i in 0..spots.len() {
+            let (s, h) = (spots[i], spot_bump * spots[i]);
+            spots[i] = s + h;
+            let up = self.price_at(&spots);
+            spots[i] = s - h;
+            let down = self.price_at(&spots);
+            spots[i] = s;
+            greeks.deltas.push((up - down) / (2.0 * h));
+            greeks.gammas.push((up - 2.0 * value + down) / h.powi(2));
+        }
+        Ok((value, greeks))
+    }
+
+    /// Describes the discretization the engine uses for its option.
+    fn diagnostics(&self) -> EngineDiagnostics;
+}
+
+impl MultiAssetEngine for BasketMonteCarlo {
+    fn spots(&self) -> &[f64] {
+        &self.basket.spots
+    }
+
+    fn price_at(&self, spots: &[f64]) -> f64 {
+        let mut basket = self.basket.clone();
+        basket.spots = spots.to_vec();
+        basket.monte_carlo_price(&self.factor, self.paths, self.seed).0
+    }
+
+    fn price(&self) -> f64 {
+        self.price_with_error().0
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        self.basket.validate()?;
+        check_paths(self.paths)
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        EngineDiagnostics {
+            engine: "basket_mc",
+            time_steps: 1,
+            space_steps: 0,
+            tree_parameters: None,
+            paths: self.paths,
+        }
+    }
+}
+
+impl MultiAssetEngine for RainbowMonteCarlo {
+    fn spots(&self) -> &[f64] {
+        &self.rainbow.spots
+    }
+
+    fn price_at(&self, spots: &[f64]) -> f64 {
+        let mut rainbow = self.rainbow.clone();
+        rainbow.spots = spots.to_vec();
+        rainbow.monte_carlo_price(&self.factor, self.paths, self.seed).0
+    }
+
+    fn price(&self) -> f64 {
+        self.price_with_error().0
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        self.rainbow.validate()?;
+        check_paths(self.paths)
+    }
+
+    fn diagnostics(&self) -> EngineDiagnostics {
+        EngineDiagnostics {
+            engine: "rainbow_mc",
+            time_steps: 1,
+            space_steps: 0,
+            tree_parameters: None,
+            paths: self.paths,
+        }
+    }
+}
+
+/// Calculates the Greeks of a two-asset engine by bumping and revaluing it.
+///
+/// Deltas, gammas, and the cross-gamma are central differences in the asset prices; the
+/// cross-gamma uses the four corners `(S1 ± h1, S2 ± h2)`. Cega is a central difference
+/// in the correlation, made one-sided where a bump would leave `[-1, 1]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoAssetBumpGreeks<E: TwoAssetEngine> {
+    /// The engine being bumped.
+    pub engine: E,
+    /// The bump applied to each asset price, relative to that price.
+    pub spot_bump: f64,
+    /// The absolute bump applied to the correlation.
+    pub correlation_bump: f64,
+}
+
+impl<E: TwoAssetEngine> TwoAssetBumpGreeks<E> {
+    /// Creates a new `TwoAssetBumpGreeks` instance with a 1% spot bump and a correlation
+    /// bump of 0.01.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The engine to bump.
+    ///
+    /// # Returns
+    ///
+    /// A new `TwoAssetBumpGreeks` instance.
+    pub fn new(engine: E) -> Self {
+        TwoAssetBumpGreeks {
+            engine,
+            spot_bump: 0.01,
+            correlation_bump: 0.01,
+        }
+    }
+
+    /// Revalues a copy of the option with both asset prices shifted.
+    fn bumped_spots(&self, ds1: f64, ds2: f64) -> f64 {
+        let mut option = self.engine.option().clone();
+        option.s1 += ds1;
+        option.s2 += ds2;
+        self.engine.price_option(&option)
+    }
+
+    /// Revalues a copy of the option at a given correlation.
+    fn at_correlation(&self, rho: f64) -> f64 {
+        let mut option = self.engine.option().clone();
+        option.rho = rho;
+        self.engine.price_option(&option)
+    }
+
+    /// Calculates the option price and Greeks.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the price and the Greeks of the option.
+    pub fn price(&self) -> (f64, TwoAssetGreeks) {
+        let value = self.engine.price();
+        let option = self.engine.option();
+        let (s1, s2, rho) = (option.s1, option.s2, option.rho);
+        let (h1, h2) = (self.spot_bump * s1, self.spot_bump * s2);
+
+        let up1 = self.bumped_spots(h1, 0.0);
+        let down1 = self.bumped_spots(-h1, 0.0);
+        let up2 = self.bumped_spots(0.0, h2);
+        let down2 = self.bumped_spots(0.0, -h2);
+        let corners = self.bumped_spots(h1, h2) - self.bumped_spots(h1, -h2) - self.bumped_spots(-h1, h2)
+            + self.bumped_spots(-h1, -h2);
+
+        let upper = (rho + self.correlation_bump).min(1.0);
+        let lower = (rho - self.correlation_bump).max(-1.0);
+        let cega = (self.at_correlation(upper) - self.at_correlation(lower)) / (upper - lower);
+
+        let greeks = TwoAssetGreeks {
+            delta1: (up1 - down1) / (2.0 * h1),
+            delta2: (up2 - down2) / (2.0 * h2),
+            gamma1: (up1 - 2.0 * value + down1) / h1.powi(2),
+            gamma2: (up2 - 2.0 * value + down2) / h2.powi(2),
+            cross_gamma: corners / (4.0 * h1 * h2),
+            cega,
+        };
+        (value, greeks)
+    }
+}
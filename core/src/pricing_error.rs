@@ -0,0 +1,27 @@
+// pricing_error.rs
+
+use std::fmt;
+
+/// Describes why an option could not be priced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PricingError {
+    /// A parameter is missing, not finite, or outside its domain.
+    InvalidInput(String),
+    /// The parameters are valid, but the engine did not produce a finite result.
+    NumericalFailure(String),
+    /// The engine does not support the option, such as an analytic formula for an
+    /// American option.
+    Unsupported(String),
+}
+
+impl fmt::Display for PricingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PricingError::InvalidInput(message)
+            | PricingError::NumericalFailure(message)
+            | PricingError::Unsupported(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for PricingError {}
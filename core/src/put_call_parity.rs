@@ -0,0 +1,80 @@
+// put_call_parity.rs
+
+/// Calculates the forward price and discount factor implied by European call and put
+/// prices at common strikes through put-call parity.
+///
+/// Parity gives `C − P = D (F − K)` at every strike, so `C − P` is linear in `K` with
+/// slope `−D` and intercept `D F`. The line is fitted by least squares, which averages
+/// out the noise of the individual mid prices.
+///
+/// # Arguments
+///
+/// * `calls` - The call prices.
+/// * `puts` - The put prices.
+/// * `strikes` - The common strike prices of the calls and puts.
+///
+/// # Returns
+///
+/// A tuple containing the implied forward price and discount factor, or `None` if the
+/// strikes do not vary or the implied discount factor or forward is not positive.
+pub fn implied_forward(calls: &[f64], puts: &[f64], strikes: &[f64]) -> Option<(f64, f64)> {
+    let n = strikes.len() as f64;
+    let differences = calls.iter().zip(puts).map(|(call, put)| call - put).collect::<Vec<_>>();
+    let mean_strike = strikes.iter().sum::<f64>() / n;
+    let mean_difference = differences.iter().sum::<f64>() / n;
+    let (covariance, variance) = strikes.iter().zip(&differences).fold((0.0, 0.0), |(covariance, variance), (&k, &d)| {
+        (covariance + (k - mean_strike) * (d - mean_difference), variance + (k - mean_strike).powi(2))
+    });
+    if variance <= 0.0 {
+        return None;
+    }
+    let discount = -covariance / variance;
+    let forward = (mean_difference + discount * mean_strike) / discount;
+    (discount > 0.0 && forward > 0.0).then_some((forward, discount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numerics::black_call;
+
+    const STRIKES: [f64; 5] = [80.0, 90.0, 100.0, 110.0, 120.0];
+
+    /// Black call and put prices with forward 105, discount factor 0.95, and a skewed smile.
+    fn chain() -> (Vec<f64>, Vec<f64>) {
+        let (forward, discount) = (105.0, 0.95);
+        let calls = STRIKES
+            .iter()
+            .map(|&k| discount * black_call(forward, k, (0.3 - 0.001 * k).powi(2)))
+            .collect::<Vec<_>>();
+        let puts = STRIKES.iter().zip(&calls).map(|(&k, &call)| call - discount * (forward - k)).collect();
+        (calls, puts)
+    }
+
+    #[test]
+    fn recovers_the_forward_and_discount_factor() {
+        let (calls, puts) = chain();
+        let (forward, discount) = implied_forward(&calls, &puts, &STRIKES).unwrap();
+        assert!((forward - 105.0).abs() < 1e-10, "{forward}");
+        assert!((discount - 0.95).abs() < 1e-12, "{discount}");
+    }
+
+    #[test]
+    fn averages_out_noise_in_the_quotes() {
+        let (mut calls, puts) = chain();
+        for (call, noise) in calls.iter_mut().zip([0.02, -0.02, 0.0, -0.02, 0.02]) {
+            *call += noise;
+        }
+        let (forward, discount) = implied_forward(&calls, &puts, &STRIKES).unwrap();
+        assert!((forward - 105.0).abs() < 0.05, "{forward}");
+        assert!((discount - 0.95).abs() < 1e-3, "{discount}");
+    }
+
+    #[test]
+    fn rejects_quotes_that_violate_parity() {
+        // C − P must decrease in the strike; here it increases, implying a negative discount.
+        let (calls, puts) = chain();
+        assert_eq!(implied_forward(&puts, &calls, &STRIKES), None);
+        assert_eq!(implied_forward(&calls[..1], &puts[..1], &STRIKES[..1]), None);
+    }
+}
@@ -0,0 +1,87 @@
+// quanto_option.rs
+
+use crate::stock_option::StockOption;
+
+/// Represents the drift correction for an option on a foreign underlying that pays in
+/// domestic currency at a fixed exchange rate.
+///
+/// Under the domestic risk-neutral measure the foreign asset grows at
+/// `r_f − q − ρ σ σ_X`, where `σ_X` is the volatility of the exchange rate (domestic
+/// units per foreign unit) and `ρ` its correlation with the asset. Discounting still
+/// happens at the domestic rate, so the correction can be expressed as an adjusted
+/// dividend yield `q + r_d − r_f + ρ σ σ_X` and priced with any single-asset engine.
+pub struct QuantoAdjustment {
+    /// The foreign risk-free interest rate.
+    pub foreign_rate: f64,
+    /// The volatility of the exchange rate.
+    pub fx_sigma: f64,
+    /// The correlation between the returns of the underlying asset and the exchange rate.
+    pub fx_rho: f64,
+}
+
+impl QuantoAdjustment {
+    /// Creates a new `QuantoAdjustment` instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `foreign_rate` - The foreign risk-free interest rate.
+    /// * `fx_sigma` - The volatility of the exchange rate.
+    /// * `fx_rho` - The correlation between the returns of the underlying asset and the exchange rate.
+    ///
+    /// # Returns
+    ///
+    /// A new `QuantoAdjustment` instance with the provided parameters.
+    pub fn new(foreign_rate: f64, fx_sigma: f64, fx_rho: f64) -> Self {
+        QuantoAdjustment {
+            foreign_rate,
+            fx_sigma,
+            fx_rho,
+        }
+    }
+
+    /// Calculates the dividend yield that reproduces the quanto drift.
+    ///
+    /// # Arguments
+    ///
+    /// * `domestic_rate` - The domestic risk-free interest rate used for discounting.
+    /// * `div` - The continuous dividend yield of the underlying asset.
+    /// * `sigma` - The volatility of the underlying asset.
+    ///
+    /// # Returns
+    ///
+    /// The adjusted dividend yield.
+    pub fn adjusted_dividend(&self, domestic_rate: f64, div: f64, sigma: f64) -> f64 {
+        div + domestic_rate - self.foreign_rate + self.fx_rho * sigma * self.fx_sigma
+    }
+
+    /// Applies the quanto drift correction to a stock option.
+    ///
+    /// The option's interest rate is taken as the domestic rate, and its dividend yield
+    /// is replaced by the adjusted total yield, which folds in the borrow rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option on the foreign underlying, denominated in foreign units.
+    pub fn apply(&self, option: &mut StockOption) {
+        option.div = self.adjusted_dividend(option.r, option.total_yield(), option.sigma);
+        option.borrow = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::option_type::{ExerciseStyle, OptionType};
+
+    #[test]
+    fn borrow_prices_like_an_equal_dividend_yield() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 1, 0.03, 0.2, OptionType::Call, ExerciseStyle::European).unwrap();
+        let adjustment = QuantoAdjustment::new(0.02, 0.1, -0.3);
+        let price = |mut option: StockOption| {
+            adjustment.apply(&mut option);
+            BlackScholesOption::new(option).price()
+        };
+        assert!((price(option.clone()) - price(option.with_yield_as_borrow())).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,164 @@
+// random.rs
+
+use rayon::prelude::*;
+
+use crate::worker_threads;
+
+/// The multipliers of the Philox4x32 rounds.
+const PHILOX_MULTIPLIERS: [u64; 2] = [0xd251_1f53, 0xcd9e_8d57];
+
+/// The Weyl increments of the Philox4x32 key schedule.
+const PHILOX_KEY_INCREMENTS: [u32; 2] = [0x9e37_79b9, 0xbb67_ae85];
+
+/// The number of rounds of Philox4x32-10.
+const PHILOX_ROUNDS: usize = 10;
+
+/// The fewest antithetic pairs in each task of a simulation, below which the cost of
+/// scheduling the task outweighs the work.
+const MINIMUM_PAIRS_PER_TASK: usize = 256;
+
+/// A small, seedable pseudo-random number generator for Monte Carlo simulation.
+///
+/// The generator is Philox4x32-10 (Salmon et al., 2011), a counter-based generator: each
+/// block of output is a keyed bijection of a 128-bit counter, so the draws of any stream
+/// can be computed without generating the streams before it. The seed is the key, and
+/// the stream number and the index of the block within the stream form the counter, so
+/// each simulated path can draw from its own stream and a simulation gives the same
+/// results however its paths are divided among threads.
+pub struct Rng {
+    /// The Philox key.
+    key: [u32; 2],
+    /// The stream number, the high half of the counter.
+    stream: u64,
+    /// The index of the next block within the stream, the low half of the counter.
+    block: u64,
+    /// The unused 64-bit outputs of the last block.
+    buffer: [u64; 2],
+    /// The number of outputs of `buffer` already used.
+    used: usize,
+    /// A cached standard normal deviate from the last Box-Muller transform.
+    spare_normal: Option<f64>,
+}
+
+impl Rng {
+    /// Creates a new `Rng` for one stream of the given seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed, which keys the generator.
+    /// * `stream` - The number of the stream, such as the index of a simulated path.
+    pub fn new(seed: u64, stream: u64) -> Self {
+        Rng {
+            key: [seed as u32, (seed >> 32) as u32],
+            stream,
+            block: 0,
+            buffer: [0; 2],
+            used: 2,
+            spare_normal: None,
+        }
+    }
+
+    /// Calculates the Philox4x32-10 block of the current counter.
+    #[inline]
+    fn next_block(&mut self) -> [u64; 2] {
+        let mut counter = [
+            self.block as u32,
+            (self.block >> 32) as u32,
+            self.stream as u32,
+            (self.stream >> 32) as u32,
+        ];
+        let mut key = self.key;
+        for _ in 0..PHILOX_ROUNDS {
+            let product0 = PHILOX_MULTIPLIERS[0] * counter[0] as u64;
+            let product1 = PHILOX_MULTIPLIERS[1] * counter[2] as u64;
+            counter = [
+                (product1 >> 32) as u32 ^ counter[1] ^ key[0],
+                product1 as u32,
+                (product0 >> 32) as u32 ^ counter[3] ^ key[1],
+                product0 as u32,
+            ];
+            key[0] = key[0].wrapping_add(PHILOX_KEY_INCREMENTS[0]);
+            key[1] = key[1].wrapping_add(PHILOX_KEY_INCREMENTS[1]);
+        }
+        self.block += 1;
+        [
+            counter[0] as u64 | (counter[1] as u64) << 32,
+            counter[2] as u64 | (counter[3] as u64) << 32,
+        ]
+    }
+
+    /// Generates the next raw 64-bit output of the generator.
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        if self.used == 2 {
+            self.buffer = self.next_block();
+            self.used = 0;
+        }
+        self.used += 1;
+        self.buffer[self.used - 1]
+    }
+
+    /// Generates a uniform deviate in the open interval (0, 1).
+    #[inline]
+    pub fn next_uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Generates a standard normal deviate using the Box-Muller transform.
+    #[inline]
+    pub fn next_normal(&mut self) -> f64 {
+        if let Some(z) = self.spare_normal.take() {
+            return z;
+        }
+        let radius = (-2.0 * self.next_uniform().ln()).sqrt();
+        let angle = 2.0 * std::f64::consts::PI * self.next_uniform();
+        self.spare_normal = Some(radius * angle.sin());
+        radius * angle.cos()
+    }
+
+    /// Generates a vector of correlated standard normal deviates.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The lower-triangular Cholesky factor of the correlation matrix.
+    ///
+    /// # Returns
+    ///
+    /// The deviates `L z`, where `z` is a vector of independent standard normals.
+    pub fn next_correlated_normals(&mut self, factor: &[Vec<f64>]) -> Vec<f64> {
+        let z = (0..factor.len()).map(|_| self.next_normal()).collect::<Vec<_>>();
+        factor
+            .iter()
+            .map(|row| row.iter().zip(&z).map(|(l, z)| l * z).sum())
+            .collect()
+    }
+}
+
+/// Simulates antithetic pairs of paths in parallel.
+///
+/// Pair `i` draws from stream `i` of the seed, and the pairs are split into tasks on the
+/// worker thread pool. Since the samples are returned in the order of the pairs, any
+/// statistic accumulated over them in order is identical for every number of threads.
+///
+/// # Arguments
+///
+/// * `pairs` - The number of antithetic pairs.
+/// * `seed` - The seed of the random number generator.
+/// * `sample` - Simulates one pair from its generator and returns its sample.
+///
+/// # Returns
+///
+/// The sample of each pair, in order.
+pub fn simulate_pairs<T, F>(pairs: usize, seed: u64, sample: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&mut Rng) -> T + Sync,
+{
+    worker_threads::install(|| {
+        (0..pairs)
+            .into_par_iter()
+            .with_min_len(MINIMUM_PAIRS_PER_TASK)
+            .map(|pair| sample(&mut Rng::new(seed, pair as u64)))
+            .collect()
+    })
+}
@@ -0,0 +1,156 @@
+// real_option.rs
+
+use crate::binomial_tree_option::BinomialTreeOption;
+
+/// Represents a capital project with managerial flexibility on a Cox-Ross-Rubinstein lattice.
+///
+/// The underlying is the present value of the project's expected cash flows, its
+/// volatility is that of the project value, and the dividend yield of the tree is the
+/// rate at which cash flows leak out of the project. At every step until `t` management
+/// may:
+///
+/// - expand the project by a fraction `factor` of its value at a cost `cost`;
+/// - abandon the project for its salvage value;
+/// - defer the initial investment, if the project has not been started yet.
+///
+/// Expansion and abandonment are treated as mutually exclusive, terminal decisions, as in
+/// the textbook treatment (Trigeorgis, 1996; Copeland and Antikarov, 2001). The option to
+/// defer is an American call on the project, including its embedded options, struck at
+/// the investment cost.
+pub struct RealOption {
+    /// The underlying binomial tree; `s0` is the present value of the project and the
+    /// strike of its option is unused.
+    pub tree: BinomialTreeOption,
+    /// The option to expand, as a `(factor, cost)` pair: paying `cost` increases the
+    /// project value by `factor` times its current value.
+    pub expansion: Option<(f64, f64)>,
+    /// The salvage value received if the project is abandoned.
+    pub salvage_value: Option<f64>,
+    /// The initial investment, if it has not been made yet and may be deferred.
+    pub investment_cost: Option<f64>,
+}
+
+impl RealOption {
+    /// Creates a new `RealOption` without any flexibility.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The binomial tree representing the project value and horizon.
+    ///
+    /// # Returns
+    ///
+    /// A new `RealOption` instance.
+    pub fn new(tree: BinomialTreeOption) -> Self {
+        RealOption {
+            tree,
+            expansion: None,
+            salvage_value: None,
+            investment_cost: None,
+        }
+    }
+
+    /// Calculates the value of exercising the best of the options to expand or abandon
+    /// at a node, relative to keeping the project unchanged.
+    fn exercise_value(&self, v: f64) -> f64 {
+        let mut value = 0.0_f64;
+        if let Some((factor, cost)) = self.expansion {
+            value = value.max(factor * v - cost);
+        }
+        if let Some(salvage) = self.salvage_value {
+            value = value.max(salvage - v);
+        }
+        value
+    }
+
+    /// Calculates the value of the project and of its flexibility.
+    ///
+    /// The owner of a running project receives the cash flows that leak out of it, so the
+    /// project is worth its present value `V` plus the value of the options to expand and
+    /// abandon, which are American options on `V`. Without the option to defer, this is
+    /// the value returned. With it, the value is that of the right to invest, net of the
+    /// investment cost, at the best node up to `t`; until then the cash flows are forgone.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the expanded net present value and the value of the real
+    /// options, which is the expanded net present value less the static one
+    /// (`V − I` when the investment is deferrable, and `V` otherwise).
+    pub fn price(&mut self) -> (f64, f64) {
+        self.tree.setup_crr_parameters();
+        self.tree.init_stock_price_tree();
+
+        let option = &self.tree.option;
+        let n = option.n;
+        let (qu, qd, discount) = (self.tree.qu, self.tree.qd, option.df());
+        let investment_cost = self.investment_cost.unwrap_or(0.0);
+
+        // Each node carries the value of the flexibility of a running project and the
+        // value of the right to start the project.
+        let mut values = option.sts[n]
+            .iter()
+            .map(|&v| {
+                let flexibility = self.exercise_value(v);
+                (flexibility, (v + flexibility - investment_cost).max(0.0))
+            })
+            .collect::<Vec<_>>();
+        for i in (0..n).rev() {
+            values = values
+                .windows(2)
+                .zip(&option.sts[i])
+                .map(|(w, &v)| {
+                    let ((flexibility_up, deferred_up), (flexibility_down, deferred_down)) = (w[0], w[1]);
+                    let flexibility = ((flexibility_up * qu + flexibility_down * qd) * discount)
+                        .max(self.exercise_value(v));
+                    let deferred = ((deferred_up * qu + deferred_down * qd) * discount)
+                        .max(v + flexibility - investment_cost);
+                    (flexibility, deferred)
+                })
+                .collect();
+        }
+
+        let (flexibility, deferred) = values[0];
+        let static_value = option.s0 - investment_cost;
+        match self.investment_cost {
+            Some(_) => (deferred, deferred - static_value),
+            None => (option.s0 + flexibility, flexibility),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binomial_crr_option::BinomialCRROption;
+    use crate::option_type::{ExerciseStyle, OptionType};
+    use crate::stock_option::StockOption;
+
+    fn tree(k: f64, option_type: OptionType) -> BinomialTreeOption {
+        BinomialTreeOption::new(StockOption::new(100.0, k, 0.05, 3.0, 150, 0.03, 0.35, option_type, ExerciseStyle::American).unwrap())
+    }
+
+    #[test]
+    fn without_flexibility_is_the_project_value() {
+        assert_eq!(RealOption::new(tree(1.0, OptionType::Call)).price(), (100.0, 0.0));
+    }
+
+    #[test]
+    fn single_options_are_american_options_on_the_project() {
+        // Expanding by a factor f at a cost C is f American calls struck at C / f.
+        let mut expand = RealOption::new(tree(1.0, OptionType::Call));
+        expand.expansion = Some((0.5, 40.0));
+        let call = 0.5 * BinomialCRROption::new(tree(80.0, OptionType::Call)).price();
+        assert!((expand.price().1 - call).abs() < 1e-10, "{:?} vs {call}", expand.price());
+
+        let mut abandon = RealOption::new(tree(1.0, OptionType::Call));
+        abandon.salvage_value = Some(90.0);
+        let put = BinomialCRROption::new(tree(90.0, OptionType::Put)).price();
+        assert!((abandon.price().1 - put).abs() < 1e-10, "{:?} vs {put}", abandon.price());
+
+        let mut defer = RealOption::new(tree(1.0, OptionType::Call));
+        defer.investment_cost = Some(105.0);
+        let call = BinomialCRROption::new(tree(105.0, OptionType::Call)).price();
+        let (value, flexibility) = defer.price();
+        assert!((value - call).abs() < 1e-10, "{value} vs {call}");
+        assert!((flexibility - (call + 5.0)).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,162 @@
+// realized_vol.rs
+
+/// Identifies an estimator of volatility from a history of prices.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RealizedVolEstimator {
+    /// The sample standard deviation of close-to-close log returns.
+    CloseToClose,
+    /// Parkinson (1980), from the high-low range of each period.
+    Parkinson,
+    /// Garman and Klass (1980), from the open, high, low, and close of each period.
+    GarmanKlass,
+    /// Yang and Zhang (2000), which combines the overnight, open-to-close, and
+    /// Rogers-Satchell variances and remains unbiased in the presence of drift and opening
+    /// jumps.
+    YangZhang,
+}
+
+/// Represents a history of open, high, low, and close prices, one entry per period.
+pub struct OhlcSeries {
+    /// The opening price of each period.
+    pub opens: Vec<f64>,
+    /// The highest price of each period.
+    pub highs: Vec<f64>,
+    /// The lowest price of each period.
+    pub lows: Vec<f64>,
+    /// The closing price of each period.
+    pub closes: Vec<f64>,
+}
+
+impl OhlcSeries {
+    /// Creates a new `OhlcSeries` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `opens` - The opening price of each period.
+    /// * `highs` - The highest price of each period.
+    /// * `lows` - The lowest price of each period.
+    /// * `closes` - The closing price of each period.
+    ///
+    /// # Returns
+    ///
+    /// A new `OhlcSeries` instance.
+    pub fn new(opens: Vec<f64>, highs: Vec<f64>, lows: Vec<f64>, closes: Vec<f64>) -> Self {
+        OhlcSeries { opens, highs, lows, closes }
+    }
+
+    /// Estimates the annualized volatility of the series.
+    ///
+    /// Close-to-close and Yang-Zhang use the `n - 1` periods that have a previous close,
+    /// while Parkinson and Garman-Klass use all `n` periods. The variance per period is
+    /// scaled by `periods_per_year` before taking the square root.
+    ///
+    /// # Arguments
+    ///
+    /// * `estimator` - The estimator to use; close-to-close only reads the closes.
+    /// * `periods_per_year` - The number of periods in a year, such as 252 for daily prices.
+    ///
+    /// # Returns
+    ///
+    /// The annualized volatility.
+    pub fn volatility(&self, estimator: RealizedVolEstimator, periods_per_year: f64) -> f64 {
+        let variance = match estimator {
+            RealizedVolEstimator::CloseToClose => {
+                let returns = self.closes.windows(2).map(|w| (w[1] / w[0]).ln()).collect::<Vec<_>>();
+                sample_variance(&returns)
+            }
+            RealizedVolEstimator::Parkinson => {
+                let sum = self.ranges().map(|range| range * range).sum::<f64>();
+                sum / (4.0 * std::f64::consts::LN_2 * self.highs.len() as f64)
+            }
+            RealizedVolEstimator::GarmanKlass => {
+                let sum = self
+                    .ranges()
+                    .zip(self.opens.iter().zip(&self.closes))
+                    .map(|(range, (open, close))| {
+                        let body = (close / open).ln();
+                        0.5 * range * range - (2.0 * std::f64::consts::LN_2 - 1.0) * body * body
+                    })
+                    .sum::<f64>();
+                sum / self.highs.len() as f64
+            }
+            RealizedVolEstimator::YangZhang => {
+                let periods = self.closes.len() - 1;
+                let overnight = (1..=periods).map(|i| (self.opens[i] / self.closes[i - 1]).ln()).collect::<Vec<_>>();
+                let open_to_close = (1..=periods).map(|i| (self.closes[i] / self.opens[i]).ln()).collect::<Vec<_>>();
+                let rogers_satchell = (1..=periods)
+                    .map(|i| {
+                        let (high, low) = (self.highs[i], self.lows[i]);
+                        (high / self.closes[i]).ln() * (high / self.opens[i]).ln()
+                            + (low / self.closes[i]).ln() * (low / self.opens[i]).ln()
+                    })
+                    .sum::<f64>()
+                    / periods as f64;
+                let n = periods as f64;
+                let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+                sample_variance(&overnight) + k * sample_variance(&open_to_close) + (1.0 - k) * rogers_satchell
+            }
+        };
+        (variance * periods_per_year).sqrt()
+    }
+
+    /// Returns the log high-low range `ln(H / L)` of each period.
+    fn ranges(&self) -> impl Iterator<Item = f64> + '_ {
+        self.highs.iter().zip(&self.lows).map(|(high, low)| (high / low).ln())
+    }
+}
+
+/// Calculates the sample variance of a series, with the `n - 1` denominator.
+fn sample_variance(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    values.iter().map(|value| (value - mean) * (value - mean)).sum::<f64>() / (n - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four days on which the stock moves by +10%, −10%, and +10%, with a 2% log range and
+    /// no overnight gaps.
+    fn series() -> OhlcSeries {
+        let closes = vec![100.0, 110.0, 99.0, 108.9];
+        let opens = vec![100.0, 100.0, 110.0, 99.0];
+        let highs = opens.iter().zip(&closes).map(|(&o, &c): (&f64, &f64)| o.max(c) * 0.01f64.exp()).collect::<Vec<_>>();
+        let lows = highs.iter().map(|&h| h * (-0.02f64).exp()).collect();
+        OhlcSeries::new(opens, highs, lows, closes)
+    }
+
+    #[test]
+    fn close_to_close_is_the_sample_deviation_of_log_returns() {
+        let (up, down) = (1.1f64.ln(), 0.9f64.ln());
+        let mean = (2.0 * up + down) / 3.0;
+        let variance = (2.0 * (up - mean).powi(2) + (down - mean).powi(2)) / 2.0;
+        let vol = series().volatility(RealizedVolEstimator::CloseToClose, 252.0);
+        assert!((vol - (252.0 * variance).sqrt()).abs() < 1e-12, "{vol}");
+    }
+
+    #[test]
+    fn range_estimators_use_the_high_low_range() {
+        let parkinson = series().volatility(RealizedVolEstimator::Parkinson, 252.0);
+        let expected = (252.0 * 0.02f64.powi(2) / (4.0 * std::f64::consts::LN_2)).sqrt();
+        assert!((parkinson - expected).abs() < 1e-12, "{parkinson} vs {expected}");
+
+        let flat = OhlcSeries::new(vec![100.0; 3], vec![102.0; 3], vec![98.0; 3], vec![100.0; 3]);
+        let garman_klass = flat.volatility(RealizedVolEstimator::GarmanKlass, 252.0);
+        let expected = (252.0 * 0.5 * (102.0f64 / 98.0).ln().powi(2)).sqrt();
+        assert!((garman_klass - expected).abs() < 1e-12, "{garman_klass} vs {expected}");
+    }
+
+    #[test]
+    fn constant_prices_have_no_volatility() {
+        let constant = OhlcSeries::new(vec![50.0; 5], vec![50.0; 5], vec![50.0; 5], vec![50.0; 5]);
+        for estimator in [
+            RealizedVolEstimator::CloseToClose,
+            RealizedVolEstimator::Parkinson,
+            RealizedVolEstimator::GarmanKlass,
+            RealizedVolEstimator::YangZhang,
+        ] {
+            assert_eq!(constant.volatility(estimator, 252.0), 0.0);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// shout_option.rs
+
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::option_type::OptionType;
+
+/// Represents a European shout option on a Cox-Ross-Rubinstein lattice.
+///
+/// Once during its life the holder may "shout" to lock in the current intrinsic value.
+/// A call shouted at price `L` pays `max(S_T − K, L − K)` at expiration, i.e. the locked
+/// value `L − K` plus an at-the-money call struck at `L`.
+///
+/// Each node therefore carries two states: not yet shouted, and shouted at the price of
+/// the node. The shouted state needs no further induction, because by homogeneity the
+/// at-the-money call from a node with price `S` and `m` steps left is worth `S` times the
+/// same call on a unit stock price, which depends only on `m`.
+pub struct ShoutOption {
+    /// The underlying binomial tree option.
+    pub tree: BinomialTreeOption,
+}
+
+impl ShoutOption {
+    /// Creates a new `ShoutOption` with the given binomial tree option.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The binomial tree option representing the underlying asset and option parameters.
+    pub fn new(tree: BinomialTreeOption) -> Self {
+        ShoutOption { tree }
+    }
+
+    /// Calculates the prices on the lattice of at-the-money European options on a unit stock price.
+    ///
+    /// # Returns
+    ///
+    /// A vector whose `m`-th entry is the price of the option with `m` steps to expiration.
+    fn unit_at_the_money_prices(&self) -> Vec<f64> {
+        let option = &self.tree.option;
+        let (u, d, qu, qd) = (self.tree.u, self.tree.d, self.tree.qu, self.tree.qd);
+        let discount = (-option.r * option.dt()).exp();
+
+        (0..=option.n)
+            .map(|m| {
+                // Sum the payoff over the binomial distribution of up moves, in log space
+                // so that the probabilities do not underflow for long trees.
+                let mut ln_probability = m as f64 * qd.ln();
+                let mut value = 0.0;
+                for k in 0..=m {
+                    if k > 0 {
+                        ln_probability += ((m - k + 1) as f64 / k as f64).ln() + (qu / qd).ln();
+                    }
+                    let s = u.powi(k as i32) * d.powi((m - k) as i32);
+                    let payoff = if option.option_type == OptionType::Call { s - 1.0 } else { 1.0 - s };
+                    value += ln_probability.exp() * payoff.max(0.0);
+                }
+                value * discount.powi(m as i32)
+            })
+            .collect()
+    }
+
+    /// Calculates the price of the shout option.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price(&mut self) -> f64 {
+        self.tree.setup_crr_parameters();
+        self.tree.init_stock_price_tree();
+
+        let option = &self.tree.option;
+        let n = option.n;
+        let discount = (-option.r * option.dt()).exp();
+        let unit_prices = self.unit_at_the_money_prices();
+        let intrinsic = |s: f64| if option.option_type == OptionType::Call { s - option.k } else { option.k - s };
+
+        let mut values = option.sts[n].iter().map(|&s| intrinsic(s).max(0.0)).collect::<Vec<_>>();
+        for i in (0..n).rev() {
+            let remaining = n - i;
+            values = values
+                .windows(2)
+                .zip(&option.sts[i])
+                .map(|(w, &s)| {
+                    let continuation = (w[0] * self.tree.qu + w[1] * self.tree.qd) * discount;
+                    let locked = intrinsic(s);
+                    if locked > 0.0 {
+                        let shouted = locked * discount.powi(remaining as i32) + s * unit_prices[remaining];
+                        continuation.max(shouted)
+                    } else {
+                        continuation
+                    }
+                })
+                .collect();
+        }
+        values[0]
+    }
+}
@@ -0,0 +1,776 @@
+// stock_option.rs
+
+use std::f64::consts::E;
+use crate::barrier::{Barrier, DoubleBarrier};
+use crate::dividend_schedule::DividendSchedule;
+use crate::option_type::{ExerciseStyle, OptionType};
+use crate::payoff::PayoffKind;
+use crate::price_tree::PriceTree;
+use crate::pricing_error::PricingError;
+use crate::vol_term_structure::VolTermStructure;
+use crate::yield_curve::YieldCurve;
+
+/// Represents a stock option with its associated parameters.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StockOption {
+    /// The initial stock price.
+    pub s0: f64,
+    /// The strike price of the option.
+    pub k: f64,
+    /// The risk-free interest rate.
+    pub r: f64,
+    /// The time to expiration of the option (in years).
+    pub t: f64,
+    /// The number of time steps in the binomial tree.
+    pub n: usize,
+    /// The stock price tree.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub sts: PriceTree,
+    /// The continuous dividend yield of the underlying asset.
+    pub div: f64,
+    /// The continuous stock borrow (repo) rate earned by lending the stock out.
+    ///
+    /// Like the dividend yield, it lowers the forward price, but it is kept apart because
+    /// hard-to-borrow stocks carry a borrow rate that changes independently of their
+    /// dividends.
+    pub borrow: f64,
+    /// The volatility of the underlying asset.
+    pub sigma: f64,
+    /// Whether the option is a call or a put.
+    pub option_type: OptionType,
+    /// Whether the option is European-style or American-style.
+    pub exercise: ExerciseStyle,
+    /// The payoff of the option at exercise.
+    pub payoff: PayoffKind,
+    /// The times (in years) at which a Bermudan option may be exercised before expiration.
+    ///
+    /// When set, this schedule takes precedence over `exercise`.
+    pub exercise_dates: Option<Vec<f64>>,
+    /// The optional barrier that knocks the option in or out.
+    pub barrier: Option<Barrier>,
+    /// The optional pair of barriers that knock the option in or out.
+    pub double_barrier: Option<DoubleBarrier>,
+    /// A boolean indicating whether the underlying is a futures price rather than a stock.
+    ///
+    /// A futures price has zero drift under the risk-neutral measure, so the dividend
+    /// yield is ignored and only the discounting depends on the interest rate.
+    pub is_futures: bool,
+    /// The optional term structure of interest rates used to discount each time step.
+    ///
+    /// When set, `r` should be the curve's zero rate to expiration, which sets the average
+    /// growth of the tree, while each step discounts and grows at the curve's forward rate.
+    pub curve: Option<YieldCurve>,
+    /// The optional schedule of discrete dividends paid before expiration.
+    ///
+    /// The schedule is paid on top of the continuous dividend yield `div`, which is
+    /// usually zero when a schedule is given.
+    pub dividends: Option<DividendSchedule>,
+    /// The optional term structure of volatility used to space the time steps of the tree.
+    ///
+    /// When set, `sigma` should be the term structure's volatility to expiration, which sets
+    /// the size of the moves, while the steps are spaced so that each carries the same
+    /// forward variance.
+    pub vol_term: Option<VolTermStructure>,
+    /// The time (in years) from exercise to the settlement of the payoff, such as `2 / 365`
+    /// for T+2 settlement.
+    pub settlement_lag: f64,
+    /// The time (in years) from the valuation to the payment of the premium.
+    ///
+    /// A deferred premium, as in FX markets, is paid at or after the settlement of the
+    /// payoff, so its lag exceeds the time to expiration.
+    pub premium_lag: f64,
+    /// The number of units of the underlying asset delivered per contract, such as 100
+    /// for US equity options or the foreign notional of an FX option.
+    ///
+    /// Prices and Greeks are per unit of the underlying when it is 1, and in currency per
+    /// contract otherwise.
+    pub multiplier: f64,
+    /// Whether the stock prices of a binomial tree are computed in log space.
+    ///
+    /// Each node is then the exponential of its log price, a sum of the log root price
+    /// and the log up and down factors, rather than a running product of the factors.
+    /// This keeps the rounding error of a node independent of its depth, and the log
+    /// prices are clamped so that extreme volatilities, maturities, or step counts no
+    /// longer overflow to infinity or underflow to zero.
+    pub log_space: bool,
+}
+
+impl StockOption {
+    /// Creates a new `StockOption` instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `s0` - The initial stock price.
+    /// * `k` - The strike price of the option.
+    /// * `r` - The risk-free interest rate.
+    /// * `t` - The time to expiration of the option (in years).
+    /// * `n` - The number of time steps in the binomial tree.
+    /// * `div` - The continuous dividend yield of the underlying asset.
+    /// * `sigma` - The volatility of the underlying asset.
+    /// * `option_type` - Whether the option is a call or a put.
+    /// * `exercise` - Whether the option is European-style or American-style.
+    ///
+    /// # Returns
+    ///
+    /// A new `StockOption` instance with the provided parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` naming the first offending parameter if
+    /// `validate` rejects the option.
+    pub fn new(
+        s0: f64,
+        k: f64,
+        r: f64,
+        t: f64,
+        n: usize,
+        div: f64,
+        sigma: f64,
+        option_type: OptionType,
+        exercise: ExerciseStyle,
+    ) -> Result<Self, PricingError> {
+        let option = Self::unvalidated(s0, k, r, t, n, div, sigma, option_type, exercise);
+        option.validate()?;
+        Ok(option)
+    }
+
+    /// Creates a `StockOption` with the given parameters and defaults for the rest,
+    /// leaving `validate` to the caller.
+    fn unvalidated(
+        s0: f64,
+        k: f64,
+        r: f64,
+        t: f64,
+        n: usize,
+        div: f64,
+        sigma: f64,
+        option_type: OptionType,
+        exercise: ExerciseStyle,
+    ) -> Self {
+        StockOption {
+            s0,
+            k,
+            r,
+            t,
+            n,
+            sts: PriceTree::default(),
+            div,
+            borrow: 0.0,
+            sigma,
+            option_type,
+            exercise,
+            payoff: PayoffKind::Vanilla,
+            exercise_dates: None,
+            barrier: None,
+            double_barrier: None,
+            is_futures: false,
+            curve: None,
+            dividends: None,
+            vol_term: None,
+            settlement_lag: 0.0,
+            premium_lag: 0.0,
+            multiplier: 1.0,
+            log_space: false,
+        }
+    }
+
+    /// Creates a new `StockOption` instance priced off a market forward and discount factor.
+    ///
+    /// The option is set up as an option on the forward: the forward has zero drift, like
+    /// a futures price, and the interest rate is the continuously compounded rate implied
+    /// by the discount factor. No dividend yield or compounding convention is assumed, so
+    /// European prices reproduce the forward and discount factor exactly. Early exercise
+    /// receives the forward price rather than the spot price, as for options on futures.
+    ///
+    /// # Arguments
+    ///
+    /// * `forward` - The forward price of the underlying asset for delivery at expiration.
+    /// * `discount` - The discount factor to expiration.
+    /// * `k` - The strike price of the option.
+    /// * `t` - The time to expiration of the option (in years).
+    /// * `n` - The number of time steps in the binomial tree.
+    /// * `sigma` - The volatility of the underlying asset.
+    /// * `option_type` - Whether the option is a call or a put.
+    /// * `exercise` - Whether the option is European-style or American-style.
+    ///
+    /// # Returns
+    ///
+    /// A new `StockOption` instance on the forward.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the time to expiration or the discount factor
+    /// is not positive or if `validate` rejects the option.
+    pub fn from_forward(
+        forward: f64,
+        discount: f64,
+        k: f64,
+        t: f64,
+        n: usize,
+        sigma: f64,
+        option_type: OptionType,
+        exercise: ExerciseStyle,
+    ) -> Result<Self, PricingError> {
+        if t.is_nan() || t <= 0.0 {
+            return Err(PricingError::InvalidInput(format!("The time to expiration must be positive, not {t}.")));
+        }
+        if discount.is_nan() || discount <= 0.0 {
+            return Err(PricingError::InvalidInput(format!("The discount factor must be positive, not {discount}.")));
+        }
+        let r = -discount.ln() / t;
+        let mut option = StockOption::new(forward, k, r, t, n, 0.0, sigma, option_type, exercise)?;
+        option.is_futures = true;
+        Ok(option)
+    }
+
+    /// Starts building a `StockOption` from named parameters.
+    ///
+    /// # Returns
+    ///
+    /// A `StockOptionBuilder` with no parameters set.
+    pub fn builder() -> StockOptionBuilder {
+        StockOptionBuilder::default()
+    }
+
+    /// Checks that the parameters of the option are finite and within their domains.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` naming the first offending parameter if a
+    /// parameter is not finite, if the spot price, strike price, time to expiration,
+    /// volatility, multiplier, or number of steps is not positive, or if the settlement
+    /// lag or premium lag is negative, if the cash dividends are worth at least the spot
+    /// price, or if a double barrier does not enclose the spot price. A zero volatility is
+    /// rejected because the lattices are spaced by it.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        let parameters = [
+            ("spot price", self.s0),
+            ("strike price", self.k),
+            ("interest rate", self.r),
+            ("time to expiration", self.t),
+            ("dividend yield", self.div),
+            ("borrow rate", self.borrow),
+            ("volatility", self.sigma),
+            ("settlement lag", self.settlement_lag),
+            ("premium lag", self.premium_lag),
+            ("multiplier", self.multiplier),
+        ];
+        if let Some((name, value)) = parameters.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(PricingError::InvalidInput(format!("The {name} must be finite, not {value}.")));
+        }
+        let checks = [
+            ("spot price", self.s0, self.s0 > 0.0, "positive"),
+            ("strike price", self.k, self.k > 0.0, "positive"),
+            ("time to expiration", self.t, self.t > 0.0, "positive"),
+            ("volatility", self.sigma, self.sigma > 0.0, "positive"),
+            ("settlement lag", self.settlement_lag, self.settlement_lag >= 0.0, "non-negative"),
+            ("premium lag", self.premium_lag, self.premium_lag >= 0.0, "non-negative"),
+            ("multiplier", self.multiplier, self.multiplier > 0.0, "positive"),
+        ];
+        if let Some((name, value, _, requirement)) = checks.iter().find(|(_, _, valid, _)| !valid) {
+            return Err(PricingError::InvalidInput(format!("The {name} must be {requirement}, not {value}.")));
+        }
+        self.check_net_spot()?;
+        if let Some(barrier) = &self.double_barrier {
+            if !(barrier.lower < self.s0 && self.s0 < barrier.upper) {
+                return Err(PricingError::InvalidInput(
+                    "The barriers must satisfy lower < s0 < upper.".to_string(),
+                ));
+            }
+        }
+        Self::check_steps(self.n)
+    }
+
+    /// Checks that the cash dividends of the option are worth less than its stock price.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the stock price net of the escrowed cash
+    /// dividends is not positive.
+    pub fn check_net_spot(&self) -> Result<(), PricingError> {
+        if self.net_spot() <= 0.0 {
+            return Err(PricingError::InvalidInput(
+                "The present value of the cash dividends must be below the spot price.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that a lattice or grid has at least one time step.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `n` is zero.
+    pub fn check_steps(n: usize) -> Result<(), PricingError> {
+        if n == 0 {
+            return Err(PricingError::InvalidInput("The number of steps must be positive, not 0.".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Calculates the time step size (Δt) of the binomial tree.
+    ///
+    /// # Returns
+    ///
+    /// The time step size (Δt) of the binomial tree.
+    pub fn dt(&self) -> f64 {
+        self.t / self.n as f64
+    }
+
+    /// Calculates the discount factor for each time step.
+    ///
+    /// The discount factor is calculated using the risk-free interest rate.
+    ///
+    /// # Returns
+    ///
+    /// The discount factor for each time step.
+    pub fn df(&self) -> f64 {
+        E.powf(-self.r * self.dt())
+    }
+
+    /// Calculates the expected growth factor of the stock price for each time step.
+    ///
+    /// The growth factor is calculated using the risk-free interest rate net of the dividend yield.
+    ///
+    /// # Returns
+    ///
+    /// The risk-neutral growth factor for each time step.
+    pub fn growth(&self) -> f64 {
+        E.powf(self.cost_of_carry() * self.dt())
+    }
+
+    /// Calculates the cost of carry, the risk-neutral drift rate of the underlying.
+    ///
+    /// # Returns
+    ///
+    /// The interest rate net of the dividend yield and borrow rate, or zero for a futures price.
+    pub fn cost_of_carry(&self) -> f64 {
+        if self.is_futures {
+            0.0
+        } else {
+            self.r - self.total_yield()
+        }
+    }
+
+    /// Calculates the total yield of holding the stock.
+    ///
+    /// # Returns
+    ///
+    /// The continuous dividend yield plus the borrow rate.
+    pub fn total_yield(&self) -> f64 {
+        self.div + self.borrow
+    }
+
+    /// Calculates the factor that turns the value of the payoff at exercise into the
+    /// premium paid on the premium date.
+    ///
+    /// The payoff is received `settlement_lag` after exercise, which discounts every
+    /// exercise value by the same factor and leaves the exercise policy unchanged, and the
+    /// present value is carried forward to the premium date.
+    ///
+    /// # Returns
+    ///
+    /// The factor `e^{−r (settlement_lag − premium_lag)}`, which is 1 without lags.
+    pub fn settlement_factor(&self) -> f64 {
+        (-self.r * (self.settlement_lag - self.premium_lag)).exp()
+    }
+
+    /// Calculates the factor that turns the value of one unit of the payoff at exercise
+    /// into the premium of one contract.
+    ///
+    /// # Returns
+    ///
+    /// The settlement factor times the contract multiplier.
+    pub fn contract_factor(&self) -> f64 {
+        self.settlement_factor() * self.multiplier
+    }
+
+    /// Calculates the time of a step of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The index of the time step.
+    ///
+    /// # Returns
+    ///
+    /// The time at which the term structure's total variance reaches `i / n` of its value at
+    /// expiration, or `i Δt` without a volatility term structure.
+    pub fn step_time(&self, i: usize) -> f64 {
+        match &self.vol_term {
+            Some(term) => term.time_at_variance(i as f64 / self.n as f64 * term.total_variance(self.t)),
+            None => i as f64 * self.dt(),
+        }
+    }
+
+    /// Calculates the length of one time step of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The index of the time step, which runs from `step_time(i)` to `step_time(i + 1)`.
+    ///
+    /// # Returns
+    ///
+    /// The length of the step (in years), or `dt()` without a volatility term structure.
+    pub fn step_length(&self, i: usize) -> f64 {
+        match &self.vol_term {
+            Some(_) => self.step_time(i + 1) - self.step_time(i),
+            None => self.dt(),
+        }
+    }
+
+    /// Calculates the discount factor over one time step of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The index of the time step, which runs from `step_time(i)` to `step_time(i + 1)`.
+    ///
+    /// # Returns
+    ///
+    /// The discount factor at the curve's forward rate over the step, or at `r` over the
+    /// length of the step without a curve.
+    pub fn step_df(&self, i: usize) -> f64 {
+        match &self.curve {
+            Some(curve) => curve.discount(self.step_time(i + 1)) / curve.discount(self.step_time(i)),
+            None if self.vol_term.is_some() => E.powf(-self.r * self.step_length(i)),
+            None => self.df(),
+        }
+    }
+
+    /// Calculates the expected growth factor of the stock price over one time step of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The index of the time step, which runs from `step_time(i)` to `step_time(i + 1)`.
+    ///
+    /// # Returns
+    ///
+    /// The risk-neutral growth factor at the curve's forward rate net of the total yield,
+    /// or at the cost of carry over the length of the step without a curve or for a
+    /// futures price.
+    pub fn step_growth(&self, i: usize) -> f64 {
+        match &self.curve {
+            Some(_) if !self.is_futures => E.powf(-self.total_yield() * self.step_length(i)) / self.step_df(i),
+            _ if self.vol_term.is_some() => E.powf(self.cost_of_carry() * self.step_length(i)),
+            _ => self.growth(),
+        }
+    }
+
+    /// Calculates the discount factor from expiration back to a time step of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The index of the time step.
+    ///
+    /// # Returns
+    ///
+    /// The discount factor from `step_time(i)` to the expiration of the option.
+    pub fn discount_to_expiry(&self, i: usize) -> f64 {
+        match &self.curve {
+            Some(curve) => curve.discount(self.t) / curve.discount(self.step_time(i)),
+            None if self.vol_term.is_some() => E.powf(-self.r * (self.t - self.step_time(i))),
+            None => self.df().powi((self.n - i) as i32),
+        }
+    }
+
+    /// Calculates the initial value of the process on which the engines are built.
+    ///
+    /// # Returns
+    ///
+    /// The initial stock price net of the escrowed cash dividends, or `s0` without a
+    /// dividend schedule.
+    pub fn net_spot(&self) -> f64 {
+        match &self.dividends {
+            Some(dividends) => dividends.net_spot(self.s0, self.cost_of_carry(), self.t),
+            None => self.s0,
+        }
+    }
+
+    /// Calculates the spot price implied by the forward price to expiration.
+    ///
+    /// Closed-form engines use this in place of `s0`, since the stock price at expiration
+    /// is lognormal with the forward `adjusted_spot() e^{bT}`.
+    ///
+    /// # Returns
+    ///
+    /// The forward price to expiration discounted at the cost of carry, or `s0` without a
+    /// dividend schedule.
+    pub fn adjusted_spot(&self) -> f64 {
+        match &self.dividends {
+            Some(dividends) => {
+                let carry = self.cost_of_carry();
+                dividends.forward(self.s0, carry, self.t) * (-carry * self.t).exp()
+            }
+            None => self.s0,
+        }
+    }
+
+    /// Maps a value of the process returned by `net_spot` to the stock price.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The value of the process at `time`.
+    /// * `time` - The time of the value (in years).
+    ///
+    /// # Returns
+    ///
+    /// The stock price at `time`, or `x` itself without a dividend schedule.
+    pub fn stock_price_at(&self, x: f64, time: f64) -> f64 {
+        match &self.dividends {
+            Some(dividends) => dividends.stock_price(x, self.cost_of_carry(), time, self.t),
+            None => x,
+        }
+    }
+
+    /// Checks whether early exercise provably never adds value to the option.
+    ///
+    /// On a risk-neutral step the discounted expected stock price is `S e^{(b − r) Δt}`,
+    /// where `b` is the cost of carry, and the discounted strike is `K e^{−r Δt}`. For a
+    /// vanilla call with `r ≥ 0` and `b ≥ r`, as without dividends or borrow costs, the
+    /// continuation value therefore never falls below the exercise value, and likewise for
+    /// a vanilla put with `r ≤ 0` and `b ≤ r`. The argument is not made for barriers,
+    /// discrete dividends, or yield curves, whose rates may change sign.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the option is worth the same as its European twin.
+    pub fn early_exercise_never_binds(&self) -> bool {
+        let plain = matches!(self.payoff, PayoffKind::Vanilla)
+            && self.barrier.is_none()
+            && self.double_barrier.is_none()
+            && self.dividends.is_none()
+            && self.curve.is_none();
+        let carry_over_rate = self.cost_of_carry() - self.r;
+        plain
+            && match self.option_type {
+                OptionType::Call => self.r >= 0.0 && carry_over_rate >= 0.0,
+                OptionType::Put => self.r <= 0.0 && carry_over_rate <= 0.0,
+            }
+    }
+
+    /// Determines at which time steps the option may be exercised early.
+    ///
+    /// Bermudan exercise dates are mapped to the nearest time step of the tree, measured in
+    /// total variance when the steps follow a volatility term structure; dates outside the
+    /// life of the option are ignored. Without a schedule, every step is an
+    /// exercise opportunity for American options and none is for European options.
+    ///
+    /// # Returns
+    ///
+    /// A vector of length `n + 1` indicating whether early exercise is allowed at each step.
+    pub fn exercise_schedule(&self) -> Vec<bool> {
+        match &self.exercise_dates {
+            Some(dates) => {
+                let mut schedule = vec![false; self.n + 1];
+                for &date in dates {
+                    let step = match &self.vol_term {
+                        Some(term) => (self.n as f64 * term.total_variance(date) / term.total_variance(self.t)).round(),
+                        None => (date / self.dt()).round(),
+                    };
+                    if (0.0..=self.n as f64).contains(&step) {
+                        schedule[step as usize] = true;
+                    }
+                }
+                schedule
+            }
+            None => vec![self.exercise == ExerciseStyle::American; self.n + 1],
+        }
+    }
+}
+
+/// Builds a `StockOption` from named parameters, validated when the option is built.
+///
+/// The spot price, strike price, time to expiration, volatility, number of steps, and
+/// option type must be set. The interest rate, dividend yield, and borrow rate default to
+/// zero, and the exercise style to European.
+#[derive(Default)]
+pub struct StockOptionBuilder {
+    s0: Option<f64>,
+    k: Option<f64>,
+    r: f64,
+    t: Option<f64>,
+    n: Option<usize>,
+    div: f64,
+    borrow: f64,
+    sigma: Option<f64>,
+    option_type: Option<OptionType>,
+    exercise: Option<ExerciseStyle>,
+}
+
+impl StockOptionBuilder {
+    /// Sets the initial stock price.
+    pub fn spot(mut self, s0: f64) -> Self {
+        self.s0 = Some(s0);
+        self
+    }
+
+    /// Sets the strike price of the option.
+    pub fn strike(mut self, k: f64) -> Self {
+        self.k = Some(k);
+        self
+    }
+
+    /// Sets the risk-free interest rate.
+    pub fn rate(mut self, r: f64) -> Self {
+        self.r = r;
+        self
+    }
+
+    /// Sets the time to expiration of the option (in years).
+    pub fn expiry(mut self, t: f64) -> Self {
+        self.t = Some(t);
+        self
+    }
+
+    /// Sets the number of time steps in the binomial tree.
+    pub fn steps(mut self, n: usize) -> Self {
+        self.n = Some(n);
+        self
+    }
+
+    /// Sets the continuous dividend yield of the underlying asset.
+    pub fn dividend_yield(mut self, div: f64) -> Self {
+        self.div = div;
+        self
+    }
+
+    /// Sets the continuous stock borrow (repo) rate.
+    pub fn borrow_rate(mut self, borrow: f64) -> Self {
+        self.borrow = borrow;
+        self
+    }
+
+    /// Sets the volatility of the underlying asset.
+    pub fn volatility(mut self, sigma: f64) -> Self {
+        self.sigma = Some(sigma);
+        self
+    }
+
+    /// Sets whether the option is a call or a put.
+    pub fn option_type(mut self, option_type: OptionType) -> Self {
+        self.option_type = Some(option_type);
+        self
+    }
+
+    /// Sets whether the option is European-style or American-style.
+    pub fn exercise(mut self, exercise: ExerciseStyle) -> Self {
+        self.exercise = Some(exercise);
+        self
+    }
+
+    /// Builds the option after validating its parameters.
+    ///
+    /// # Returns
+    ///
+    /// The `StockOption` with the given parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` naming the parameter if a required parameter is
+    /// missing, if the number of steps is zero, or if `StockOption::validate` rejects the option.
+    pub fn build(self) -> Result<StockOption, PricingError> {
+        let required = |value: Option<f64>, name: &str| {
+            value.ok_or_else(|| PricingError::InvalidInput(format!("The {name} is required.")))
+        };
+        let s0 = required(self.s0, "spot price")?;
+        let k = required(self.k, "strike price")?;
+        let t = required(self.t, "time to expiration")?;
+        let sigma = required(self.sigma, "volatility")?;
+        let option_type = self
+            .option_type
+            .ok_or_else(|| PricingError::InvalidInput("The option type is required.".to_string()))?;
+        let n = self.n.ok_or_else(|| PricingError::InvalidInput("The number of steps is required.".to_string()))?;
+
+        let exercise = self.exercise.unwrap_or(ExerciseStyle::European);
+        let mut option = StockOption::unvalidated(s0, k, self.r, t, n, self.div, sigma, option_type, exercise);
+        option.borrow = self.borrow;
+        option.validate()?;
+        Ok(option)
+    }
+}
+
+#[cfg(test)]
+impl StockOption {
+    /// Returns a copy of the option whose continuous dividend yield is charged as a
+    /// borrow rate instead, which every engine must price identically.
+    pub(crate) fn with_yield_as_borrow(&self) -> StockOption {
+        let mut option = self.clone();
+        option.borrow = option.div;
+        option.div = 0.0;
+        option
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder() -> StockOptionBuilder {
+        StockOption::builder()
+            .spot(100.0)
+            .strike(95.0)
+            .rate(0.05)
+            .expiry(1.0)
+            .steps(101)
+            .volatility(0.25)
+            .option_type(OptionType::Put)
+            .exercise(ExerciseStyle::American)
+    }
+
+    #[test]
+    fn new_rejects_each_invalid_parameter() {
+        let new = |s0, k, r, t, n, div, sigma| {
+            StockOption::new(s0, k, r, t, n, div, sigma, OptionType::Call, ExerciseStyle::European)
+        };
+        assert!(new(100.0, 95.0, 0.05, 1.0, 101, 0.02, 0.25).is_ok());
+        let rejected = |option: Result<StockOption, PricingError>, name: &str| match option {
+            Err(PricingError::InvalidInput(message)) => assert!(message.contains(name), "{message}"),
+            other => panic!("expected an error naming the {name}, got {other:?}"),
+        };
+        rejected(new(-100.0, 95.0, 0.05, 1.0, 101, 0.02, 0.25), "spot price");
+        rejected(new(0.0, 95.0, 0.05, 1.0, 101, 0.02, 0.25), "spot price");
+        rejected(new(100.0, -95.0, 0.05, 1.0, 101, 0.02, 0.25), "strike price");
+        rejected(new(100.0, 95.0, f64::NAN, 1.0, 101, 0.02, 0.25), "interest rate");
+        rejected(new(100.0, 95.0, 0.05, -1.0, 101, 0.02, 0.25), "time to expiration");
+        rejected(new(100.0, 95.0, 0.05, 1.0, 0, 0.02, 0.25), "number of steps");
+        rejected(new(100.0, 95.0, 0.05, 1.0, 101, f64::INFINITY, 0.25), "dividend yield");
+        rejected(new(100.0, 95.0, 0.05, 1.0, 101, 0.02, -0.25), "volatility");
+        rejected(new(100.0, 95.0, 0.05, 1.0, 101, 0.02, 0.0), "volatility");
+    }
+
+    #[test]
+    fn from_forward_rejects_a_non_positive_discount_factor() {
+        let option = |discount| {
+            StockOption::from_forward(105.0, discount, 100.0, 1.0, 101, 0.2, OptionType::Call, ExerciseStyle::European)
+        };
+        assert!(matches!(option(0.0), Err(PricingError::InvalidInput(_))));
+        assert!(option(0.95).unwrap().is_futures);
+    }
+
+    #[test]
+    fn from_forward_rejects_a_non_positive_time_before_deriving_the_rate() {
+        let option = |t| {
+            StockOption::from_forward(105.0, 0.95, 100.0, t, 101, 0.2, OptionType::Call, ExerciseStyle::European)
+        };
+        for t in [0.0, -1.0, f64::NAN] {
+            assert_eq!(
+                option(t),
+                Err(PricingError::InvalidInput(format!("The time to expiration must be positive, not {t}.")))
+            );
+        }
+    }
+
+    #[test]
+    fn builder_requires_the_number_of_steps() {
+        let missing = StockOptionBuilder { n: None, ..builder() }.build();
+        assert_eq!(missing, Err(PricingError::InvalidInput("The number of steps is required.".to_string())));
+        assert!(matches!(builder().steps(0).build(), Err(PricingError::InvalidInput(_))));
+        assert_eq!(builder().build().unwrap().n, 101);
+    }
+
+    #[test]
+    fn builder_validates_the_borrow_rate() {
+        let option = builder().borrow_rate(f64::NAN).build();
+        assert_eq!(option, Err(PricingError::InvalidInput("The borrow rate must be finite, not NaN.".to_string())));
+    }
+}
@@ -0,0 +1,227 @@
+// svi.rs
+
+use crate::numerics::{nelder_mead, solve_linear_system};
+
+/// The bounds on the SVI smoothing parameter `σ` searched by the fit.
+const SIGMA_BOUNDS: (f64, f64) = (1e-4, 10.0);
+
+/// Represents a volatility smile in Gatheral's raw SVI (stochastic volatility inspired)
+/// parameterization.
+///
+/// The total implied variance at log-moneyness `k = ln(K / F)` is
+/// `w(k) = a + b (ρ (k − m) + √((k − m)² + σ²))`, so the smile is a hyperbola whose wings
+/// are asymptotically linear in `k`, as Lee's moment formula requires.
+#[derive(Clone, Copy)]
+pub struct SviSmile {
+    /// The level of the total variance.
+    pub a: f64,
+    /// The slope of the wings.
+    pub b: f64,
+    /// The skew, between -1 and 1.
+    pub rho: f64,
+    /// The log-moneyness of the vertex of the smile.
+    pub m: f64,
+    /// The curvature of the smile at its vertex.
+    pub sigma: f64,
+    /// The forward price of the underlying asset at expiration.
+    pub forward: f64,
+    /// The time to expiration (in years).
+    pub t: f64,
+}
+
+impl SviSmile {
+    /// Calculates the total implied variance at a log-moneyness.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The log-moneyness `ln(K / F)`.
+    ///
+    /// # Returns
+    ///
+    /// The total implied variance `σ_BS² T`.
+    pub fn total_variance(&self, k: f64) -> f64 {
+        let x = k - self.m;
+        self.a + self.b * (self.rho * x + (x * x + self.sigma * self.sigma).sqrt())
+    }
+
+    /// Calculates the implied volatility at a strike.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - The strike price.
+    ///
+    /// # Returns
+    ///
+    /// The implied volatility.
+    pub fn volatility(&self, strike: f64) -> f64 {
+        (self.total_variance((strike / self.forward).ln()).max(0.0) / self.t).sqrt()
+    }
+
+    /// Fits a raw SVI smile to the implied volatilities of one expiration by weighted least
+    /// squares on the total variance.
+    ///
+    /// This is the quasi-explicit method of Zeliade (2009). In the variables
+    /// `y = (k − m)/σ`, the total variance `a + d y + c √(y² + 1)` is linear in `(a, d, c)`,
+    /// so for fixed `m` and `σ` the least-squares problem is a small quadratic program,
+    /// solved exactly over its active constraints. The remaining two parameters are found
+    /// by Nelder-Mead. The constraints `0 ≤ a ≤ max w`, `|d| ≤ c`, and `|d| ≤ 4σ − c` keep
+    /// the variance non-negative and the wings within Lee's bound `b (1 + |ρ|) ≤ 4`.
+    ///
+    /// # Arguments
+    ///
+    /// * `strikes` - The strike prices of the quotes.
+    /// * `vols` - The implied volatilities of the quotes.
+    /// * `forward` - The forward price of the underlying asset at expiration.
+    /// * `t` - The time to expiration (in years).
+    /// * `weights` - The non-negative weight of each quote's squared error.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the fitted smile and the unweighted root-mean-square error of its
+    /// volatilities at the quotes.
+    pub fn fit(strikes: &[f64], vols: &[f64], forward: f64, t: f64, weights: &[f64]) -> (Self, f64) {
+        let ks = strikes.iter().map(|&strike| (strike / forward).ln()).collect::<Vec<_>>();
+        let ws = vols.iter().map(|&vol| vol * vol * t).collect::<Vec<_>>();
+        let clamp_sigma = |log_sigma: f64| log_sigma.exp().clamp(SIGMA_BOUNDS.0, SIGMA_BOUNDS.1);
+
+        let vertex = ws.iter().enumerate().min_by(|a, b| a.1.total_cmp(b.1)).map_or(0.0, |(i, _)| ks[i]);
+        let spread = ks.iter().fold(0.0_f64, |acc, &k| acc.max((k - vertex).abs())).max(0.01);
+        let mut best: Option<(Vec<f64>, f64)> = None;
+        for start_sigma in [0.05, 0.2, 0.5] {
+            let (point, value) = nelder_mead(
+                |p| fit_linear(&ks, &ws, weights, p[0], clamp_sigma(p[1])).1,
+                &[vertex, f64::ln(start_sigma * spread)],
+                &[0.1 * spread, 0.5],
+                1e-18,
+                400,
+            );
+            if best.as_ref().is_none_or(|(_, best_value)| value < *best_value) {
+                best = Some((point, value));
+            }
+        }
+
+        let point = best.map_or(vec![vertex, 0.0], |(point, _)| point);
+        let (m, sigma) = (point[0], clamp_sigma(point[1]));
+        let ([a, d, c], _) = fit_linear(&ks, &ws, weights, m, sigma);
+        let smile = SviSmile {
+            a,
+            b: c / sigma,
+            rho: if c > 0.0 { d / c } else { 0.0 },
+            m,
+            sigma,
+            forward,
+            t,
+        };
+        let squared_error = strikes
+            .iter()
+            .zip(vols)
+            .map(|(&strike, &vol)| (smile.volatility(strike) - vol).powi(2))
+            .sum::<f64>();
+        (smile, (squared_error / strikes.len() as f64).sqrt())
+    }
+}
+
+/// Fits the total variance `a + d y + c √(y² + 1)` by weighted least squares subject to the
+/// SVI constraints, for fixed `m` and `σ`.
+///
+/// The problem is a convex quadratic program in three variables with six linear
+/// inequality constraints, so its minimum is the feasible minimum over the faces of the
+/// feasible set; each face is solved through its KKT equations.
+///
+/// # Returns
+///
+/// A tuple containing `(a, d, c)` and the weighted sum of squared errors of the fit.
+fn fit_linear(ks: &[f64], ws: &[f64], weights: &[f64], m: f64, sigma: f64) -> ([f64; 3], f64) {
+    let basis = ks
+        .iter()
+        .map(|&k| {
+            let y = (k - m) / sigma;
+            [1.0, y, (y * y + 1.0).sqrt()]
+        })
+        .collect::<Vec<_>>();
+    let mut normal = [[0.0; 3]; 3];
+    let mut projection = [0.0; 3];
+    for ((row, &w), &weight) in basis.iter().zip(ws).zip(weights) {
+        for i in 0..3 {
+            projection[i] += weight * row[i] * w;
+            for j in 0..3 {
+                normal[i][j] += weight * row[i] * row[j];
+            }
+        }
+    }
+
+    // The constraints as rows `g · x ≤ h`.
+    let max_w = ws.iter().fold(0.0_f64, |acc, &w| acc.max(w));
+    let constraints = [
+        ([-1.0, 0.0, 0.0], 0.0),
+        ([1.0, 0.0, 0.0], max_w),
+        ([0.0, 1.0, -1.0], 0.0),
+        ([0.0, -1.0, -1.0], 0.0),
+        ([0.0, 1.0, 1.0], 4.0 * sigma),
+        ([0.0, -1.0, 1.0], 4.0 * sigma),
+    ];
+    let feasible = |x: &[f64]| {
+        constraints
+            .iter()
+            .all(|(g, h)| g[0] * x[0] + g[1] * x[1] + g[2] * x[2] <= h + 1e-12 * (1.0 + h.abs()))
+    };
+    let squared_error = |x: &[f64]| {
+        basis
+            .iter()
+            .zip(ws)
+            .zip(weights)
+            .map(|((row, &w), &weight)| weight * (row[0] * x[0] + row[1] * x[1] + row[2] * x[2] - w).powi(2))
+            .sum::<f64>()
+    };
+
+    let mut best = ([0.0; 3], squared_error(&[0.0; 3]));
+    for active in 0..1usize << constraints.len() {
+        let rows = (0..constraints.len()).filter(|&i| active >> i & 1 == 1).collect::<Vec<_>>();
+        if rows.len() > 3 {
+            continue;
+        }
+        let size = 3 + rows.len();
+        let mut matrix = vec![vec![0.0; size]; size];
+        let mut rhs = vec![0.0; size];
+        for i in 0..3 {
+            matrix[i][..3].copy_from_slice(&normal[i]);
+            rhs[i] = projection[i];
+        }
+        for (index, &row) in rows.iter().enumerate() {
+            let (g, h) = constraints[row];
+            for j in 0..3 {
+                matrix[3 + index][j] = g[j];
+                matrix[j][3 + index] = g[j];
+            }
+            rhs[3 + index] = h;
+        }
+        if let Some(solution) = solve_linear_system(&matrix, &rhs) {
+            let x = [solution[0], solution[1], solution[2]];
+            if feasible(&x) {
+                let error = squared_error(&x);
+                if error < best.1 {
+                    best = (x, error);
+                }
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_recovers_the_smile_that_generated_the_quotes() {
+        let smile = SviSmile { a: 0.02, b: 0.1, rho: -0.4, m: 0.05, sigma: 0.15, forward: 100.0, t: 0.5 };
+        let strikes = (0..13).map(|i| 60.0 + 7.5 * f64::from(i)).collect::<Vec<_>>();
+        let vols = strikes.iter().map(|&strike| smile.volatility(strike)).collect::<Vec<_>>();
+        let (fitted, rmse) = SviSmile::fit(&strikes, &vols, 100.0, 0.5, &vec![1.0; strikes.len()]);
+        assert!(rmse < 1e-6, "{rmse}");
+        let parameters = |smile: SviSmile| [smile.a, smile.b, smile.rho, smile.m, smile.sigma];
+        for (fitted, exact) in parameters(fitted).into_iter().zip(parameters(smile)) {
+            assert!((fitted - exact).abs() < 1e-3, "{fitted} vs {exact}");
+        }
+    }
+}
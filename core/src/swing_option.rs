@@ -0,0 +1,145 @@
+// swing_option.rs
+
+use crate::binomial_tree_option::BinomialTreeOption;
+use crate::option_type::OptionType;
+
+/// Represents a swing option on a multi-layer Cox-Ross-Rubinstein lattice.
+///
+/// The contract has `n` exercise dates, one at each step of the tree after the first,
+/// so the last date is at expiration. On each date the holder may exercise at most one
+/// right, receiving `volume` times the intrinsic value `S − K` (calls) or `K − S` (puts),
+/// which may be negative. At most `max_exercises` rights may be used in total, and every
+/// exercise short of `min_exercises` at expiration costs `penalty` per unit of volume.
+///
+/// The lattice carries one layer of option values per number of rights already used, and
+/// the holder chooses at each node between continuing in the same layer and exercising
+/// into the next one (Jaillet, Ronn and Tompaidis, 2004).
+pub struct SwingOption {
+    /// The underlying binomial tree option.
+    pub tree: BinomialTreeOption,
+    /// The maximum number of exercise rights.
+    pub max_exercises: usize,
+    /// The minimum number of exercises required to avoid the penalty.
+    pub min_exercises: usize,
+    /// The volume delivered on each exercise.
+    pub volume: f64,
+    /// The penalty per unit of volume for each exercise short of `min_exercises`.
+    pub penalty: f64,
+}
+
+impl SwingOption {
+    /// Creates a new `SwingOption` instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The binomial tree option representing the underlying asset and option parameters.
+    /// * `max_exercises` - The maximum number of exercise rights.
+    /// * `min_exercises` - The minimum number of exercises required to avoid the penalty.
+    /// * `volume` - The volume delivered on each exercise.
+    /// * `penalty` - The penalty per unit of volume for each missing exercise.
+    ///
+    /// # Returns
+    ///
+    /// A new `SwingOption` instance.
+    pub fn new(tree: BinomialTreeOption, max_exercises: usize, min_exercises: usize, volume: f64, penalty: f64) -> Self {
+        SwingOption {
+            tree,
+            max_exercises,
+            min_exercises,
+            volume,
+            penalty,
+        }
+    }
+
+    /// Calculates the price of the swing option.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price(&mut self) -> f64 {
+        self.tree.setup_crr_parameters();
+        self.tree.init_stock_price_tree();
+
+        let option = &self.tree.option;
+        let n = option.n;
+        let (qu, qd, discount) = (self.tree.qu, self.tree.qd, option.df());
+        let max_exercises = self.max_exercises;
+        let exercise_value = |s: f64| {
+            let intrinsic = if option.option_type == OptionType::Call { s - option.k } else { option.k - s };
+            self.volume * intrinsic
+        };
+        let shortfall_penalty =
+            |used: usize| self.penalty * self.volume * self.min_exercises.saturating_sub(used) as f64;
+
+        // layers[m][j] is the value at node j with m rights already used.
+        let mut layers = (0..=max_exercises)
+            .map(|used| {
+                option.sts[n]
+                    .iter()
+                    .map(|&s| {
+                        let hold = -shortfall_penalty(used);
+                        if used < max_exercises {
+                            hold.max(exercise_value(s) - shortfall_penalty(used + 1))
+                        } else {
+                            hold
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for i in (0..n).rev() {
+            let continuation = layers
+                .iter()
+                .map(|values| {
+                    values
+                        .windows(2)
+                        .map(|w| (w[0] * qu + w[1] * qd) * discount)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            layers = (0..=max_exercises)
+                .map(|used| {
+                    if i == 0 || used == max_exercises {
+                        return continuation[used].clone();
+                    }
+                    continuation[used]
+                        .iter()
+                        .zip(&continuation[used + 1])
+                        .zip(&option.sts[i])
+                        .map(|((&hold, &exercised), &s)| hold.max(exercise_value(s) + exercised))
+                        .collect()
+                })
+                .collect();
+        }
+        layers[0][0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binomial_crr_option::BinomialCRROption;
+    use crate::option_type::ExerciseStyle;
+    use crate::stock_option::StockOption;
+
+    fn tree(option_type: OptionType) -> BinomialTreeOption {
+        BinomialTreeOption::new(StockOption::new(100.0, 100.0, 0.05, 1.0, 100, 0.02, 0.3, option_type, ExerciseStyle::American).unwrap())
+    }
+
+    #[test]
+    fn one_right_is_an_american_option() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let american = BinomialCRROption::new(tree(option_type)).price();
+            let swing = SwingOption::new(tree(option_type), 1, 0, 1.0, 0.0).price();
+            assert!((swing - american).abs() < 1e-10, "{option_type:?}: {swing} vs {american}");
+        }
+    }
+
+    #[test]
+    fn value_scales_with_the_volume() {
+        let single = SwingOption::new(tree(OptionType::Call), 3, 1, 1.0, 2.0).price();
+        let scaled = SwingOption::new(tree(OptionType::Call), 3, 1, 2.5, 2.0).price();
+        assert!((scaled - 2.5 * single).abs() < 1e-9, "{scaled} vs {single}");
+    }
+}
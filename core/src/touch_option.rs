@@ -0,0 +1,105 @@
+// touch_option.rs
+
+use std::sync::Arc;
+
+use crate::barrier::{Barrier, BarrierKind};
+use crate::black_scholes_option::BlackScholesOption;
+use crate::option_type::ExerciseStyle;
+use crate::payoff::{ClosurePayoff, PayoffKind};
+use crate::pricing_error::PricingError;
+use crate::stock_option::StockOption;
+
+/// Identifies whether a touch option pays when the barrier is hit or when it is not.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TouchKind {
+    /// Pays a fixed amount if the barrier is hit before expiration (an American binary).
+    OneTouch,
+    /// Pays a fixed amount at expiration if the barrier is never hit.
+    NoTouch,
+}
+
+/// Represents a one-touch or no-touch binary option on a single barrier.
+///
+/// On the lattice a touch option is a European knock-out whose vanilla payoff is zero
+/// (one-touch) or the cash amount (no-touch), with the cash paid as the knock-out rebate
+/// of a one-touch. The strike of the underlying option is moved onto the barrier, which
+/// places the barrier on a row of nodes of the Leisen-Reimer tree.
+pub struct TouchOption {
+    /// The underlying option, configured as the equivalent knock-out.
+    pub option: StockOption,
+    /// Whether the option pays when the barrier is hit or when it is not.
+    pub kind: TouchKind,
+    /// The fixed amount paid.
+    pub cash: f64,
+}
+
+impl TouchOption {
+    /// Creates a new `TouchOption` instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying option; its strike, payoff, exercise style, and barriers are replaced.
+    /// * `barrier` - The barrier; its kind and rebate are ignored, and its rebate timing is
+    ///   when a one-touch pays. A no-touch always pays at expiration.
+    /// * `kind` - Whether the option pays when the barrier is hit or when it is not.
+    /// * `cash` - The fixed amount paid.
+    ///
+    /// # Returns
+    ///
+    /// A new `TouchOption` instance.
+    pub fn new(mut option: StockOption, barrier: Barrier, kind: TouchKind, cash: f64) -> Self {
+        let (terminal, rebate) = match kind {
+            TouchKind::OneTouch => (0.0, cash),
+            TouchKind::NoTouch => (cash, 0.0),
+        };
+        option.k = barrier.level;
+        option.exercise = ExerciseStyle::European;
+        option.exercise_dates = None;
+        option.payoff = PayoffKind::Custom(Arc::new(ClosurePayoff(move |_| terminal)));
+        option.barrier = Some(Barrier {
+            kind: BarrierKind::KnockOut,
+            rebate,
+            ..barrier
+        });
+        option.double_barrier = None;
+        TouchOption { option, kind, cash }
+    }
+
+    /// Calculates the analytic price of the touch option under Black-Scholes dynamics.
+    ///
+    /// # Returns
+    ///
+    /// The price of the option.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the barrier of the option has been removed.
+    pub fn analytic_price(self) -> Result<f64, PricingError> {
+        let barrier = self
+            .option
+            .barrier
+            .ok_or_else(|| PricingError::InvalidInput("A touch option requires a barrier.".to_string()))?;
+        let analytic = BlackScholesOption::new(self.option);
+        let value = match self.kind {
+            TouchKind::OneTouch => analytic.one_touch(&barrier, barrier.rebate_timing),
+            TouchKind::NoTouch => analytic.no_touch(&barrier),
+        };
+        Ok(self.cash * value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::barrier::BarrierDirection;
+    use crate::option_type::OptionType;
+
+    #[test]
+    fn analytic_price_without_a_barrier_is_invalid_input() {
+        let option = StockOption::new(100.0, 100.0, 0.05, 1.0, 1, 0.0, 0.2, OptionType::Call, ExerciseStyle::European).unwrap();
+        let barrier = Barrier::new(110.0, BarrierDirection::Up, BarrierKind::KnockOut, 0.0);
+        let mut touch = TouchOption::new(option, barrier, TouchKind::OneTouch, 1.0);
+        touch.option.barrier = None;
+        assert!(matches!(touch.analytic_price(), Err(PricingError::InvalidInput(_))));
+    }
+}
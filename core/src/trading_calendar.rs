@@ -0,0 +1,238 @@
+// trading_calendar.rs
+
+use crate::day_count::Date;
+
+/// The number of trading days per year, by which business-day counts and daily steps are
+/// converted to and from years.
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Identifies how a date that falls on a non-business day is rolled to a business day.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BusinessDayConvention {
+    /// The date is kept as it is.
+    Unadjusted,
+    /// The date rolls forward to the next business day.
+    Following,
+    /// The date rolls forward, unless that crosses into the next month, in which case it
+    /// rolls backward.
+    ModifiedFollowing,
+    /// The date rolls backward to the previous business day.
+    Preceding,
+}
+
+/// Represents an exchange calendar of business days.
+///
+/// Saturdays and Sundays are never business days. The built-in US equity calendar adds
+/// the regular NYSE holidays, observed on the nearest weekday when they fall on a weekend,
+/// and any further closures are listed explicitly.
+#[derive(Clone)]
+pub struct TradingCalendar {
+    /// Whether the regular US equity market holidays are closed.
+    pub us_equity: bool,
+    /// The additional dates on which the exchange is closed.
+    pub holidays: Vec<Date>,
+}
+
+impl TradingCalendar {
+    /// Creates a new `TradingCalendar` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `us_equity` - Whether the regular US equity market holidays are closed.
+    /// * `holidays` - The additional dates on which the exchange is closed.
+    ///
+    /// # Returns
+    ///
+    /// A new `TradingCalendar` instance.
+    pub fn new(us_equity: bool, holidays: Vec<Date>) -> Self {
+        TradingCalendar { us_equity, holidays }
+    }
+
+    /// Determines whether the exchange is open on a date.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the date is a weekday that is not a holiday.
+    pub fn is_business_day(&self, date: Date) -> bool {
+        date.weekday() < 5
+            && !self.holidays.contains(&date)
+            && !(self.us_equity && us_equity_holidays(date.year).contains(&date))
+    }
+
+    /// Counts the business days in the period `(start, end]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start date of the period, which is not counted.
+    /// * `end` - The end date of the period, which is counted.
+    ///
+    /// # Returns
+    ///
+    /// The number of business days, negative if `end` is before `start`.
+    pub fn business_days(&self, start: Date, end: Date) -> i64 {
+        let (first, last, sign) = if start <= end { (start, end, 1) } else { (end, start, -1) };
+        let count = (first.serial() + 1..=last.serial())
+            .filter(|&serial| self.is_business_day(Date::from_serial(serial)))
+            .count();
+        sign * count as i64
+    }
+
+    /// Rolls a date to a business day.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date to roll.
+    /// * `convention` - The business-day convention.
+    ///
+    /// # Returns
+    ///
+    /// The adjusted date, which is `date` itself if it is a business day.
+    pub fn adjust(&self, date: Date, convention: BusinessDayConvention) -> Date {
+        let roll = |step: i64| {
+            let mut rolled = date;
+            while !self.is_business_day(rolled) {
+                rolled = rolled.add_days(step);
+            }
+            rolled
+        };
+        match convention {
+            BusinessDayConvention::Unadjusted => date,
+            BusinessDayConvention::Following => roll(1),
+            BusinessDayConvention::ModifiedFollowing => {
+                let following = roll(1);
+                if following.month == date.month {
+                    following
+                } else {
+                    roll(-1)
+                }
+            }
+            BusinessDayConvention::Preceding => roll(-1),
+        }
+    }
+}
+
+/// Returns the regular US equity market holidays of a year, as observed.
+///
+/// Holidays on a Saturday are observed on the Friday before and holidays on a Sunday on
+/// the Monday after, except that New Year's Day is not observed when it falls on a
+/// Saturday. Martin Luther King Jr. Day is included from 1998 and Juneteenth from 2022.
+fn us_equity_holidays(year: i32) -> Vec<Date> {
+    let observed = |date: Date| match date.weekday() {
+        5 => date.add_days(-1),
+        6 => date.add_days(1),
+        _ => date,
+    };
+    let mut holidays = vec![
+        nth_weekday(year, 2, 0, 3),
+        easter(year).add_days(-2),
+        last_weekday(year, 5, 0),
+        observed(Date { year, month: 7, day: 4 }),
+        nth_weekday(year, 9, 0, 1),
+        nth_weekday(year, 11, 3, 4),
+        observed(Date { year, month: 12, day: 25 }),
+    ];
+    let new_year = Date { year, month: 1, day: 1 };
+    if new_year.weekday() != 5 {
+        holidays.push(observed(new_year));
+    }
+    if year >= 1998 {
+        holidays.push(nth_weekday(year, 1, 0, 3));
+    }
+    if year >= 2022 {
+        holidays.push(observed(Date { year, month: 6, day: 19 }));
+    }
+    holidays
+}
+
+/// Returns the `n`-th occurrence of a weekday (0 for Monday) in a month.
+fn nth_weekday(year: i32, month: u32, weekday: u32, n: u32) -> Date {
+    let first = Date { year, month, day: 1 };
+    let offset = (weekday + 7 - first.weekday()) % 7;
+    first.add_days(i64::from(offset + 7 * (n - 1)))
+}
+
+/// Returns the last occurrence of a weekday (0 for Monday) in a month.
+fn last_weekday(year: i32, month: u32, weekday: u32) -> Date {
+    let next_month = if month == 12 { Date { year: year + 1, month: 1, day: 1 } } else { Date { year, month: month + 1, day: 1 } };
+    let last = next_month.add_days(-1);
+    let offset = (last.weekday() + 7 - weekday) % 7;
+    last.add_days(-i64::from(offset))
+}
+
+/// Returns the date of Easter Sunday in the Gregorian calendar, by the anonymous
+/// Gregorian algorithm (Meeus/Jones/Butcher).
+fn easter(year: i32) -> Date {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    Date { year, month: month as u32, day: day as u32 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_count::DayCount;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn us_equity_holidays_are_observed_on_weekdays() {
+        let calendar = TradingCalendar::new(true, Vec::new());
+        // Independence Day 2026 falls on a Saturday and is observed on Friday 3 July.
+        assert!(!calendar.is_business_day(date(2026, 7, 3)));
+        assert!(calendar.is_business_day(date(2026, 7, 2)));
+        // Good Friday, Thanksgiving, and Juneteenth.
+        assert!(!calendar.is_business_day(date(2026, 4, 3)));
+        assert!(!calendar.is_business_day(date(2026, 11, 26)));
+        assert!(!calendar.is_business_day(date(2026, 6, 19)));
+        assert!(!calendar.is_business_day(date(2026, 10, 17)));
+        let weekends = TradingCalendar::new(false, vec![date(2026, 10, 16)]);
+        assert!(weekends.is_business_day(date(2026, 7, 3)));
+        assert!(!weekends.is_business_day(date(2026, 10, 16)));
+    }
+
+    #[test]
+    fn holidays_roll_by_the_business_day_convention() {
+        let calendar = TradingCalendar::new(true, Vec::new());
+        let holiday = date(2026, 7, 3);
+        assert_eq!(calendar.adjust(holiday, BusinessDayConvention::Unadjusted), holiday);
+        assert_eq!(calendar.adjust(holiday, BusinessDayConvention::Following), date(2026, 7, 6));
+        assert_eq!(calendar.adjust(holiday, BusinessDayConvention::ModifiedFollowing), date(2026, 7, 6));
+        assert_eq!(calendar.adjust(holiday, BusinessDayConvention::Preceding), date(2026, 7, 2));
+        // Saturday 31 January 2026 rolls forward into February, so modified following
+        // rolls back to Friday 30 January.
+        let month_end = date(2026, 1, 31);
+        assert_eq!(calendar.adjust(month_end, BusinessDayConvention::Following), date(2026, 2, 2));
+        assert_eq!(calendar.adjust(month_end, BusinessDayConvention::ModifiedFollowing), date(2026, 1, 30));
+        let business_day = date(2026, 10, 15);
+        assert_eq!(calendar.adjust(business_day, BusinessDayConvention::Following), business_day);
+    }
+
+    #[test]
+    fn business_days_skip_weekends_and_holidays() {
+        let calendar = TradingCalendar::new(true, Vec::new());
+        // New Year's Day 2026 is a Thursday, so only Friday 2 January counts.
+        assert_eq!(calendar.business_days(date(2025, 12, 31), date(2026, 1, 4)), 1);
+        assert_eq!(calendar.business_days(date(2026, 1, 4), date(2025, 12, 31)), -1);
+        assert_eq!(calendar.business_days(date(2026, 1, 1), date(2027, 1, 1)), 251);
+        let fraction = DayCount::Business252(calendar).year_fraction(date(2026, 10, 9), date(2026, 10, 16));
+        assert_eq!(fraction, 5.0 / TRADING_DAYS_PER_YEAR);
+    }
+}
@@ -0,0 +1,144 @@
+// trinomial_tree_option.rs
+
+use crate::payoff::Payoff;
+use crate::pricing_error::PricingError;
+use crate::stock_option::StockOption;
+
+/// Represents a trinomial tree option pricing model.
+///
+/// At each step the logarithm of the stock price, net of the escrowed cash dividends of
+/// any dividend schedule, moves up or down by `σ√(3Δt)` or stays put (Hull's
+/// parameterization). The middle branch has a probability of 2/3 and the outer branches
+/// match the drift of the step, which follows the yield curve when the option has one.
+/// Early exercise is checked at every step allowed by the exercise schedule.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrinomialTreeOption {
+    /// The underlying stock option.
+    pub option: StockOption,
+}
+
+impl TrinomialTreeOption {
+    /// Creates a new `TrinomialTreeOption` instance with the given stock option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying stock option.
+    ///
+    /// # Returns
+    ///
+    /// A new `TrinomialTreeOption` instance.
+    pub fn new(option: StockOption) -> Self {
+        TrinomialTreeOption { option }
+    }
+
+    /// Returns the move of the log price over one step, `σ√(3Δt)`.
+    pub fn log_move(&self) -> f64 {
+        self.option.sigma * (3.0 * self.option.dt()).sqrt()
+    }
+
+    /// Calculates the risk-neutral probabilities of the moves over a time step.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - The index of the time step.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the probabilities of an up move, no move, and a down move.
+    pub fn probabilities(&self, i: usize) -> (f64, f64, f64) {
+        let option = &self.option;
+        let drift = option.step_growth(i).ln() - option.sigma.powi(2) * option.dt() / 2.0;
+        let skew = drift / (2.0 * self.log_move());
+        (1.0 / 6.0 + skew, 2.0 / 3.0, 1.0 / 6.0 - skew)
+    }
+
+    /// Checks that the probabilities of the moves lie in `[0, 1]` at every step.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the drift of a step is too large for the
+    /// outer branches to match.
+    pub fn check_probabilities(&self) -> Result<(), PricingError> {
+        for i in 0..self.option.n {
+            let (up, _, down) = self.probabilities(i);
+            if let Some(p) = [up, down].into_iter().find(|p| !(0.0..=1.0).contains(p)) {
+                return Err(PricingError::InvalidInput(format!(
+                    "The risk-neutral probabilities of the trinomial tree must be in [0, 1], not {p}. \
+                     Use more steps or a higher volatility."
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Calculates the price of the option by backward induction on the tree.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price(&self) -> f64 {
+        let option = &self.option;
+        let n = option.n;
+        let (log_move, x0) = (self.log_move(), option.net_spot());
+        let schedule = option.exercise_schedule();
+        // Node `j` of step `i` lies `j − i` moves above the initial net price.
+        let exercise_value = |i: usize, j: usize, time: f64| {
+            let x = x0 * ((j as f64 - i as f64) * log_move).exp();
+            option.payoff.value(option.stock_price_at(x, time), option.k, option.option_type)
+        };
+
+        let mut values = (0..=2 * n).map(|j| exercise_value(n, j, option.t)).collect::<Vec<_>>();
+        for i in (0..n).rev() {
+            let (up, middle, down) = self.probabilities(i);
+            let df = option.step_df(i);
+            let time = option.step_time(i);
+            for j in 0..=2 * i {
+                let continuation = df * (up * values[j + 2] + middle * values[j + 1] + down * values[j]);
+                values[j] = if schedule[i] {
+                    continuation.max(exercise_value(i, j, time))
+                } else {
+                    continuation
+                };
+            }
+            values.truncate(2 * i + 1);
+        }
+        values[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binomial_lr_option::BinomialLROption;
+    use crate::binomial_tree_option::BinomialTreeOption;
+    use crate::black_scholes_option::BlackScholesOption;
+    use crate::option_type::{ExerciseStyle, OptionType};
+
+    #[test]
+    fn european_price_converges_to_black_scholes() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            let option = StockOption::new(100.0, 105.0, 0.05, 1.0, 500, 0.02, 0.25, option_type, ExerciseStyle::European).unwrap();
+            let exact = BlackScholesOption::new(option.clone()).price();
+            let price = TrinomialTreeOption::new(option).price();
+            assert!((price - exact).abs() < 5e-3, "{price} vs {exact}");
+        }
+    }
+
+    #[test]
+    fn american_put_agrees_with_the_leisen_reimer_tree() {
+        // Hull's American put, worth 4.49 on a five-step tree and about 4.284 in the limit.
+        let option = StockOption::new(50.0, 50.0, 0.1, 5.0 / 12.0, 1000, 0.0, 0.4, OptionType::Put, ExerciseStyle::American).unwrap();
+        let lr = BinomialLROption::new(BinomialTreeOption::new(StockOption { n: 2001, ..option.clone() })).price();
+        let price = TrinomialTreeOption::new(option).price();
+        assert!((price - lr).abs() < 2e-3, "{price} vs {lr}");
+        assert!((price - 4.284).abs() < 2e-3, "{price}");
+    }
+
+    #[test]
+    fn rejects_a_drift_the_branches_cannot_match() {
+        let option = StockOption::new(100.0, 100.0, 0.5, 1.0, 1, 0.0, 0.05, OptionType::Call, ExerciseStyle::European).unwrap();
+        let tree = TrinomialTreeOption::new(option);
+        assert!(matches!(tree.check_probabilities(), Err(PricingError::InvalidInput(_))));
+    }
+}
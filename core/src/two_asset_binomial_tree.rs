@@ -0,0 +1,233 @@
+// two_asset_binomial_tree.rs
+
+use crate::option_type::ExerciseStyle;
+use crate::pricing_error::PricingError;
+use crate::two_asset_option::TwoAssetOption;
+
+/// Represents the Boyle-Evnine-Gibbs two-asset binomial lattice.
+///
+/// At each step both assets move up or down by `u_i = e^{σ_i √Δt}` and `d_i = 1 / u_i`,
+/// giving four branches whose probabilities match the means, variances, and correlation
+/// of the two log-price processes.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoAssetBinomialTree {
+    /// The underlying two-asset option.
+    pub option: TwoAssetOption,
+    /// The up factor of the first asset.
+    pub u1: f64,
+    /// The up factor of the second asset.
+    pub u2: f64,
+    /// The probability of both assets moving up.
+    pub puu: f64,
+    /// The probability of the first asset moving up and the second moving down.
+    pub pud: f64,
+    /// The probability of the first asset moving down and the second moving up.
+    pub pdu: f64,
+    /// The probability of both assets moving down.
+    pub pdd: f64,
+}
+
+impl TwoAssetBinomialTree {
+    /// Creates a new `TwoAssetBinomialTree` instance with the given two-asset option.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The underlying two-asset option.
+    ///
+    /// # Returns
+    ///
+    /// A new `TwoAssetBinomialTree` instance.
+    pub fn new(option: TwoAssetOption) -> Self {
+        TwoAssetBinomialTree {
+            option,
+            u1: 0.0,
+            u2: 0.0,
+            puu: 0.0,
+            pud: 0.0,
+            pdu: 0.0,
+            pdd: 0.0,
+        }
+    }
+
+    /// Sets up the move factors and branch probabilities of the lattice.
+    ///
+    /// The probabilities follow Boyle, Evnine, and Gibbs (1989), using the risk-neutral
+    /// log drifts `ν_i = r - q_i - σ_i² / 2`.
+    pub fn setup_parameters(&mut self) {
+        let dt = self.option.dt();
+        let sqrt_dt = dt.sqrt();
+        self.u1 = (self.option.sigma1 * sqrt_dt).exp();
+        self.u2 = (self.option.sigma2 * sqrt_dt).exp();
+
+        let nu1 = self.option.r - self.option.div1 - self.option.sigma1.powi(2) / 2.0;
+        let nu2 = self.option.r - self.option.div2 - self.option.sigma2.powi(2) / 2.0;
+        let drift1 = sqrt_dt * nu1 / self.option.sigma1;
+        let drift2 = sqrt_dt * nu2 / self.option.sigma2;
+
+        self.puu = 0.25 * (1.0 + self.option.rho + drift1 + drift2);
+        self.pud = 0.25 * (1.0 - self.option.rho + drift1 - drift2);
+        self.pdu = 0.25 * (1.0 - self.option.rho - drift1 + drift2);
+        self.pdd = 0.25 * (1.0 + self.option.rho - drift1 - drift2);
+    }
+
+    /// Checks that the four branch probabilities lie in `[0, 1]`.
+    ///
+    /// With a strong correlation or a drift that is large against the volatilities, a
+    /// coarse lattice can only match the moments with a negative probability.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` naming the first offending branch.
+    pub fn check_probabilities(&self) -> Result<(), PricingError> {
+        let branches = [("puu", self.puu), ("pud", self.pud), ("pdu", self.pdu), ("pdd", self.pdd)];
+        match branches.iter().find(|(_, p)| !(0.0..=1.0).contains(p)) {
+            Some((name, p)) => Err(PricingError::InvalidInput(format!(
+                "The branch probability {name} must be in [0, 1], not {p}. \
+                 Use more steps, higher volatilities, or a weaker correlation."
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the lattice of the option has at least one step and valid branch
+    /// probabilities.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the correlation is outside `[-1, 1]`, the
+    /// number of steps is zero, or a branch probability lies outside `[0, 1]`.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        self.option.validate()?;
+        if self.option.n == 0 {
+            return Err(PricingError::InvalidInput("The number of steps must be positive, not 0.".to_string()));
+        }
+        let mut tree = TwoAssetBinomialTree::new(self.option.clone());
+        tree.setup_parameters();
+        tree.check_probabilities()
+    }
+
+    /// Calculates the asset prices at a node of the lattice.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - The time step of the node.
+    /// * `j1` - The number of down moves of the first asset.
+    /// * `j2` - The number of down moves of the second asset.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the prices of the first and second asset at the node.
+    pub fn node_prices(&self, step: usize, j1: usize, j2: usize) -> (f64, f64) {
+        let s1 = self.option.s1 * self.u1.powi(step as i32 - 2 * j1 as i32);
+        let s2 = self.option.s2 * self.u2.powi(step as i32 - 2 * j2 as i32);
+        (s1, s2)
+    }
+
+    /// Traverses the lattice backward to calculate the option values at the root.
+    ///
+    /// Values at each step are stored in a flat `(step + 1) × (step + 1)` grid indexed by
+    /// the number of down moves of each asset. Early exercise is checked at every node
+    /// for American options.
+    ///
+    /// # Returns
+    ///
+    /// The option value at the root node.
+    pub fn begin_tree_traversal(&self) -> f64 {
+        let n = self.option.n;
+        let k = self.option.k;
+        let df = self.option.df();
+
+        let mut values = Vec::with_capacity((n + 1) * (n + 1));
+        for j1 in 0..=n {
+            for j2 in 0..=n {
+                let (s1, s2) = self.node_prices(n, j1, j2);
+                values.push(self.option.payoff.value(s1, s2, k));
+            }
+        }
+
+        for step in (0..n).rev() {
+            let width = step + 1;
+            let child_width = step + 2;
+            let mut level = Vec::with_capacity(width * width);
+            for j1 in 0..width {
+                for j2 in 0..width {
+                    let child = |d1: usize, d2: usize| values[(j1 + d1) * child_width + j2 + d2];
+                    let mut value = (self.puu * child(0, 0)
+                        + self.pud * child(0, 1)
+                        + self.pdu * child(1, 0)
+                        + self.pdd * child(1, 1))
+                        * df;
+                    if self.option.exercise == ExerciseStyle::American {
+                        let (s1, s2) = self.node_prices(step, j1, j2);
+                        value = value.max(self.option.payoff.value(s1, s2, k));
+                    }
+                    level.push(value);
+                }
+            }
+            values = level;
+        }
+
+        values[0]
+    }
+
+    /// Calculates the price of the option using the two-asset lattice.
+    ///
+    /// The lattice is set up on a scratch copy, so the tree itself is never changed.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price(&self) -> f64 {
+        self.price_option(&self.option)
+    }
+
+    /// Validates the lattice and calculates the price of the option.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `validate`.
+    pub fn try_price(&self) -> Result<f64, PricingError> {
+        self.validate()?;
+        Ok(self.price())
+    }
+
+    /// Calculates the price of another option using the two-asset lattice.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to price.
+    ///
+    /// # Returns
+    ///
+    /// The calculated price of the option.
+    pub fn price_option(&self, option: &TwoAssetOption) -> f64 {
+        let mut tree = TwoAssetBinomialTree::new(option.clone());
+        tree.setup_parameters();
+        tree.begin_tree_traversal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::two_asset_option::TwoAssetPayoff;
+
+    #[test]
+    fn rejects_negative_branch_probabilities() {
+        let option = |n: usize, r: f64, sigma1: f64| {
+            TwoAssetOption::new(
+                100.0, 100.0, 0.0, r, 1.0, n, 0.0, 0.0, sigma1, 0.4, 0.9,
+                TwoAssetPayoff::Exchange, ExerciseStyle::European,
+            )
+        };
+        assert!(TwoAssetBinomialTree::new(option(100, 0.05, 0.2)).try_price().is_ok());
+        // A drift far larger than the first volatility makes pdu negative on one step.
+        assert!(matches!(
+            TwoAssetBinomialTree::new(option(1, 0.3, 0.1)).try_price(),
+            Err(PricingError::InvalidInput(_))
+        ));
+        let no_steps = TwoAssetBinomialTree::new(option(0, 0.05, 0.2));
+        assert!(matches!(no_steps.validate(), Err(PricingError::InvalidInput(_))));
+    }
+}
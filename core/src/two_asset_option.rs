@@ -0,0 +1,159 @@
+// two_asset_option.rs
+
+use std::f64::consts::E;
+use crate::option_type::ExerciseStyle;
+use crate::pricing_error::PricingError;
+
+/// Represents the payoff of an option written on two underlying assets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TwoAssetPayoff {
+    /// A call on the spread: `max(S1 - S2 - K, 0)`.
+    SpreadCall,
+    /// A put on the spread: `max(K - (S1 - S2), 0)`.
+    SpreadPut,
+    /// The option to exchange asset 2 for asset 1: `max(S1 - S2, 0)`.
+    Exchange,
+    /// A call on the better of the two assets: `max(max(S1, S2) - K, 0)`.
+    BestOfCall,
+    /// A put on the better of the two assets: `max(K - max(S1, S2), 0)`.
+    BestOfPut,
+    /// A call on the worse of the two assets: `max(min(S1, S2) - K, 0)`.
+    WorstOfCall,
+    /// A put on the worse of the two assets: `max(K - min(S1, S2), 0)`.
+    WorstOfPut,
+}
+
+impl TwoAssetPayoff {
+    /// Calculates the payoff for the given asset prices and strike.
+    ///
+    /// # Arguments
+    ///
+    /// * `s1` - The price of the first asset.
+    /// * `s2` - The price of the second asset.
+    /// * `k` - The strike price of the option.
+    ///
+    /// # Returns
+    ///
+    /// The payoff of the option.
+    pub fn value(&self, s1: f64, s2: f64, k: f64) -> f64 {
+        match self {
+            TwoAssetPayoff::SpreadCall => (s1 - s2 - k).max(0.0),
+            TwoAssetPayoff::SpreadPut => (k - (s1 - s2)).max(0.0),
+            TwoAssetPayoff::Exchange => (s1 - s2).max(0.0),
+            TwoAssetPayoff::BestOfCall => (s1.max(s2) - k).max(0.0),
+            TwoAssetPayoff::BestOfPut => (k - s1.max(s2)).max(0.0),
+            TwoAssetPayoff::WorstOfCall => (s1.min(s2) - k).max(0.0),
+            TwoAssetPayoff::WorstOfPut => (k - s1.min(s2)).max(0.0),
+        }
+    }
+}
+
+/// Represents an option on two correlated underlying assets with its associated parameters.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoAssetOption {
+    /// The initial price of the first asset.
+    pub s1: f64,
+    /// The initial price of the second asset.
+    pub s2: f64,
+    /// The strike price of the option.
+    pub k: f64,
+    /// The risk-free interest rate.
+    pub r: f64,
+    /// The time to expiration of the option (in years).
+    pub t: f64,
+    /// The number of time steps in the lattice.
+    pub n: usize,
+    /// The continuous dividend yield of the first asset.
+    pub div1: f64,
+    /// The continuous dividend yield of the second asset.
+    pub div2: f64,
+    /// The volatility of the first asset.
+    pub sigma1: f64,
+    /// The volatility of the second asset.
+    pub sigma2: f64,
+    /// The correlation between the returns of the two assets.
+    pub rho: f64,
+    /// The payoff of the option.
+    pub payoff: TwoAssetPayoff,
+    /// Whether the option is European-style or American-style.
+    pub exercise: ExerciseStyle,
+}
+
+impl TwoAssetOption {
+    /// Creates a new `TwoAssetOption` instance with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `s1` - The initial price of the first asset.
+    /// * `s2` - The initial price of the second asset.
+    /// * `k` - The strike price of the option.
+    /// * `r` - The risk-free interest rate.
+    /// * `t` - The time to expiration of the option (in years).
+    /// * `n` - The number of time steps in the lattice.
+    /// * `div1` - The continuous dividend yield of the first asset.
+    /// * `div2` - The continuous dividend yield of the second asset.
+    /// * `sigma1` - The volatility of the first asset.
+    /// * `sigma2` - The volatility of the second asset.
+    /// * `rho` - The correlation between the returns of the two assets.
+    /// * `payoff` - The payoff of the option.
+    /// * `exercise` - Whether the option is European-style or American-style.
+    ///
+    /// # Returns
+    ///
+    /// A new `TwoAssetOption` instance with the provided parameters.
+    pub fn new(
+        s1: f64,
+        s2: f64,
+        k: f64,
+        r: f64,
+        t: f64,
+        n: usize,
+        div1: f64,
+        div2: f64,
+        sigma1: f64,
+        sigma2: f64,
+        rho: f64,
+        payoff: TwoAssetPayoff,
+        exercise: ExerciseStyle,
+    ) -> Self {
+        TwoAssetOption {
+            s1,
+            s2,
+            k,
+            r,
+            t,
+            n: n.max(1),
+            div1,
+            div2,
+            sigma1,
+            sigma2,
+            rho,
+            payoff,
+            exercise,
+        }
+    }
+
+    /// Checks that the correlation of the two assets is in `[-1, 1]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if `rho` is outside `[-1, 1]` or not finite.
+    pub fn validate(&self) -> Result<(), PricingError> {
+        if !(-1.0..=1.0).contains(&self.rho) {
+            return Err(PricingError::InvalidInput("Invalid rho. Must be in the range [-1, 1].".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Calculates the time step size (Δt) of the lattice.
+    pub fn dt(&self) -> f64 {
+        self.t / self.n as f64
+    }
+
+    /// Calculates the discount factor for each time step.
+    pub fn df(&self) -> f64 {
+        E.powf(-self.r * self.dt())
+    }
+}
@@ -0,0 +1,196 @@
+// vol_surface.rs
+
+use crate::arbitrage::{find_arbitrage, ArbitrageReport};
+use crate::numerics::{cubic_spline, cubic_spline_curvatures, interpolate};
+use crate::pricing_engine::PricingEngine;
+use crate::pricing_error::PricingError;
+use crate::workspace::Workspace;
+
+/// The interpolation of the volatility smile of each expiration across strikes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SmileInterpolation {
+    /// Linear interpolation of the variance between strikes.
+    LinearVariance,
+    /// A natural cubic spline through the volatilities.
+    CubicSpline,
+}
+
+/// Represents an implied volatility surface quoted on a grid of expirations and strikes.
+///
+/// Each expiration's smile is interpolated across strikes with the chosen
+/// `SmileInterpolation`. Between expirations the total variance `σ²T` is interpolated
+/// linearly in time, which keeps forward variances positive when the quotes are free of
+/// calendar arbitrage. Outside the grid the volatility is extrapolated flat in both
+/// strike and expiration.
+///
+/// With the `serde` feature the surface serializes as its quotes and interpolation, and
+/// is rebuilt through `VolSurface::new` when it is deserialized, so invalid quotes are
+/// rejected.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "VolSurfaceQuotes"))]
+pub struct VolSurface {
+    /// The increasing expirations of the quotes (in years).
+    pub expiries: Vec<f64>,
+    /// The increasing strike prices of the quotes.
+    pub strikes: Vec<f64>,
+    /// The implied volatilities, with one row per expiration and one column per strike.
+    pub vols: Vec<Vec<f64>>,
+    /// The interpolation of each smile across strikes.
+    pub interpolation: SmileInterpolation,
+    /// The second derivatives of the spline through each smile.
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    curvatures: Vec<Vec<f64>>,
+}
+
+/// The serialized fields of a `VolSurface`, which are validated when it is rebuilt.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct VolSurfaceQuotes {
+    expiries: Vec<f64>,
+    strikes: Vec<f64>,
+    vols: Vec<Vec<f64>>,
+    interpolation: SmileInterpolation,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<VolSurfaceQuotes> for VolSurface {
+    type Error = PricingError;
+
+    fn try_from(quotes: VolSurfaceQuotes) -> Result<Self, PricingError> {
+        VolSurface::new(quotes.expiries, quotes.strikes, quotes.vols, quotes.interpolation)
+    }
+}
+
+impl VolSurface {
+    /// Creates a new `VolSurface` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `expiries` - The increasing expirations of the quotes (in years).
+    /// * `strikes` - The increasing strike prices of the quotes.
+    /// * `vols` - The implied volatilities, with one row per expiration and one column per strike.
+    /// * `interpolation` - The interpolation of each smile across strikes.
+    ///
+    /// # Returns
+    ///
+    /// A new `VolSurface` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the expirations or strikes are not positive
+    /// and increasing, or if the volatilities do not form a positive grid of matching shape.
+    pub fn new(
+        expiries: Vec<f64>,
+        strikes: Vec<f64>,
+        vols: Vec<Vec<f64>>,
+        interpolation: SmileInterpolation,
+    ) -> Result<Self, PricingError> {
+        let increasing = |values: &[f64]| {
+            !values.is_empty()
+                && values[0] > 0.0
+                && values.windows(2).all(|w| w[0] < w[1])
+                && values[values.len() - 1].is_finite()
+        };
+        if !increasing(&expiries) || !increasing(&strikes) {
+            return Err(PricingError::InvalidInput(
+                "Invalid surface. The expiries and strikes must be positive and increasing.".to_string(),
+            ));
+        }
+        let valid_grid = vols.len() == expiries.len()
+            && vols.iter().all(|row| row.len() == strikes.len() && row.iter().all(|&vol| vol > 0.0 && vol.is_finite()));
+        if !valid_grid {
+            return Err(PricingError::InvalidInput(
+                "Invalid surface. vols must have one row of positive volatilities per expiry, with one column per strike."
+                    .to_string(),
+            ));
+        }
+        let curvatures = vols.iter().map(|row| cubic_spline_curvatures(&strikes, row)).collect();
+        Ok(VolSurface {
+            expiries,
+            strikes,
+            vols,
+            interpolation,
+            curvatures,
+        })
+    }
+
+    /// Calculates the volatility of the smile of one quoted expiration at a strike.
+    fn smile_volatility(&self, expiry: usize, k: f64) -> f64 {
+        let row = &self.vols[expiry];
+        match self.interpolation {
+            SmileInterpolation::LinearVariance => {
+                let variances = row.iter().map(|vol| vol * vol).collect::<Vec<_>>();
+                interpolate(&self.strikes, &variances, k).sqrt()
+            }
+            SmileInterpolation::CubicSpline => {
+                cubic_spline(&self.strikes, row, &self.curvatures[expiry], k).max(0.0)
+            }
+        }
+    }
+
+    /// Calculates the implied volatility at a strike and expiration.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The strike price.
+    /// * `t` - The time to expiration (in years).
+    ///
+    /// # Returns
+    ///
+    /// The interpolated implied volatility.
+    pub fn volatility(&self, k: f64, t: f64) -> f64 {
+        let last = self.expiries.len() - 1;
+        if t <= self.expiries[0] {
+            return self.smile_volatility(0, k);
+        }
+        if t >= self.expiries[last] {
+            return self.smile_volatility(last, k);
+        }
+        let upper = self.expiries.partition_point(|&expiry| expiry < t);
+        let lower = upper - 1;
+        let (t_lower, t_upper) = (self.expiries[lower], self.expiries[upper]);
+        let w_lower = self.smile_volatility(lower, k).powi(2) * t_lower;
+        let w_upper = self.smile_volatility(upper, k).powi(2) * t_upper;
+        let weight = (t - t_lower) / (t_upper - t_lower);
+        ((w_lower + weight * (w_upper - w_lower)) / t).sqrt()
+    }
+
+    /// Prices an option with the volatility read from the surface at its strike and expiration.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The engine pricing the option, which prices a copy of its option with
+    ///   the volatility from the surface.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the option price and the volatility used.
+    pub fn price<E: PricingEngine>(&self, engine: &E) -> (f64, f64) {
+        let mut option = engine.option().clone();
+        let sigma = self.volatility(option.k, option.t);
+        option.sigma = sigma;
+        (engine.price_option(&option, &mut Workspace::default()), sigma)
+    }
+
+    /// Finds the butterfly and calendar-spread arbitrage among the quoted nodes of the surface.
+    ///
+    /// # Arguments
+    ///
+    /// * `s0` - The initial stock price.
+    /// * `carry` - The cost of carry `r − q` that determines the forward of each expiration.
+    ///
+    /// # Returns
+    ///
+    /// The `ArbitrageReport` listing the offending nodes.
+    pub fn find_arbitrage(&self, s0: f64, carry: f64) -> ArbitrageReport {
+        let (mut strikes, mut expiries, mut vols) = (Vec::new(), Vec::new(), Vec::new());
+        for (&t, row) in self.expiries.iter().zip(&self.vols) {
+            strikes.extend_from_slice(&self.strikes);
+            expiries.extend(std::iter::repeat_n(t, self.strikes.len()));
+            vols.extend_from_slice(row);
+        }
+        find_arbitrage(&strikes, &expiries, &vols, s0, carry)
+    }
+}
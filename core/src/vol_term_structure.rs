@@ -0,0 +1,162 @@
+// vol_term_structure.rs
+
+use crate::pricing_engine::PricingEngine;
+use crate::pricing_error::PricingError;
+use crate::workspace::Workspace;
+
+/// Represents a term structure of at-the-money implied volatilities.
+///
+/// Total variance `σ²(t) t` is interpolated linearly between the expiries, which makes the
+/// instantaneous forward variance piecewise constant. Before the first expiry the first
+/// volatility applies, and beyond the last expiry the forward variance of the last
+/// segment is held flat.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolTermStructure {
+    /// The increasing, positive expiries of the term structure (in years).
+    pub expiries: Vec<f64>,
+    /// The implied volatility to each expiry.
+    pub vols: Vec<f64>,
+}
+
+impl VolTermStructure {
+    /// Creates a new `VolTermStructure` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `expiries` - The increasing, positive expiries of the term structure (in years).
+    /// * `vols` - The implied volatility to each expiry.
+    ///
+    /// # Returns
+    ///
+    /// A new `VolTermStructure` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the term structure is empty, its lists have
+    /// different lengths, its expiries are not positive and increasing, or its total
+    /// variance `vol² · expiry` is not positive and increasing.
+    pub fn new(expiries: Vec<f64>, vols: Vec<f64>) -> Result<Self, PricingError> {
+        let increasing = !expiries.is_empty() && expiries[0] > 0.0 && expiries.windows(2).all(|w| w[0] < w[1]);
+        if !increasing || expiries.len() != vols.len() || expiries[expiries.len() - 1].is_infinite() {
+            return Err(PricingError::InvalidInput(
+                "Invalid term structure. The expiries must be positive and increasing, with one volatility per expiry."
+                    .to_string(),
+            ));
+        }
+        let term = VolTermStructure { expiries, vols };
+        let variances = term.variances();
+        let finite = term.vols.iter().all(|vol| vol.is_finite());
+        if !finite || term.vols[0] <= 0.0 || variances.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(PricingError::InvalidInput(
+                "Invalid term structure. The volatilities must be positive and the total variance increasing."
+                    .to_string(),
+            ));
+        }
+        Ok(term)
+    }
+
+    /// Returns the total variance to each expiry.
+    fn variances(&self) -> Vec<f64> {
+        self.expiries.iter().zip(&self.vols).map(|(t, vol)| vol * vol * t).collect()
+    }
+
+    /// Calculates the total variance to a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time (in years).
+    ///
+    /// # Returns
+    ///
+    /// The total variance `σ²(t) t`.
+    pub fn total_variance(&self, t: f64) -> f64 {
+        let variances = self.variances();
+        let last = self.expiries.len() - 1;
+        let segment = self.expiries.partition_point(|&expiry| expiry < t).min(last);
+        let (t_lower, w_lower) = if segment == 0 {
+            (0.0, 0.0)
+        } else {
+            (self.expiries[segment - 1], variances[segment - 1])
+        };
+        let (t_upper, w_upper) = (self.expiries[segment], variances[segment]);
+        w_lower + (w_upper - w_lower) * (t - t_lower) / (t_upper - t_lower)
+    }
+
+    /// Calculates the time at which the total variance reaches a level, the inverse of
+    /// `total_variance`.
+    ///
+    /// # Arguments
+    ///
+    /// * `variance` - The non-negative total variance.
+    ///
+    /// # Returns
+    ///
+    /// The time (in years) at which the total variance equals `variance`.
+    pub fn time_at_variance(&self, variance: f64) -> f64 {
+        let variances = self.variances();
+        let last = variances.len() - 1;
+        let segment = variances.partition_point(|&w| w < variance).min(last);
+        let (t_lower, w_lower) = if segment == 0 {
+            (0.0, 0.0)
+        } else {
+            (self.expiries[segment - 1], variances[segment - 1])
+        };
+        let (t_upper, w_upper) = (self.expiries[segment], variances[segment]);
+        t_lower + (t_upper - t_lower) * (variance - w_lower) / (w_upper - w_lower)
+    }
+
+    /// Calculates the implied volatility to a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time (in years).
+    ///
+    /// # Returns
+    ///
+    /// The volatility `√(w(t) / t)`, or the first volatility at `t = 0`.
+    pub fn volatility(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            self.vols[0]
+        } else {
+            (self.total_variance(t) / t).sqrt()
+        }
+    }
+
+    /// Creates a copy of the term structure with every volatility shifted by the same amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `shift` - The parallel shift of the volatilities.
+    ///
+    /// # Returns
+    ///
+    /// The shifted `VolTermStructure`.
+    pub fn shifted(&self, shift: f64) -> Self {
+        VolTermStructure {
+            expiries: self.expiries.clone(),
+            vols: self.vols.iter().map(|vol| vol + shift).collect(),
+        }
+    }
+
+    /// Prices an option with its volatility taken from the term structure.
+    ///
+    /// The option's volatility is set to the implied volatility to its expiration, and
+    /// lattice engines space their time steps so that each carries the same forward
+    /// variance, which keeps the tree recombining with constant up and down moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The engine pricing the option, which prices a copy of its option with
+    ///   the volatility and term structure set from this term structure.
+    ///
+    /// # Returns
+    ///
+    /// The price of the option.
+    pub fn price<E: PricingEngine>(&self, engine: &E) -> f64 {
+        let mut option = engine.option().clone();
+        option.sigma = self.volatility(option.t);
+        option.vol_term = Some(self.clone());
+        engine.price_option(&option, &mut Workspace::default())
+    }
+}
@@ -0,0 +1,102 @@
+// worker_threads.rs
+
+use std::sync::{Arc, RwLock};
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// The worker thread pool of the parallel pricers, built on first use, and the number of
+/// threads set by `set_worker_threads`, or `None` for one per available core.
+static POOL: RwLock<(Option<Arc<ThreadPool>>, Option<usize>)> = RwLock::new((None, None));
+
+/// Sets the number of worker threads of the parallel pricers for the whole process.
+///
+/// Batch pricing, implied volatilities, and Monte Carlo simulation run on one thread pool
+/// that the process shares. Setting the number of threads replaces the pool: calls already
+/// running finish on the old pool, and the next call builds the new one. An application
+/// that runs the pricers from its own thread pool can set one thread, so that they run
+/// single-threaded within each of its workers.
+///
+/// # Arguments
+///
+/// * `threads` - The number of worker threads, or `None` for one per available core.
+pub fn set_worker_threads(threads: Option<usize>) {
+    let mut pool = POOL.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *pool = (None, threads.filter(|&threads| threads > 0));
+}
+
+/// Returns the number of worker threads of the parallel pricers.
+///
+/// # Returns
+///
+/// The number set by `set_worker_threads`, or the available parallelism of the machine if
+/// none is set.
+pub fn worker_threads() -> usize {
+    threads_for(POOL.read().unwrap_or_else(|poisoned| poisoned.into_inner()).1)
+}
+
+/// Resolves a number of threads set by `set_worker_threads` to the number to start.
+fn threads_for(threads: Option<usize>) -> usize {
+    threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |threads| threads.get()))
+}
+
+/// Returns the worker thread pool, building it with `worker_threads` threads if this is
+/// the first parallel call since the number of threads was set.
+///
+/// The number of threads is read under the same lock that installs the pool, so a
+/// concurrent `set_worker_threads` either replaces the new pool or is seen by it.
+///
+/// # Returns
+///
+/// The pool, or `None` if the operating system refused to start its threads.
+fn pool() -> Option<Arc<ThreadPool>> {
+    if let Some(pool) = &POOL.read().unwrap_or_else(|poisoned| poisoned.into_inner()).0 {
+        return Some(Arc::clone(pool));
+    }
+    let mut guard = POOL.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if guard.0.is_none() {
+        let builder = ThreadPoolBuilder::new()
+            .num_threads(threads_for(guard.1))
+            .thread_name(|index| format!("numerical-options-{index}"));
+        guard.0 = builder.build().ok().map(Arc::new);
+    }
+    guard.0.clone()
+}
+
+/// Runs a parallel computation on the worker thread pool.
+///
+/// The rayon iterators of `op` split their work across the threads of the pool. If the
+/// pool cannot be built, they fall back to rayon's global pool.
+///
+/// # Arguments
+///
+/// * `op` - The computation.
+///
+/// # Returns
+///
+/// The result of `op`.
+pub fn install<R: Send>(op: impl FnOnce() -> R + Send) -> R {
+    match pool() {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::simulate_pairs;
+
+    #[test]
+    fn results_do_not_depend_on_the_number_of_threads() {
+        let simulate = || simulate_pairs(5000, 7, |rng| rng.next_normal());
+        set_worker_threads(Some(1));
+        assert_eq!(worker_threads(), 1);
+        let single = simulate();
+        set_worker_threads(Some(3));
+        assert_eq!(worker_threads(), 3);
+        assert_eq!(pool().map(|pool| pool.current_num_threads()), Some(3));
+        let parallel = simulate();
+        set_worker_threads(None);
+        assert_eq!(single, parallel);
+    }
+}
@@ -0,0 +1,19 @@
+// workspace.rs
+
+use crate::price_tree::PriceTree;
+
+/// The buffers of a binomial tree engine, carried from one valuation to the next.
+///
+/// Engines price through `&self`, so they keep no scratch buffers of their own: the stock
+/// price tree and the node values of backward induction live in a workspace that the
+/// caller passes to `PricingEngine::price_option`. The revaluations of bumped Greeks share one workspace and
+/// allocate the trees only once, and passing it on to the engines of other options extends
+/// the reuse across options: a loop over many options then allocates only when the trees
+/// grow. Each thread needs its own workspace.
+#[derive(Default)]
+pub struct Workspace {
+    /// The stock prices at the nodes of the tree.
+    pub stock_prices: PriceTree,
+    /// The values at the nodes of one level of backward induction.
+    pub values: Vec<f64>,
+}
@@ -0,0 +1,157 @@
+// yield_curve.rs
+
+use crate::pricing_engine::PricingEngine;
+use crate::pricing_error::PricingError;
+use crate::stock_option::StockOption;
+use crate::workspace::Workspace;
+
+/// Represents a term structure of continuously compounded zero rates.
+///
+/// Discount factors are interpolated log-linearly between the tenors, which makes the
+/// instantaneous forward rate piecewise constant. Before the first tenor the first zero
+/// rate applies, and beyond the last tenor the forward rate of the last segment is held
+/// flat.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YieldCurve {
+    /// The increasing, positive tenors of the curve (in years).
+    pub tenors: Vec<f64>,
+    /// The continuously compounded zero rate to each tenor.
+    pub zero_rates: Vec<f64>,
+}
+
+impl YieldCurve {
+    /// Creates a new `YieldCurve` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenors` - The increasing, positive tenors of the curve (in years).
+    /// * `zero_rates` - The continuously compounded zero rate to each tenor.
+    ///
+    /// # Returns
+    ///
+    /// A new `YieldCurve` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PricingError::InvalidInput` if the curve is empty, its lists have different
+    /// lengths, its tenors are not positive and increasing, or a zero rate is not finite.
+    pub fn new(tenors: Vec<f64>, zero_rates: Vec<f64>) -> Result<Self, PricingError> {
+        let increasing = !tenors.is_empty() && tenors[0] > 0.0 && tenors.windows(2).all(|w| w[0] < w[1]);
+        if !increasing || tenors.len() != zero_rates.len() || tenors[tenors.len() - 1].is_infinite() {
+            return Err(PricingError::InvalidInput(
+                "Invalid curve. The tenors must be positive and increasing, with one zero rate per tenor.".to_string(),
+            ));
+        }
+        if let Some(rate) = zero_rates.iter().find(|rate| !rate.is_finite()) {
+            return Err(PricingError::InvalidInput(format!("The zero rates must be finite, not {rate}.")));
+        }
+        Ok(YieldCurve { tenors, zero_rates })
+    }
+
+    /// Calculates the discount factor to a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time (in years).
+    ///
+    /// # Returns
+    ///
+    /// The discount factor `e^{−r(t) t}`.
+    pub fn discount(&self, t: f64) -> f64 {
+        // The log discount factor `−r t` is linear between the nodes, starting from 0 at t = 0.
+        let log_discount = |i: usize| -self.zero_rates[i] * self.tenors[i];
+        let last = self.tenors.len() - 1;
+        let segment = self.tenors.partition_point(|&tenor| tenor < t).min(last);
+        let (t_lower, y_lower) = if segment == 0 {
+            (0.0, 0.0)
+        } else {
+            (self.tenors[segment - 1], log_discount(segment - 1))
+        };
+        let (t_upper, y_upper) = (self.tenors[segment], log_discount(segment));
+        (y_lower + (y_upper - y_lower) * (t - t_lower) / (t_upper - t_lower)).exp()
+    }
+
+    /// Calculates the continuously compounded zero rate to a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time (in years).
+    ///
+    /// # Returns
+    ///
+    /// The zero rate `−ln D(t) / t`, or the first zero rate at `t = 0`.
+    pub fn zero_rate(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            self.zero_rates[0]
+        } else {
+            -self.discount(t).ln() / t
+        }
+    }
+
+    /// Creates a copy of the curve with every zero rate shifted by the same amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `shift` - The parallel shift of the zero rates.
+    ///
+    /// # Returns
+    ///
+    /// The shifted `YieldCurve`.
+    pub fn shifted(&self, shift: f64) -> Self {
+        YieldCurve {
+            tenors: self.tenors.clone(),
+            zero_rates: self.zero_rates.iter().map(|rate| rate + shift).collect(),
+        }
+    }
+
+    /// Prices an option with its interest rates taken from the curve.
+    ///
+    /// The option's rate is set to the zero rate to its expiration, and lattice engines
+    /// discount and grow each time step at the curve's forward rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The engine pricing the option, which prices a copy of its option with
+    ///   the rate and curve set from this curve.
+    ///
+    /// # Returns
+    ///
+    /// The price of the option.
+    pub fn price<E: PricingEngine>(&self, engine: &E) -> f64 {
+        let mut option = engine.option().clone();
+        self.apply(&mut option);
+        engine.price_option(&option, &mut Workspace::default())
+    }
+
+    /// Sets the rate of an option to the zero rate to its expiration and its curve to
+    /// this curve, as `price` does.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to update.
+    pub fn apply(&self, option: &mut StockOption) {
+        option.r = self.zero_rate(option.t);
+        option.curve = Some(self.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_rejects_empty_and_unsorted_tenors() {
+        let invalid = |tenors: Vec<f64>, rates: Vec<f64>| {
+            matches!(YieldCurve::new(tenors, rates), Err(PricingError::InvalidInput(_)))
+        };
+        assert!(invalid(vec![], vec![]));
+        assert!(invalid(vec![1.0, 0.5], vec![0.05, 0.04]));
+        assert!(invalid(vec![0.5, 0.5], vec![0.05, 0.04]));
+        assert!(invalid(vec![0.0, 1.0], vec![0.05, 0.04]));
+        assert!(invalid(vec![0.5, 1.0], vec![0.05]));
+        assert!(invalid(vec![0.5, 1.0], vec![0.05, f64::NAN]));
+        let curve = YieldCurve::new(vec![0.5, 1.0, 2.0], vec![0.04, 0.05, 0.055]).unwrap();
+        assert!((curve.zero_rate(1.0) - 0.05).abs() < 1e-15);
+    }
+}
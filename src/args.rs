@@ -0,0 +1,197 @@
+// args.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::greeks::{BumpStyle, DifferenceScheme, GreeksConfig, ThetaConvention, ThetaUnit};
+use numerical_options_core::pricing_engine::{engine_by_name, PricingEngine};
+use numerical_options_core::precision::Precision;
+use numerical_options_core::option_type::{ExerciseStyle, OptionType};
+use numerical_options_core::pricing_error::PricingError;
+
+use crate::errors::{py_err, analytic_european_only};
+
+/// Builds the bump-and-revalue configuration of the Greeks from the optional Python arguments.
+///
+/// # Arguments
+///
+/// * `t` - The time to expiration of the option (in years).
+/// * `vol_bump` - The volatility bump used for vega.
+/// * `rate_bump` - The interest rate bump used for rho.
+/// * `time_bump` - The time bump used for theta (in years).
+/// * `difference` - The finite-difference scheme, either "one_sided" or "central".
+/// * `bump_type` - Whether the bumps are "absolute" or "relative" to the parameters.
+/// * `theta_unit` - The unit of theta: "year", "calendar_day", or "trading_day".
+/// * `include_carry` - Whether theta includes the carry of the position.
+///
+/// # Returns
+///
+/// The `GreeksConfig`, with defaults for the missing arguments.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `difference` or `bump_type` is invalid, or if a bump
+/// is not positive or the time bump is not smaller than `t`.
+pub(crate) fn parse_greeks_config(
+    t: f64,
+    vol_bump: Option<f64>,
+    rate_bump: Option<f64>,
+    time_bump: Option<f64>,
+    difference: Option<&str>,
+    bump_type: Option<&str>,
+    theta_unit: Option<&str>,
+    include_carry: Option<bool>,
+) -> PyResult<GreeksConfig> {
+    let default = GreeksConfig::default();
+    let config = GreeksConfig {
+        vol_bump: vol_bump.unwrap_or(default.vol_bump),
+        rate_bump: rate_bump.unwrap_or(default.rate_bump),
+        time_bump: time_bump.unwrap_or(default.time_bump),
+        scheme: match difference.unwrap_or("one_sided") {
+            "one_sided" => DifferenceScheme::OneSided,
+            "central" => DifferenceScheme::Central,
+            _ => {
+                return Err(PyValueError::new_err(
+                    "Invalid difference. Must be 'one_sided' or 'central'.",
+                ))
+            }
+        },
+        style: match bump_type.unwrap_or("absolute") {
+            "absolute" => BumpStyle::Absolute,
+            "relative" => BumpStyle::Relative,
+            _ => {
+                return Err(PyValueError::new_err(
+                    "Invalid bump_type. Must be 'absolute' or 'relative'.",
+                ))
+            }
+        },
+        theta: parse_theta_convention(theta_unit, include_carry)?,
+    };
+    let bumps = [config.vol_bump, config.rate_bump, config.time_bump];
+    if bumps.iter().any(|&bump| bump <= 0.0) || config.absolute_bump(config.time_bump, t) >= t {
+        return Err(PyValueError::new_err(
+            "The bumps must be positive and the time bump smaller than t.",
+        ));
+    }
+    Ok(config)
+}
+
+/// Creates the pricing engine named by the `engine` argument of a Python function.
+///
+/// # Arguments
+///
+/// * `engine` - The name of the engine, as for `engine_by_name`.
+/// * `engines` - The names the function accepts, which its error message lists.
+/// * `stock_option` - The option the engine prices.
+/// * `moves` - The up and down moves `(pu, pd)` of the "tree" engine.
+/// * `space_steps` - The number of spatial nodes of the "fd" engine.
+///
+/// # Returns
+///
+/// The engine, so that callers price every model through `PricingEngine`.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `engine` is not one of `engines`, and an
+/// `UnsupportedError` if the analytic engine is requested for an American option.
+pub(crate) fn select_engine(
+    engine: &str,
+    engines: &[&str],
+    stock_option: StockOption,
+    moves: (f64, f64),
+    space_steps: usize,
+) -> PyResult<Box<dyn PricingEngine + Send + Sync>> {
+    if !engines.contains(&engine) {
+        let names: Vec<String> = engines.iter().map(|name| format!("'{name}'")).collect();
+        let listed = match names.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{}, or {last}", rest.join(", ")),
+            _ => names.concat(),
+        };
+        return Err(py_err(PricingError::InvalidInput(format!("Invalid engine. Must be {listed}."))));
+    }
+    if engine == "analytic" && stock_option.exercise == ExerciseStyle::American {
+        return Err(analytic_european_only("engine"));
+    }
+    engine_by_name(engine, stock_option, moves, space_steps).map_err(py_err)
+}
+
+/// Parses how theta is reported from the optional Python arguments.
+///
+/// # Arguments
+///
+/// * `theta_unit` - The unit of theta: "year", "calendar_day", or "trading_day". `None`
+///   reports the derivative with respect to the time to expiration.
+/// * `include_carry` - Whether theta includes the carry of the position. Defaults to true.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `theta_unit` is not recognized.
+pub(crate) fn parse_theta_convention(theta_unit: Option<&str>, include_carry: Option<bool>) -> PyResult<ThetaConvention> {
+    let unit = match theta_unit {
+        None => ThetaUnit::TimeToExpiration,
+        Some("year") => ThetaUnit::Year,
+        Some("calendar_day") => ThetaUnit::CalendarDay,
+        Some("trading_day") => ThetaUnit::TradingDay,
+        Some(_) => {
+            return Err(PyValueError::new_err(
+                "Invalid theta_unit. Must be 'year', 'calendar_day', or 'trading_day'.",
+            ))
+        }
+    };
+    Ok(ThetaConvention {
+        unit,
+        include_carry: include_carry.unwrap_or(true),
+    })
+}
+
+/// Parses the `precision` string passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `precision` is not recognized.
+pub(crate) fn parse_precision(precision: Option<&str>) -> PyResult<Precision> {
+    match precision.unwrap_or("double") {
+        "double" => Ok(Precision::Double),
+        "single" => Ok(Precision::Single),
+        _ => Err(PyValueError::new_err("Invalid precision. Must be 'double' or 'single'.")),
+    }
+}
+
+/// Parses the `options_type` string passed from Python.
+///
+/// # Arguments
+///
+/// * `options_type` - The type of the option, either "call" or "put".
+///
+/// # Returns
+///
+/// The `OptionType` named by the string.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put".
+pub(crate) fn parse_options_type(options_type: &str) -> PyResult<OptionType> {
+    match options_type {
+        "call" => Ok(OptionType::Call),
+        "put" => Ok(OptionType::Put),
+        _ => Err(PyValueError::new_err("Invalid options_type. Must be 'call' or 'put'.")),
+    }
+}
+
+/// Converts the `is_am` flag passed from Python into an exercise style.
+///
+/// # Arguments
+///
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+///
+/// # Returns
+///
+/// `ExerciseStyle::American` if `is_am` is true, `ExerciseStyle::European` otherwise.
+pub(crate) fn exercise_style(is_am: bool) -> ExerciseStyle {
+    if is_am {
+        ExerciseStyle::American
+    } else {
+        ExerciseStyle::European
+    }
+}
@@ -0,0 +1,390 @@
+// barriers.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::binomial_tree_option::BinomialTreeOption;
+use numerical_options_core::binomial_lr_option::BinomialLROption;
+use numerical_options_core::binomial_crr_option::BinomialCRROption;
+use numerical_options_core::trading_calendar::TRADING_DAYS_PER_YEAR;
+use numerical_options_core::finite_difference_option::FiniteDifferenceOption;
+use numerical_options_core::barrier::{Barrier, BarrierDirection, BarrierKind, DoubleBarrier, RebateTiming};
+use numerical_options_core::black_scholes_option::BlackScholesOption;
+use numerical_options_core::touch_option::{TouchKind, TouchOption};
+use numerical_options_core::pricing_engine::PricingEngine;
+use numerical_options_core::option_type::{ExerciseStyle, OptionType};
+
+use crate::args::{parse_greeks_config, parse_options_type, exercise_style};
+use crate::errors::{py_err, analytic_european_only};
+use crate::payoffs::price_by_method;
+use crate::vanilla::PyPricingResult;
+
+/// Calculates the price of a single-barrier option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `barrier` - The barrier level.
+/// * `barrier_type` - The barrier type: "down_and_out", "down_and_in", "up_and_out", or "up_and_in".
+/// * `rebate` - The cash rebate paid at the hitting time (knock-out) or at expiration if never hit (knock-in).
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (Reiner-Rubinstein).
+/// * `monitoring` - How often the barrier is observed: "continuous", "daily", or "weekly". Defaults to "continuous".
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type`, `barrier_type`, `method`, or `monitoring`
+/// is invalid, or if the analytic method is requested for an American option.
+#[pyfunction]
+fn calculate_barrier_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    barrier: f64,
+    barrier_type: &str,
+    rebate: f64,
+    method: &str,
+    monitoring: Option<&str>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let (direction, kind) = parse_barrier_type(barrier_type)?;
+
+    let mut barrier = Barrier::new(barrier, direction, kind, rebate);
+    barrier.monitoring_interval = parse_monitoring(monitoring)?;
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.barrier = Some(barrier);
+    price_by_method(py, stock_option, method)
+}
+
+/// Calculates the price and Greeks of a single-barrier option on a Cox-Ross-Rubinstein
+/// tree by bumping and revaluing it.
+///
+/// The barrier is moved onto the rows of nodes on either side of it and the two prices
+/// are interpolated, so the price is continuous in the parameters and the Greeks do not
+/// jump as rows of nodes cross the barrier.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `barrier` - The barrier level.
+/// * `barrier_type` - The barrier type: "down_and_out", "down_and_in", "up_and_out", or "up_and_in".
+/// * `rebate` - The cash rebate paid at the hitting time (knock-out) or at expiration if never hit (knock-in).
+/// * `monitoring` - How often the barrier is observed: "continuous", "daily", or "weekly". Defaults to "continuous".
+/// * `spot_bump` - The stock price bump for delta and gamma, relative to `s0`. Defaults to 0.01.
+/// * `vol_bump` - The volatility bump used for vega. Defaults to 0.01.
+/// * `rate_bump` - The interest rate bump used for rho. Defaults to 0.01.
+/// * `time_bump` - The time bump used for theta (in years). Defaults to 0.0001.
+/// * `difference` - The finite-difference scheme for theta, vega, and rho, either "one_sided" (the default) or "central".
+/// * `bump_type` - Whether the bumps are "absolute" (the default) or "relative" to the parameters.
+/// * `theta_unit` - The unit of theta: "year", "calendar_day", or "trading_day", in which case
+///   theta is the change in value as time passes. Defaults to the derivative with respect to
+///   the time to expiration.
+/// * `include_carry` - Whether theta includes the interest on the value and the drift of the
+///   stock at the cost of carry. Defaults to true.
+///
+/// # Returns
+///
+/// A `PricingResult` with the price, delta, gamma, theta, vega, and rho of the option, as
+/// for `calculate_option_price_and_greeks`.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if any of the string arguments is invalid, or if a bump is not
+/// positive or the time bump is not smaller than `t`.
+#[pyfunction]
+fn calculate_barrier_option_price_and_greeks(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    barrier: f64,
+    barrier_type: &str,
+    rebate: f64,
+    monitoring: Option<&str>,
+    spot_bump: Option<f64>,
+    vol_bump: Option<f64>,
+    rate_bump: Option<f64>,
+    time_bump: Option<f64>,
+    difference: Option<&str>,
+    bump_type: Option<&str>,
+    theta_unit: Option<&str>,
+    include_carry: Option<bool>,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let (direction, kind) = parse_barrier_type(barrier_type)?;
+    let config = parse_greeks_config(t, vol_bump, rate_bump, time_bump, difference, bump_type, theta_unit, include_carry)?;
+    let spot_bump = spot_bump.unwrap_or(0.01);
+    if spot_bump <= 0.0 {
+        return Err(PyValueError::new_err("The bumps must be positive and the time bump smaller than t."));
+    }
+
+    let mut barrier = Barrier::new(barrier, direction, kind, rebate);
+    barrier.monitoring_interval = parse_monitoring(monitoring)?;
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.barrier = Some(barrier);
+    let mut binomial_crr_option = BinomialCRROption::new(BinomialTreeOption::new(stock_option));
+    binomial_crr_option.interpolate_barriers = true;
+    py.allow_threads(|| binomial_crr_option.greeks(config, spot_bump)).map(PyPricingResult::from).map_err(py_err)
+}
+
+/// Parses the `barrier_type` string passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `barrier_type` is not recognized.
+fn parse_barrier_type(barrier_type: &str) -> PyResult<(BarrierDirection, BarrierKind)> {
+    match barrier_type {
+        "down_and_out" => Ok((BarrierDirection::Down, BarrierKind::KnockOut)),
+        "down_and_in" => Ok((BarrierDirection::Down, BarrierKind::KnockIn)),
+        "up_and_out" => Ok((BarrierDirection::Up, BarrierKind::KnockOut)),
+        "up_and_in" => Ok((BarrierDirection::Up, BarrierKind::KnockIn)),
+        _ => Err(PyValueError::new_err(
+            "Invalid barrier_type. Must be 'down_and_out', 'down_and_in', 'up_and_out', or 'up_and_in'.",
+        )),
+    }
+}
+
+/// Parses how often a barrier is observed into the time between observations.
+///
+/// # Arguments
+///
+/// * `monitoring` - One of "continuous", "daily", or "weekly"; `None` means "continuous".
+///
+/// # Returns
+///
+/// The monitoring interval (in years), or `None` for continuous monitoring.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the monitoring is invalid.
+fn parse_monitoring(monitoring: Option<&str>) -> PyResult<Option<f64>> {
+    match monitoring.unwrap_or("continuous") {
+        "continuous" => Ok(None),
+        "daily" => Ok(Some(1.0 / TRADING_DAYS_PER_YEAR)),
+        "weekly" => Ok(Some(1.0 / 52.0)),
+        _ => Err(PyValueError::new_err(
+            "Invalid monitoring. Must be 'continuous', 'daily', or 'weekly'.",
+        )),
+    }
+}
+
+/// Parses when a barrier payment is made.
+///
+/// # Arguments
+///
+/// * `timing` - Either "at_hit" or "at_expiry".
+/// * `name` - The name of the Python argument, used in the error message.
+///
+/// # Returns
+///
+/// The parsed `RebateTiming`.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the timing is invalid.
+fn parse_rebate_timing(timing: &str, name: &str) -> PyResult<RebateTiming> {
+    match timing {
+        "at_hit" => Ok(RebateTiming::AtHit),
+        "at_expiry" => Ok(RebateTiming::AtExpiry),
+        _ => Err(PyValueError::new_err(format!(
+            "Invalid {}. Must be 'at_hit' or 'at_expiry'.",
+            name
+        ))),
+    }
+}
+
+/// Calculates the price of a double-barrier option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree or finite-difference grid (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `lower` - The lower barrier level.
+/// * `upper` - The upper barrier level.
+/// * `barrier_type` - The barrier type, either "knock_out" or "knock_in".
+/// * `rebate` - The cash rebate paid if the option is knocked out or never knocked in.
+/// * `rebate_timing` - When a knock-out rebate is paid, either "at_hit" or "at_expiry".
+/// * `method` - The pricing method: "tree" (binomial LR), "fd" (Crank-Nicolson), or "analytic" (Ikeda-Kunitomo).
+/// * `space_steps` - The number of spatial nodes for the "fd" method. Defaults to 201.
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if any of the string arguments is invalid or if the analytic
+/// method is requested for an American option, and an `InvalidInputError` if the barriers
+/// do not bracket `s0` or a parameter of the option is outside its domain.
+#[pyfunction]
+fn calculate_double_barrier_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    lower: f64,
+    upper: f64,
+    barrier_type: &str,
+    rebate: f64,
+    rebate_timing: &str,
+    method: &str,
+    space_steps: Option<usize>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let kind = match barrier_type {
+        "knock_out" => BarrierKind::KnockOut,
+        "knock_in" => BarrierKind::KnockIn,
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid barrier_type. Must be 'knock_out' or 'knock_in'.",
+            ))
+        }
+    };
+    let rebate_timing = parse_rebate_timing(rebate_timing, "rebate_timing")?;
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.double_barrier = Some(DoubleBarrier::new(lower, upper, kind, rebate, rebate_timing));
+    stock_option.validate().map_err(py_err)?;
+
+    let engine: Box<dyn PricingEngine + Send + Sync> = match method {
+        "tree" => Box::new(BinomialLROption::new(BinomialTreeOption::new(stock_option))),
+        "fd" => Box::new(FiniteDifferenceOption::new(stock_option, space_steps.unwrap_or(201))),
+        "analytic" if is_am => return Err(analytic_european_only("method")),
+        "analytic" => Box::new(BlackScholesOption::new(stock_option)),
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid method. Must be 'tree', 'fd', or 'analytic'.",
+            ))
+        }
+    };
+    Ok(py.allow_threads(|| engine.price()))
+}
+
+/// Calculates the price of a one-touch or no-touch binary option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `barrier` - The barrier level; the barrier is an up barrier if it lies above `s0`.
+/// * `touch_type` - Either "one_touch" (pays if the barrier is hit) or "no_touch" (pays if it is not).
+/// * `cash` - The fixed amount paid.
+/// * `payment` - When a one-touch pays, either "at_hit" or "at_expiry". A no-touch always pays at expiration.
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (Reiner-Rubinstein).
+/// * `monitoring` - How often the barrier is observed: "continuous", "daily", or "weekly". Defaults to "continuous".
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if any of the string arguments is invalid.
+#[pyfunction]
+fn calculate_touch_option_price(
+    py: Python,
+    s0: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    barrier: f64,
+    touch_type: &str,
+    cash: f64,
+    payment: &str,
+    method: &str,
+    monitoring: Option<&str>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let kind = match touch_type {
+        "one_touch" => TouchKind::OneTouch,
+        "no_touch" => TouchKind::NoTouch,
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid touch_type. Must be 'one_touch' or 'no_touch'.",
+            ))
+        }
+    };
+    let direction = if barrier > s0 {
+        BarrierDirection::Up
+    } else {
+        BarrierDirection::Down
+    };
+    let mut touch_barrier = Barrier::new(barrier, direction, BarrierKind::KnockOut, 0.0);
+    touch_barrier.rebate_timing = parse_rebate_timing(payment, "payment")?;
+    touch_barrier.monitoring_interval = parse_monitoring(monitoring)?;
+
+    let stock_option = StockOption::new(
+        s0, barrier, r, t, n, div, sigma, OptionType::Call, ExerciseStyle::European,
+    ).map_err(py_err)?;
+    let touch_option = TouchOption::new(stock_option, touch_barrier, kind, cash);
+    match method {
+        "analytic" => py.allow_threads(|| touch_option.analytic_price()).map_err(py_err),
+        _ => price_by_method(py, touch_option.option, method),
+    }
+}
+
+/// Adds the barrier and touch option functions to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_barrier_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_barrier_option_price_and_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_double_barrier_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_touch_option_price, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,444 @@
+// batch.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyBufferError, PyIndexError, PyValueError};
+use pyo3::ffi;
+use pyo3::types::{PyIterator, PyTuple};
+use pyo3::AsPyPointer;
+use std::os::raw::{c_char, c_int, c_void};
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::pricing_engine::PricingResult;
+use numerical_options_core::batch_pricing::{price_batch, BatchEngine, OptionSpec};
+use numerical_options_core::worker_threads::{self, worker_threads};
+use numerical_options_core::option_type::OptionType;
+use numerical_options_core::pricing_error::PricingError;
+
+use crate::args::{parse_greeks_config, parse_precision, parse_options_type, exercise_style};
+use crate::errors::{py_err, analytic_european_only};
+
+/// The prices, deltas, gammas, thetas, vegas, and rhos of a batch of options.
+type BatchGreeks = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>);
+
+/// Calculates the prices and Greeks of a batch of options on one underlying in parallel,
+/// such as a whole option chain.
+///
+/// # Arguments
+///
+/// * `py` - The Python interpreter, whose global lock is released while pricing.
+/// * `strikes` - The strike prices of the options, as a list or one-dimensional NumPy array.
+/// * `expiries` - The times to expiration of the options (in years).
+/// * `sigmas` - The volatility of each option.
+/// * `options_types` - The type of each option, either "call" or "put".
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `n` - The number of time steps of the tree or finite-difference grid (ignored by the analytic engine).
+/// * `is_am` - A boolean indicating whether the options are American-style (true) or European-style (false).
+/// * `engine` - The pricing engine: "crr", "lr" (the default), "fd", or "analytic".
+/// * `precision` - The floating-point precision of the tree engines, "double" (the default)
+///   or "single".
+/// * `as_arrays` - Whether the results are returned as `Float64Array`s, which NumPy wraps
+///   without copying, rather than lists. Defaults to false.
+///
+/// # Returns
+///
+/// A tuple containing the price, delta, gamma, theta, vega, and rho of each option, as
+/// for `calculate_option_price_and_greeks_with_engine` with the default bumps.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the lists have different lengths, if an options type, the
+/// `engine`, or the `precision` is invalid, if an expiry is not longer than the time bump,
+/// or if the analytic engine is requested for American options, and an `InvalidInputError`
+/// naming the index of the first option with a parameter outside its domain.
+#[pyfunction]
+fn calculate_option_prices_batch(
+    py: Python,
+    strikes: Vec<f64>,
+    expiries: Vec<f64>,
+    sigmas: Vec<f64>,
+    options_types: Vec<String>,
+    s0: f64,
+    r: f64,
+    div: f64,
+    n: usize,
+    is_am: bool,
+    engine: Option<&str>,
+    precision: Option<&str>,
+    as_arrays: Option<bool>,
+) -> PyResult<PyObject> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let options = strikes.len();
+    if expiries.len() != options || sigmas.len() != options || options_types.len() != options {
+        return Err(PyValueError::new_err(
+            "strikes, expiries, sigmas, and options_types must have the same length.",
+        ));
+    }
+    let engine = parse_batch_engine(engine, is_am)?;
+    let precision = parse_precision(precision)?;
+    for &t in &expiries {
+        parse_greeks_config(t, None, None, None, None, None, None, None)?;
+    }
+    let specs = (0..options)
+        .map(|i| {
+            Ok(OptionSpec {
+                s0,
+                k: strikes[i],
+                r,
+                t: expiries[i],
+                div,
+                sigma: sigmas[i],
+                option_type: parse_options_type(&options_types[i])?,
+                exercise: exercise_style(is_am),
+                n,
+                engine,
+                precision,
+            })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let results = py.allow_threads(|| price_batch(&specs));
+    let results = batch_results(results, 0).map_err(py_err)?;
+    batch_output(py, &results, as_arrays.unwrap_or(false))
+}
+
+/// Collects the results of a batch, naming the first option that failed.
+///
+/// # Arguments
+///
+/// * `results` - The results of `price_batch`.
+/// * `offset` - The index of the first option in the whole batch.
+///
+/// # Returns
+///
+/// The price and Greeks of each option.
+///
+/// # Errors
+///
+/// Returns the error of the first option that failed, prefixed with its index.
+fn batch_results(
+    results: Vec<Result<PricingResult, PricingError>>,
+    offset: usize,
+) -> Result<Vec<PricingResult>, PricingError> {
+    let prefix = |i: usize, message: String| format!("Option {}: {message}", offset + i);
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(i, result)| {
+            result.map_err(|error| match error {
+                PricingError::InvalidInput(message) => PricingError::InvalidInput(prefix(i, message)),
+                PricingError::NumericalFailure(message) => PricingError::NumericalFailure(prefix(i, message)),
+                PricingError::Unsupported(message) => PricingError::Unsupported(prefix(i, message)),
+            })
+        })
+        .collect()
+}
+
+/// Parses the `engine` string of the batch functions.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `engine` is not recognized, or if the analytic engine
+/// is requested for American options.
+fn parse_batch_engine(engine: Option<&str>, is_am: bool) -> PyResult<BatchEngine> {
+    match engine.unwrap_or("lr") {
+        "crr" => Ok(BatchEngine::Crr),
+        "lr" => Ok(BatchEngine::Lr),
+        "fd" => Ok(BatchEngine::Fd),
+        "analytic" if is_am => Err(analytic_european_only("engine")),
+        "analytic" => Ok(BatchEngine::Analytic),
+        _ => Err(PyValueError::new_err("Invalid engine. Must be 'crr', 'lr', 'fd', or 'analytic'.")),
+    }
+}
+
+/// Splits the results of a batch into one list per quantity.
+fn batch_columns(results: &[PricingResult]) -> BatchGreeks {
+    let column = |field: fn(&PricingResult) -> f64| results.iter().map(field).collect::<Vec<_>>();
+    (
+        column(|result| result.price),
+        column(|result| result.greeks.delta),
+        column(|result| result.greeks.gamma),
+        column(|result| result.greeks.theta),
+        column(|result| result.greeks.vega),
+        column(|result| result.greeks.rho),
+    )
+}
+
+/// The buffer-protocol format string of a C `double`.
+const DOUBLE_FORMAT: &std::ffi::CStr = c"d";
+
+/// A one-dimensional array of `f64` values allocated in Rust and shared with Python.
+///
+/// The array exports its values through the buffer protocol as a read-only C-contiguous
+/// array of doubles, so `numpy.asarray` and `memoryview` wrap them without copying and
+/// without a Python float per element. It also supports `len`, indexing, and iteration.
+///
+/// The bindings use this class rather than `numpy::PyArray1` so that NumPy stays an
+/// optional dependency: the arrays work with `memoryview`, `array`, and any other consumer
+/// of the buffer protocol, and NumPy users get the same zero-copy view from
+/// `numpy.asarray`. The round trip through the buffer protocol is covered by
+/// `tests/test_float64_array.py`.
+#[pyclass(module = "numerical_options_rs")]
+struct Float64Array {
+    /// The values, which are never modified once the array is created.
+    values: Vec<f64>,
+}
+
+#[pymethods]
+impl Float64Array {
+    fn __len__(&self) -> usize {
+        self.values.len()
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<f64> {
+        let len = self.values.len() as isize;
+        let position = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&position) {
+            return Err(PyIndexError::new_err("Float64Array index out of range."));
+        }
+        Ok(self.values[position as usize])
+    }
+
+    /// Exports the values as a read-only buffer of doubles.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `BufferError` if a writable buffer is requested.
+    unsafe fn __getbuffer__(slf: PyRef<'_, Self>, view: *mut ffi::Py_buffer, flags: c_int) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("The buffer view is null."));
+        }
+        if flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("Float64Array is read-only."));
+        }
+        // The view holds a reference to the array, which keeps the values alive and in
+        // place until it is released.
+        (*view).obj = ffi::_Py_NewRef(slf.as_ptr());
+        (*view).buf = slf.values.as_ptr() as *mut c_void;
+        (*view).len = (slf.values.len() * std::mem::size_of::<f64>()) as isize;
+        (*view).readonly = 1;
+        (*view).itemsize = std::mem::size_of::<f64>() as isize;
+        (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+            DOUBLE_FORMAT.as_ptr() as *mut c_char
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).ndim = 1;
+        // The shape is the number of values, which the buffer does not store, so it is
+        // kept in the view's spare `internal` slot.
+        (*view).internal = Box::into_raw(Box::new(slf.values.len() as isize)) as *mut c_void;
+        (*view).shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+            (*view).internal as *mut isize
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+            &mut (*view).itemsize
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).suboffsets = std::ptr::null_mut();
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, view: *mut ffi::Py_buffer) {
+        drop(Box::from_raw((*view).internal as *mut isize));
+    }
+}
+
+/// Converts columns of values to a Python tuple of `Float64Array`s.
+pub(crate) fn float64_arrays(py: Python, columns: Vec<Vec<f64>>) -> PyResult<PyObject> {
+    let arrays = columns
+        .into_iter()
+        .map(|values| Py::new(py, Float64Array { values }))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyTuple::new(py, arrays).into())
+}
+
+/// Converts the results of a batch to Python, as six lists or as six `Float64Array`s.
+fn batch_output(py: Python, results: &[PricingResult], as_arrays: bool) -> PyResult<PyObject> {
+    let columns = batch_columns(results);
+    if !as_arrays {
+        return Ok(columns.into_py(py));
+    }
+    let (prices, deltas, gammas, thetas, vegas, rhos) = columns;
+    float64_arrays(py, vec![prices, deltas, gammas, thetas, vegas, rhos])
+}
+
+/// The number of options priced together by a stream unless the caller chooses otherwise.
+const DEFAULT_STREAM_CHUNK: usize = 10_000;
+
+/// Prices the options of a Python iterable chunk by chunk, as returned by
+/// `calculate_option_prices_stream`.
+///
+/// Only one chunk of options and its results are held at a time, so the iterable may be a
+/// generator over more positions than fit in memory.
+#[pyclass(module = "numerical_options_rs")]
+struct OptionPriceStream {
+    /// The iterator over the `(strike, expiry, sigma, options_type)` rows still to price.
+    rows: Py<PyIterator>,
+    /// The number of options priced per chunk.
+    chunk_size: usize,
+    /// The terms shared by every option, with the strike, expiry, volatility, and type of
+    /// each row filled in.
+    template: OptionSpec,
+    /// Whether each chunk is returned as `Float64Array`s rather than lists.
+    as_arrays: bool,
+    /// The number of rows priced by the previous chunks, which indexes the errors.
+    offset: usize,
+}
+
+#[pymethods]
+impl OptionPriceStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Prices the next chunk of rows.
+    ///
+    /// # Returns
+    ///
+    /// The prices and Greeks of the chunk, as for `calculate_option_prices_batch`, or
+    /// `None` to stop the iteration once the rows are exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PyValueError` if a row is not a `(strike, expiry, sigma, options_type)`
+    /// tuple, if its options type is invalid, or if its expiry is not longer than the time
+    /// bump, and an `InvalidInputError` naming the index of the first invalid option of the
+    /// chunk; rows of the chunk before it are then discarded.
+    fn __next__(mut slf: PyRefMut<'_, Self>, py: Python) -> PyResult<Option<PyObject>> {
+        let mut specs = Vec::with_capacity(slf.chunk_size);
+        let rows = slf.rows.as_ref(py);
+        for row in rows.take(slf.chunk_size) {
+            let (k, t, sigma, options_type) = row?.extract::<(f64, f64, f64, String)>()?;
+            parse_greeks_config(t, None, None, None, None, None, None, None)?;
+            let option_type = parse_options_type(&options_type)?;
+            specs.push(OptionSpec { k, t, sigma, option_type, ..slf.template });
+        }
+        if specs.is_empty() {
+            return Ok(None);
+        }
+        let offset = slf.offset;
+        slf.offset += specs.len();
+        let results = py.allow_threads(|| price_batch(&specs));
+        let results = batch_results(results, offset).map_err(py_err)?;
+        batch_output(py, &results, slf.as_arrays).map(Some)
+    }
+}
+
+/// Prices a stream of options on one underlying chunk by chunk, with bounded memory.
+///
+/// # Arguments
+///
+/// * `rows` - An iterable of `(strike, expiry, sigma, options_type)` tuples, such as a
+///   generator reading positions from a file or database.
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `n` - The number of time steps of the tree or finite-difference grid (ignored by the analytic engine).
+/// * `is_am` - A boolean indicating whether the options are American-style (true) or European-style (false).
+/// * `engine` - The pricing engine, as for `calculate_option_prices_batch`.
+/// * `precision` - The floating-point precision of the tree engines, as for
+///   `calculate_option_prices_batch`.
+/// * `chunk_size` - The number of options priced together. Defaults to 10,000.
+/// * `as_arrays` - Whether each chunk is returned as `Float64Array`s, as for
+///   `calculate_option_prices_batch`. Defaults to false.
+///
+/// # Returns
+///
+/// An iterator whose items are the prices and Greeks of successive chunks of rows, as
+/// for `calculate_option_prices_batch`. The rows are only read as the iterator advances.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `engine` or `precision` is invalid, if the analytic
+/// engine is requested for American options, or if `chunk_size` is zero, and a
+/// `TypeError` if `rows` is not iterable. Invalid rows raise a `PyValueError` or an
+/// `InvalidInputError` when their chunk is priced.
+#[pyfunction]
+fn calculate_option_prices_stream(
+    rows: &PyAny,
+    s0: f64,
+    r: f64,
+    div: f64,
+    n: usize,
+    is_am: bool,
+    engine: Option<&str>,
+    precision: Option<&str>,
+    chunk_size: Option<usize>,
+    as_arrays: Option<bool>,
+) -> PyResult<OptionPriceStream> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let engine = parse_batch_engine(engine, is_am)?;
+    let precision = parse_precision(precision)?;
+    let chunk_size = chunk_size.unwrap_or(DEFAULT_STREAM_CHUNK);
+    if chunk_size == 0 {
+        return Err(PyValueError::new_err("chunk_size must be positive."));
+    }
+    let template = OptionSpec {
+        s0,
+        k: 0.0,
+        r,
+        t: 0.0,
+        div,
+        sigma: 0.0,
+        option_type: OptionType::Call,
+        exercise: exercise_style(is_am),
+        n,
+        engine,
+        precision,
+    };
+    Ok(OptionPriceStream {
+        rows: rows.iter()?.into(),
+        chunk_size,
+        template,
+        as_arrays: as_arrays.unwrap_or(false),
+        offset: 0,
+    })
+}
+
+/// Sets the number of worker threads of the parallel pricers.
+///
+/// Batch pricing, streaming, implied volatilities, and Monte Carlo simulation split their
+/// work across the worker threads. The setting applies to the whole process, so an
+/// application that prices from its own thread pool can set 1 to avoid oversubscribing
+/// the cores. Results do not depend on the number of threads.
+///
+/// # Arguments
+///
+/// * `threads` - The number of worker threads, or `None` for one per available core (the
+///   default).
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if `threads` is zero.
+#[pyfunction]
+fn set_worker_threads(threads: Option<usize>) -> PyResult<()> {
+    if threads == Some(0) {
+        return Err(PyValueError::new_err("The number of worker threads must be positive."));
+    }
+    worker_threads::set_worker_threads(threads);
+    Ok(())
+}
+
+/// Returns the number of worker threads of the parallel pricers.
+///
+/// # Returns
+///
+/// The number set by `set_worker_threads`, or the number of available cores if none is set.
+#[pyfunction]
+fn get_worker_threads() -> usize {
+    worker_threads()
+}
+
+/// Adds the batch and streaming pricing functions and their array class to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_option_prices_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_prices_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(set_worker_threads, m)?)?;
+    m.add_function(wrap_pyfunction!(get_worker_threads, m)?)?;
+    m.add_class::<Float64Array>()?;
+    m.add_class::<OptionPriceStream>()?;
+    Ok(())
+}
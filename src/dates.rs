@@ -0,0 +1,226 @@
+// dates.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyDate, PyDateAccess};
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::day_count::{Date, DayCount};
+use numerical_options_core::greeks::GreeksConfig;
+use numerical_options_core::pricing_engine::ENGINE_NAMES;
+use numerical_options_core::trading_calendar::{BusinessDayConvention, TradingCalendar};
+
+use crate::args::{exercise_style, parse_options_type, select_engine};
+use crate::errors::py_err;
+use crate::vanilla::PyPricingResult;
+
+/// Calculates the option price and Greeks with a chosen pricing engine, with the time to
+/// expiration measured between two calendar dates.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `valuation_date` - The date of the valuation, a `datetime.date`.
+/// * `expiry_date` - The expiration date of the option, a `datetime.date`.
+/// * `n` - The number of time steps of the tree or finite-difference grid (ignored by the analytic engine).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `engine` - The pricing engine, as for `calculate_option_price_and_greeks_with_engine`.
+/// * `day_count` - The day-count convention: "act/365" (the default), "act/360", "30/360",
+///   or "bus/252" (business days of the trading calendar over 252).
+/// * `calendar` - The trading calendar: "us_equity" (the default) or "weekends".
+/// * `holidays` - Additional dates on which the exchange is closed.
+/// * `business_day_convention` - How an expiration on a non-business day is rolled:
+///   "unadjusted" (the default), "following", "modified_following", or "preceding".
+/// * `pu` - The up move of the "tree" engine, whose up factor is `1 + pu`. Defaults to 0.
+/// * `pd` - The down move of the "tree" engine, whose down factor is `1 - pd`. Defaults to 0.
+///
+/// # Returns
+///
+/// A `PricingResult` with the price, delta, gamma, theta, vega, and rho of the option, as
+/// for `calculate_option_price_and_greeks_with_engine` with its default bumps.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if any of the string arguments is invalid, if the (adjusted)
+/// expiration date is not after the valuation date, or if the analytic engine is
+/// requested for an American option.
+#[pyfunction]
+fn calculate_option_price_and_greeks_by_date(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    valuation_date: &PyDate,
+    expiry_date: &PyDate,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    engine: &str,
+    day_count: Option<&str>,
+    calendar: Option<&str>,
+    holidays: Option<Vec<&PyDate>>,
+    business_day_convention: Option<&str>,
+    pu: Option<f64>,
+    pd: Option<f64>,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let calendar = parse_trading_calendar(calendar, holidays)?;
+    let convention = parse_business_day_convention(business_day_convention)?;
+    let expiry_date = calendar.adjust(date_from_py(expiry_date)?, convention);
+    let day_count = parse_day_count(day_count, calendar)?;
+    let t = day_count.year_fraction(date_from_py(valuation_date)?, expiry_date);
+    if t <= 0.0 {
+        return Err(PyValueError::new_err("The expiry date must be after the valuation date."));
+    }
+    let config = GreeksConfig::default();
+    if config.absolute_bump(config.time_bump, t) >= t {
+        return Err(PyValueError::new_err("The bumps must be positive and the time bump smaller than t."));
+    }
+
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let moves = (pu.unwrap_or(0.0), pd.unwrap_or(0.0));
+    // The grid size and spot bump are the defaults of `calculate_option_price_and_greeks_with_engine`.
+    let engine = select_engine(engine, &ENGINE_NAMES, stock_option, moves, 201)?;
+    py.allow_threads(|| engine.greeks(config, 0.01)).map(PyPricingResult::from).map_err(py_err)
+}
+
+/// Calculates the year fraction between two calendar dates.
+///
+/// # Arguments
+///
+/// * `start` - The start date, a `datetime.date`.
+/// * `end` - The end date, a `datetime.date`.
+/// * `day_count` - The day-count convention: "act/365" (the default), "act/360", "30/360",
+///   or "bus/252" (business days of the trading calendar over 252).
+/// * `calendar` - The trading calendar of "bus/252": "us_equity" (the default) or "weekends".
+/// * `holidays` - Additional dates on which the exchange is closed.
+///
+/// # Returns
+///
+/// The length of the period in years, negative if `end` is before `start`.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `day_count` or `calendar` is invalid.
+#[pyfunction]
+fn calculate_year_fraction(
+    start: &PyDate,
+    end: &PyDate,
+    day_count: Option<&str>,
+    calendar: Option<&str>,
+    holidays: Option<Vec<&PyDate>>,
+) -> PyResult<f64> {
+    let day_count = parse_day_count(day_count, parse_trading_calendar(calendar, holidays)?)?;
+    Ok(day_count.year_fraction(date_from_py(start)?, date_from_py(end)?))
+}
+
+/// Rolls a date that falls on a non-business day of a trading calendar to a business day.
+///
+/// # Arguments
+///
+/// * `date` - The date to roll, a `datetime.date`.
+/// * `business_day_convention` - The convention: "unadjusted", "following" (the default),
+///   "modified_following", or "preceding".
+/// * `calendar` - The trading calendar: "us_equity" (the default) or "weekends".
+/// * `holidays` - Additional dates on which the exchange is closed.
+///
+/// # Returns
+///
+/// The adjusted `datetime.date`.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `business_day_convention` or `calendar` is invalid.
+#[pyfunction]
+fn calculate_adjusted_date<'py>(
+    py: Python<'py>,
+    date: &PyDate,
+    business_day_convention: Option<&str>,
+    calendar: Option<&str>,
+    holidays: Option<Vec<&PyDate>>,
+) -> PyResult<&'py PyDate> {
+    let calendar = parse_trading_calendar(calendar, holidays)?;
+    let convention = parse_business_day_convention(Some(business_day_convention.unwrap_or("following")))?;
+    let adjusted = calendar.adjust(date_from_py(date)?, convention);
+    PyDate::new(py, adjusted.year, adjusted.month as u8, adjusted.day as u8)
+}
+
+/// Converts a `datetime.date` passed from Python to a `Date`.
+fn date_from_py(date: &PyDate) -> PyResult<Date> {
+    Date::new(date.get_year(), u32::from(date.get_month()), u32::from(date.get_day())).map_err(py_err)
+}
+
+/// Parses the `day_count` string passed from Python.
+///
+/// # Arguments
+///
+/// * `day_count` - The day-count convention. Defaults to "act/365".
+/// * `calendar` - The trading calendar whose business days "bus/252" counts.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `day_count` is not recognized.
+fn parse_day_count(day_count: Option<&str>, calendar: TradingCalendar) -> PyResult<DayCount> {
+    match day_count.unwrap_or("act/365") {
+        "act/365" => Ok(DayCount::Act365),
+        "act/360" => Ok(DayCount::Act360),
+        "30/360" => Ok(DayCount::Thirty360),
+        "bus/252" => Ok(DayCount::Business252(calendar)),
+        _ => Err(PyValueError::new_err(
+            "Invalid day_count. Must be 'act/365', 'act/360', '30/360', or 'bus/252'.",
+        )),
+    }
+}
+
+/// Builds a `TradingCalendar` from the arguments passed from Python.
+///
+/// # Arguments
+///
+/// * `calendar` - Either "us_equity" (the default) or "weekends".
+/// * `holidays` - Additional dates on which the exchange is closed.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `calendar` is not recognized.
+fn parse_trading_calendar(calendar: Option<&str>, holidays: Option<Vec<&PyDate>>) -> PyResult<TradingCalendar> {
+    let us_equity = match calendar.unwrap_or("us_equity") {
+        "us_equity" => true,
+        "weekends" => false,
+        _ => return Err(PyValueError::new_err("Invalid calendar. Must be 'us_equity' or 'weekends'.")),
+    };
+    let holidays = holidays.unwrap_or_default().into_iter().map(date_from_py).collect::<PyResult<_>>()?;
+    Ok(TradingCalendar::new(us_equity, holidays))
+}
+
+/// Parses the `business_day_convention` string passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `business_day_convention` is not recognized.
+fn parse_business_day_convention(convention: Option<&str>) -> PyResult<BusinessDayConvention> {
+    match convention.unwrap_or("unadjusted") {
+        "unadjusted" => Ok(BusinessDayConvention::Unadjusted),
+        "following" => Ok(BusinessDayConvention::Following),
+        "modified_following" => Ok(BusinessDayConvention::ModifiedFollowing),
+        "preceding" => Ok(BusinessDayConvention::Preceding),
+        _ => Err(PyValueError::new_err(
+            "Invalid business_day_convention. Must be 'unadjusted', 'following', 'modified_following', or 'preceding'.",
+        )),
+    }
+}
+
+/// Adds the calendar date functions to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_option_price_and_greeks_by_date, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_year_fraction, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_adjusted_date, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+// errors.rs
+
+use pyo3::prelude::*;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyArithmeticError, PyValueError};
+
+use numerical_options_core::pricing_error::PricingError;
+
+create_exception!(
+    libnumerical_options_rs,
+    InvalidInputError,
+    PyValueError,
+    "Raised when a parameter is missing, not finite, or outside its domain."
+);
+create_exception!(
+    libnumerical_options_rs,
+    NumericalFailureError,
+    PyArithmeticError,
+    "Raised when valid parameters do not produce a finite result."
+);
+create_exception!(
+    libnumerical_options_rs,
+    UnsupportedError,
+    PyValueError,
+    "Raised when the requested engine or method does not support the option."
+);
+
+/// Converts a `PricingError` of the core library into the matching Python exception.
+///
+/// The conversion is a function rather than a `From` implementation because neither type
+/// is defined in this crate.
+pub(crate) fn py_err(error: PricingError) -> PyErr {
+    let message = error.to_string();
+    match error {
+        PricingError::InvalidInput(_) => InvalidInputError::new_err(message),
+        PricingError::NumericalFailure(_) => NumericalFailureError::new_err(message),
+        PricingError::Unsupported(_) => UnsupportedError::new_err(message),
+    }
+}
+
+/// The error raised when the analytic formulas are requested for an American option.
+///
+/// # Arguments
+///
+/// * `parameter` - The name of the argument that selected them, "engine" or "method".
+pub(crate) fn analytic_european_only(parameter: &str) -> PyErr {
+    py_err(PricingError::Unsupported(format!("The analytic {parameter} only supports European options.")))
+}
+
+/// Adds the exception classes to the Python module.
+pub(crate) fn register(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("InvalidInputError", py.get_type::<InvalidInputError>())?;
+    m.add("NumericalFailureError", py.get_type::<NumericalFailureError>())?;
+    m.add("UnsupportedError", py.get_type::<UnsupportedError>())?;
+    Ok(())
+}
@@ -0,0 +1,1036 @@
+// exotics.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::binomial_tree_option::BinomialTreeOption;
+use numerical_options_core::garch_option::{GarchModel, GarchMonteCarlo, GarchOption};
+use numerical_options_core::trading_calendar::TRADING_DAYS_PER_YEAR;
+use numerical_options_core::employee_stock_option::EmployeeStockOption;
+use numerical_options_core::forward_shooting_grid::{AuxiliaryState, ForwardShootingGrid};
+use numerical_options_core::greeks::MonteCarloEstimator;
+use numerical_options_core::asian_option::{AsianMonteCarlo, AsianOption, AsianStyle};
+use numerical_options_core::forward_start_option::{CliquetOption, ForwardStartOption};
+use numerical_options_core::shout_option::ShoutOption;
+use numerical_options_core::convertible_bond::ConvertibleBond;
+use numerical_options_core::swing_option::SwingOption;
+use numerical_options_core::installment_option::InstallmentOption;
+use numerical_options_core::real_option::RealOption;
+use numerical_options_core::pricing_engine::PricingEngine;
+use numerical_options_core::option_type::{ExerciseStyle, OptionType};
+
+use crate::args::{parse_options_type, exercise_style};
+use crate::errors::py_err;
+use crate::market::set_dividends;
+
+/// Calculates the Heston-Nandi GARCH(1,1) closed-form price of a European option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The annual risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The annual continuous dividend yield of the underlying asset.
+/// * `omega` - The constant term of the daily variance equation.
+/// * `alpha` - The ARCH coefficient of the daily variance equation.
+/// * `beta` - The GARCH coefficient of the daily variance equation.
+/// * `gamma` - The leverage coefficient of the daily variance equation.
+/// * `lam` - The daily price of risk in the physical return equation.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `h0` - The conditional variance of the first daily return. Defaults to the unconditional variance.
+///
+/// # Returns
+///
+/// The closed-form price of the option.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", and an
+/// `InvalidInputError` if the initial variance is not positive or a parameter of the
+/// option is outside its domain.
+#[pyfunction]
+fn calculate_garch_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    omega: f64,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    lam: f64,
+    options_type: &str,
+    h0: Option<f64>,
+) -> PyResult<f64> {
+    let garch_option = new_garch_option(
+        s0, k, r, t, div, omega, alpha, beta, gamma, lam, options_type, GarchModel::HestonNandi, h0,
+    )?;
+    Ok(py.allow_threads(|| garch_option.closed_form_price()))
+}
+
+/// Calculates the Monte Carlo price of a European option under GARCH(1,1) dynamics.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The annual risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The annual continuous dividend yield of the underlying asset.
+/// * `omega` - The constant term of the daily variance equation.
+/// * `alpha` - The ARCH coefficient of the daily variance equation.
+/// * `beta` - The GARCH coefficient of the daily variance equation.
+/// * `gamma` - The leverage coefficient of the daily variance equation.
+/// * `lam` - The daily price of risk in the physical return equation.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `model` - The GARCH dynamics, either "heston_nandi" or "duan".
+/// * `paths` - The number of simulated paths.
+/// * `seed` - The seed of the random number generator.
+/// * `h0` - The conditional variance of the first daily return. Defaults to the unconditional variance.
+///
+/// # Returns
+///
+/// A tuple containing the following values:
+/// - `option_price`: The Monte Carlo option price.
+/// - `std_error`: The standard error of the Monte Carlo estimate.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `model` is invalid, and an
+/// `InvalidInputError` if `paths` is zero, the initial variance is not positive, or a
+/// parameter of the option is outside its domain.
+#[pyfunction]
+fn calculate_garch_option_price_mc(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    omega: f64,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    lam: f64,
+    options_type: &str,
+    model: &str,
+    paths: usize,
+    seed: u64,
+    h0: Option<f64>,
+) -> PyResult<(f64, f64)> {
+    let model = match model {
+        "heston_nandi" => GarchModel::HestonNandi,
+        "duan" => GarchModel::Duan,
+        _ => return Err(PyValueError::new_err("Invalid model. Must be 'heston_nandi' or 'duan'.")),
+    };
+
+    let garch_option = new_garch_option(
+        s0, k, r, t, div, omega, alpha, beta, gamma, lam, options_type, model, h0,
+    )?;
+    let engine = GarchMonteCarlo::new(garch_option, model, paths, seed);
+    engine.validate().map_err(py_err)?;
+    Ok(py.allow_threads(|| engine.price_with_error()))
+}
+
+/// Builds a `GarchOption` from the arguments passed from Python.
+///
+/// When `h0` is not given, the unconditional variance of the selected model is used.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", and an
+/// `InvalidInputError` if the initial variance is not positive or a parameter of the
+/// option is outside its domain.
+fn new_garch_option(
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    omega: f64,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    lam: f64,
+    options_type: &str,
+    model: GarchModel,
+    h0: Option<f64>,
+) -> PyResult<GarchOption> {
+    let option_type = parse_options_type(options_type)?;
+    let days = ((t * TRADING_DAYS_PER_YEAR).round() as usize).max(1);
+    let h0 = h0.unwrap_or_else(|| model.unconditional_variance(omega, alpha, beta, gamma));
+    GarchOption::check_initial_variance(h0).map_err(py_err)?;
+    let sigma = (h0 * TRADING_DAYS_PER_YEAR).sqrt();
+    let stock_option =
+        StockOption::new(s0, k, r, t, days, div, sigma, option_type, ExerciseStyle::European).map_err(py_err)?;
+    Ok(GarchOption::new(stock_option, omega, alpha, beta, gamma, lam, h0))
+}
+
+/// Calculates the value of an employee stock option using the Hull-White lattice model.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `vesting` - The length of the vesting period (in years).
+/// * `exercise_multiple` - The multiple of the strike price at which the employee exercises early.
+/// * `exit_rate` - The annual rate at which employees leave the company.
+///
+/// # Returns
+///
+/// The value of the employee stock option.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if `exercise_multiple` is less than one or `exit_rate` is negative.
+#[pyfunction]
+fn calculate_employee_stock_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    vesting: f64,
+    exercise_multiple: f64,
+    exit_rate: f64,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    if exercise_multiple < 1.0 {
+        return Err(PyValueError::new_err("Invalid exercise_multiple. Must be at least 1."));
+    }
+    if exit_rate < 0.0 {
+        return Err(PyValueError::new_err("Invalid exit_rate. Must be non-negative."));
+    }
+
+    let stock_option = StockOption::new(
+        s0, k, r, t, n, div, sigma, OptionType::Call, ExerciseStyle::American,
+    ).map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let mut employee_stock_option =
+        EmployeeStockOption::new(binomial_tree_option, vesting, exercise_multiple, exit_rate);
+    Ok(py.allow_threads(|| employee_stock_option.price()))
+}
+
+/// Calculates the price of a path-dependent option on a forward-shooting-grid lattice.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option (ignored for average-strike and floating-strike payoffs).
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `payoff` - The payoff type: "average_price_call", "average_price_put",
+///   "average_strike_call", "average_strike_put", "lookback_fixed_call",
+///   "lookback_fixed_put", "lookback_floating_call", or "lookback_floating_put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `points` - The number of representative averages per node for Asian payoffs.
+/// * `window_start` - The time (in years) at which averaging starts for Asian payoffs. Defaults to 0.
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `payoff` is not recognized, and an `InvalidInputError`
+/// if `window_start` is not in `[0, t)`.
+#[pyfunction]
+fn calculate_path_dependent_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    payoff: &str,
+    is_am: bool,
+    points: usize,
+    window_start: Option<f64>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let average = AuxiliaryState::average_from(points, window_start.unwrap_or(0.0), t, n).map_err(py_err)?;
+    let (state, payoff): (AuxiliaryState, Box<dyn Fn(f64, f64) -> f64 + Send + Sync>) = match payoff {
+        "average_price_call" => (average, Box::new(move |_, a| (a - k).max(0.0))),
+        "average_price_put" => (average, Box::new(move |_, a| (k - a).max(0.0))),
+        "average_strike_call" => (average, Box::new(|s, a| (s - a).max(0.0))),
+        "average_strike_put" => (average, Box::new(|s, a| (a - s).max(0.0))),
+        "lookback_fixed_call" => (AuxiliaryState::Maximum, Box::new(move |_, m| (m - k).max(0.0))),
+        "lookback_fixed_put" => (AuxiliaryState::Minimum, Box::new(move |_, m| (k - m).max(0.0))),
+        "lookback_floating_call" => (AuxiliaryState::Minimum, Box::new(|s, m| s - m)),
+        "lookback_floating_put" => (AuxiliaryState::Maximum, Box::new(|s, m| m - s)),
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid payoff. Must be 'average_price_call', 'average_price_put', \
+                 'average_strike_call', 'average_strike_put', 'lookback_fixed_call', \
+                 'lookback_fixed_put', 'lookback_floating_call', or 'lookback_floating_put'.",
+            ))
+        }
+    };
+
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, OptionType::Call, exercise_style(is_am)).map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let mut forward_shooting_grid = ForwardShootingGrid::new(binomial_tree_option, state, payoff);
+    Ok(py.allow_threads(|| forward_shooting_grid.price()))
+}
+
+/// Calculates the Monte Carlo price of an arithmetic-average Asian option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option (ignored for average-strike options).
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `average_type` - Either "average_price" or "average_strike".
+/// * `window_start` - The time (in years) at which the averaging window opens.
+/// * `observations` - The number of equally spaced fixings in the averaging window.
+/// * `paths` - The number of simulated paths.
+/// * `seed` - The seed of the random number generator.
+/// * `dividends` - The discrete dividends paid before expiration as `(time, amount, kind)`
+///   tuples, where `kind` is "cash" or "proportional". Defaults to none.
+///
+/// # Returns
+///
+/// A tuple containing the Monte Carlo price and its standard error.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `average_type` is invalid, if
+/// `window_start` is not in `[0, t)`, if `paths` is zero, or if the dividends are invalid.
+#[pyfunction]
+fn calculate_asian_option_price_mc(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    average_type: &str,
+    window_start: f64,
+    observations: usize,
+    paths: usize,
+    seed: u64,
+    dividends: Option<Vec<(f64, f64, String)>>,
+) -> PyResult<(f64, f64)> {
+    let option_type = parse_options_type(options_type)?;
+    let style = parse_average_type(average_type)?;
+
+    let mut stock_option = StockOption::new(
+        s0, k, r, t, observations, div, sigma, option_type, ExerciseStyle::European,
+    ).map_err(py_err)?;
+    set_dividends(&mut stock_option, dividends)?;
+    let asian_option = AsianOption::new(stock_option, style, window_start, observations);
+    let engine = AsianMonteCarlo::new(asian_option, paths, seed);
+    engine.validate().map_err(py_err)?;
+    Ok(py.allow_threads(|| engine.price_with_error()))
+}
+
+/// Calculates the Monte Carlo delta and vega of an arithmetic-average Asian option with
+/// the pathwise or likelihood-ratio estimator.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option (ignored for average-strike options).
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `average_type` - Either "average_price" or "average_strike".
+/// * `window_start` - The time (in years) at which the averaging window opens.
+/// * `observations` - The number of equally spaced fixings in the averaging window.
+/// * `paths` - The number of simulated paths.
+/// * `seed` - The seed of the random number generator.
+/// * `estimator` - Either "pathwise" (the default) or "likelihood_ratio".
+///
+/// # Returns
+///
+/// A tuple containing the delta, its standard error, the vega, and its standard error.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type`, `average_type`, or `estimator` is
+/// invalid, if `window_start` is not in `[0, t)`, or if `paths` is zero.
+#[pyfunction]
+fn calculate_asian_option_greeks_mc(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    average_type: &str,
+    window_start: f64,
+    observations: usize,
+    paths: usize,
+    seed: u64,
+    estimator: Option<&str>,
+) -> PyResult<(f64, f64, f64, f64)> {
+    let option_type = parse_options_type(options_type)?;
+    let style = parse_average_type(average_type)?;
+    let estimator = match estimator.unwrap_or("pathwise") {
+        "pathwise" => MonteCarloEstimator::Pathwise,
+        "likelihood_ratio" => MonteCarloEstimator::LikelihoodRatio,
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid estimator. Must be 'pathwise' or 'likelihood_ratio'.",
+            ))
+        }
+    };
+
+    let stock_option = StockOption::new(
+        s0, k, r, t, observations, div, sigma, option_type, ExerciseStyle::European,
+    ).map_err(py_err)?;
+    let asian_option = AsianOption::new(stock_option, style, window_start, observations);
+    let engine = AsianMonteCarlo::new(asian_option, paths, seed);
+    engine.validate().map_err(py_err)?;
+    Ok(py.allow_threads(|| engine.asian.monte_carlo_greeks(paths, seed, estimator)))
+}
+
+/// Calculates the closed-form price of a geometric-average Asian option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option (ignored for average-strike options).
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `average_type` - Either "average_price" or "average_strike".
+/// * `window_start` - The time (in years) at which the averaging window opens.
+/// * `observations` - The number of equally spaced fixings in the averaging window. Defaults to continuous averaging.
+///
+/// # Returns
+///
+/// The closed-form price of the option.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `average_type` is invalid, and an
+/// `InvalidInputError` if `window_start` is not in `[0, t)` or a parameter of the option
+/// is outside its domain.
+#[pyfunction]
+fn calculate_geometric_asian_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    average_type: &str,
+    window_start: f64,
+    observations: Option<usize>,
+) -> PyResult<f64> {
+    let option_type = parse_options_type(options_type)?;
+    let style = parse_average_type(average_type)?;
+
+    let fixings = observations.unwrap_or(1);
+    let stock_option = StockOption::new(
+        s0, k, r, t, fixings, div, sigma, option_type, ExerciseStyle::European,
+    ).map_err(py_err)?;
+    let asian_option = AsianOption::new(stock_option, style, window_start, fixings);
+    asian_option.validate().map_err(py_err)?;
+    Ok(py.allow_threads(|| match observations {
+        Some(_) => asian_option.geometric_price(),
+        None => asian_option.continuous_geometric_price(),
+    }))
+}
+
+/// Parses the `average_type` string passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `average_type` is not recognized.
+fn parse_average_type(average_type: &str) -> PyResult<AsianStyle> {
+    match average_type {
+        "average_price" => Ok(AsianStyle::AveragePrice),
+        "average_strike" => Ok(AsianStyle::AverageStrike),
+        _ => Err(PyValueError::new_err(
+            "Invalid average_type. Must be 'average_price' or 'average_strike'.",
+        )),
+    }
+}
+
+/// Calculates the closed-form price of a European forward-start option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `start` - The time (in years) at which the strike is set.
+/// * `moneyness` - The strike as a multiple of the stock price at `start`.
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is invalid, and an `InvalidInputError`
+/// if `start` is not in `[0, t)` or a parameter of the option is outside its domain.
+#[pyfunction]
+fn calculate_forward_start_option_price(
+    py: Python,
+    s0: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    start: f64,
+    moneyness: f64,
+) -> PyResult<f64> {
+    let forward_start_option = new_forward_start_option(s0, r, t, div, sigma, options_type, start, moneyness)?;
+    Ok(py.allow_threads(|| forward_start_option.price()))
+}
+
+/// Calculates the Monte Carlo price of a European forward-start option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `start` - The time (in years) at which the strike is set.
+/// * `moneyness` - The strike as a multiple of the stock price at `start`.
+/// * `paths` - The number of simulated paths.
+/// * `seed` - The seed of the random number generator.
+///
+/// # Returns
+///
+/// A tuple containing the Monte Carlo price and its standard error.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is invalid, and an `InvalidInputError`
+/// if `start` is not in `[0, t)` or a parameter of the option is outside its domain.
+#[pyfunction]
+fn calculate_forward_start_option_price_mc(
+    py: Python,
+    s0: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    start: f64,
+    moneyness: f64,
+    paths: usize,
+    seed: u64,
+) -> PyResult<(f64, f64)> {
+    let forward_start_option = new_forward_start_option(s0, r, t, div, sigma, options_type, start, moneyness)?;
+    Ok(py.allow_threads(|| forward_start_option.monte_carlo_price(paths, seed)))
+}
+
+/// Creates a `ForwardStartOption` from the arguments passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is invalid, and an `InvalidInputError`
+/// if `start` is not in `[0, t)` or a parameter of the option is outside its domain.
+fn new_forward_start_option(
+    s0: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    start: f64,
+    moneyness: f64,
+) -> PyResult<ForwardStartOption> {
+    let option_type = parse_options_type(options_type)?;
+    let stock_option = StockOption::new(
+        s0, moneyness * s0, r, t, 1, div, sigma, option_type, ExerciseStyle::European,
+    ).map_err(py_err)?;
+    let forward_start_option = ForwardStartOption::new(stock_option, start, moneyness);
+    forward_start_option.validate().map_err(py_err)?;
+    Ok(forward_start_option)
+}
+
+/// Calculates the closed-form price of a cliquet option without global caps or floors.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `reset_dates` - The increasing dates (in years) at which the periods end; the last date is the expiration.
+/// * `notional` - The notional amount the summed return is paid on.
+/// * `local_floor` - The minimum return credited for each period.
+/// * `local_cap` - The maximum return credited for each period.
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns an `InvalidInputError` if the reset dates are not positive and increasing, or
+/// if a parameter of the option is outside its domain.
+#[pyfunction]
+fn calculate_cliquet_option_price(
+    py: Python,
+    s0: f64,
+    r: f64,
+    div: f64,
+    sigma: f64,
+    reset_dates: Vec<f64>,
+    notional: f64,
+    local_floor: Option<f64>,
+    local_cap: Option<f64>,
+) -> PyResult<f64> {
+    let mut cliquet_option = new_cliquet_option(s0, r, div, sigma, reset_dates, notional)?;
+    cliquet_option.local_floor = local_floor;
+    cliquet_option.local_cap = local_cap;
+    py.allow_threads(|| cliquet_option.price())
+        .ok_or_else(|| PyValueError::new_err("The closed form does not support global caps or floors."))
+}
+
+/// Calculates the Monte Carlo price of a cliquet option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `reset_dates` - The increasing dates (in years) at which the periods end; the last date is the expiration.
+/// * `notional` - The notional amount the summed return is paid on.
+/// * `paths` - The number of simulated paths.
+/// * `seed` - The seed of the random number generator.
+/// * `local_floor` - The minimum return credited for each period.
+/// * `local_cap` - The maximum return credited for each period.
+/// * `global_floor` - The minimum total return paid at expiration.
+/// * `global_cap` - The maximum total return paid at expiration.
+///
+/// # Returns
+///
+/// A tuple containing the Monte Carlo price and its standard error.
+///
+/// # Errors
+///
+/// Returns an `InvalidInputError` if the reset dates are not positive and increasing, or
+/// if a parameter of the option is outside its domain.
+#[pyfunction]
+fn calculate_cliquet_option_price_mc(
+    py: Python,
+    s0: f64,
+    r: f64,
+    div: f64,
+    sigma: f64,
+    reset_dates: Vec<f64>,
+    notional: f64,
+    paths: usize,
+    seed: u64,
+    local_floor: Option<f64>,
+    local_cap: Option<f64>,
+    global_floor: Option<f64>,
+    global_cap: Option<f64>,
+) -> PyResult<(f64, f64)> {
+    let mut cliquet_option = new_cliquet_option(s0, r, div, sigma, reset_dates, notional)?;
+    cliquet_option.local_floor = local_floor;
+    cliquet_option.local_cap = local_cap;
+    cliquet_option.global_floor = global_floor;
+    cliquet_option.global_cap = global_cap;
+    Ok(py.allow_threads(|| cliquet_option.monte_carlo_price(paths, seed)))
+}
+
+/// Creates a `CliquetOption` without caps or floors from the arguments passed from Python.
+///
+/// # Errors
+///
+/// Returns an `InvalidInputError` if the reset dates are not positive and increasing, or
+/// if a parameter of the option is outside its domain.
+fn new_cliquet_option(
+    s0: f64,
+    r: f64,
+    div: f64,
+    sigma: f64,
+    reset_dates: Vec<f64>,
+    notional: f64,
+) -> PyResult<CliquetOption> {
+    let t = reset_dates.last().copied().unwrap_or(0.0);
+    let stock_option = StockOption::new(
+        s0, s0, r, t, reset_dates.len(), div, sigma, OptionType::Call, ExerciseStyle::European,
+    ).map_err(py_err)?;
+    let cliquet_option = CliquetOption::new(stock_option, reset_dates, notional);
+    cliquet_option.validate().map_err(py_err)?;
+    Ok(cliquet_option)
+}
+
+/// Calculates the price of a European shout option on a Cox-Ross-Rubinstein lattice.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put".
+#[pyfunction]
+fn calculate_shout_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, ExerciseStyle::European).map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let mut shout_option = ShoutOption::new(binomial_tree_option);
+    Ok(py.allow_threads(|| shout_option.price()))
+}
+
+/// Calculates the price of a swing option on a multi-layer Cox-Ross-Rubinstein lattice.
+///
+/// # Arguments
+///
+/// * `s0` - The initial price of the underlying.
+/// * `k` - The strike price paid or received on each exercise.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to the last exercise date (in years).
+/// * `n` - The number of equally spaced exercise dates, which is also the number of time steps.
+/// * `div` - The continuous dividend yield (or convenience yield) of the underlying.
+/// * `sigma` - The volatility of the underlying.
+/// * `options_type` - The type of each exercise, either "call" or "put".
+/// * `max_exercises` - The maximum number of exercise rights, at most one per date.
+/// * `min_exercises` - The minimum number of exercises required to avoid the penalty.
+/// * `volume` - The volume delivered on each exercise.
+/// * `penalty` - The penalty per unit of volume for each exercise short of `min_exercises`.
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is invalid, or if `min_exercises`
+/// exceeds `max_exercises` or `max_exercises` exceeds `n`.
+#[pyfunction]
+fn calculate_swing_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    max_exercises: usize,
+    min_exercises: usize,
+    volume: f64,
+    penalty: f64,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    if min_exercises > max_exercises || max_exercises > n.max(1) {
+        return Err(PyValueError::new_err(
+            "Invalid exercise rights. Must satisfy min_exercises <= max_exercises <= n.",
+        ));
+    }
+
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, ExerciseStyle::American).map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let mut swing_option = SwingOption::new(binomial_tree_option, max_exercises, min_exercises, volume, penalty);
+    Ok(py.allow_threads(|| swing_option.price()))
+}
+
+/// Calculates the up-front premium of an installment option on a Cox-Ross-Rubinstein lattice.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `installment` - The amount paid on each installment date.
+/// * `installment_dates` - The times (in years) of the installments after inception.
+///
+/// # Returns
+///
+/// The value of the option at inception, net of the future installments.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", or if the spot
+/// price, time to expiration, or number of steps is not positive, or the strike price or
+/// volatility is negative.
+#[pyfunction]
+fn calculate_installment_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    installment: f64,
+    installment_dates: Vec<f64>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let stock_option = StockOption::builder()
+        .spot(s0)
+        .strike(k)
+        .rate(r)
+        .expiry(t)
+        .steps(n)
+        .dividend_yield(div)
+        .volatility(sigma)
+        .option_type(parse_options_type(options_type)?)
+        .exercise(exercise_style(is_am))
+        .build().map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let mut installment_option = InstallmentOption::new(binomial_tree_option, installment, installment_dates);
+    Ok(py.allow_threads(|| installment_option.price()))
+}
+
+/// Calculates the fair installment of an installment option whose first installment is
+/// paid at inception.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `installment_dates` - The times (in years) of the installments after inception.
+///
+/// # Returns
+///
+/// The installment paid at inception and on every installment date.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", or if the spot
+/// price, time to expiration, or number of steps is not positive, or the strike price or
+/// volatility is negative.
+#[pyfunction]
+fn calculate_fair_installment(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    installment_dates: Vec<f64>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let stock_option = StockOption::builder()
+        .spot(s0)
+        .strike(k)
+        .rate(r)
+        .expiry(t)
+        .steps(n)
+        .dividend_yield(div)
+        .volatility(sigma)
+        .option_type(parse_options_type(options_type)?)
+        .exercise(exercise_style(is_am))
+        .build().map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let mut installment_option = InstallmentOption::new(binomial_tree_option, 0.0, installment_dates);
+    Ok(py.allow_threads(|| installment_option.fair_installment()))
+}
+
+/// Calculates the value of a capital project with options to expand, abandon, or defer
+/// on a Cox-Ross-Rubinstein lattice.
+///
+/// # Arguments
+///
+/// * `v0` - The present value of the project's expected cash flows.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The horizon over which the options may be exercised (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The rate at which cash flows leak out of the project, as a continuous yield.
+/// * `sigma` - The volatility of the project value.
+/// * `expansion` - The option to expand, as a `(factor, cost)` pair: paying `cost` increases
+///   the project value by `factor` times its current value. Defaults to no expansion.
+/// * `salvage_value` - The value received if the project is abandoned. Defaults to no abandonment.
+/// * `investment_cost` - The initial investment, if it may be deferred until `t`. Defaults to
+///   a project that has already been started.
+///
+/// # Returns
+///
+/// A tuple containing the expanded net present value of the project and the value of its
+/// real options, which is the expanded net present value less the static one.
+#[pyfunction]
+fn calculate_real_option_value(
+    py: Python,
+    v0: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    expansion: Option<(f64, f64)>,
+    salvage_value: Option<f64>,
+    investment_cost: Option<f64>,
+) -> PyResult<(f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    // A project has no strike; its value stands in for one.
+    let stock_option = StockOption::new(
+        v0, v0, r, t, n, div, sigma, OptionType::Call, ExerciseStyle::American,
+    )
+    .map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let mut real_option = RealOption::new(binomial_tree_option);
+    real_option.expansion = expansion;
+    real_option.salvage_value = salvage_value;
+    real_option.investment_cost = investment_cost;
+    Ok(py.allow_threads(|| real_option.price()))
+}
+
+/// Calculates the price of a convertible bond on a Cox-Ross-Rubinstein lattice using the
+/// Tsiveriotis-Fernandes split into cash and equity components.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The maturity of the bond (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the stock.
+/// * `sigma` - The volatility of the stock.
+/// * `face` - The face value repaid at maturity.
+/// * `conversion_ratio` - The number of shares received on conversion.
+/// * `credit_spread` - The credit spread over the risk-free rate used to discount cash flows.
+/// * `coupon_rate` - The annual coupon rate.
+/// * `coupon_frequency` - The number of coupons per year; zero gives a zero-coupon bond.
+/// * `call_schedule` - The optional `(start time, call price)` pairs from which the issuer may call the bond.
+/// * `put_schedule` - The optional `(time, put price)` pairs on which the holder may put the bond.
+///
+/// # Returns
+///
+/// A tuple containing the price of the bond and its cash-only (bond) component.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if `face` is not positive or `conversion_ratio` is negative.
+#[pyfunction]
+fn calculate_convertible_bond_price(
+    py: Python,
+    s0: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    face: f64,
+    conversion_ratio: f64,
+    credit_spread: f64,
+    coupon_rate: f64,
+    coupon_frequency: usize,
+    call_schedule: Option<Vec<(f64, f64)>>,
+    put_schedule: Option<Vec<(f64, f64)>>,
+) -> PyResult<(f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    if face <= 0.0 {
+        return Err(PyValueError::new_err("Invalid face. Must be positive."));
+    }
+    if conversion_ratio < 0.0 {
+        return Err(PyValueError::new_err("Invalid conversion_ratio. Must be non-negative."));
+    }
+
+    let stock_option = StockOption::new(
+        s0, face, r, t, n, div, sigma, OptionType::Call, ExerciseStyle::American,
+    ).map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let mut convertible_bond = ConvertibleBond::new(binomial_tree_option, face, conversion_ratio, credit_spread);
+    convertible_bond.coupons = ConvertibleBond::coupon_schedule(face, coupon_rate, coupon_frequency, t);
+    convertible_bond.call_schedule = call_schedule.unwrap_or_default();
+    convertible_bond.put_schedule = put_schedule.unwrap_or_default();
+    Ok(py.allow_threads(|| convertible_bond.price()))
+}
+
+/// Adds the path-dependent and other exotic option functions to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_garch_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_garch_option_price_mc, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_employee_stock_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_path_dependent_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_asian_option_price_mc, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_asian_option_greeks_mc, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_geometric_asian_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_forward_start_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_forward_start_option_price_mc, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_cliquet_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_cliquet_option_price_mc, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_shout_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_swing_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_installment_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_fair_installment, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_real_option_value, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_convertible_bond_price, m)?)?;
+    Ok(())
+}
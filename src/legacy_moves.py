@@ -0,0 +1,84 @@
+"""Keeps the old calls that pass `pu` and `pd` to the tree pricers working.
+
+The pricers used to take the moves `pu` and `pd` of the plain binomial tree as positional
+arguments, although only the "tree" engine reads them. The wrappers installed here
+accept the old argument lists: they drop the two moves, or pass them on as keywords to
+the pricers whose "tree" engine still reads them, and emit a `DeprecationWarning`.
+"""
+
+import functools
+import numbers
+import warnings
+
+# The index of `pu` among the positional arguments of each pricer in its old signature.
+PU_INDEX = {
+    "calculate_option_price_and_greeks": 5,
+    "calculate_option_price_and_greeks_with_engine": 5,
+    "get_engine_diagnostics": 5,
+    "calculate_option_price_and_greeks_by_date": 6,
+    "calculate_cross_greeks": 5,
+    "calculate_extended_greeks": 5,
+    "calculate_strategy_price_and_greeks": 4,
+    "calculate_legs_price_and_greeks": 4,
+    "calculate_digital_option_price": 5,
+    "calculate_gap_option_price": 5,
+    "calculate_quanto_option_price": 5,
+    "calculate_custom_option_price": 5,
+    "calculate_expression_option_price": 5,
+    "calculate_power_option_price": 5,
+    "calculate_digital_option_price_and_greeks": 5,
+    "calculate_option_price_truncated": 5,
+    "calculate_bermudan_option_price": 5,
+    "calculate_exercise_statistics": 5,
+    "calculate_exercise_boundary": 5,
+    "calculate_early_exercise_premium": 5,
+    "calculate_barrier_option_price": 5,
+    "calculate_double_barrier_option_price": 5,
+}
+
+# The pricers whose "tree" engine still reads `pu` and `pd`, now as keyword arguments.
+TREE_PRICERS = {
+    "calculate_option_price_and_greeks_with_engine",
+    "get_engine_diagnostics",
+    "calculate_option_price_and_greeks_by_date",
+    "calculate_exercise_boundary",
+}
+
+
+def _is_number(value):
+    return isinstance(value, numbers.Real) and not isinstance(value, bool)
+
+
+def _accept_moves(pricer, index, keeps_moves):
+    @functools.wraps(pricer)
+    def wrapper(*args, **kwargs):
+        # In the new signature, `div` and `sigma` take the place of `pu` and `pd`, and the
+        # options type or exercise flag follows them. A number there is the `div` of an old
+        # call.
+        if len(args) > index + 2 and _is_number(args[index + 2]):
+            pu, pd = args[index:index + 2]
+            args = args[:index] + args[index + 2:]
+            if keeps_moves:
+                kwargs.setdefault("pu", pu)
+                kwargs.setdefault("pd", pd)
+            _warn(pricer, keeps_moves)
+        elif not keeps_moves and ("pu" in kwargs or "pd" in kwargs):
+            kwargs.pop("pu", None)
+            kwargs.pop("pd", None)
+            _warn(pricer, keeps_moves)
+        return pricer(*args, **kwargs)
+
+    return wrapper
+
+
+def _warn(pricer, keeps_moves):
+    advice = "pass them as keywords for the 'tree' engine" if keeps_moves else "remove them from the call"
+    warnings.warn(
+        f"{pricer.__name__} no longer takes pu and pd after n; {advice}.", DeprecationWarning, stacklevel=3
+    )
+
+
+def install(module):
+    """Replaces the pricers of `module` by wrappers that accept their old arguments."""
+    for name, index in PU_INDEX.items():
+        setattr(module, name, _accept_moves(getattr(module, name), index, name in TREE_PRICERS))
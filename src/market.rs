@@ -0,0 +1,507 @@
+// market.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::binomial_tree_option::BinomialTreeOption;
+use numerical_options_core::binomial_lr_option::BinomialLROption;
+use numerical_options_core::binomial_crr_option::BinomialCRROption;
+use numerical_options_core::trading_calendar::TRADING_DAYS_PER_YEAR;
+use numerical_options_core::finite_difference_option::FiniteDifferenceOption;
+use numerical_options_core::black_scholes_option::BlackScholesOption;
+use numerical_options_core::yield_curve::YieldCurve;
+use numerical_options_core::dividend_schedule::{Dividend, DividendKind, DividendSchedule};
+use numerical_options_core::vol_term_structure::VolTermStructure;
+use numerical_options_core::market_snapshot::MarketSnapshot;
+use numerical_options_core::realized_vol::{OhlcSeries, RealizedVolEstimator};
+use numerical_options_core::corporate_action::{back_adjust, ContractTerms, CorporateAction};
+
+use crate::args::{parse_options_type, exercise_style};
+use crate::errors::{py_err, analytic_european_only};
+use crate::volatility::new_vol_surface;
+
+/// Calculates the price and curve rho of an option with interest rates taken from a
+/// yield curve rather than a flat rate.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `tenors` - The increasing, positive tenors of the zero curve (in years).
+/// * `zero_rates` - The continuously compounded zero rate to each tenor.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `exercise_dates` - The optional early exercise dates of a Bermudan option (in years).
+/// * `engine` - The lattice, either "crr" or "lr" (the default).
+///
+/// # Returns
+///
+/// A tuple containing the option price and its sensitivity to a parallel shift of the
+/// zero rates.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `engine` is invalid, or if the curve
+/// is empty, its lists have different lengths, or its tenors are not positive and increasing.
+#[pyfunction]
+fn calculate_option_price_with_curve(
+    py: Python,
+    s0: f64,
+    k: f64,
+    tenors: Vec<f64>,
+    zero_rates: Vec<f64>,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    exercise_dates: Option<Vec<f64>>,
+    engine: Option<&str>,
+) -> PyResult<(f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let curve = YieldCurve::new(tenors, zero_rates).map_err(py_err)?;
+    let is_crr = match engine.unwrap_or("lr") {
+        "crr" => true,
+        "lr" => false,
+        _ => return Err(PyValueError::new_err("Invalid engine. Must be 'crr' or 'lr'.")),
+    };
+    let mut stock_option = StockOption::new(
+        s0, k, curve.zero_rate(t), t, n, div, sigma, option_type, exercise_style(is_am),
+    )
+    .map_err(py_err)?;
+    stock_option.exercise_dates = exercise_dates;
+    let shift = 1e-4;
+    let price = |curve: &YieldCurve| {
+        let tree = BinomialTreeOption::new(stock_option.clone());
+        if is_crr {
+            curve.price(&BinomialCRROption::new(tree))
+        } else {
+            curve.price(&BinomialLROption::new(tree))
+        }
+    };
+    Ok(py.allow_threads(|| {
+        let rho = (price(&curve.shifted(shift)) - price(&curve.shifted(-shift))) / (2.0 * shift);
+        (price(&curve), rho)
+    }))
+}
+
+
+
+/// Calculates the price and vega of an option with a volatility that depends on the
+/// time to expiration.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `expiries` - The increasing, positive expiries of the volatility term structure (in years).
+/// * `vols` - The implied volatility to each expiry.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `exercise_dates` - The optional early exercise dates of a Bermudan option (in years).
+/// * `engine` - The lattice, either "crr" or "lr" (the default).
+///
+/// # Returns
+///
+/// A tuple containing the option price and its sensitivity to a parallel shift of the
+/// volatilities.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `engine` is invalid, and an
+/// `InvalidInputError` if the term structure is empty, its lists have different lengths,
+/// its expiries are not positive and increasing, or its total variance does not increase.
+#[pyfunction]
+fn calculate_option_price_with_vol_term(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    expiries: Vec<f64>,
+    vols: Vec<f64>,
+    options_type: &str,
+    is_am: bool,
+    exercise_dates: Option<Vec<f64>>,
+    engine: Option<&str>,
+) -> PyResult<(f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let term = VolTermStructure::new(expiries, vols).map_err(py_err)?;
+    let is_crr = match engine.unwrap_or("lr") {
+        "crr" => true,
+        "lr" => false,
+        _ => return Err(PyValueError::new_err("Invalid engine. Must be 'crr' or 'lr'.")),
+    };
+    let mut stock_option =
+        StockOption::new(s0, k, r, t, n, div, term.volatility(t), option_type, exercise_style(is_am))
+            .map_err(py_err)?;
+    stock_option.exercise_dates = exercise_dates;
+    let shift = 1e-4;
+    let price = |term: &VolTermStructure| {
+        let tree = BinomialTreeOption::new(stock_option.clone());
+        if is_crr {
+            term.price(&BinomialCRROption::new(tree))
+        } else {
+            term.price(&BinomialLROption::new(tree))
+        }
+    };
+    Ok(py.allow_threads(|| {
+        let vega = (price(&term.shifted(shift)) - price(&term.shifted(-shift))) / (2.0 * shift);
+        (price(&term), vega)
+    }))
+}
+
+/// Attaches a schedule of discrete dividends passed from Python to an option.
+///
+/// # Arguments
+///
+/// * `option` - The option whose underlying pays the dividends.
+/// * `dividends` - The dividends as `(time, amount, kind)` tuples, as for `parse_dividends`.
+///
+/// # Errors
+///
+/// Returns the errors of `parse_dividends`, and an `InvalidInputError` if the cash
+/// dividends are worth at least `s0`.
+pub(crate) fn set_dividends(option: &mut StockOption, dividends: Option<Vec<(f64, f64, String)>>) -> PyResult<()> {
+    let Some(schedule) = parse_dividends(dividends)? else {
+        return Ok(());
+    };
+    option.dividends = Some(schedule);
+    option.check_net_spot().map_err(py_err)
+}
+
+/// Parses a schedule of discrete dividends passed from Python.
+///
+/// # Arguments
+///
+/// * `dividends` - The dividends as `(time, amount, kind)` tuples, where `kind` is "cash"
+///   for a fixed amount per share or "proportional" for a fraction of the stock price.
+///   Dividends after the expiration of the option are ignored.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if a kind is invalid, and an `InvalidInputError` if a time or
+/// amount is negative or a proportional dividend is not below 1.
+fn parse_dividends(dividends: Option<Vec<(f64, f64, String)>>) -> PyResult<Option<DividendSchedule>> {
+    let Some(dividends) = dividends else {
+        return Ok(None);
+    };
+    let dividends = dividends
+        .into_iter()
+        .map(|(time, amount, kind)| {
+            let kind = match kind.as_str() {
+                "cash" => DividendKind::Cash,
+                "proportional" => DividendKind::Proportional,
+                _ => return Err(PyValueError::new_err("Invalid dividend kind. Must be 'cash' or 'proportional'.")),
+            };
+            Ok(Dividend { time, amount, kind })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    DividendSchedule::new(dividends).map(Some).map_err(py_err)
+}
+
+/// Creates a snapshot of the market data of one underlying, written as JSON so that it
+/// can be stored and replayed later with `calculate_option_price_from_snapshot`.
+///
+/// # Arguments
+///
+/// * `as_of` - The time at which the data was observed, as an ISO 8601 timestamp.
+/// * `spot` - The spot price of the underlying asset.
+/// * `tenors` - The increasing, positive tenors of the zero curve (in years).
+/// * `zero_rates` - The continuously compounded zero rate to each tenor.
+/// * `expiries` - The increasing expirations of the volatility surface (in years).
+/// * `strikes` - The increasing strike prices of the volatility surface.
+/// * `vols` - The implied volatilities, with one row per expiration and one column per strike.
+/// * `dividends` - The optional discrete dividends as `(time, amount, kind)` tuples, where
+///   `kind` is "cash" or "proportional".
+/// * `interpolation` - The interpolation across strikes, either "linear_variance" (the
+///   default) or "cubic_spline".
+///
+/// # Returns
+///
+/// The JSON text of the snapshot.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `interpolation` or a dividend kind is invalid, and an
+/// `InvalidInputError` if the `spot` is not positive, or if the curve, surface, or
+/// dividends are invalid.
+#[pyfunction]
+fn calculate_market_snapshot(
+    as_of: String,
+    spot: f64,
+    tenors: Vec<f64>,
+    zero_rates: Vec<f64>,
+    expiries: Vec<f64>,
+    strikes: Vec<f64>,
+    vols: Vec<Vec<f64>>,
+    dividends: Option<Vec<(f64, f64, String)>>,
+    interpolation: Option<&str>,
+) -> PyResult<String> {
+    let curve = YieldCurve::new(tenors, zero_rates).map_err(py_err)?;
+    let surface = new_vol_surface(expiries, strikes, vols, interpolation)?;
+    let snapshot = MarketSnapshot::new(as_of, spot, curve, parse_dividends(dividends)?, surface).map_err(py_err)?;
+    snapshot.to_json().map_err(py_err)
+}
+
+/// Calculates the price of an option with all of its market data replayed from a snapshot.
+///
+/// The snapshot supplies the stock price, the zero curve, the discrete dividends, and the
+/// volatility at the option's strike and expiration, so that a valuation can be reproduced
+/// exactly from stored inputs.
+///
+/// # Arguments
+///
+/// * `snapshot` - The JSON text of a snapshot from `calculate_market_snapshot`.
+/// * `k` - The strike price of the option.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps of the tree engines.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `engine` - The pricing engine: "crr" (Cox-Ross-Rubinstein), "lr" (Leisen-Reimer, the
+///   default), "fd" (Crank-Nicolson), or "analytic" (Black-Scholes).
+///
+/// # Returns
+///
+/// A tuple containing the option price and the volatility read from the surface.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `engine` is invalid, if the analytic
+/// engine is requested for an American option, or if the snapshot is not valid JSON or
+/// holds invalid market data.
+#[pyfunction]
+fn calculate_option_price_from_snapshot(
+    py: Python,
+    snapshot: &str,
+    k: f64,
+    t: f64,
+    n: usize,
+    options_type: &str,
+    is_am: bool,
+    engine: Option<&str>,
+) -> PyResult<(f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let snapshot = MarketSnapshot::from_json(snapshot).map_err(py_err)?;
+    let sigma = snapshot.surface.volatility(k, t);
+    let mut stock_option = StockOption::new(
+        snapshot.spot, k, snapshot.curve.zero_rate(t), t, n, 0.0, sigma, option_type, exercise_style(is_am),
+    )
+    .map_err(py_err)?;
+    if let Some(schedule) = &snapshot.dividends {
+        stock_option.dividends = Some(schedule.clone());
+        stock_option.r = snapshot.curve.zero_rate(t);
+        stock_option.check_net_spot().map_err(py_err)?;
+    }
+
+    let engine = engine.unwrap_or("lr");
+    match engine {
+        "crr" | "lr" | "fd" => {}
+        "analytic" if is_am => return Err(analytic_european_only("engine")),
+        "analytic" => {}
+        _ => return Err(PyValueError::new_err("Invalid engine. Must be 'crr', 'lr', 'fd', or 'analytic'.")),
+    }
+    Ok(py.allow_threads(|| match engine {
+        "crr" => snapshot.price(&BinomialCRROption::new(BinomialTreeOption::new(stock_option))),
+        "lr" => snapshot.price(&BinomialLROption::new(BinomialTreeOption::new(stock_option))),
+        "fd" => snapshot.price(&FiniteDifferenceOption::new(stock_option, 201)),
+        _ => snapshot.price(&BlackScholesOption::new(stock_option)),
+    }))
+}
+
+/// Estimates the annualized historical volatility of an asset from its price history.
+///
+/// # Arguments
+///
+/// * `closes` - The closing price of each period, oldest first.
+/// * `opens` - The opening price of each period, required by all estimators except
+///   "close_to_close".
+/// * `highs` - The highest price of each period, required by all estimators except
+///   "close_to_close".
+/// * `lows` - The lowest price of each period, required by all estimators except
+///   "close_to_close".
+/// * `estimator` - The estimator: "close_to_close" (the default), "parkinson",
+///   "garman_klass", or "yang_zhang".
+/// * `periods_per_year` - The number of periods in a year. Defaults to 252 for daily prices.
+///
+/// # Returns
+///
+/// The annualized volatility, suitable as the `sigma` of the pricing functions.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `estimator` is invalid, if an estimator other than
+/// "close_to_close" lacks the opens, highs, or lows, if the price lists have different
+/// lengths or fewer than three periods, if a price or `periods_per_year` is not positive,
+/// or if a high is below or a low above the open or close of its period.
+#[pyfunction]
+fn calculate_realized_volatility(
+    closes: Vec<f64>,
+    opens: Option<Vec<f64>>,
+    highs: Option<Vec<f64>>,
+    lows: Option<Vec<f64>>,
+    estimator: Option<&str>,
+    periods_per_year: Option<f64>,
+) -> PyResult<f64> {
+    let estimator = match estimator.unwrap_or("close_to_close") {
+        "close_to_close" => RealizedVolEstimator::CloseToClose,
+        "parkinson" => RealizedVolEstimator::Parkinson,
+        "garman_klass" => RealizedVolEstimator::GarmanKlass,
+        "yang_zhang" => RealizedVolEstimator::YangZhang,
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid estimator. Must be 'close_to_close', 'parkinson', 'garman_klass', or 'yang_zhang'.",
+            ))
+        }
+    };
+    let periods_per_year = periods_per_year.unwrap_or(TRADING_DAYS_PER_YEAR);
+    if periods_per_year <= 0.0 {
+        return Err(PyValueError::new_err("periods_per_year must be positive."));
+    }
+    let series = match (opens, highs, lows) {
+        (Some(opens), Some(highs), Some(lows)) => OhlcSeries::new(opens, highs, lows, closes),
+        _ if estimator == RealizedVolEstimator::CloseToClose => {
+            OhlcSeries::new(closes.clone(), closes.clone(), closes.clone(), closes)
+        }
+        _ => return Err(PyValueError::new_err("This estimator requires the opens, highs, and lows.")),
+    };
+    let periods = series.closes.len();
+    let lengths = [series.opens.len(), series.highs.len(), series.lows.len()];
+    if periods < 3 || lengths.iter().any(|&length| length != periods) {
+        return Err(PyValueError::new_err(
+            "closes, opens, highs, and lows must have the same length, with at least three periods.",
+        ));
+    }
+    let valid = (0..periods).all(|i| {
+        let (open, high, low, close) = (series.opens[i], series.highs[i], series.lows[i], series.closes[i]);
+        low > 0.0 && close > 0.0 && open > 0.0 && high >= open.max(close) && low <= open.min(close)
+    });
+    if !valid {
+        return Err(PyValueError::new_err(
+            "Invalid prices. Prices must be positive, with each high and low bounding the open and close.",
+        ));
+    }
+    Ok(series.volatility(estimator, periods_per_year))
+}
+
+/// Adjusts the terms of an option contract for a stock split or special dividend, as
+/// listed options are adjusted on the ex-date.
+///
+/// # Arguments
+///
+/// * `spot` - The price of the underlying asset before the action.
+/// * `strike` - The strike price of the option before the action.
+/// * `multiplier` - The number of shares delivered per contract before the action.
+/// * `action` - The action, either "split" or "special_dividend".
+/// * `amount` - The number of new shares per old share for a split, or the cash dividend
+///   per share for a special dividend.
+///
+/// # Returns
+///
+/// A tuple containing the spot, strike, and multiplier after the action.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `action` is invalid, if a split ratio is not positive,
+/// or if a dividend is negative or not below the spot and strike.
+#[pyfunction]
+fn calculate_corporate_action_adjustment(
+    spot: f64,
+    strike: f64,
+    multiplier: f64,
+    action: &str,
+    amount: f64,
+) -> PyResult<(f64, f64, f64)> {
+    let action = parse_corporate_action(action, amount)?;
+    if let CorporateAction::SpecialDividend(dividend) = action {
+        if dividend >= spot.min(strike) {
+            return Err(PyValueError::new_err("The special dividend must be below the spot and strike."));
+        }
+    }
+    let terms = action.adjust(ContractTerms { spot, strike, multiplier });
+    Ok((terms.spot, terms.strike, terms.multiplier))
+}
+
+/// Back-adjusts a price series through stock splits and special dividends, so that a
+/// backtest sees no jumps on the ex-dates.
+///
+/// # Arguments
+///
+/// * `prices` - The unadjusted prices, oldest first.
+/// * `actions` - The actions as `(index, action, amount)` tuples, where `index` is the
+///   position of the first price on the new basis, and `action` and `amount` are as for
+///   `calculate_corporate_action_adjustment`.
+///
+/// # Returns
+///
+/// The prices before each action rescaled onto the basis after it.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if a price is not positive, if an action is invalid, if an
+/// index is zero or beyond the series, or if a dividend is not below the price before it.
+#[pyfunction]
+fn calculate_adjusted_price_series(prices: Vec<f64>, actions: Vec<(usize, String, f64)>) -> PyResult<Vec<f64>> {
+    if prices.iter().any(|&price| price <= 0.0) {
+        return Err(PyValueError::new_err("The prices must be positive."));
+    }
+    let actions = actions
+        .into_iter()
+        .map(|(index, action, amount)| {
+            if index == 0 || index >= prices.len() {
+                return Err(PyValueError::new_err(
+                    "Invalid action index. Must be within the series, after its first price.",
+                ));
+            }
+            let action = parse_corporate_action(&action, amount)?;
+            if action.price_factor(prices[index - 1]) <= 0.0 {
+                return Err(PyValueError::new_err("A special dividend must be below the price before it."));
+            }
+            Ok((index, action))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(back_adjust(&prices, &actions))
+}
+
+/// Parses a corporate action passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `action` is not "split" or "special_dividend", if a
+/// split ratio is not positive, or if a dividend is negative.
+fn parse_corporate_action(action: &str, amount: f64) -> PyResult<CorporateAction> {
+    match action {
+        "split" if amount > 0.0 => Ok(CorporateAction::Split(amount)),
+        "split" => Err(PyValueError::new_err("The split ratio must be positive.")),
+        "special_dividend" if amount >= 0.0 => Ok(CorporateAction::SpecialDividend(amount)),
+        "special_dividend" => Err(PyValueError::new_err("The special dividend must be non-negative.")),
+        _ => Err(PyValueError::new_err("Invalid action. Must be 'split' or 'special_dividend'.")),
+    }
+}
+
+/// Adds the market data functions to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_option_price_with_curve, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_with_vol_term, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_market_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_from_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_realized_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_corporate_action_adjustment, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_adjusted_price_series, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,637 @@
+// multi_asset.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::two_asset_option::{TwoAssetOption, TwoAssetPayoff};
+use numerical_options_core::two_asset_binomial_tree::TwoAssetBinomialTree;
+use numerical_options_core::adi_solver::{AdiScheme, AdiSolver};
+use numerical_options_core::spread_option::{SpreadApproximation, SpreadOption};
+use numerical_options_core::exchange_option::ExchangeOption;
+use numerical_options_core::basket_option::{BasketMonteCarlo, BasketOption};
+use numerical_options_core::rainbow_option::{RainbowMonteCarlo, RainbowOption, RainbowPayoff};
+use numerical_options_core::pricing_engine::{MultiAssetEngine, TwoAssetBumpGreeks};
+use numerical_options_core::numerics::correlation_factor;
+use numerical_options_core::option_type::OptionType;
+
+use crate::args::{parse_options_type, exercise_style};
+use crate::errors::py_err;
+
+/// Calculates the price of a two-asset option using the Boyle-Evnine-Gibbs lattice.
+///
+/// # Arguments
+///
+/// * `s1` - The initial price of the first asset.
+/// * `s2` - The initial price of the second asset.
+/// * `k` - The strike price of the option (ignored for exchange options).
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the lattice.
+/// * `div1` - The continuous dividend yield of the first asset.
+/// * `div2` - The continuous dividend yield of the second asset.
+/// * `sigma1` - The volatility of the first asset.
+/// * `sigma2` - The volatility of the second asset.
+/// * `rho` - The correlation between the returns of the two assets.
+/// * `payoff` - The payoff type: "spread_call", "spread_put", "exchange", "best_of_call",
+///   "best_of_put", "worst_of_call", or "worst_of_put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `payoff` is not recognized, and an `InvalidInputError`
+/// if `rho` is not in [-1, 1].
+#[pyfunction]
+fn calculate_two_asset_option_price(
+    py: Python,
+    s1: f64,
+    s2: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div1: f64,
+    div2: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    payoff: &str,
+    is_am: bool,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let payoff = parse_two_asset_payoff(payoff)?;
+
+    let option = TwoAssetOption::new(
+        s1, s2, k, r, t, n, div1, div2, sigma1, sigma2, rho, payoff, exercise_style(is_am),
+    );
+    option.validate().map_err(py_err)?;
+    let tree = TwoAssetBinomialTree::new(option);
+    py.allow_threads(|| tree.try_price()).map_err(py_err)
+}
+
+/// Calculates the price and Greeks of a two-asset option by bumping and revaluing it.
+///
+/// # Arguments
+///
+/// * `s1` - The initial price of the first asset.
+/// * `s2` - The initial price of the second asset.
+/// * `k` - The strike price of the option (ignored for exchange options).
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps.
+/// * `div1` - The continuous dividend yield of the first asset.
+/// * `div2` - The continuous dividend yield of the second asset.
+/// * `sigma1` - The volatility of the first asset.
+/// * `sigma2` - The volatility of the second asset.
+/// * `rho` - The correlation between the returns of the two assets.
+/// * `payoff` - The payoff type, as for `calculate_two_asset_option_price`.
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `method` - Either "tree" (Boyle-Evnine-Gibbs) or "adi" (Craig-Sneyd ADI finite differences).
+/// * `space_steps` - The number of nodes in the log-price grid of each asset for the "adi" method. Defaults to 101.
+/// * `spot_bump` - The bump applied to each asset price, relative to that price. Defaults to 0.01.
+/// * `correlation_bump` - The bump applied to the correlation. Defaults to 0.01.
+///
+/// # Returns
+///
+/// A tuple containing the price, the two deltas, the two gammas, the cross-gamma, and
+/// the cega (the sensitivity to the correlation) of the option.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `payoff` or `method` is not recognized or if a bump is
+/// not positive, and an `InvalidInputError` if `rho` is not in [-1, 1].
+#[pyfunction]
+fn calculate_two_asset_option_greeks(
+    py: Python,
+    s1: f64,
+    s2: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div1: f64,
+    div2: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    payoff: &str,
+    is_am: bool,
+    method: &str,
+    space_steps: Option<usize>,
+    spot_bump: Option<f64>,
+    correlation_bump: Option<f64>,
+) -> PyResult<(f64, f64, f64, f64, f64, f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let payoff = parse_two_asset_payoff(payoff)?;
+    let spot_bump = spot_bump.unwrap_or(0.01);
+    let correlation_bump = correlation_bump.unwrap_or(0.01);
+    if spot_bump <= 0.0 || correlation_bump <= 0.0 {
+        return Err(PyValueError::new_err("The bumps must be positive."));
+    }
+
+    let option = TwoAssetOption::new(
+        s1, s2, k, r, t, n, div1, div2, sigma1, sigma2, rho, payoff, exercise_style(is_am),
+    );
+    option.validate().map_err(py_err)?;
+    let (value, greeks) = match method {
+        "tree" => {
+            let tree = TwoAssetBinomialTree::new(option);
+            tree.validate().map_err(py_err)?;
+            let mut bump_greeks = TwoAssetBumpGreeks::new(tree);
+            bump_greeks.spot_bump = spot_bump;
+            bump_greeks.correlation_bump = correlation_bump;
+            py.allow_threads(|| bump_greeks.price())
+        }
+        "adi" => {
+            let solver = AdiSolver::new(option, space_steps.unwrap_or(101), AdiScheme::CraigSneyd);
+            let mut bump_greeks = TwoAssetBumpGreeks::new(solver);
+            bump_greeks.spot_bump = spot_bump;
+            bump_greeks.correlation_bump = correlation_bump;
+            py.allow_threads(|| bump_greeks.price())
+        }
+        _ => return Err(PyValueError::new_err("Invalid method. Must be 'tree' or 'adi'.")),
+    };
+    Ok((
+        value,
+        greeks.delta1,
+        greeks.delta2,
+        greeks.gamma1,
+        greeks.gamma2,
+        greeks.cross_gamma,
+        greeks.cega,
+    ))
+}
+
+/// Calculates the price of an option on the spread between two assets.
+///
+/// # Arguments
+///
+/// * `s1` - The initial price of the first asset.
+/// * `s2` - The initial price of the second asset.
+/// * `k` - The strike price of the option on `S1 - S2`.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the lattice (ignored by the analytic methods).
+/// * `div1` - The continuous dividend yield of the first asset.
+/// * `div2` - The continuous dividend yield of the second asset.
+/// * `sigma1` - The volatility of the first asset.
+/// * `sigma2` - The volatility of the second asset.
+/// * `rho` - The correlation between the returns of the two assets.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `method` - The pricing method: "kirk", "bjerksund_stensland", or "tree" (Boyle-Evnine-Gibbs).
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `method` is invalid or if an analytic
+/// method is requested for an American option, and an `InvalidInputError` if `rho` is not
+/// in [-1, 1].
+#[pyfunction]
+fn calculate_spread_option_price(
+    py: Python,
+    s1: f64,
+    s2: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div1: f64,
+    div2: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    options_type: &str,
+    is_am: bool,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let payoff = match parse_options_type(options_type)? {
+        OptionType::Call => TwoAssetPayoff::SpreadCall,
+        OptionType::Put => TwoAssetPayoff::SpreadPut,
+    };
+
+    let option = TwoAssetOption::new(
+        s1, s2, k, r, t, n, div1, div2, sigma1, sigma2, rho, payoff, exercise_style(is_am),
+    );
+    option.validate().map_err(py_err)?;
+    let approximation = match method {
+        "tree" => {
+            let tree = TwoAssetBinomialTree::new(option);
+            return py.allow_threads(|| tree.try_price()).map_err(py_err);
+        }
+        "kirk" => SpreadApproximation::Kirk,
+        "bjerksund_stensland" => SpreadApproximation::BjerksundStensland,
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid method. Must be 'kirk', 'bjerksund_stensland', or 'tree'.",
+            ))
+        }
+    };
+    if is_am {
+        return Err(PyValueError::new_err(
+            "The analytic methods only support European options.",
+        ));
+    }
+    let spread_option = SpreadOption::new(option);
+    Ok(py.allow_threads(|| spread_option.price(approximation)))
+}
+
+/// Calculates the price of the option to exchange the second asset for the first.
+///
+/// # Arguments
+///
+/// * `s1` - The initial price of the asset received.
+/// * `s2` - The initial price of the asset delivered.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the lattice (ignored by the Margrabe formula).
+/// * `div1` - The continuous dividend yield of the first asset.
+/// * `div2` - The continuous dividend yield of the second asset.
+/// * `sigma1` - The volatility of the first asset.
+/// * `sigma2` - The volatility of the second asset.
+/// * `rho` - The correlation between the returns of the two assets.
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `method` - The pricing method: "margrabe" or "tree" (Boyle-Evnine-Gibbs).
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `method` is invalid or if the Margrabe formula is
+/// requested for an American option, and an `InvalidInputError` if `rho` is not in [-1, 1].
+#[pyfunction]
+fn calculate_exchange_option_price(
+    py: Python,
+    s1: f64,
+    s2: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div1: f64,
+    div2: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    is_am: bool,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option = TwoAssetOption::new(
+        s1, s2, 0.0, r, t, n, div1, div2, sigma1, sigma2, rho, TwoAssetPayoff::Exchange, exercise_style(is_am),
+    );
+    option.validate().map_err(py_err)?;
+    match method {
+        "tree" => {
+            let tree = TwoAssetBinomialTree::new(option);
+            py.allow_threads(|| tree.try_price()).map_err(py_err)
+        }
+        "margrabe" if is_am => Err(PyValueError::new_err(
+            "The Margrabe formula only supports European options.",
+        )),
+        "margrabe" => {
+            let exchange_option = ExchangeOption::new(option);
+            Ok(py.allow_threads(|| exchange_option.margrabe_price()))
+        }
+        _ => Err(PyValueError::new_err("Invalid method. Must be 'margrabe' or 'tree'.")),
+    }
+}
+
+/// Calculates the moment-matched lognormal approximation to the price of a basket option.
+///
+/// # Arguments
+///
+/// * `spots` - The initial prices of the assets.
+/// * `weights` - The weights of the assets in the basket.
+/// * `sigmas` - The volatilities of the assets.
+/// * `divs` - The continuous dividend yields of the assets.
+/// * `correlation` - The correlation matrix of the asset returns.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `options_type` - The type of the option, either "call" or "put".
+///
+/// # Returns
+///
+/// The approximate option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is invalid, if the inputs have
+/// inconsistent lengths, or if `correlation` is not a valid correlation matrix.
+#[pyfunction]
+fn calculate_basket_option_price(
+    py: Python,
+    spots: Vec<f64>,
+    weights: Vec<f64>,
+    sigmas: Vec<f64>,
+    divs: Vec<f64>,
+    correlation: Vec<Vec<f64>>,
+    k: f64,
+    r: f64,
+    t: f64,
+    options_type: &str,
+) -> PyResult<f64> {
+    let basket_option = new_basket_option(spots, weights, sigmas, divs, correlation, k, r, t, options_type)?;
+    Ok(py.allow_threads(|| basket_option.moment_matched_price()))
+}
+
+/// Calculates the Monte Carlo price of a basket option.
+///
+/// # Arguments
+///
+/// * `spots` - The initial prices of the assets.
+/// * `weights` - The weights of the assets in the basket.
+/// * `sigmas` - The volatilities of the assets.
+/// * `divs` - The continuous dividend yields of the assets.
+/// * `correlation` - The correlation matrix of the asset returns.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `paths` - The number of simulated paths.
+/// * `seed` - The seed of the random number generator.
+///
+/// # Returns
+///
+/// A tuple containing the Monte Carlo price and its standard error.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is invalid, if the inputs have
+/// inconsistent lengths, if `correlation` is not a valid correlation matrix, or if
+/// `paths` is zero.
+#[pyfunction]
+fn calculate_basket_option_price_mc(
+    py: Python,
+    spots: Vec<f64>,
+    weights: Vec<f64>,
+    sigmas: Vec<f64>,
+    divs: Vec<f64>,
+    correlation: Vec<Vec<f64>>,
+    k: f64,
+    r: f64,
+    t: f64,
+    options_type: &str,
+    paths: usize,
+    seed: u64,
+) -> PyResult<(f64, f64)> {
+    let basket_option = new_basket_option(spots, weights, sigmas, divs, correlation, k, r, t, options_type)?;
+    let engine = BasketMonteCarlo::new(basket_option, paths, seed).map_err(py_err)?;
+    engine.validate().map_err(py_err)?;
+    Ok(py.allow_threads(|| engine.price_with_error()))
+}
+
+/// Creates a `BasketOption` from the arguments passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is invalid, and an `InvalidInputError`
+/// if the inputs have inconsistent lengths or if `correlation` is not a valid correlation
+/// matrix.
+fn new_basket_option(
+    spots: Vec<f64>,
+    weights: Vec<f64>,
+    sigmas: Vec<f64>,
+    divs: Vec<f64>,
+    correlation: Vec<Vec<f64>>,
+    k: f64,
+    r: f64,
+    t: f64,
+    options_type: &str,
+) -> PyResult<BasketOption> {
+    let option_type = parse_options_type(options_type)?;
+    let basket_option = BasketOption::new(spots, weights, sigmas, divs, correlation, k, r, t, option_type);
+    basket_option.validate().map_err(py_err)?;
+    correlation_factor(&basket_option.correlation).map_err(py_err)?;
+    Ok(basket_option)
+}
+
+/// Calculates the Stulz price of a two-asset rainbow option.
+///
+/// # Arguments
+///
+/// * `spots` - The initial prices of the two assets.
+/// * `sigmas` - The volatilities of the two assets.
+/// * `divs` - The continuous dividend yields of the two assets.
+/// * `correlation` - The 2x2 correlation matrix of the asset returns.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `payoff` - The payoff, one of "best_of_call", "best_of_put", "worst_of_call", or "worst_of_put".
+///
+/// # Returns
+///
+/// The option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `payoff` is invalid, if the option is not on exactly
+/// two assets, or if `correlation` is not a valid correlation matrix.
+#[pyfunction]
+fn calculate_rainbow_option_price(
+    py: Python,
+    spots: Vec<f64>,
+    sigmas: Vec<f64>,
+    divs: Vec<f64>,
+    correlation: Vec<Vec<f64>>,
+    k: f64,
+    r: f64,
+    t: f64,
+    payoff: &str,
+) -> PyResult<f64> {
+    let rainbow_option = new_rainbow_option(spots, sigmas, divs, correlation, k, r, t, payoff)?;
+    py.allow_threads(|| rainbow_option.stulz_price())
+        .ok_or_else(|| PyValueError::new_err("The closed form requires exactly two assets."))
+}
+
+/// Calculates the Monte Carlo price of a rainbow option on any number of assets.
+///
+/// # Arguments
+///
+/// * `spots` - The initial prices of the assets.
+/// * `sigmas` - The volatilities of the assets.
+/// * `divs` - The continuous dividend yields of the assets.
+/// * `correlation` - The correlation matrix of the asset returns.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `payoff` - The payoff, one of "best_of_call", "best_of_put", "worst_of_call", or "worst_of_put".
+/// * `paths` - The number of simulated paths.
+/// * `seed` - The seed of the random number generator.
+///
+/// # Returns
+///
+/// A tuple containing the Monte Carlo price and its standard error.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `payoff` is invalid, if the inputs have
+/// inconsistent lengths, if `correlation` is not a valid correlation matrix, or if
+/// `paths` is zero.
+#[pyfunction]
+fn calculate_rainbow_option_price_mc(
+    py: Python,
+    spots: Vec<f64>,
+    sigmas: Vec<f64>,
+    divs: Vec<f64>,
+    correlation: Vec<Vec<f64>>,
+    k: f64,
+    r: f64,
+    t: f64,
+    payoff: &str,
+    paths: usize,
+    seed: u64,
+) -> PyResult<(f64, f64)> {
+    let rainbow_option = new_rainbow_option(spots, sigmas, divs, correlation, k, r, t, payoff)?;
+    let engine = RainbowMonteCarlo::new(rainbow_option, paths, seed).map_err(py_err)?;
+    engine.validate().map_err(py_err)?;
+    Ok(py.allow_threads(|| engine.price_with_error()))
+}
+
+/// Creates a `RainbowOption` from the arguments passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `payoff` is invalid, and an `InvalidInputError` if the
+/// inputs have inconsistent lengths or if `correlation` is not a valid correlation matrix.
+fn new_rainbow_option(
+    spots: Vec<f64>,
+    sigmas: Vec<f64>,
+    divs: Vec<f64>,
+    correlation: Vec<Vec<f64>>,
+    k: f64,
+    r: f64,
+    t: f64,
+    payoff: &str,
+) -> PyResult<RainbowOption> {
+    let payoff = parse_rainbow_payoff(payoff)?;
+    let rainbow_option = RainbowOption::new(spots, sigmas, divs, correlation, k, r, t, payoff);
+    rainbow_option.validate().map_err(py_err)?;
+    correlation_factor(&rainbow_option.correlation).map_err(py_err)?;
+    Ok(rainbow_option)
+}
+
+/// Parses the rainbow payoff passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the payoff is not recognized.
+fn parse_rainbow_payoff(payoff: &str) -> PyResult<RainbowPayoff> {
+    match payoff {
+        "best_of_call" => Ok(RainbowPayoff::BestOfCall),
+        "best_of_put" => Ok(RainbowPayoff::BestOfPut),
+        "worst_of_call" => Ok(RainbowPayoff::WorstOfCall),
+        "worst_of_put" => Ok(RainbowPayoff::WorstOfPut),
+        _ => Err(PyValueError::new_err(
+            "Invalid payoff. Must be 'best_of_call', 'best_of_put', 'worst_of_call', or 'worst_of_put'.",
+        )),
+    }
+}
+
+/// Calculates the price of a two-asset option using an ADI finite-difference solver.
+///
+/// # Arguments
+///
+/// * `s1` - The initial price of the first asset.
+/// * `s2` - The initial price of the second asset.
+/// * `k` - The strike price of the option (ignored for exchange options).
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps.
+/// * `div1` - The continuous dividend yield of the first asset.
+/// * `div2` - The continuous dividend yield of the second asset.
+/// * `sigma1` - The volatility of the first asset.
+/// * `sigma2` - The volatility of the second asset.
+/// * `rho` - The correlation between the returns of the two assets.
+/// * `payoff` - The payoff type, as for `calculate_two_asset_option_price`.
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `space_steps` - The number of nodes in the log-price grid of each asset.
+/// * `scheme` - The ADI scheme, either "douglas" or "craig_sneyd".
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `payoff` or `scheme` is not recognized, and an
+/// `InvalidInputError` if `rho` is not in [-1, 1].
+#[pyfunction]
+fn calculate_two_asset_option_price_adi(
+    py: Python,
+    s1: f64,
+    s2: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div1: f64,
+    div2: f64,
+    sigma1: f64,
+    sigma2: f64,
+    rho: f64,
+    payoff: &str,
+    is_am: bool,
+    space_steps: usize,
+    scheme: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let payoff = parse_two_asset_payoff(payoff)?;
+    let scheme = match scheme {
+        "douglas" => AdiScheme::Douglas,
+        "craig_sneyd" => AdiScheme::CraigSneyd,
+        _ => return Err(PyValueError::new_err("Invalid scheme. Must be 'douglas' or 'craig_sneyd'.")),
+    };
+
+    let option = TwoAssetOption::new(
+        s1, s2, k, r, t, n, div1, div2, sigma1, sigma2, rho, payoff, exercise_style(is_am),
+    );
+    option.validate().map_err(py_err)?;
+    let solver = AdiSolver::new(option, space_steps, scheme);
+    Ok(py.allow_threads(|| solver.price()))
+}
+
+/// Parses the two-asset `payoff` string passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `payoff` is not recognized.
+fn parse_two_asset_payoff(payoff: &str) -> PyResult<TwoAssetPayoff> {
+    match payoff {
+        "spread_call" => Ok(TwoAssetPayoff::SpreadCall),
+        "spread_put" => Ok(TwoAssetPayoff::SpreadPut),
+        "exchange" => Ok(TwoAssetPayoff::Exchange),
+        "best_of_call" => Ok(TwoAssetPayoff::BestOfCall),
+        "best_of_put" => Ok(TwoAssetPayoff::BestOfPut),
+        "worst_of_call" => Ok(TwoAssetPayoff::WorstOfCall),
+        "worst_of_put" => Ok(TwoAssetPayoff::WorstOfPut),
+        _ => Err(PyValueError::new_err(
+            "Invalid payoff. Must be 'spread_call', 'spread_put', 'exchange', 'best_of_call', \
+             'best_of_put', 'worst_of_call', or 'worst_of_put'.",
+        )),
+    }
+}
+
+/// Adds the multi-asset option functions to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_two_asset_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_two_asset_option_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_spread_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_exchange_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_basket_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_basket_option_price_mc, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_rainbow_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_rainbow_option_price_mc, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_two_asset_option_price_adi, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,641 @@
+// payoffs.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::binomial_tree_option::BinomialTreeOption;
+use numerical_options_core::binomial_lr_option::BinomialLROption;
+use numerical_options_core::binomial_lr_with_greeks::BinomialLRWithGreeks;
+use numerical_options_core::black_scholes_option::BlackScholesOption;
+use numerical_options_core::payoff::{ClosurePayoff, PayoffKind};
+use numerical_options_core::payoff_expression::PayoffExpression;
+use numerical_options_core::quanto_option::QuantoAdjustment;
+use numerical_options_core::fx_option::FxOption;
+use numerical_options_core::pricing_engine::PricingEngine;
+use numerical_options_core::option_type::OptionType;
+
+use crate::args::{parse_options_type, exercise_style};
+use crate::errors::{py_err, analytic_european_only};
+use crate::vanilla::PyPricingResult;
+
+/// Calculates the price of a digital option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option pays as soon as it is in the money (true) or only at expiration (false).
+/// * `digital_type` - The payoff type, either "cash_or_nothing" or "asset_or_nothing".
+/// * `cash` - The amount paid by a cash-or-nothing option (ignored for asset-or-nothing).
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (Black-Scholes).
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type`, `digital_type`, or `method` is invalid,
+/// or if the analytic method is requested for an American option.
+#[pyfunction]
+fn calculate_digital_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    digital_type: &str,
+    cash: f64,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.payoff = parse_digital_type(digital_type, cash)?;
+    price_by_method(py, stock_option, method)
+}
+
+/// Calculates the price of a gap option, whose trigger strike differs from its payout strike.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The trigger strike that decides whether the option pays.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `payout_strike` - The strike used to calculate the amount paid.
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (Black-Scholes).
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `method` is invalid, or if the
+/// analytic method is requested for an American option.
+#[pyfunction]
+fn calculate_gap_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    payout_strike: f64,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.payoff = PayoffKind::Gap { payout_strike };
+    price_by_method(py, stock_option, method)
+}
+
+/// Calculates the price of a quanto option on a foreign underlying that pays in domestic
+/// currency at a fixed exchange rate.
+///
+/// # Arguments
+///
+/// * `s0` - The initial price of the underlying, in foreign currency.
+/// * `k` - The strike price of the option, in foreign currency.
+/// * `r` - The domestic risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `foreign_rate` - The foreign risk-free interest rate.
+/// * `fx_sigma` - The volatility of the exchange rate (domestic units per foreign unit).
+/// * `fx_rho` - The correlation between the returns of the underlying and the exchange rate.
+/// * `fixed_rate` - The fixed exchange rate at which the payoff is converted.
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (Black-Scholes).
+///
+/// # Returns
+///
+/// The calculated option price, in domestic currency.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `method` is invalid, if `fx_sigma`
+/// is negative or `fx_rho` is not in [-1, 1], or if the analytic method is requested for
+/// an American option.
+#[pyfunction]
+fn calculate_quanto_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    foreign_rate: f64,
+    fx_sigma: f64,
+    fx_rho: f64,
+    fixed_rate: f64,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    if fx_sigma < 0.0 {
+        return Err(PyValueError::new_err("Invalid fx_sigma. Must be non-negative."));
+    }
+    if !(-1.0..=1.0).contains(&fx_rho) {
+        return Err(PyValueError::new_err("Invalid fx_rho. Must be in the range [-1, 1]."));
+    }
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    QuantoAdjustment::new(foreign_rate, fx_sigma, fx_rho).apply(&mut stock_option);
+    Ok(fixed_rate * price_by_method(py, stock_option, method)?)
+}
+
+/// Calculates the price of an FX option using the Garman-Kohlhagen model.
+///
+/// # Arguments
+///
+/// * `spot` - The spot exchange rate (domestic units per foreign unit).
+/// * `k` - The strike exchange rate.
+/// * `domestic_rate` - The domestic risk-free interest rate.
+/// * `foreign_rate` - The foreign risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `sigma` - The volatility of the exchange rate.
+/// * `options_type` - The type of the option on the foreign currency, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (Garman-Kohlhagen).
+///
+/// # Returns
+///
+/// The calculated option price, in domestic currency per unit of foreign notional.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `method` is invalid, or if the
+/// analytic method is requested for an American option.
+#[pyfunction]
+fn calculate_fx_option_price(
+    py: Python,
+    spot: f64,
+    k: f64,
+    domestic_rate: f64,
+    foreign_rate: f64,
+    t: f64,
+    n: usize,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let fx_option = FxOption::new(spot, k, domestic_rate, foreign_rate, t, sigma, option_type);
+    match method {
+        "analytic" if is_am => Err(analytic_european_only("method")),
+        "analytic" => Ok(py.allow_threads(|| fx_option.price())),
+        _ => price_by_method(py, fx_option.stock_option(n, exercise_style(is_am)).map_err(py_err)?, method),
+    }
+}
+
+/// The price and Greeks returned by `calculate_fx_option_price_and_greeks`.
+type FxPriceAndGreeks = (f64, f64, f64, f64, f64, f64, f64, f64);
+
+/// Calculates the Garman-Kohlhagen price and FX-market Greeks of a European FX option.
+///
+/// # Arguments
+///
+/// * `spot` - The spot exchange rate (domestic units per foreign unit).
+/// * `k` - The strike exchange rate.
+/// * `domestic_rate` - The domestic risk-free interest rate.
+/// * `foreign_rate` - The foreign risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `sigma` - The volatility of the exchange rate.
+/// * `options_type` - The type of the option on the foreign currency, either "call" or "put".
+///
+/// # Returns
+///
+/// A tuple containing the following values:
+/// - `option_price`: The option price, in domestic currency per unit of foreign notional.
+/// - `spot_delta`: The sensitivity of the price to the spot rate.
+/// - `premium_adjusted_delta`: The spot delta net of a premium paid in foreign currency.
+/// - `forward_delta`: The sensitivity of the undiscounted price to the forward rate.
+/// - `gamma`: The sensitivity of the spot delta to the spot rate.
+/// - `vega`: The sensitivity of the price to the volatility.
+/// - `domestic_rho`: The sensitivity of the price to the domestic interest rate.
+/// - `foreign_rho`: The sensitivity of the price to the foreign interest rate.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put".
+#[pyfunction]
+fn calculate_fx_option_price_and_greeks(
+    py: Python,
+    spot: f64,
+    k: f64,
+    domestic_rate: f64,
+    foreign_rate: f64,
+    t: f64,
+    sigma: f64,
+    options_type: &str,
+) -> PyResult<FxPriceAndGreeks> {
+    let option_type = parse_options_type(options_type)?;
+    let fx_option = FxOption::new(spot, k, domestic_rate, foreign_rate, t, sigma, option_type);
+    let (price, greeks) = py.allow_threads(|| (fx_option.price(), fx_option.greeks()));
+    Ok((
+        price,
+        greeks.spot_delta,
+        greeks.premium_adjusted_delta,
+        greeks.forward_delta,
+        greeks.gamma,
+        greeks.vega,
+        greeks.domestic_rho,
+        greeks.foreign_rho,
+    ))
+}
+
+/// Calculates the price of an option on a futures contract.
+///
+/// The lattice evolves the futures price with zero drift and discounts at `r`, and the
+/// analytic method is the Black (1976) formula.
+///
+/// # Arguments
+///
+/// * `f0` - The initial futures price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `sigma` - The volatility of the futures price.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (Black).
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `method` is invalid, or if the
+/// analytic method is requested for an American option.
+#[pyfunction]
+fn calculate_futures_option_price(
+    py: Python,
+    f0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let mut stock_option = StockOption::new(f0, k, r, t, n, 0.0, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.is_futures = true;
+    price_by_method(py, stock_option, method)
+}
+
+/// Calculates the price and Greeks of an option on a futures contract using the binomial
+/// LR (Leisen-Reimer) model.
+///
+/// # Arguments
+///
+/// * `f0` - The initial futures price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `sigma` - The volatility of the futures price.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+///
+/// # Returns
+///
+/// A `PricingResult` with the option price, delta, gamma, theta, vega, and rho, as for
+/// `calculate_option_price_and_greeks`, with delta and gamma taken with respect to the
+/// futures price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put".
+#[pyfunction]
+fn calculate_futures_option_price_and_greeks(
+    py: Python,
+    f0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+
+    let mut stock_option = StockOption::new(f0, k, r, t, n, 0.0, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.is_futures = true;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+    let binomial_lr_with_greeks = BinomialLRWithGreeks::new(binomial_lr_option);
+
+    Ok(py.allow_threads(|| binomial_lr_with_greeks.price()).into())
+}
+
+/// Calculates the price of an option with a payoff defined by a Python callable.
+///
+/// The callable is evaluated at every terminal node, and at every node where early
+/// exercise is allowed, so it should be cheap.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The reference strike, which centers the Leisen-Reimer tree on the payoff's kink.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `payoff` - A callable taking the stock price and returning the payoff.
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (numerical
+///   integration against the Black-Scholes terminal distribution).
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `method` is invalid or if the analytic method is
+/// requested for an American option, and propagates any exception raised by `payoff`.
+#[pyfunction]
+fn calculate_custom_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    is_am: bool,
+    payoff: PyObject,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let error = Arc::new(Mutex::new(None));
+    let callback_error = Arc::clone(&error);
+    let closure = move |s: f64| {
+        Python::with_gil(|py| match payoff.call1(py, (s,)).and_then(|value| value.extract::<f64>(py)) {
+            Ok(value) => value,
+            Err(err) => {
+                callback_error.lock().unwrap().get_or_insert(err);
+                f64::NAN
+            }
+        })
+    };
+
+    let mut stock_option = StockOption::new(
+        s0, k, r, t, n, div, sigma, OptionType::Call, exercise_style(is_am),
+    ).map_err(py_err)?;
+    stock_option.payoff = PayoffKind::Custom(Arc::new(ClosurePayoff(closure)));
+    // A failed callback yields NaN, so its error takes precedence over the pricing error.
+    let price = price_by_method(py, stock_option, method);
+    let callback_error = error.lock().unwrap().take();
+    match callback_error {
+        Some(err) => Err(err),
+        None => price,
+    }
+}
+
+/// Calculates the price of an option with a payoff given as an expression.
+///
+/// The expression is parsed once in Rust, so pricing does not call back into Python.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price, available as `K` in the expression; it also centers the Leisen-Reimer tree.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `expression` - The payoff as an expression of the stock price `S`, e.g. "max(S - K, 0)".
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (numerical
+///   integration against the Black-Scholes terminal distribution).
+/// * `parameters` - The optional values of the other names used in the expression.
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the expression cannot be parsed, if the `method` is
+/// invalid, or if the analytic method is requested for an American option.
+#[pyfunction]
+fn calculate_expression_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    is_am: bool,
+    expression: &str,
+    method: &str,
+    parameters: Option<HashMap<String, f64>>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let payoff = PayoffExpression::parse(expression, &parameters.unwrap_or_default())
+        .map_err(PyValueError::new_err)?;
+    let mut stock_option = StockOption::new(
+        s0, k, r, t, n, div, sigma, OptionType::Call, exercise_style(is_am),
+    ).map_err(py_err)?;
+    stock_option.payoff = PayoffKind::Custom(Arc::new(payoff));
+    price_by_method(py, stock_option, method)
+}
+
+/// Calculates the price of an asymmetric power option, which pays `max(S^p - K, 0)` for calls.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price, expressed in units of `S^p`.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `exponent` - The power `p` applied to the stock price.
+/// * `method` - The pricing method, either "tree" (binomial LR) or "analytic" (Black-Scholes).
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `method` is invalid, if the exponent
+/// is not positive, or if the analytic method is requested for an American option.
+#[pyfunction]
+fn calculate_power_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    exponent: f64,
+    method: &str,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    if exponent.is_nan() || exponent <= 0.0 {
+        return Err(PyValueError::new_err("exponent must be positive."));
+    }
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.payoff = PayoffKind::Power { exponent };
+    price_by_method(py, stock_option, method)
+}
+
+/// Prices an option with the binomial LR tree or the analytic Black-Scholes formulas,
+/// releasing the GIL while the engine runs.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `method` is not "tree" or "analytic", or the error of
+/// `PricingEngine::try_price` if the option is invalid, the analytic method is requested
+/// for an American option, or the price is not finite.
+pub(crate) fn price_by_method(py: Python, stock_option: StockOption, method: &str) -> PyResult<f64> {
+    let engine: Box<dyn PricingEngine + Send + Sync> = match method {
+        "tree" => Box::new(BinomialLROption::new(BinomialTreeOption::new(stock_option))),
+        "analytic" => Box::new(BlackScholesOption::new(stock_option)),
+        _ => return Err(PyValueError::new_err("Invalid method. Must be 'tree' or 'analytic'.")),
+    };
+    py.allow_threads(|| engine.try_price()).map_err(py_err)
+}
+
+/// Calculates the price and Greeks of a digital option using the binomial LR (Leisen-Reimer) model.
+///
+/// The terminal payoffs are averaged over the cell of each node, so the Greeks do not
+/// oscillate as the strike moves between nodes.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option pays as soon as it is in the money (true) or only at expiration (false).
+/// * `digital_type` - The payoff type, either "cash_or_nothing" or "asset_or_nothing".
+/// * `cash` - The amount paid by a cash-or-nothing option (ignored for asset-or-nothing).
+///
+/// # Returns
+///
+/// A `PricingResult` with the option price, delta, gamma, theta, vega, and rho, as for
+/// `calculate_option_price_and_greeks`.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `digital_type` is invalid.
+#[pyfunction]
+fn calculate_digital_option_price_and_greeks(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    digital_type: &str,
+    cash: f64,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.payoff = parse_digital_type(digital_type, cash)?;
+
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+    let binomial_lr_with_greeks = BinomialLRWithGreeks::new(binomial_lr_option);
+    Ok(py.allow_threads(|| binomial_lr_with_greeks.price()).into())
+}
+
+/// Parses the `digital_type` string passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `digital_type` is not recognized.
+fn parse_digital_type(digital_type: &str, cash: f64) -> PyResult<PayoffKind> {
+    match digital_type {
+        "cash_or_nothing" => Ok(PayoffKind::CashOrNothing { cash }),
+        "asset_or_nothing" => Ok(PayoffKind::AssetOrNothing),
+        _ => Err(PyValueError::new_err(
+            "Invalid digital_type. Must be 'cash_or_nothing' or 'asset_or_nothing'.",
+        )),
+    }
+}
+
+/// Adds the functions that price options with other payoffs to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_digital_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_gap_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_quanto_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_fx_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_fx_option_price_and_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_futures_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_futures_option_price_and_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_custom_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_expression_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_power_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_digital_option_price_and_greeks, m)?)?;
+    Ok(())
+}
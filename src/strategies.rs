@@ -0,0 +1,165 @@
+// strategies.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::option_strategy::{Instrument, Leg, OptionStrategy};
+
+use crate::args::{parse_options_type, exercise_style};
+use crate::errors::py_err;
+use crate::vanilla::PyPricingResult;
+
+/// Calculates the price and Greeks of a standard multi-leg option strategy using the
+/// binomial LR (Leisen-Reimer) model.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the options (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `is_am` - A boolean indicating whether the options are American-style (true) or European-style (false).
+/// * `strategy` - The strategy: "vertical_spread" (strikes `[lower, upper]`), "collar"
+///   (`[put_strike, call_strike]`), "straddle" (`[k]`), "strangle" (`[put_strike, call_strike]`),
+///   or "butterfly" (`[lower, middle, upper]`).
+/// * `strikes` - The strike prices of the strategy, in increasing order.
+/// * `options_type` - The type of the legs of a vertical spread or butterfly, either "call" or "put". Defaults to "call".
+///
+/// # Returns
+///
+/// A `PricingResult` with the price, delta, gamma, theta, vega, and rho of the strategy,
+/// as for `calculate_option_price_and_greeks`, and no diagnostics.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `strategy` or `options_type` is invalid, or if the
+/// number or order of the strikes does not match the strategy, and an `InvalidInputError`
+/// if a parameter of a leg is outside its domain.
+#[pyfunction]
+fn calculate_strategy_price_and_greeks(
+    py: Python,
+    s0: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    is_am: bool,
+    strategy: &str,
+    strikes: Vec<f64>,
+    options_type: Option<&str>,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type.unwrap_or("call"))?;
+    let expected = match strategy {
+        "straddle" => 1,
+        "vertical_spread" | "collar" | "strangle" => 2,
+        "butterfly" => 3,
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid strategy. Must be 'vertical_spread', 'collar', 'straddle', 'strangle', or 'butterfly'.",
+            ))
+        }
+    };
+    if strikes.len() != expected {
+        return Err(PyValueError::new_err(format!(
+            "The {} strategy takes {} strike(s) but was given {}.",
+            strategy,
+            expected,
+            strikes.len()
+        )));
+    }
+    if strikes.windows(2).any(|w| w[0] > w[1]) {
+        return Err(PyValueError::new_err("The strikes must be in increasing order."));
+    }
+
+    let option_strategy = match strategy {
+        "straddle" => OptionStrategy::straddle(strikes[0]),
+        "vertical_spread" => OptionStrategy::vertical_spread(strikes[0], strikes[1], option_type),
+        "collar" => OptionStrategy::collar(strikes[0], strikes[1]),
+        "strangle" => OptionStrategy::strangle(strikes[0], strikes[1]),
+        _ => OptionStrategy::butterfly(strikes[0], strikes[1], strikes[2], option_type),
+    };
+    py.allow_threads(|| {
+        option_strategy.price_and_greeks(s0, |k, option_type| {
+            StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am))
+        })
+    })
+    .map(PyPricingResult::from)
+    .map_err(py_err)
+}
+
+/// Calculates the price and Greeks of an arbitrary combination of options and the
+/// underlying asset using the binomial LR (Leisen-Reimer) model.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the options (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `is_am` - A boolean indicating whether the options are American-style (true) or European-style (false).
+/// * `legs` - The legs as `(instrument, strike, quantity)` tuples, where the instrument is
+///   "call", "put", or "underlying" (whose strike is ignored) and negative quantities are
+///   short positions.
+///
+/// # Returns
+///
+/// A `PricingResult` with the price, delta, gamma, theta, vega, and rho of the combination,
+/// as for `calculate_option_price_and_greeks`, and no diagnostics.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if an instrument is invalid, and an `InvalidInputError` if a
+/// parameter of an option leg is outside its domain.
+#[pyfunction]
+fn calculate_legs_price_and_greeks(
+    py: Python,
+    s0: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    is_am: bool,
+    legs: Vec<(String, f64, f64)>,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let legs = legs
+        .iter()
+        .map(|(instrument, k, quantity)| {
+            let instrument = match instrument.as_str() {
+                "call" => Instrument::Call,
+                "put" => Instrument::Put,
+                "underlying" => Instrument::Underlying,
+                _ => {
+                    return Err(PyValueError::new_err(
+                        "Invalid instrument. Must be 'call', 'put', or 'underlying'.",
+                    ))
+                }
+            };
+            Ok(Leg::new(instrument, *k, *quantity))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let option_strategy = OptionStrategy::new(legs);
+    py.allow_threads(|| {
+        option_strategy.price_and_greeks(s0, |k, option_type| {
+            StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am))
+        })
+    })
+    .map(PyPricingResult::from)
+    .map_err(py_err)
+}
+
+/// Adds the option strategy functions to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_strategy_price_and_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_legs_price_and_greeks, m)?)?;
+    Ok(())
+}
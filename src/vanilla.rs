@@ -0,0 +1,1308 @@
+// vanilla.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::types::{PyDict, PyIterator, PyTuple};
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::binomial_tree_option::BinomialTreeOption;
+use numerical_options_core::binomial_lr_option::BinomialLROption;
+use numerical_options_core::binomial_crr_option::BinomialCRROption;
+use numerical_options_core::binomial_lr_with_greeks::{BinomialLRWithGreeks, LadderRow};
+use numerical_options_core::binomial_lr_with_ad::BinomialLRWithAD;
+use numerical_options_core::finite_difference_option::FiniteDifferenceOption;
+use numerical_options_core::black_scholes_option::BlackScholesOption;
+use numerical_options_core::greeks::Greeks;
+use numerical_options_core::pricing_engine::{EngineDiagnostics, PricingResult, ENGINE_NAMES};
+use numerical_options_core::adaptive_steps::price_to_tolerance;
+use numerical_options_core::option_type::ExerciseStyle;
+
+use crate::args::{parse_greeks_config, select_engine, parse_theta_convention, parse_options_type, exercise_style};
+use crate::batch::float64_arrays;
+use crate::errors::{py_err, analytic_european_only};
+use crate::market::set_dividends;
+
+/// Calculates the option price and Greeks using the binomial LR (Leisen-Reimer) model.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `vol_bump` - The volatility bump used for vega. Defaults to 0.01.
+/// * `rate_bump` - The interest rate bump used for rho. Defaults to 0.01.
+/// * `time_bump` - The time bump used for theta (in years). Defaults to 0.0001.
+/// * `difference` - The finite-difference scheme, either "one_sided" (the default) or "central".
+/// * `bump_type` - Whether the bumps are "absolute" (the default) or "relative" to the parameters.
+/// * `theta_unit` - The unit of theta: "year", "calendar_day", or "trading_day", in which case
+///   theta is the change in value as time passes. Defaults to the derivative with respect to
+///   the time to expiration.
+/// * `include_carry` - Whether theta includes the interest on the value and the drift of the
+///   stock at the cost of carry. Defaults to true.
+///
+/// # Returns
+///
+/// A `PricingResult` with the following attributes, which also unpacks in this order like
+/// a tuple, and the diagnostics of the Leisen-Reimer tree:
+/// - `price`: The calculated option price.
+/// - `delta`: The option's delta (rate of change of option price with respect to the underlying asset price).
+/// - `gamma`: The option's gamma (rate of change of delta with respect to the underlying asset price).
+/// - `theta`: The option's theta (rate of change of option price with respect to time).
+/// - `vega`: The option's vega (sensitivity of option price to changes in volatility).
+/// - `rho`: The option's rho (sensitivity of option price to changes in the risk-free interest rate).
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type`, `difference`, `bump_type`, or `theta_unit`
+/// is invalid, or if a bump is not positive or the time bump is not smaller than `t`, and an
+/// `InvalidInputError` if a parameter of the option is outside its domain.
+#[pyfunction]
+fn calculate_option_price_and_greeks(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    vol_bump: Option<f64>,
+    rate_bump: Option<f64>,
+    time_bump: Option<f64>,
+    difference: Option<&str>,
+    bump_type: Option<&str>,
+    theta_unit: Option<&str>,
+    include_carry: Option<bool>,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let config = parse_greeks_config(t, vol_bump, rate_bump, time_bump, difference, bump_type, theta_unit, include_carry)?;
+
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+    let mut binomial_lr_with_greeks = BinomialLRWithGreeks::new(binomial_lr_option);
+    binomial_lr_with_greeks.config = config;
+
+    Ok(py.allow_threads(|| binomial_lr_with_greeks.price()).into())
+}
+
+/// Calculates the option price and Greeks using the binomial LR (Leisen-Reimer) model
+/// with automatic differentiation.
+///
+/// The tree is traversed once in dual-number arithmetic, which yields the exact
+/// derivatives of the tree price instead of finite-difference estimates.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `theta_unit` - The unit of theta, as for `calculate_option_price_and_greeks`.
+/// * `include_carry` - Whether theta includes the carry of the position. Defaults to true.
+///
+/// # Returns
+///
+/// A `PricingResult` with the price, delta, gamma, theta, vega, and rho of the option, as
+/// for `calculate_option_price_and_greeks`.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `theta_unit` is invalid, or if the
+/// spot price, time to expiration, or number of steps is not positive, or the strike
+/// price or volatility is negative.
+#[pyfunction]
+fn calculate_option_price_and_greeks_ad(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    theta_unit: Option<&str>,
+    include_carry: Option<bool>,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let stock_option = StockOption::builder()
+        .spot(s0)
+        .strike(k)
+        .rate(r)
+        .expiry(t)
+        .steps(n)
+        .dividend_yield(div)
+        .volatility(sigma)
+        .option_type(parse_options_type(options_type)?)
+        .exercise(exercise_style(is_am))
+        .build().map_err(py_err)?;
+    let mut binomial_lr_with_ad = BinomialLRWithAD::new(stock_option);
+    binomial_lr_with_ad.theta = parse_theta_convention(theta_unit, include_carry)?;
+    Ok(py.allow_threads(|| binomial_lr_with_ad.price()).into())
+}
+
+/// Calculates the option price and Greeks over a ladder of stock prices using the
+/// binomial LR (Leisen-Reimer) model.
+///
+/// # Arguments
+///
+/// * `spots` - The stock prices at which to evaluate the option.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `vol_bump` - The volatility bump used for vega. Defaults to 0.01.
+/// * `rate_bump` - The interest rate bump used for rho. Defaults to 0.01.
+/// * `time_bump` - The time bump used for theta (in years). Defaults to 0.0001.
+/// * `difference` - The finite-difference scheme, either "one_sided" (the default) or "central".
+/// * `bump_type` - Whether the bumps are "absolute" (the default) or "relative" to the parameters.
+/// * `theta_unit` - The unit of theta, as for `calculate_option_price_and_greeks`.
+/// * `include_carry` - Whether theta includes the carry of the position. Defaults to true.
+/// * `as_arrays` - Whether the ladder is returned as a tuple of seven `Float64Array`
+///   columns, the stock prices followed by the price and Greeks, which NumPy wraps without
+///   copying. Defaults to false.
+///
+/// # Returns
+///
+/// A list with, for each stock price, a tuple containing the stock price and the price,
+/// delta, gamma, theta, vega, and rho of the option, or its columns as arrays.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if any of the string arguments is invalid, or if a bump is not
+/// positive or the time bump is not smaller than `t`.
+#[pyfunction]
+fn calculate_spot_ladder(
+    py: Python,
+    spots: Vec<f64>,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    vol_bump: Option<f64>,
+    rate_bump: Option<f64>,
+    time_bump: Option<f64>,
+    difference: Option<&str>,
+    bump_type: Option<&str>,
+    theta_unit: Option<&str>,
+    include_carry: Option<bool>,
+    as_arrays: Option<bool>,
+) -> PyResult<PyObject> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let config = parse_greeks_config(t, vol_bump, rate_bump, time_bump, difference, bump_type, theta_unit, include_carry)?;
+
+    // The ladder replaces the spot price, so the strike stands in for it.
+    let stock_option = StockOption::new(k, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+    let mut binomial_lr_with_greeks = BinomialLRWithGreeks::new(binomial_lr_option);
+    binomial_lr_with_greeks.config = config;
+
+    let rows = py.allow_threads(|| binomial_lr_with_greeks.spot_ladder(&spots));
+    if !as_arrays.unwrap_or(false) {
+        return Ok(rows.into_py(py));
+    }
+    let column = |field: fn(&LadderRow) -> f64| rows.iter().map(field).collect::<Vec<_>>();
+    float64_arrays(
+        py,
+        vec![
+            column(|row| row.0),
+            column(|row| row.1),
+            column(|row| row.2),
+            column(|row| row.3),
+            column(|row| row.4),
+            column(|row| row.5),
+            column(|row| row.6),
+        ],
+    )
+}
+
+/// Calculates the option price and Greeks with a chosen pricing engine by bumping and
+/// revaluing it.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps of the tree or finite-difference grid (ignored by the analytic engine).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `engine` - The pricing engine: "tree" (plain binomial tree with the moves `pu` and `pd`),
+///   "crr" (Cox-Ross-Rubinstein), "lr" (Leisen-Reimer), "trinomial" (trinomial tree), "fd"
+///   (Crank-Nicolson), or "analytic" (Black-Scholes).
+/// * `space_steps` - The number of spatial nodes for the "fd" engine. Defaults to 201.
+/// * `spot_bump` - The stock price bump for delta and gamma, relative to `s0`. Defaults to 0.01.
+/// * `vol_bump` - The volatility bump used for vega. Defaults to 0.01.
+/// * `rate_bump` - The interest rate bump used for rho. Defaults to 0.01.
+/// * `time_bump` - The time bump used for theta (in years). Defaults to 0.0001.
+/// * `difference` - The finite-difference scheme for theta, vega, and rho, either "one_sided" (the default) or "central".
+/// * `bump_type` - Whether the bumps are "absolute" (the default) or "relative" to the parameters.
+/// * `theta_unit` - The unit of theta: "year", "calendar_day", or "trading_day", in which case
+///   theta is the change in value as time passes. Defaults to the derivative with respect to
+///   the time to expiration.
+/// * `include_carry` - Whether theta includes the interest on the value and the drift of the
+///   stock at the cost of carry. Defaults to true.
+/// * `dividends` - The discrete dividends paid before expiration as `(time, amount, kind)`
+///   tuples, where `kind` is "cash" or "proportional". Defaults to none.
+/// * `borrow` - The continuous stock borrow (repo) rate, which lowers the forward price on
+///   top of `div`. Defaults to 0.
+/// * `settlement_lag` - The time (in years) from exercise to the settlement of the payoff.
+///   Defaults to 0.
+/// * `premium_lag` - The time (in years) from the valuation to the payment of the premium.
+///   Defaults to 0.
+/// * `log_space` - Whether the tree engines compute the stock price of each node from its
+///   log price, for very large `n` or extreme volatilities and maturities. Defaults to false.
+/// * `pu` - The up move of the "tree" engine, whose up factor is `1 + pu`. Defaults to 0.
+/// * `pd` - The down move of the "tree" engine, whose down factor is `1 - pd`. Defaults to 0.
+///
+/// # Returns
+///
+/// A `PricingResult` with the price, delta, gamma, theta, vega, and rho of the option, as
+/// for `calculate_option_price_and_greeks`, all as amounts paid on the premium date, and
+/// the diagnostics of the chosen engine.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if any of the string arguments is invalid, if a bump is not
+/// positive or the time bump is not smaller than `t`, or if the dividends are invalid.
+/// Raises `InvalidInputError` if `StockOption::validate` rejects the option, such as for a
+/// negative lag, `UnsupportedError` if the analytic engine is requested for an American option,
+/// and `NumericalFailureError` if the price is not finite.
+#[pyfunction]
+pub(crate) fn calculate_option_price_and_greeks_with_engine(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    engine: &str,
+    space_steps: Option<usize>,
+    spot_bump: Option<f64>,
+    vol_bump: Option<f64>,
+    rate_bump: Option<f64>,
+    time_bump: Option<f64>,
+    difference: Option<&str>,
+    bump_type: Option<&str>,
+    theta_unit: Option<&str>,
+    include_carry: Option<bool>,
+    dividends: Option<Vec<(f64, f64, String)>>,
+    borrow: Option<f64>,
+    settlement_lag: Option<f64>,
+    premium_lag: Option<f64>,
+    log_space: Option<bool>,
+    pu: Option<f64>,
+    pd: Option<f64>,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let config = parse_greeks_config(t, vol_bump, rate_bump, time_bump, difference, bump_type, theta_unit, include_carry)?;
+    let spot_bump = spot_bump.unwrap_or(0.01);
+    if spot_bump <= 0.0 {
+        return Err(PyValueError::new_err("The bumps must be positive and the time bump smaller than t."));
+    }
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.borrow = borrow.unwrap_or(0.0);
+    stock_option.settlement_lag = settlement_lag.unwrap_or(0.0);
+    stock_option.premium_lag = premium_lag.unwrap_or(0.0);
+    stock_option.log_space = log_space.unwrap_or(false);
+    set_dividends(&mut stock_option, dividends)?;
+    let moves = (pu.unwrap_or(0.0), pd.unwrap_or(0.0));
+    let engine = select_engine(engine, &ENGINE_NAMES, stock_option, moves, space_steps.unwrap_or(201))?;
+    py.allow_threads(|| engine.greeks(config, spot_bump)).map(PyPricingResult::from).map_err(py_err)
+}
+
+/// The price and Greeks of an option, with the discretization of the engine behind them,
+/// as returned by `calculate_option_price_and_greeks` and the other pricing functions.
+///
+/// The values are read-only attributes, `greeks` and `diagnostics` are dictionaries, and
+/// `to_dict` returns everything as one dictionary. The result also unpacks, indexes, and
+/// iterates as the price, delta, gamma, theta, vega, and rho, like the tuples these
+/// functions returned before.
+#[pyclass(name = "PricingResult", module = "numerical_options_rs")]
+pub(crate) struct PyPricingResult {
+    /// The result calculated by the core crate.
+    result: PricingResult,
+}
+
+impl From<PricingResult> for PyPricingResult {
+    fn from(result: PricingResult) -> Self {
+        PyPricingResult { result }
+    }
+}
+
+impl PyPricingResult {
+    /// The price followed by the delta, gamma, theta, vega, and rho.
+    fn values(&self) -> [f64; 6] {
+        let greeks = &self.result.greeks;
+        [self.result.price, greeks.delta, greeks.gamma, greeks.theta, greeks.vega, greeks.rho]
+    }
+
+    /// Converts the Greeks to a dictionary keyed by their names.
+    fn greeks_dict<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(py);
+        for (name, value) in GREEK_NAMES.iter().zip(&self.values()[1..]) {
+            dict.set_item(name, value)?;
+        }
+        Ok(dict)
+    }
+}
+
+/// The names of the Greeks of a `PricingResult`, in the order it unpacks them.
+const GREEK_NAMES: [&str; 5] = ["delta", "gamma", "theta", "vega", "rho"];
+
+/// Converts the diagnostics of an engine to a dictionary with the fields of
+/// `get_engine_diagnostics`.
+fn diagnostics_dict<'py>(py: Python<'py>, diagnostics: &EngineDiagnostics) -> PyResult<&'py PyDict> {
+    let dict = PyDict::new(py);
+    dict.set_item("engine", diagnostics.engine)?;
+    dict.set_item("time_steps", diagnostics.time_steps)?;
+    dict.set_item("space_steps", diagnostics.space_steps)?;
+    dict.set_item("tree_parameters", diagnostics.tree_parameters)?;
+    dict.set_item("paths", diagnostics.paths)?;
+    Ok(dict)
+}
+
+#[pymethods]
+impl PyPricingResult {
+    /// The price of the option.
+    #[getter]
+    fn price(&self) -> f64 {
+        self.result.price
+    }
+
+    /// The sensitivity of the price to the stock price.
+    #[getter]
+    fn delta(&self) -> f64 {
+        self.result.greeks.delta
+    }
+
+    /// The sensitivity of delta to the stock price.
+    #[getter]
+    fn gamma(&self) -> f64 {
+        self.result.greeks.gamma
+    }
+
+    /// The sensitivity of the price to the passage of time, in the requested theta unit.
+    #[getter]
+    fn theta(&self) -> f64 {
+        self.result.greeks.theta
+    }
+
+    /// The sensitivity of the price to the volatility.
+    #[getter]
+    fn vega(&self) -> f64 {
+        self.result.greeks.vega
+    }
+
+    /// The sensitivity of the price to the interest rate.
+    #[getter]
+    fn rho(&self) -> f64 {
+        self.result.greeks.rho
+    }
+
+    /// The delta, gamma, theta, vega, and rho as a dictionary.
+    #[getter]
+    fn greeks<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        self.greeks_dict(py)
+    }
+
+    /// The engine, time steps, spatial nodes, and tree parameters behind the result, as a
+    /// dictionary, or `None` if the result combines several valuations.
+    #[getter]
+    fn diagnostics<'py>(&self, py: Python<'py>) -> PyResult<Option<&'py PyDict>> {
+        self.result.diagnostics.as_ref().map(|diagnostics| diagnostics_dict(py, diagnostics)).transpose()
+    }
+
+    /// Converts the result to a dictionary with the price, the Greeks, and the diagnostics.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(py);
+        dict.set_item("price", self.result.price)?;
+        dict.set_item("greeks", self.greeks_dict(py)?)?;
+        dict.set_item("diagnostics", self.diagnostics(py)?)?;
+        Ok(dict)
+    }
+
+    fn __len__(&self) -> usize {
+        self.values().len()
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<f64> {
+        let values = self.values();
+        let len = values.len() as isize;
+        let position = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&position) {
+            return Err(PyIndexError::new_err("PricingResult index out of range."));
+        }
+        Ok(values[position as usize])
+    }
+
+    fn __iter__(&self, py: Python) -> PyResult<Py<PyIterator>> {
+        Ok(PyIterator::from_object(py, PyTuple::new(py, self.values()))?.into())
+    }
+
+    fn __repr__(&self) -> String {
+        let greeks = &self.result.greeks;
+        format!(
+            "PricingResult(price={}, delta={}, gamma={}, theta={}, vega={}, rho={})",
+            self.result.price, greeks.delta, greeks.gamma, greeks.theta, greeks.vega, greeks.rho,
+        )
+    }
+}
+
+/// The engine name, time steps, spatial nodes, and tree parameters returned by
+/// `get_engine_diagnostics`.
+type Diagnostics = (&'static str, usize, usize, Option<(f64, f64, f64)>);
+
+/// Describes how a chosen pricing engine discretizes an option, to check the tree or
+/// grid behind a price.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps of the tree or finite-difference grid.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `engine` - The pricing engine, as for `calculate_option_price_and_greeks_with_engine`.
+/// * `space_steps` - The number of spatial nodes for the "fd" engine. Defaults to 201.
+/// * `pu` - The up move of the "tree" engine, whose up factor is `1 + pu`. Defaults to 0.
+/// * `pd` - The down move of the "tree" engine, whose down factor is `1 - pd`. Defaults to 0.
+///
+/// # Returns
+///
+/// A tuple containing the name of the engine, its number of time steps (0 for the
+/// analytic engine), its number of spatial nodes (0 for the trees), and, for the trees,
+/// the up factor, down factor, and risk-neutral probability of an up move.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `engine` is invalid, an
+/// `InvalidInputError` if the engine rejects the option, and an `UnsupportedError` if the
+/// analytic engine is requested for an American option.
+#[pyfunction]
+fn get_engine_diagnostics(
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    engine: &str,
+    space_steps: Option<usize>,
+    pu: Option<f64>,
+    pd: Option<f64>,
+) -> PyResult<Diagnostics> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let moves = (pu.unwrap_or(0.0), pd.unwrap_or(0.0));
+    let engine = select_engine(engine, &ENGINE_NAMES, stock_option, moves, space_steps.unwrap_or(201))?;
+    engine.validate().map_err(py_err)?;
+    engine.check_supported().map_err(py_err)?;
+    let diagnostics = engine.diagnostics();
+    Ok((diagnostics.engine, diagnostics.time_steps, diagnostics.space_steps, diagnostics.tree_parameters))
+}
+
+/// Calculates the option price on a tree with as many time steps as a target accuracy
+/// requires.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `tolerance` - The largest acceptable difference between successive estimates of the price.
+/// * `engine` - The tree: "crr" (Cox-Ross-Rubinstein) or "lr" (Leisen-Reimer, the default).
+/// * `initial_steps` - The number of time steps of the coarsest tree. Defaults to 25.
+/// * `max_steps` - The largest number of time steps to use. Defaults to 10,000.
+///
+/// # Returns
+///
+/// A tuple containing the Richardson-extrapolated price, the number of time steps of the
+/// finest tree priced, and the estimated error, which exceeds `tolerance` if `max_steps`
+/// was reached first.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `engine` is invalid, if `tolerance`
+/// is not positive, or if `initial_steps` is zero or larger than `max_steps`.
+#[pyfunction]
+fn calculate_option_price_adaptive(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    tolerance: f64,
+    engine: Option<&str>,
+    initial_steps: Option<usize>,
+    max_steps: Option<usize>,
+) -> PyResult<(f64, usize, f64)> {
+    let option_type = parse_options_type(options_type)?;
+    let initial_steps = initial_steps.unwrap_or(25);
+    let max_steps = max_steps.unwrap_or(10_000);
+    if tolerance <= 0.0 {
+        return Err(PyValueError::new_err("The tolerance must be positive."));
+    }
+    if initial_steps == 0 || initial_steps > max_steps {
+        return Err(PyValueError::new_err(
+            "initial_steps must be positive and no larger than max_steps.",
+        ));
+    }
+    let option = StockOption::new(s0, k, r, t, initial_steps, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let tree = BinomialTreeOption::new(option);
+    // The CRR tree converges at first order. The Leisen-Reimer tree converges at second
+    // order for European options, but early exercise brings it back to first order.
+    match engine.unwrap_or("lr") {
+        "crr" => {
+            let engine = BinomialCRROption::new(tree);
+            Ok(py.allow_threads(|| price_to_tolerance(&engine, 1.0, tolerance, initial_steps, max_steps)))
+        }
+        "lr" => {
+            let order = if is_am { 1.0 } else { 2.0 };
+            let initial_steps = BinomialLROption::odd_steps(initial_steps);
+            let engine = BinomialLROption::new(tree);
+            Ok(py.allow_threads(|| price_to_tolerance(&engine, order, tolerance, initial_steps, max_steps)))
+        }
+        _ => Err(PyValueError::new_err("Invalid engine. Must be 'crr' or 'lr'.")),
+    }
+}
+
+/// Calculates the prices of options at many strikes on one Cox-Ross-Rubinstein tree.
+///
+/// The options of a chain share the spot, rate, volatility and expiry, so the stock
+/// price tree is built once and the backward induction runs for every strike together.
+/// Each price equals that of `calculate_option_price_and_greeks_with_engine` with the
+/// "crr" engine.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `strikes` - The strike prices of the options.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the options (in years).
+/// * `n` - The number of time steps of the tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the options, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the options are American-style (true) or European-style (false).
+///
+/// # Returns
+///
+/// The price of the option at each strike, in order.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is invalid.
+#[pyfunction]
+fn calculate_option_prices_by_strike(
+    py: Python,
+    s0: f64,
+    strikes: Vec<f64>,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+) -> PyResult<Vec<f64>> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let option = StockOption::new(s0, s0, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let crr_option = BinomialCRROption::new(BinomialTreeOption::new(option));
+    Ok(py.allow_threads(|| crr_option.price_strikes(&strikes)))
+}
+
+/// Calculates the option price and Greeks off a market forward and discount factor
+/// instead of the spot price, interest rate, and dividend yield.
+///
+/// # Arguments
+///
+/// * `forward` - The forward price of the underlying asset for delivery at expiration.
+/// * `discount` - The discount factor to expiration.
+/// * `k` - The strike price of the option.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps of the tree or finite-difference grid (ignored by the analytic engine).
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `engine` - The pricing engine: "crr", "lr" (the default), "trinomial", "fd", or
+///   "analytic", as for `calculate_option_price_and_greeks_with_engine`.
+///
+/// # Returns
+///
+/// A `PricingResult` with the price, delta, gamma, theta, vega, and rho of the option, with
+/// delta and gamma taken with respect to the forward and rho with respect to the interest
+/// rate implied by the discount factor at a fixed forward.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `engine` is invalid, if the forward,
+/// discount factor, or time to expiration is not positive, or if the analytic engine is
+/// requested for an American option, and an `InvalidInputError` if another parameter of
+/// the option is outside its domain.
+#[pyfunction]
+fn calculate_option_price_and_greeks_from_forward(
+    py: Python,
+    forward: f64,
+    discount: f64,
+    k: f64,
+    t: f64,
+    n: usize,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    engine: Option<&str>,
+) -> PyResult<PyPricingResult> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    if forward <= 0.0 || discount <= 0.0 || t <= 0.0 {
+        return Err(PyValueError::new_err("The forward, discount factor, and t must be positive."));
+    }
+    let config = parse_greeks_config(t, None, None, None, None, None, None, None)?;
+
+    let stock_option = StockOption::from_forward(forward, discount, k, t, n, sigma, option_type, exercise_style(is_am))
+        .map_err(py_err)?;
+    let engine = select_engine(engine.unwrap_or("lr"), &ENGINE_NAMES[1..], stock_option, (0.0, 0.0), 201)?;
+    py.allow_threads(|| engine.greeks(config, 0.01)).map(PyPricingResult::from).map_err(py_err)
+}
+
+/// The currency code followed by the price, delta, gamma, theta, vega, and rho of one contract.
+type ContractGreeks = (String, f64, f64, f64, f64, f64, f64);
+
+/// Calculates the price and Greeks of an option contract in currency terms, for one
+/// contract rather than one unit of the underlying asset.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps of the tree or finite-difference grid (ignored by the analytic engine).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `multiplier` - The number of units of the underlying asset per contract, such as 100
+///   for US equity options or the foreign notional of an FX option.
+/// * `currency` - The ISO 4217 code of the currency of the prices, such as "USD".
+/// * `engine` - The pricing engine: "crr", "lr" (the default), "trinomial", "fd", or
+///   "analytic", as for `calculate_option_price_and_greeks_with_engine`.
+///
+/// # Returns
+///
+/// A tuple containing the currency code in upper case, and the price, delta, gamma, theta,
+/// vega, and rho of one contract. Delta is the number of units of the underlying asset
+/// that hedge one contract.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `engine` is invalid, if the
+/// `multiplier` is not positive, if the `currency` is not three letters, or if the
+/// analytic engine is requested for an American option, and an `InvalidInputError` if a
+/// parameter of the option is outside its domain.
+#[pyfunction]
+fn calculate_contract_price_and_greeks(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    multiplier: f64,
+    currency: &str,
+    engine: Option<&str>,
+) -> PyResult<ContractGreeks> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    if multiplier <= 0.0 {
+        return Err(PyValueError::new_err("The multiplier must be positive."));
+    }
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(PyValueError::new_err("Invalid currency. Must be a three-letter ISO 4217 code."));
+    }
+    let config = parse_greeks_config(t, None, None, None, None, None, None, None)?;
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.multiplier = multiplier;
+    let engine = select_engine(engine.unwrap_or("lr"), &ENGINE_NAMES[1..], stock_option, (0.0, 0.0), 201)?;
+    let result = py.allow_threads(|| engine.greeks(config, 0.01)).map_err(py_err)?;
+    let Greeks { delta, gamma, theta, vega, rho } = result.greeks;
+    Ok((currency.to_ascii_uppercase(), result.price, delta, gamma, theta, vega, rho))
+}
+
+/// Calculates the second-order cross Greeks (vanna, volga, and charm) of an option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `method` - The pricing method, either "tree" (binomial LR with central-difference bumps) or "analytic" (Black-Scholes).
+/// * `vol_bump` - The absolute volatility bump of the tree method. Defaults to 0.01.
+/// * `time_bump` - The time bump of the tree method (in years). Defaults to one calendar day.
+///
+/// # Returns
+///
+/// A tuple containing the vanna (`∂²V/∂S∂σ`), volga (`∂²V/∂σ²`), and charm (`−∂²V/∂S∂T`,
+/// the change in delta as time passes) of the option.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `method` is invalid, if the analytic
+/// method is requested for an American option, or if a bump is not positive or the time
+/// bump is not smaller than `t`.
+#[pyfunction]
+fn calculate_cross_greeks(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    method: &str,
+    vol_bump: Option<f64>,
+    time_bump: Option<f64>,
+) -> PyResult<(f64, f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let vol_bump = vol_bump.unwrap_or(0.01);
+    let time_bump = time_bump.unwrap_or(1.0 / 365.0);
+    if vol_bump <= 0.0 || time_bump <= 0.0 || time_bump >= t {
+        return Err(PyValueError::new_err(
+            "The bumps must be positive and the time bump smaller than t.",
+        ));
+    }
+
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let greeks = match method {
+        "tree" => {
+            let binomial_tree_option = BinomialTreeOption::new(stock_option);
+            let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+            let binomial_lr_with_greeks = BinomialLRWithGreeks::new(binomial_lr_option);
+            py.allow_threads(|| binomial_lr_with_greeks.cross_greeks(vol_bump, time_bump))
+        }
+        "analytic" if is_am => return Err(analytic_european_only("method")),
+        "analytic" => {
+            let black_scholes_option = BlackScholesOption::new(stock_option);
+            py.allow_threads(|| black_scholes_option.cross_greeks())
+        }
+        _ => return Err(PyValueError::new_err("Invalid method. Must be 'tree' or 'analytic'.")),
+    };
+    Ok((greeks.vanna, greeks.volga, greeks.charm))
+}
+
+/// Calculates the third-order spot Greeks (speed, zomma, and color) and the dividend and
+/// strike sensitivities (psi, dual delta, and dual gamma) of an option.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree (ignored by the analytic method).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `method` - The pricing method, either "tree" (binomial LR with central-difference bumps) or "analytic" (Black-Scholes).
+/// * `spot_bump` - The stock price bump of the tree method, relative to `s0`. Defaults to 0.01.
+/// * `vol_bump` - The absolute volatility bump of the tree method. Defaults to 0.01.
+/// * `time_bump` - The time bump of the tree method (in years). Defaults to one calendar day.
+/// * `div_bump` - The absolute dividend yield bump of the tree method. Defaults to 0.01.
+/// * `strike_bump` - The strike bump of the tree method, relative to `k`. Defaults to 0.01.
+///
+/// # Returns
+///
+/// A tuple containing the speed (`∂³V/∂S³`), zomma (`∂³V/∂S²∂σ`), color (`−∂³V/∂S²∂T`,
+/// the change in gamma as time passes), psi (`∂V/∂q`), dual delta (`∂V/∂K`), and dual
+/// gamma (`∂²V/∂K²`) of the option.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `method` is invalid, if the analytic
+/// method is requested for an American option, or if a bump is not positive or the time
+/// bump is not smaller than `t`.
+#[pyfunction]
+fn calculate_extended_greeks(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    method: &str,
+    spot_bump: Option<f64>,
+    vol_bump: Option<f64>,
+    time_bump: Option<f64>,
+    div_bump: Option<f64>,
+    strike_bump: Option<f64>,
+) -> PyResult<(f64, f64, f64, f64, f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let spot_bump = spot_bump.unwrap_or(0.01);
+    let vol_bump = vol_bump.unwrap_or(0.01);
+    let time_bump = time_bump.unwrap_or(1.0 / 365.0);
+    let div_bump = div_bump.unwrap_or(0.01);
+    let strike_bump = strike_bump.unwrap_or(0.01);
+    let bumps = [spot_bump, vol_bump, time_bump, div_bump, strike_bump];
+    if bumps.iter().any(|&bump| bump <= 0.0) || time_bump >= t {
+        return Err(PyValueError::new_err(
+            "The bumps must be positive and the time bump smaller than t.",
+        ));
+    }
+
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let greeks = match method {
+        "tree" => {
+            let binomial_tree_option = BinomialTreeOption::new(stock_option);
+            let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+            let binomial_lr_with_greeks = BinomialLRWithGreeks::new(binomial_lr_option);
+            py.allow_threads(|| {
+                binomial_lr_with_greeks.extended_greeks(spot_bump, vol_bump, time_bump, div_bump, strike_bump)
+            })
+        }
+        "analytic" if is_am => return Err(analytic_european_only("method")),
+        "analytic" => {
+            let black_scholes_option = BlackScholesOption::new(stock_option);
+            py.allow_threads(|| black_scholes_option.extended_greeks())
+        }
+        _ => return Err(PyValueError::new_err("Invalid method. Must be 'tree' or 'analytic'.")),
+    };
+    Ok((
+        greeks.speed,
+        greeks.zomma,
+        greeks.color,
+        greeks.psi,
+        greeks.dual_delta,
+        greeks.dual_gamma,
+    ))
+}
+
+/// Calculates the option price using a truncated binomial LR (Leisen-Reimer) tree.
+///
+/// Nodes whose risk-neutral reach probability falls below `epsilon` are skipped during
+/// backward induction, which cuts the work for very large `n` when the option is deep
+/// in or out of the money.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `epsilon` - The reach-probability threshold below which nodes are dropped.
+///
+/// # Returns
+///
+/// A tuple containing the following values:
+/// - `option_price`: The calculated option price.
+/// - `error_bound`: An upper bound on the pricing error introduced by the truncation.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", or if `epsilon`
+/// is not in the range [0, 1).
+#[pyfunction]
+fn calculate_option_price_truncated(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    epsilon: f64,
+) -> PyResult<(f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    if !(0.0..1.0).contains(&epsilon) {
+        return Err(PyValueError::new_err("Invalid epsilon. Must be in the range [0, 1)."));
+    }
+
+    let stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let mut binomial_tree_option = BinomialTreeOption::new(stock_option);
+    binomial_tree_option.truncation = Some(epsilon);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+
+    Ok(py.allow_threads(|| binomial_lr_option.price_truncated()))
+}
+
+/// Calculates the price of a Bermudan option using the binomial LR (Leisen-Reimer) model.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `exercise_dates` - The times (in years) at which the option may be exercised before expiration.
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put".
+#[pyfunction]
+fn calculate_bermudan_option_price(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    exercise_dates: Vec<f64>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, ExerciseStyle::European).map_err(py_err)?;
+    stock_option.exercise_dates = Some(exercise_dates);
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+    Ok(py.allow_threads(|| binomial_lr_option.price()))
+}
+
+/// Calculates the risk-neutral probability of early exercise and the expected exercise time
+/// of an American or Bermudan option using the binomial LR (Leisen-Reimer) model.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `exercise_dates` - The times (in years) at which a Bermudan option may be exercised
+///   before expiration. Overrides `is_am` when given.
+///
+/// # Returns
+///
+/// A tuple containing the option price, the probability that the option is exercised
+/// before expiration, and the expected time of exercise (in years), counting options held
+/// to expiration as exercised at expiration.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put".
+#[pyfunction]
+fn calculate_exercise_statistics(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    exercise_dates: Option<Vec<f64>>,
+) -> PyResult<(f64, f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.exercise_dates = exercise_dates;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+    Ok(py.allow_threads(|| binomial_lr_option.exercise_statistics()))
+}
+
+/// Extracts the early exercise boundary of an American or Bermudan option from a binomial tree.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `exercise_dates` - The times (in years) at which a Bermudan option may be exercised
+///   before expiration. Overrides `is_am` when given.
+/// * `engine` - The lattice: "tree" (plain binomial tree with the moves `pu` and `pd`), "crr"
+///   (Cox-Ross-Rubinstein), or "lr" (Leisen-Reimer). Defaults to "lr".
+/// * `pu` - The up move of the "tree" lattice, whose up factor is `1 + pu`. Defaults to 0.
+/// * `pd` - The down move of the "tree" lattice, whose down factor is `1 - pd`. Defaults to 0.
+///
+/// # Returns
+///
+/// A list of `(t, s_star)` pairs giving, for each time step where early exercise is
+/// optimal somewhere, the time (in years) and the critical stock price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` or `engine` is invalid, and an
+/// `InvalidInputError` if the moves of the "tree" lattice are invalid.
+#[pyfunction]
+fn calculate_exercise_boundary(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    exercise_dates: Option<Vec<f64>>,
+    engine: Option<&str>,
+    pu: Option<f64>,
+    pd: Option<f64>,
+) -> PyResult<Vec<(f64, f64)>> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, exercise_style(is_am)).map_err(py_err)?;
+    stock_option.exercise_dates = exercise_dates;
+    let mut binomial_tree_option = BinomialTreeOption::with_moves(stock_option, pu.unwrap_or(0.0), pd.unwrap_or(0.0));
+    match engine.unwrap_or("lr") {
+        "tree" => {
+            binomial_tree_option.check_moves().map_err(py_err)?;
+            Ok(py.allow_threads(|| {
+                binomial_tree_option.setup_parameters();
+                binomial_tree_option.init_stock_price_tree();
+                binomial_tree_option.exercise_boundary()
+            }))
+        }
+        "crr" => {
+            let crr_option = BinomialCRROption::new(binomial_tree_option);
+            Ok(py.allow_threads(|| crr_option.exercise_boundary()))
+        }
+        "lr" => {
+            let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+            Ok(py.allow_threads(|| binomial_lr_option.exercise_boundary()))
+        }
+        _ => Err(PyValueError::new_err("Invalid engine. Must be 'tree', 'crr', or 'lr'.")),
+    }
+}
+
+/// Decomposes the price of an American or Bermudan option into the price of its European
+/// twin and the early-exercise premium using the binomial LR (Leisen-Reimer) model.
+///
+/// Both options are priced in the same traversal of the same tree, so the premium is not
+/// polluted by differences in discretization between two separate calls.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `exercise_dates` - The times (in years) at which a Bermudan option may be exercised
+///   before expiration. Defaults to American exercise.
+/// * `borrow` - The continuous stock borrow (repo) rate, which lowers the forward price on
+///   top of `div`. Defaults to 0.
+///
+/// # Returns
+///
+/// A tuple containing the price of the option, the price of its European twin, and the
+/// early-exercise premium.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put".
+#[pyfunction]
+fn calculate_early_exercise_premium(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    exercise_dates: Option<Vec<f64>>,
+    borrow: Option<f64>,
+) -> PyResult<(f64, f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, sigma, option_type, ExerciseStyle::American).map_err(py_err)?;
+    stock_option.exercise_dates = exercise_dates;
+    stock_option.borrow = borrow.unwrap_or(0.0);
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+    let (price, european_price) = py.allow_threads(|| binomial_lr_option.price_with_european());
+    Ok((price, european_price, price - european_price))
+}
+
+/// Calculates the option price using the Crank-Nicolson finite-difference method.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `sigma` - The volatility of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `space_steps` - The number of nodes in the log-price grid.
+///
+/// # Returns
+///
+/// The calculated option price.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", or if the spot
+/// price, time to expiration, or number of steps is not positive, or the strike price or
+/// volatility is negative.
+#[pyfunction]
+fn calculate_option_price_fd(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    sigma: f64,
+    options_type: &str,
+    is_am: bool,
+    space_steps: usize,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let stock_option = StockOption::builder()
+        .spot(s0)
+        .strike(k)
+        .rate(r)
+        .expiry(t)
+        .steps(n)
+        .dividend_yield(div)
+        .volatility(sigma)
+        .option_type(parse_options_type(options_type)?)
+        .exercise(exercise_style(is_am))
+        .build().map_err(py_err)?;
+    let finite_difference_option = FiniteDifferenceOption::new(stock_option, space_steps);
+    Ok(py.allow_threads(|| finite_difference_option.price()))
+}
+
+/// Adds the functions that price vanilla options and their Greeks to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_option_price_and_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_and_greeks_ad, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_spot_ladder, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_and_greeks_with_engine, m)?)?;
+    m.add_function(wrap_pyfunction!(get_engine_diagnostics, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_adaptive, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_prices_by_strike, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_and_greeks_from_forward, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_contract_price_and_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_cross_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_extended_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_truncated, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_bermudan_option_price, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_exercise_statistics, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_exercise_boundary, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_early_exercise_premium, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_fd, m)?)?;
+    m.add_class::<PyPricingResult>()?;
+    Ok(())
+}
@@ -0,0 +1,1063 @@
+// volatility.rs
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use numerical_options_core::stock_option::StockOption;
+use numerical_options_core::binomial_tree_option::BinomialTreeOption;
+use numerical_options_core::binomial_lr_option::BinomialLROption;
+use numerical_options_core::binomial_crr_option::BinomialCRROption;
+use numerical_options_core::finite_difference_option::FiniteDifferenceOption;
+use numerical_options_core::black_scholes_option::BlackScholesOption;
+use numerical_options_core::numerics::black_vega;
+use numerical_options_core::implied_volatility::{american_implied_volatility, implied_volatilities, implied_volatility};
+use numerical_options_core::vol_surface::{SmileInterpolation, VolSurface};
+use numerical_options_core::svi::SviSmile;
+use numerical_options_core::sabr::SabrSmile;
+use numerical_options_core::heston::HestonModel;
+use numerical_options_core::put_call_parity::implied_forward;
+use numerical_options_core::arbitrage::{find_arbitrage, ArbitrageReport};
+use numerical_options_core::implied_density::implied_density;
+use numerical_options_core::option_chain::{
+    LiquidityFilter, OptionChain, OptionQuote, FLAG_HALTED, FLAG_NON_STANDARD, FLAG_STALE,
+};
+use numerical_options_core::option_type::{ExerciseStyle, OptionType};
+
+use crate::args::{parse_options_type, exercise_style};
+use crate::errors::{py_err, analytic_european_only};
+
+/// Calculates the forward price, discount factor, financing rate, and dividend yield
+/// implied by European call and put prices of one expiration through put-call parity.
+///
+/// # Arguments
+///
+/// * `calls` - The call prices, typically mid prices.
+/// * `puts` - The put prices at the same strikes.
+/// * `strikes` - The common strike prices, at least two of them distinct.
+/// * `s0` - The initial stock price.
+/// * `t` - The time to expiration of the options (in years).
+///
+/// # Returns
+///
+/// A tuple containing the implied forward price, discount factor, continuously
+/// compounded financing rate, and continuous dividend yield.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the lists have different lengths, if fewer than two
+/// distinct strikes are given, if `s0` or `t` is not positive, or if the prices imply a
+/// non-positive forward or discount factor.
+#[pyfunction]
+fn calculate_implied_forward(
+    calls: Vec<f64>,
+    puts: Vec<f64>,
+    strikes: Vec<f64>,
+    s0: f64,
+    t: f64,
+) -> PyResult<(f64, f64, f64, f64)> {
+    if calls.len() != strikes.len() || puts.len() != strikes.len() {
+        return Err(PyValueError::new_err("calls, puts, and strikes must have the same length."));
+    }
+    if s0 <= 0.0 || t <= 0.0 {
+        return Err(PyValueError::new_err("s0 and t must be positive."));
+    }
+    let (forward, discount) = implied_forward(&calls, &puts, &strikes).ok_or_else(|| {
+        PyValueError::new_err("The prices do not imply a positive forward and discount factor from distinct strikes.")
+    })?;
+    let rate = -discount.ln() / t;
+    let div = rate - (forward / s0).ln() / t;
+    Ok((forward, discount, rate, div))
+}
+
+/// Calculates the forward price and dividend yield implied by a European call and put
+/// at one strike through put-call parity, given the financing rate.
+///
+/// # Arguments
+///
+/// * `call` - The call price.
+/// * `put` - The put price.
+/// * `k` - The common strike price.
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the options (in years).
+///
+/// # Returns
+///
+/// A tuple containing the implied forward price and continuous dividend yield.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if `s0` or `t` is not positive, or if the prices imply a
+/// non-positive forward.
+#[pyfunction]
+fn calculate_implied_dividend(call: f64, put: f64, k: f64, s0: f64, r: f64, t: f64) -> PyResult<(f64, f64)> {
+    if s0 <= 0.0 || t <= 0.0 {
+        return Err(PyValueError::new_err("s0 and t must be positive."));
+    }
+    let forward = k + (call - put) * (r * t).exp();
+    if forward <= 0.0 {
+        return Err(PyValueError::new_err("The prices imply a non-positive forward."));
+    }
+    Ok((forward, r - (forward / s0).ln() / t))
+}
+
+/// Calculates the Black-Scholes implied volatility of a European option price.
+///
+/// The price is inverted with Jäckel's "Let's Be Rational" method, which is accurate to
+/// close to machine precision for any attainable price.
+///
+/// # Arguments
+///
+/// * `price` - The price of the option.
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+///
+/// # Returns
+///
+/// The implied volatility.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", or if the price
+/// is outside the no-arbitrage bounds.
+#[pyfunction]
+fn calculate_implied_volatility(
+    py: Python,
+    price: f64,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    options_type: &str,
+) -> PyResult<f64> {
+    let option_type = parse_options_type(options_type)?;
+    let forward = s0 * ((r - div) * t).exp();
+    py.allow_threads(|| implied_volatility(price, forward, k, t, (-r * t).exp(), option_type))
+        .ok_or_else(|| PyValueError::new_err("The price is outside the no-arbitrage bounds."))
+}
+
+/// Calculates the Black-Scholes implied volatilities of a chain of European option prices
+/// with a common expiration.
+///
+/// # Arguments
+///
+/// * `prices` - The prices of the options.
+/// * `strikes` - The strike prices of the options.
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the options (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `options_type` - The type of the options, either "call" or "put".
+///
+/// # Returns
+///
+/// The implied volatility of each option, or NaN where the price is outside the
+/// no-arbitrage bounds.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", or if `prices`
+/// and `strikes` have different lengths.
+#[pyfunction]
+fn calculate_implied_volatilities(
+    py: Python,
+    prices: Vec<f64>,
+    strikes: Vec<f64>,
+    s0: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    options_type: &str,
+) -> PyResult<Vec<f64>> {
+    let option_type = parse_options_type(options_type)?;
+    if prices.len() != strikes.len() {
+        return Err(PyValueError::new_err("prices and strikes must have the same length."));
+    }
+    let forward = s0 * ((r - div) * t).exp();
+    let discount = (-r * t).exp();
+    Ok(py.allow_threads(|| {
+        prices
+            .iter()
+            .zip(&strikes)
+            .map(|(&price, &k)| implied_volatility(price, forward, k, t, discount, option_type).unwrap_or(f64::NAN))
+            .collect()
+    }))
+}
+
+/// Calculates the Black-Scholes implied volatilities of an option chain across strikes
+/// and expirations in parallel.
+///
+/// # Arguments
+///
+/// * `py` - The Python interpreter, whose global lock is released while solving.
+/// * `prices` - The prices of the options, as a list or one-dimensional NumPy array.
+/// * `strikes` - The strike prices of the options.
+/// * `expiries` - The times to expiration of the options (in years).
+/// * `options_types` - The type of each option, either "call" or "put".
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+///
+/// # Returns
+///
+/// The implied volatility of each option, or NaN where the price is outside the
+/// no-arbitrage bounds.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the inputs have different lengths, or if an options type
+/// is not "call" or "put".
+#[pyfunction]
+fn calculate_chain_implied_volatilities(
+    py: Python,
+    prices: Vec<f64>,
+    strikes: Vec<f64>,
+    expiries: Vec<f64>,
+    options_types: Vec<String>,
+    s0: f64,
+    r: f64,
+    div: f64,
+) -> PyResult<Vec<f64>> {
+    let quotes = prices.len();
+    if strikes.len() != quotes || expiries.len() != quotes || options_types.len() != quotes {
+        return Err(PyValueError::new_err(
+            "prices, strikes, expiries, and options_types must have the same length.",
+        ));
+    }
+    let option_types = options_types
+        .iter()
+        .map(|options_type| parse_options_type(options_type))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(py.allow_threads(|| implied_volatilities(&prices, &strikes, &expiries, &option_types, s0, r, div)))
+}
+
+/// The strikes, expiries, mid prices, options types, spreads, and implied volatilities of
+/// the quotes kept from an option chain.
+type FilteredChain = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<String>, Vec<f64>, Vec<f64>);
+
+/// Filters an option chain by moneyness and liquidity, and returns the kept quotes in the
+/// form taken by the batch implied volatility solver and the calibrators.
+///
+/// # Arguments
+///
+/// * `py` - The Python interpreter, whose global lock is released while solving.
+/// * `strikes` - The strike prices of the quotes.
+/// * `expiries` - The times to expiration of the quotes (in years).
+/// * `bids` - The bid prices of the quotes.
+/// * `asks` - The ask prices of the quotes.
+/// * `options_types` - The type of each quote, either "call" or "put".
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `volumes` - The optional session volume of each quote. Defaults to zero.
+/// * `open_interests` - The optional open interest of each quote. Defaults to zero.
+/// * `flags` - The optional flags of each quote, from "stale", "non_standard", and "halted".
+/// * `min_moneyness` - The lowest moneyness `K / F` kept. Defaults to 0.
+/// * `max_moneyness` - The highest moneyness `K / F` kept. Defaults to no limit.
+/// * `min_volume` - The smallest volume kept. Defaults to 0.
+/// * `min_open_interest` - The smallest open interest kept. Defaults to 0.
+/// * `max_relative_spread` - The largest bid-ask spread kept, relative to the mid price.
+///   Defaults to no limit.
+/// * `keep_flagged` - Whether quotes with any flag are kept. Defaults to false.
+///
+/// # Returns
+///
+/// A tuple containing the strike, expiry, mid price, options type, bid-ask spread, and
+/// implied volatility of the mid price of each kept quote, in their original order.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the quote lists have different lengths, or if an options
+/// type or flag is invalid.
+#[pyfunction]
+fn calculate_option_chain_filter(
+    py: Python,
+    strikes: Vec<f64>,
+    expiries: Vec<f64>,
+    bids: Vec<f64>,
+    asks: Vec<f64>,
+    options_types: Vec<String>,
+    s0: f64,
+    r: f64,
+    div: f64,
+    volumes: Option<Vec<u64>>,
+    open_interests: Option<Vec<u64>>,
+    flags: Option<Vec<Vec<String>>>,
+    min_moneyness: Option<f64>,
+    max_moneyness: Option<f64>,
+    min_volume: Option<u64>,
+    min_open_interest: Option<u64>,
+    max_relative_spread: Option<f64>,
+    keep_flagged: Option<bool>,
+) -> PyResult<FilteredChain> {
+    let quotes = strikes.len();
+    let volumes = volumes.unwrap_or_else(|| vec![0; quotes]);
+    let open_interests = open_interests.unwrap_or_else(|| vec![0; quotes]);
+    let flags = flags.unwrap_or_else(|| vec![Vec::new(); quotes]);
+    let lengths = [
+        expiries.len(),
+        bids.len(),
+        asks.len(),
+        options_types.len(),
+        volumes.len(),
+        open_interests.len(),
+        flags.len(),
+    ];
+    if lengths.iter().any(|&length| length != quotes) {
+        return Err(PyValueError::new_err(
+            "strikes, expiries, bids, asks, options_types, volumes, open_interests, and flags must have the same length.",
+        ));
+    }
+    let quotes = (0..quotes)
+        .map(|i| {
+            let quote_flags = flags[i]
+                .iter()
+                .map(|flag| match flag.as_str() {
+                    "stale" => Ok(FLAG_STALE),
+                    "non_standard" => Ok(FLAG_NON_STANDARD),
+                    "halted" => Ok(FLAG_HALTED),
+                    _ => Err(PyValueError::new_err("Invalid flag. Must be 'stale', 'non_standard', or 'halted'.")),
+                })
+                .try_fold(0, |acc, flag| flag.map(|flag| acc | flag))?;
+            Ok(OptionQuote {
+                strike: strikes[i],
+                expiry: expiries[i],
+                option_type: parse_options_type(&options_types[i])?,
+                bid: bids[i],
+                ask: asks[i],
+                volume: volumes[i],
+                open_interest: open_interests[i],
+                flags: quote_flags,
+            })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let liquidity = LiquidityFilter {
+        min_volume: min_volume.unwrap_or(0),
+        min_open_interest: min_open_interest.unwrap_or(0),
+        max_relative_spread: max_relative_spread.unwrap_or(f64::INFINITY),
+        keep_flagged: keep_flagged.unwrap_or(false),
+    };
+    let chain = OptionChain::new(quotes)
+        .filter_moneyness(s0, r, div, min_moneyness.unwrap_or(0.0), max_moneyness.unwrap_or(f64::INFINITY))
+        .filter_liquidity(&liquidity);
+    let vols = py.allow_threads(|| chain.implied_volatilities(s0, r, div));
+    let options_types = chain
+        .option_types()
+        .iter()
+        .map(|&option_type| if option_type == OptionType::Call { "call" } else { "put" }.to_string())
+        .collect();
+    Ok((chain.strikes(), chain.expiries(), chain.mids(), options_types, chain.spreads(), vols))
+}
+
+/// Calculates the implied volatility of an American option price by inverting the
+/// binomial LR (Leisen-Reimer) tree.
+///
+/// # Arguments
+///
+/// * `price` - The price of the option.
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps in the binomial tree.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `exercise_dates` - The times (in years) at which a Bermudan option may be exercised
+///   before expiration. Defaults to American exercise.
+/// * `tolerance` - The absolute tolerance on the volatility. Defaults to 1e-8.
+///
+/// # Returns
+///
+/// The implied volatility.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", if the tolerance
+/// is not positive, or if no volatility in [1e-4, 10] reproduces the price.
+#[pyfunction]
+fn calculate_american_implied_volatility(
+    py: Python,
+    price: f64,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    options_type: &str,
+    exercise_dates: Option<Vec<f64>>,
+    tolerance: Option<f64>,
+) -> PyResult<f64> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let tolerance = tolerance.unwrap_or(1e-8);
+    if tolerance <= 0.0 {
+        return Err(PyValueError::new_err("Invalid tolerance. Must be positive."));
+    }
+
+    let mut stock_option = StockOption::new(s0, k, r, t, n, div, 0.2, option_type, ExerciseStyle::American).map_err(py_err)?;
+    stock_option.exercise_dates = exercise_dates;
+    let binomial_tree_option = BinomialTreeOption::new(stock_option);
+    let binomial_lr_option = BinomialLROption::new(binomial_tree_option);
+    py.allow_threads(|| american_implied_volatility(&binomial_lr_option, price, tolerance))
+        .ok_or_else(|| PyValueError::new_err("No volatility in [1e-4, 10] reproduces the price."))
+}
+
+/// Calculates the implied volatility at a strike and expiration from a volatility surface.
+///
+/// # Arguments
+///
+/// * `expiries` - The increasing expirations of the quotes (in years).
+/// * `strikes` - The increasing strike prices of the quotes.
+/// * `vols` - The implied volatilities, with one row per expiration and one column per strike.
+/// * `k` - The strike price.
+/// * `t` - The time to expiration (in years).
+/// * `interpolation` - The interpolation across strikes, either "linear_variance" (the
+///   default) or "cubic_spline".
+///
+/// # Returns
+///
+/// The interpolated implied volatility.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the surface or `interpolation` is invalid.
+#[pyfunction]
+fn calculate_surface_volatility(
+    expiries: Vec<f64>,
+    strikes: Vec<f64>,
+    vols: Vec<Vec<f64>>,
+    k: f64,
+    t: f64,
+    interpolation: Option<&str>,
+) -> PyResult<f64> {
+    let surface = new_vol_surface(expiries, strikes, vols, interpolation)?;
+    Ok(surface.volatility(k, t))
+}
+
+/// Calculates the price of an option with the volatility read from a volatility surface at
+/// its strike and expiration.
+///
+/// # Arguments
+///
+/// * `s0` - The initial stock price.
+/// * `k` - The strike price of the option.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the option (in years).
+/// * `n` - The number of time steps of the tree engines.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `options_type` - The type of the option, either "call" or "put".
+/// * `is_am` - A boolean indicating whether the option is American-style (true) or European-style (false).
+/// * `expiries` - The increasing expirations of the quotes (in years).
+/// * `strikes` - The increasing strike prices of the quotes.
+/// * `vols` - The implied volatilities, with one row per expiration and one column per strike.
+/// * `interpolation` - The interpolation across strikes, either "linear_variance" (the
+///   default) or "cubic_spline".
+/// * `engine` - The pricing engine: "crr" (Cox-Ross-Rubinstein), "lr" (Leisen-Reimer, the
+///   default), "fd" (Crank-Nicolson), or "analytic" (Black-Scholes).
+/// * `space_steps` - The number of spatial nodes for the "fd" engine. Defaults to 201.
+///
+/// # Returns
+///
+/// A tuple containing the option price and the volatility read from the surface.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if any of the string arguments or the surface is invalid, or
+/// if the analytic engine is requested for an American option.
+#[pyfunction]
+fn calculate_option_price_with_surface(
+    py: Python,
+    s0: f64,
+    k: f64,
+    r: f64,
+    t: f64,
+    n: usize,
+    div: f64,
+    options_type: &str,
+    is_am: bool,
+    expiries: Vec<f64>,
+    strikes: Vec<f64>,
+    vols: Vec<Vec<f64>>,
+    interpolation: Option<&str>,
+    engine: Option<&str>,
+    space_steps: Option<usize>,
+) -> PyResult<(f64, f64)> {
+    StockOption::check_steps(n).map_err(py_err)?;
+    let option_type = parse_options_type(options_type)?;
+    let surface = new_vol_surface(expiries, strikes, vols, interpolation)?;
+
+    let stock_option = StockOption::new(s0, k, r, t, n, div, 0.0, option_type, exercise_style(is_am)).map_err(py_err)?;
+    let engine = engine.unwrap_or("lr");
+    match engine {
+        "crr" | "lr" | "fd" => {}
+        "analytic" if is_am => return Err(analytic_european_only("engine")),
+        "analytic" => {}
+        _ => return Err(PyValueError::new_err("Invalid engine. Must be 'crr', 'lr', 'fd', or 'analytic'.")),
+    }
+    Ok(py.allow_threads(|| match engine {
+        "crr" => surface.price(&BinomialCRROption::new(BinomialTreeOption::new(stock_option))),
+        "lr" => surface.price(&BinomialLROption::new(BinomialTreeOption::new(stock_option))),
+        "fd" => surface.price(&FiniteDifferenceOption::new(stock_option, space_steps.unwrap_or(201))),
+        _ => surface.price(&BlackScholesOption::new(stock_option)),
+    }))
+}
+
+/// The offending `(expiry, strike)` pairs of butterfly and calendar-spread arbitrage.
+type ArbitragePairs = (Vec<(f64, f64)>, Vec<(f64, f64)>);
+
+/// Finds the butterfly and calendar-spread arbitrage among the nodes of an implied
+/// volatility surface.
+///
+/// # Arguments
+///
+/// * `expiries` - The increasing expirations of the quotes (in years).
+/// * `strikes` - The increasing strike prices of the quotes.
+/// * `vols` - The implied volatilities, with one row per expiration and one column per strike.
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+///
+/// # Returns
+///
+/// A tuple containing the `(expiry, strike)` of each butterfly violation and of each
+/// calendar-spread violation.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the surface is invalid or `s0` is not positive.
+#[pyfunction]
+fn calculate_surface_arbitrage(
+    expiries: Vec<f64>,
+    strikes: Vec<f64>,
+    vols: Vec<Vec<f64>>,
+    s0: f64,
+    r: f64,
+    div: f64,
+) -> PyResult<ArbitragePairs> {
+    if s0 <= 0.0 {
+        return Err(PyValueError::new_err("s0 must be positive."));
+    }
+    let surface = new_vol_surface(expiries, strikes, vols, None)?;
+    let report = surface.find_arbitrage(s0, r - div);
+    Ok((report.butterfly, report.calendar))
+}
+
+/// Finds the butterfly and calendar-spread arbitrage in a set of implied volatility quotes.
+///
+/// # Arguments
+///
+/// * `strikes` - The strike prices of the quotes.
+/// * `expiries` - The times to expiration of the quotes (in years).
+/// * `vols` - The implied volatilities of the quotes.
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+///
+/// # Returns
+///
+/// A tuple containing the `(expiry, strike)` of each butterfly violation and of each
+/// calendar-spread violation.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the lists have different lengths, or if a strike, expiry,
+/// volatility, or `s0` is not positive.
+#[pyfunction]
+fn calculate_quote_arbitrage(
+    strikes: Vec<f64>,
+    expiries: Vec<f64>,
+    vols: Vec<f64>,
+    s0: f64,
+    r: f64,
+    div: f64,
+) -> PyResult<ArbitragePairs> {
+    if expiries.len() != strikes.len() || vols.len() != strikes.len() {
+        return Err(PyValueError::new_err("strikes, expiries, and vols must have the same length."));
+    }
+    if s0 <= 0.0 || strikes.iter().chain(&expiries).chain(&vols).any(|&x| x <= 0.0) {
+        return Err(PyValueError::new_err("The strikes, expiries, vols, and s0 must be positive."));
+    }
+    let ArbitrageReport { butterfly, calendar } = find_arbitrage(&strikes, &expiries, &vols, s0, r - div);
+    Ok((butterfly, calendar))
+}
+
+/// Builds a `VolSurface` from the arguments passed from Python.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `interpolation` is not "linear_variance" or
+/// "cubic_spline", and an `InvalidInputError` if the expirations or strikes are not
+/// positive and increasing, or if the volatilities do not form a positive grid of
+/// matching shape.
+pub(crate) fn new_vol_surface(
+    expiries: Vec<f64>,
+    strikes: Vec<f64>,
+    vols: Vec<Vec<f64>>,
+    interpolation: Option<&str>,
+) -> PyResult<VolSurface> {
+    let interpolation = match interpolation.unwrap_or("linear_variance") {
+        "linear_variance" => SmileInterpolation::LinearVariance,
+        "cubic_spline" => SmileInterpolation::CubicSpline,
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid interpolation. Must be 'linear_variance' or 'cubic_spline'.",
+            ))
+        }
+    };
+    VolSurface::new(expiries, strikes, vols, interpolation).map_err(py_err)
+}
+
+/// Builds the weight of each quote's squared error in a calibration from the optional
+/// Python arguments.
+///
+/// # Arguments
+///
+/// * `weighting` - "uniform" (the default), "vega", or "spread".
+/// * `spreads` - The bid-ask spread of each quote, required for "spread" weighting.
+/// * `quotes` - The number of quotes.
+/// * `vegas` - Calculates the Black vega of each quote, for "vega" weighting.
+///
+/// # Returns
+///
+/// The weights, scaled so that the largest is one: the vega for "vega" weighting and the
+/// inverse squared spread for "spread" weighting.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `weighting` is invalid, or if "spread" weighting is
+/// requested without one positive spread per quote.
+fn calibration_weights<V: FnOnce() -> Vec<f64>>(
+    weighting: Option<&str>,
+    spreads: Option<Vec<f64>>,
+    quotes: usize,
+    vegas: V,
+) -> PyResult<Vec<f64>> {
+    let weights = match weighting.unwrap_or("uniform") {
+        "uniform" => return Ok(vec![1.0; quotes]),
+        "vega" => vegas().iter().map(|vega| if vega.is_finite() { *vega } else { 0.0 }).collect::<Vec<_>>(),
+        "spread" => match spreads {
+            Some(spreads) if spreads.len() == quotes && spreads.iter().all(|&spread| spread > 0.0) => {
+                spreads.iter().map(|spread| spread.powi(-2)).collect()
+            }
+            _ => {
+                return Err(PyValueError::new_err(
+                    "spread weighting requires one positive spread per quote.",
+                ))
+            }
+        },
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid weighting. Must be 'uniform', 'vega', or 'spread'.",
+            ))
+        }
+    };
+    let largest = weights.iter().fold(0.0_f64, |acc, &weight| acc.max(weight));
+    if largest > 0.0 {
+        Ok(weights.iter().map(|weight| weight / largest).collect())
+    } else {
+        Ok(vec![1.0; quotes])
+    }
+}
+
+/// Fits a raw SVI smile to the implied volatilities of one expiration.
+///
+/// # Arguments
+///
+/// * `strikes` - The strike prices of the quotes.
+/// * `vols` - The implied volatilities of the quotes.
+/// * `forward` - The forward price of the underlying asset at expiration.
+/// * `t` - The time to expiration (in years).
+/// * `weighting` - How the squared errors are weighted: "uniform" (the default), "vega", or "spread".
+/// * `spreads` - The bid-ask spread of each quote in volatility terms, for "spread" weighting.
+///
+/// # Returns
+///
+/// A tuple containing the SVI parameters `a`, `b`, `rho`, `m`, and `sigma`, and the
+/// unweighted root-mean-square error of the fitted volatilities.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if there are fewer than five quotes, if `strikes` and `vols`
+/// have different lengths, if any input is not positive, or if the weighting is invalid.
+#[pyfunction]
+fn calculate_svi_fit(
+    py: Python,
+    strikes: Vec<f64>,
+    vols: Vec<f64>,
+    forward: f64,
+    t: f64,
+    weighting: Option<&str>,
+    spreads: Option<Vec<f64>>,
+) -> PyResult<(f64, f64, f64, f64, f64, f64)> {
+    if strikes.len() != vols.len() || strikes.len() < 5 {
+        return Err(PyValueError::new_err(
+            "strikes and vols must have the same length, with at least five quotes.",
+        ));
+    }
+    if forward <= 0.0 || t <= 0.0 || strikes.iter().chain(&vols).any(|&x| x <= 0.0) {
+        return Err(PyValueError::new_err("The strikes, vols, forward, and t must be positive."));
+    }
+    let weights = calibration_weights(weighting, spreads, strikes.len(), || {
+        strikes.iter().zip(&vols).map(|(&k, &vol)| black_vega(forward, k, vol, t)).collect()
+    })?;
+    let (smile, rmse) = py.allow_threads(|| SviSmile::fit(&strikes, &vols, forward, t, &weights));
+    Ok((smile.a, smile.b, smile.rho, smile.m, smile.sigma, rmse))
+}
+
+/// Calculates the implied volatilities of a raw SVI smile at a set of strikes.
+///
+/// # Arguments
+///
+/// * `a` - The level of the total variance.
+/// * `b` - The slope of the wings.
+/// * `rho` - The skew.
+/// * `m` - The log-moneyness of the vertex of the smile.
+/// * `sigma` - The curvature of the smile at its vertex.
+/// * `forward` - The forward price of the underlying asset at expiration.
+/// * `t` - The time to expiration (in years).
+/// * `strikes` - The strike prices at which to evaluate the smile.
+///
+/// # Returns
+///
+/// The implied volatility at each strike.
+#[pyfunction]
+fn calculate_svi_volatilities(
+    a: f64,
+    b: f64,
+    rho: f64,
+    m: f64,
+    sigma: f64,
+    forward: f64,
+    t: f64,
+    strikes: Vec<f64>,
+) -> Vec<f64> {
+    let smile = SviSmile { a, b, rho, m, sigma, forward, t };
+    strikes.iter().map(|&strike| smile.volatility(strike)).collect()
+}
+
+/// The calibrated SABR parameters `(alpha, rho, nu)`, the RMSE, the number of iterations,
+/// whether the optimizer converged, and the residuals.
+type SabrFit = (f64, f64, f64, f64, usize, bool, Vec<f64>);
+
+/// Calibrates the SABR model to the implied volatilities of one expiration, with `beta` fixed.
+///
+/// # Arguments
+///
+/// * `strikes` - The strike prices of the quotes.
+/// * `vols` - The implied volatilities of the quotes.
+/// * `forward` - The forward price of the underlying asset at expiration.
+/// * `t` - The time to expiration (in years).
+/// * `beta` - The fixed elasticity of the volatility to the forward, in [0, 1].
+/// * `weighting` - How the squared errors are weighted: "uniform" (the default), "vega", or "spread".
+/// * `spreads` - The bid-ask spread of each quote in volatility terms, for "spread" weighting.
+///
+/// # Returns
+///
+/// A tuple containing the calibrated `alpha`, `rho`, and `nu`, the unweighted
+/// root-mean-square error of the model volatilities, the number of iterations, whether the optimizer converged,
+/// and the residual (model minus market volatility) of each quote.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if there are fewer than three quotes, if `strikes` and `vols`
+/// have different lengths, if any input is not positive, if `beta` is not in [0, 1], or if
+/// the weighting is invalid.
+#[pyfunction]
+fn calculate_sabr_calibration(
+    py: Python,
+    strikes: Vec<f64>,
+    vols: Vec<f64>,
+    forward: f64,
+    t: f64,
+    beta: f64,
+    weighting: Option<&str>,
+    spreads: Option<Vec<f64>>,
+) -> PyResult<SabrFit> {
+    if strikes.len() != vols.len() || strikes.len() < 3 {
+        return Err(PyValueError::new_err(
+            "strikes and vols must have the same length, with at least three quotes.",
+        ));
+    }
+    if forward <= 0.0 || t <= 0.0 || strikes.iter().chain(&vols).any(|&x| x <= 0.0) {
+        return Err(PyValueError::new_err("The strikes, vols, forward, and t must be positive."));
+    }
+    if !(0.0..=1.0).contains(&beta) {
+        return Err(PyValueError::new_err("Invalid beta. Must be in the range [0, 1]."));
+    }
+    let weights = calibration_weights(weighting, spreads, strikes.len(), || {
+        strikes.iter().zip(&vols).map(|(&k, &vol)| black_vega(forward, k, vol, t)).collect()
+    })?;
+    let calibration = py.allow_threads(|| SabrSmile::calibrate(&strikes, &vols, forward, t, beta, &weights));
+    let smile = calibration.smile;
+    Ok((
+        smile.alpha,
+        smile.rho,
+        smile.nu,
+        calibration.rmse,
+        calibration.iterations,
+        calibration.converged,
+        calibration.residuals,
+    ))
+}
+
+/// Calculates the implied volatilities of a SABR smile at a set of strikes.
+///
+/// # Arguments
+///
+/// * `alpha` - The initial volatility.
+/// * `beta` - The elasticity of the volatility to the forward.
+/// * `rho` - The correlation between the forward and its volatility.
+/// * `nu` - The volatility of the volatility.
+/// * `forward` - The forward price of the underlying asset at expiration.
+/// * `t` - The time to expiration (in years).
+/// * `strikes` - The strike prices at which to evaluate the smile.
+///
+/// # Returns
+///
+/// The implied volatility at each strike.
+#[pyfunction]
+fn calculate_sabr_volatilities(
+    alpha: f64,
+    beta: f64,
+    rho: f64,
+    nu: f64,
+    forward: f64,
+    t: f64,
+    strikes: Vec<f64>,
+) -> Vec<f64> {
+    let smile = SabrSmile { alpha, beta, rho, nu, forward, t };
+    strikes.iter().map(|&strike| smile.volatility(strike)).collect()
+}
+
+/// Calculates the risk-neutral density and distribution function implied by a fitted
+/// volatility smile with the Breeden-Litzenberger formula.
+///
+/// # Arguments
+///
+/// * `model` - The smile parameterization, either "svi" or "sabr".
+/// * `parameters` - The smile parameters: `[a, b, rho, m, sigma]` for "svi" or
+///   `[alpha, beta, rho, nu]` for "sabr".
+/// * `forward` - The forward price of the underlying asset at expiration.
+/// * `t` - The time to expiration (in years).
+/// * `strikes` - The strike prices at which to evaluate the density.
+///
+/// # Returns
+///
+/// A tuple containing the density and the cumulative distribution function of the
+/// underlying asset price at expiration at each strike.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `model` is invalid, if the number of `parameters` does
+/// not match it, or if `forward`, `t`, or a strike is not positive.
+#[pyfunction]
+fn calculate_implied_density(
+    py: Python,
+    model: &str,
+    parameters: Vec<f64>,
+    forward: f64,
+    t: f64,
+    strikes: Vec<f64>,
+) -> PyResult<(Vec<f64>, Vec<f64>)> {
+    if forward <= 0.0 || t <= 0.0 || strikes.iter().any(|&k| k <= 0.0) {
+        return Err(PyValueError::new_err("forward, t, and strikes must be positive."));
+    }
+    let points = match (model, parameters.as_slice()) {
+        ("svi", &[a, b, rho, m, sigma]) => {
+            let smile = SviSmile { a, b, rho, m, sigma, forward, t };
+            py.allow_threads(|| implied_density(|k| smile.volatility(k), forward, t, &strikes))
+        }
+        ("sabr", &[alpha, beta, rho, nu]) => {
+            let smile = SabrSmile { alpha, beta, rho, nu, forward, t };
+            py.allow_threads(|| implied_density(|k| smile.volatility(k), forward, t, &strikes))
+        }
+        _ => {
+            return Err(PyValueError::new_err(
+                "Invalid model. Must be 'svi' with [a, b, rho, m, sigma] or 'sabr' with [alpha, beta, rho, nu].",
+            ))
+        }
+    };
+    Ok(points.into_iter().unzip())
+}
+
+/// The calibrated Heston parameters `(v0, kappa, theta, xi, rho)`, the RMSE, the number of
+/// iterations, whether the optimizer converged, and the residuals.
+type HestonFit = (f64, f64, f64, f64, f64, f64, usize, bool, Vec<f64>);
+
+/// Calibrates the Heston model to the prices of European options, priced with the
+/// Carr-Madan FFT.
+///
+/// # Arguments
+///
+/// * `strikes` - The strike prices of the quotes.
+/// * `expiries` - The times to expiration of the quotes (in years).
+/// * `prices` - The prices of the quotes.
+/// * `options_types` - The type of each quote, either "call" or "put".
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `feller` - Whether to enforce the Feller condition `2 kappa theta >= xi^2` (default false).
+/// * `weighting` - How the squared errors are weighted: "uniform" (the default), "vega", or "spread".
+/// * `spreads` - The bid-ask spread of each quote in price terms, for "spread" weighting.
+///
+/// # Returns
+///
+/// A tuple containing the calibrated `v0`, `kappa`, `theta`, `xi`, and `rho`, the
+/// unweighted root-mean-square error of the model prices, the number of iterations, whether the
+/// optimizer converged, and the residual (model minus market price) of each quote.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if there are fewer than five quotes, if the quote lists have
+/// different lengths, if an options type is not "call" or "put", or if a strike, expiry,
+/// price, or `s0` is not positive, or if the weighting is invalid.
+#[pyfunction]
+fn calculate_heston_calibration(
+    py: Python,
+    strikes: Vec<f64>,
+    expiries: Vec<f64>,
+    prices: Vec<f64>,
+    options_types: Vec<String>,
+    s0: f64,
+    r: f64,
+    div: f64,
+    feller: Option<bool>,
+    weighting: Option<&str>,
+    spreads: Option<Vec<f64>>,
+) -> PyResult<HestonFit> {
+    let quotes = strikes.len();
+    if expiries.len() != quotes || prices.len() != quotes || options_types.len() != quotes || quotes < 5 {
+        return Err(PyValueError::new_err(
+            "strikes, expiries, prices, and options_types must have the same length, with at least five quotes.",
+        ));
+    }
+    if s0 <= 0.0 || strikes.iter().chain(&expiries).chain(&prices).any(|&x| x <= 0.0) {
+        return Err(PyValueError::new_err("The strikes, expiries, prices, and s0 must be positive."));
+    }
+    let option_types = options_types
+        .iter()
+        .map(|options_type| parse_options_type(options_type))
+        .collect::<PyResult<Vec<_>>>()?;
+    let weights = calibration_weights(weighting, spreads, quotes, || {
+        (0..quotes)
+            .map(|i| {
+                let t = expiries[i];
+                let forward = s0 * ((r - div) * t).exp();
+                implied_volatility(prices[i], forward, strikes[i], t, (-r * t).exp(), option_types[i])
+                    .map_or(0.0, |vol| black_vega(forward, strikes[i], vol, t))
+            })
+            .collect()
+    })?;
+    let calibration = py.allow_threads(|| {
+        HestonModel::calibrate(
+            s0,
+            r,
+            div,
+            &strikes,
+            &expiries,
+            &prices,
+            &option_types,
+            feller.unwrap_or(false),
+            &weights,
+        )
+    });
+    let model = calibration.model;
+    Ok((
+        model.v0,
+        model.kappa,
+        model.theta,
+        model.xi,
+        model.rho,
+        calibration.rmse,
+        calibration.iterations,
+        calibration.converged,
+        calibration.residuals,
+    ))
+}
+
+/// Calculates the Heston prices of European options of one expiration with the Carr-Madan FFT.
+///
+/// # Arguments
+///
+/// * `v0` - The initial variance.
+/// * `kappa` - The rate of mean reversion of the variance.
+/// * `theta` - The long-run variance.
+/// * `xi` - The volatility of the variance.
+/// * `rho` - The correlation between the asset and its variance.
+/// * `s0` - The initial stock price.
+/// * `r` - The risk-free interest rate.
+/// * `t` - The time to expiration of the options (in years).
+/// * `div` - The continuous dividend yield of the underlying asset.
+/// * `strikes` - The strike prices of the options.
+/// * `options_type` - The type of the options, either "call" or "put".
+///
+/// # Returns
+///
+/// The price of the option at each strike.
+///
+/// # Errors
+///
+/// Returns a `PyValueError` if the `options_type` is not "call" or "put", or if `v0`,
+/// `kappa`, `theta`, `xi`, `s0`, `t`, or a strike is not positive, or if `rho` is not in
+/// (-1, 1).
+#[pyfunction]
+fn calculate_heston_prices(
+    py: Python,
+    v0: f64,
+    kappa: f64,
+    theta: f64,
+    xi: f64,
+    rho: f64,
+    s0: f64,
+    r: f64,
+    t: f64,
+    div: f64,
+    strikes: Vec<f64>,
+    options_type: &str,
+) -> PyResult<Vec<f64>> {
+    let option_type = parse_options_type(options_type)?;
+    if [v0, kappa, theta, xi, s0, t].iter().chain(&strikes).any(|&x| x <= 0.0) {
+        return Err(PyValueError::new_err("The parameters, s0, t, and strikes must be positive."));
+    }
+    if rho <= -1.0 || rho >= 1.0 {
+        return Err(PyValueError::new_err("Invalid rho. Must be in the range (-1, 1)."));
+    }
+    let model = HestonModel { v0, kappa, theta, xi, rho };
+    let forward = s0 * ((r - div) * t).exp();
+    let discount = (-r * t).exp();
+    let calls = py.allow_threads(|| model.call_prices(forward, discount, t, &strikes));
+    Ok(calls
+        .iter()
+        .zip(&strikes)
+        .map(|(&call, &k)| if option_type == OptionType::Put { call - discount * (forward - k) } else { call })
+        .collect())
+}
+
+/// Adds the implied volatility, volatility surface, and calibration functions to the Python module.
+pub(crate) fn register(m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calculate_implied_forward, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_implied_dividend, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_implied_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_implied_volatilities, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_chain_implied_volatilities, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_chain_filter, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_american_implied_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_surface_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_option_price_with_surface, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_surface_arbitrage, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_quote_arbitrage, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_svi_fit, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_svi_volatilities, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_sabr_calibration, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_sabr_volatilities, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_implied_density, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_heston_calibration, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_heston_prices, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,110 @@
+"""Round-trip tests of the `Float64Array` results of the Python bindings.
+
+The tests use only the standard library, so they run without NumPy. They import the
+installed `numerical_options_rs` module, or the extension library named by the
+`NUMERICAL_OPTIONS_RS_LIB` environment variable, for example:
+
+    cargo build --release
+    NUMERICAL_OPTIONS_RS_LIB=target/release/libnumerical_options_rs.so python -m unittest discover tests
+"""
+
+import array
+import gc
+import importlib.machinery
+import importlib.util
+import os
+import struct
+import unittest
+
+
+def load_module():
+    path = os.environ.get("NUMERICAL_OPTIONS_RS_LIB")
+    if path is None:
+        import numerical_options_rs
+
+        return numerical_options_rs
+    # The module is initialized under the name of the library file, such as
+    # `libnumerical_options_rs`.
+    name = os.path.basename(path).split(".")[0]
+    loader = importlib.machinery.ExtensionFileLoader(name, path)
+    spec = importlib.util.spec_from_file_location(name, path, loader=loader)
+    module = importlib.util.module_from_spec(spec)
+    loader.exec_module(module)
+    return module
+
+
+m = load_module()
+
+STRIKES = [40.0, 50.0, 60.0]
+EXPIRIES = [1.0, 1.0, 0.5]
+SIGMAS = [0.3, 0.2, 0.25]
+TYPES = ["put", "call", "put"]
+
+
+def batch(as_arrays):
+    return m.calculate_option_prices_batch(
+        STRIKES, EXPIRIES, SIGMAS, TYPES, 50.0, 0.05, 0.01, 101, True, "lr", None, as_arrays
+    )
+
+
+class Float64ArrayTest(unittest.TestCase):
+    def test_batch_arrays_match_the_lists(self):
+        lists, arrays = batch(False), batch(True)
+        self.assertEqual(len(arrays), 6)
+        for values, column in zip(lists, arrays):
+            self.assertIsInstance(column, m.Float64Array)
+            self.assertEqual(len(column), len(values))
+            self.assertEqual(list(column), values)
+            self.assertEqual([column[i] for i in range(len(column))], values)
+            self.assertEqual(column[-1], values[-1])
+
+    def test_buffer_is_a_read_only_vector_of_doubles(self):
+        prices = batch(True)[0]
+        view = memoryview(prices)
+        self.assertEqual(view.format, "d")
+        self.assertEqual(view.itemsize, 8)
+        self.assertEqual(view.ndim, 1)
+        self.assertEqual(view.shape, (3,))
+        self.assertEqual(view.strides, (8,))
+        self.assertTrue(view.readonly)
+        self.assertTrue(view.c_contiguous)
+        self.assertEqual(view.tolist(), list(prices))
+        with self.assertRaises(TypeError):
+            view[0] = 0.0
+        view.release()
+
+    def test_raw_bytes_round_trip(self):
+        prices = batch(True)[0]
+        raw = bytes(memoryview(prices))
+        self.assertEqual(list(struct.unpack("=3d", raw)), list(prices))
+        copy = array.array("d")
+        copy.frombytes(raw)
+        self.assertEqual(copy.tolist(), list(prices))
+
+    def test_view_outlives_the_array(self):
+        expected = list(batch(True)[0])
+        view = memoryview(batch(True)[0])
+        gc.collect()
+        self.assertEqual(view.tolist(), expected)
+        view.release()
+
+    def test_index_out_of_range(self):
+        prices = batch(True)[0]
+        with self.assertRaises(IndexError):
+            prices[3]
+        with self.assertRaises(IndexError):
+            prices[-4]
+
+    def test_spot_ladder_columns(self):
+        spots = [45.0, 50.0, 55.0]
+        rows = m.calculate_spot_ladder(spots, 52.0, 0.05, 1.0, 101, 0.01, 0.3, "put", True)
+        columns = m.calculate_spot_ladder(
+            spots, 52.0, 0.05, 1.0, 101, 0.01, 0.3, "put", True, as_arrays=True
+        )
+        self.assertEqual(len(columns), 7)
+        self.assertEqual([list(column) for column in columns], [list(c) for c in zip(*rows)])
+        self.assertEqual(memoryview(columns[0]).tolist(), spots)
+
+
+if __name__ == "__main__":
+    unittest.main()
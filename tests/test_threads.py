@@ -0,0 +1,64 @@
+"""Tests of pricing from several Python threads at once.
+
+The pricing functions release the GIL while the core library runs, so calls from
+different threads proceed in parallel. The tests load the module as described in
+`test_float64_array.py`.
+"""
+
+import importlib.machinery
+import importlib.util
+import os
+import threading
+import unittest
+
+
+def load_module():
+    path = os.environ.get("NUMERICAL_OPTIONS_RS_LIB")
+    if path is None:
+        import numerical_options_rs
+
+        return numerical_options_rs
+    name = os.path.basename(path).split(".")[0]
+    loader = importlib.machinery.ExtensionFileLoader(name, path)
+    spec = importlib.util.spec_from_file_location(name, path, loader=loader)
+    module = importlib.util.module_from_spec(spec)
+    loader.exec_module(module)
+    return module
+
+
+m = load_module()
+
+
+def american_put():
+    return m.calculate_option_price_and_greeks(50.0, 52.0, 0.05, 1.0, 2001, 0.01, 0.3, "put", True).price
+
+
+def barrier_call():
+    return m.calculate_barrier_option_price(
+        100.0, 100.0, 0.05, 1.0, 2001, 0.0, 0.2, "call", False, 90.0, "down_and_out", 0.0, "tree"
+    )
+
+
+class ThreadsTest(unittest.TestCase):
+    def test_concurrent_calls_match_serial_calls(self):
+        expected = [american_put(), barrier_call()]
+        results = [None, None]
+        start = threading.Barrier(2)
+
+        def run(i, price):
+            start.wait()
+            results[i] = price()
+
+        threads = [
+            threading.Thread(target=run, args=(i, price))
+            for i, price in enumerate([american_put, barrier_call])
+        ]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+        self.assertEqual(results, expected)
+
+
+if __name__ == "__main__":
+    unittest.main()